@@ -0,0 +1,70 @@
+//! Expected time-to-solution of sequential vs random nonce sampling for a miner
+//! that restarts every few thousand hashes (mobile, laptops that sleep)
+//!
+//! Run with `cargo bench --bench nonce_strategy`. Real hashing would make this
+//! take hours, so a cheap keyed hash stands in for drillx: each trial's "epoch"
+//! hides solutions at a fixed density in the partition, and a worker restarts
+//! its search every `RESTART_EVERY` hashes until it finds one or runs out of budget.
+use gmine_mobile::nonce_strategy::{NonceSampler, NonceStrategy};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// One solution per this many nonces on average
+const SOLUTION_DENSITY: u64 = 20_000;
+const RESTART_EVERY: u64 = 5_000;
+/// Hashes a trial may spend before it counts as a miss
+const BUDGET: u64 = 400_000;
+const TRIALS: u64 = 2_000;
+/// Size of one miner's partition (u64::MAX / 1000)
+const PARTITION: u64 = u64::MAX / 1000;
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+fn is_solution(epoch: u64, nonce: u64) -> bool {
+    splitmix64(nonce ^ splitmix64(epoch)) % SOLUTION_DENSITY == 0
+}
+
+/// Hashes until the first solution, or None if the budget ran out
+fn time_to_solution(strategy: NonceStrategy, epoch: u64) -> Option<u64> {
+    let start = splitmix64(epoch) % (u64::MAX - PARTITION);
+    let mut hashes = 0;
+    let mut restart = 0;
+    while hashes < BUDGET {
+        let nonces = match strategy {
+            NonceStrategy::Sequential => NonceSampler::sequential(0, start, start + PARTITION, 0.0),
+            // Fresh entropy per run in the miner; seeded here so runs are repeatable
+            NonceStrategy::Random => NonceSampler::random(start, start + PARTITION, StdRng::seed_from_u64((epoch << 16) | restart)),
+        };
+        for nonce in nonces.take(RESTART_EVERY as usize) {
+            hashes += 1;
+            if is_solution(epoch, nonce) {
+                return Some(hashes);
+            }
+        }
+        restart += 1;
+    }
+    None
+}
+
+fn main() {
+    println!(
+        "1 solution per {} nonces, restart every {} hashes, budget {} hashes, {} trials",
+        SOLUTION_DENSITY, RESTART_EVERY, BUDGET, TRIALS
+    );
+    for strategy in [NonceStrategy::Sequential, NonceStrategy::Random] {
+        let results: Vec<Option<u64>> = (0..TRIALS).map(|epoch| time_to_solution(strategy, epoch)).collect();
+        let found: Vec<u64> = results.iter().flatten().copied().collect();
+        let mean = found.iter().sum::<u64>() as f64 / found.len().max(1) as f64;
+        println!(
+            "{:<10} found in {:>5.1}% of trials, mean {:>8.0} hashes to solution when found",
+            format!("{:?}", strategy),
+            found.len() as f64 * 100.0 / TRIALS as f64,
+            mean
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Per-epoch worker start-up cost with and without the solver memory pool
+//!
+//! Run with `cargo bench --bench solver_memory`. Each "epoch" checks out one
+//! solver buffer per worker and hashes a single nonce, which is what every
+//! worker does right after `start_mining`.
+use std::time::Instant;
+
+use gmine_miner::miner::solver_pool::SolverMemoryPool;
+
+const WORKERS: usize = 8;
+const EPOCHS: usize = 20;
+
+/// Minor page faults for this process so far (Linux only)
+fn minor_faults() -> Option<u64> {
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    // Fields after the parenthesized command name; minflt is field 10 overall
+    let after_comm = stat.rsplit_once(')')?.1;
+    after_comm.split_whitespace().nth(7)?.parse().ok()
+}
+
+fn first_hash(memory: &mut equix::SolverMemory, epoch: usize) {
+    let challenge = [epoch as u8; 32];
+    let _ = drillx::hash_with_memory(memory, &challenge, &0u64.to_le_bytes());
+}
+
+fn run(label: &str, mut epoch: impl FnMut(usize)) {
+    let faults_before = minor_faults();
+    let start = Instant::now();
+    for i in 0..EPOCHS {
+        epoch(i);
+    }
+    let per_epoch = start.elapsed() / EPOCHS as u32;
+    let faults = match (faults_before, minor_faults()) {
+        (Some(before), Some(after)) => format!("{} page faults/epoch", (after - before) / EPOCHS as u64),
+        _ => "page faults unavailable".to_string(),
+    };
+    println!("{:<8} {:>10.2?} startup/epoch, {}", label, per_epoch, faults);
+}
+
+fn main() {
+    println!("{} workers, {} epochs", WORKERS, EPOCHS);
+
+    run("fresh", |i| {
+        for _ in 0..WORKERS {
+            let mut memory = Box::new(equix::SolverMemory::new());
+            first_hash(&mut memory, i);
+        }
+    });
+
+    let pool = SolverMemoryPool::new();
+    run("pooled", |i| {
+        let mut buffers: Vec<_> = (0..WORKERS).map(|_| pool.checkout()).collect();
+        for memory in buffers.iter_mut() {
+            first_hash(memory, i);
+        }
+        for memory in buffers {
+            pool.checkin(memory);
+        }
+    });
+    assert_eq!(pool.allocated(), WORKERS);
+}
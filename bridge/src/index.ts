@@ -4,6 +4,8 @@ import dotenv from 'dotenv';
 import { 
   MsgExecuteContract,
   MsgExecuteContractCompat,
+  MsgSend,
+  Msgs,
   ChainRestAuthApi,
   createTransaction,
   createTransactionWithSigners,
@@ -29,10 +31,12 @@ interface SignRequest {
   account_number: number;
   sequence: number;
   messages: MessageData[];
+  sends?: BankSend[];
   gas_limit: number;
   gas_price: string;
   memo: string;
   request_id: string;
+  fee_granter?: string;
 }
 
 interface MessageData {
@@ -41,6 +45,11 @@ interface MessageData {
   funds: Coin[];
 }
 
+interface BankSend {
+  to_address: string;
+  amount: Coin[];
+}
+
 interface Coin {
   denom: string;
   amount: string;
@@ -62,9 +71,11 @@ class BridgeService {
   private publicKey: string;
   private ethereumChainId: EthereumChainId;
 
-  constructor(mnemonic: string, networkName: string) {
-    // Initialize private key from mnemonic
-    this.privateKey = PrivateKey.fromMnemonic(mnemonic);
+  constructor(mnemonic: string, networkName: string, hdPath?: string) {
+    // Initialize private key from mnemonic (optionally at a custom HD path)
+    this.privateKey = hdPath
+      ? PrivateKey.fromMnemonic(mnemonic, hdPath)
+      : PrivateKey.fromMnemonic(mnemonic);
     this.publicKey = this.privateKey.toPublicKey().toBase64();
     
     // Get injective address
@@ -134,7 +145,7 @@ class BridgeService {
       console.log(`[${req.request_id}] Message: ${JSON.stringify(req.messages[0]?.msg)}`);
       
       // Create messages array
-      const messages = req.messages.map(msgData => {
+      const contractMessages = req.messages.map(msgData => {
         // Fix for SDK empty object issue
         let processedMsg = msgData.msg;
         
@@ -179,6 +190,17 @@ class BridgeService {
         return executeMsg;
       });
 
+      // Bank sends follow the contract messages
+      const sendMessages = (req.sends || []).map(send => {
+        console.log(`[${req.request_id}] Creating MsgSend to ${send.to_address}:`, JSON.stringify(send.amount));
+        return MsgSend.fromJSON({
+          srcInjectiveAddress: this.address,
+          dstInjectiveAddress: send.to_address,
+          amount: send.amount,
+        });
+      });
+      const messages: Msgs[] = [...contractMessages, ...sendMessages];
+
       // Get account details if not provided
       let accountNumber = req.account_number;
       let sequence = req.sequence;
@@ -200,6 +222,8 @@ class BridgeService {
           amount: new BigNumberInBase(0.0005).toWei().toFixed(), // 0.0005 INJ
         }],
         gas: req.gas_limit.toString(),
+        // feegrant: the granter's allowance pays, so the signer needs no INJ
+        ...(req.fee_granter ? { granter: req.fee_granter, feePayer: req.fee_granter } : {}),
       };
 
       // Debug: Log messages before EIP-712 conversion
@@ -238,6 +262,9 @@ class BridgeService {
           
           // Create a custom message that bypasses the issue
           const customMessages = messages.map(msg => {
+            if (msg instanceof MsgSend) {
+              return msg;
+            }
             const msgObj = msg.toDirectSign ? msg.toDirectSign() : msg;
             // Remove any empty arrays from the message
             const cleanMsg = JSON.parse(JSON.stringify(msgObj, (key, value) => {
@@ -352,7 +379,8 @@ async function main() {
   const apiKey = process.env.BRIDGE_API_KEY;
 
   // Create bridge service
-  const bridge = new BridgeService(mnemonic, network);
+  const hdPath = process.env.HD_PATH;
+  const bridge = new BridgeService(mnemonic, network, hdPath);
 
   // Create Express app
   const app = express();
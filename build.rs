@@ -1,6 +1,17 @@
 use std::io::Result;
 
 fn main() -> Result<()> {
+    // Embed the git commit for `gmine version`
+    let commit = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GMINE_GIT_COMMIT={}", commit);
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    
     // Only compile protos if the proto directory exists
     if std::path::Path::new("proto").exists() {
         println!("cargo:rerun-if-changed=proto");
@@ -1,32 +1,184 @@
-use crate::types::{Epoch, MiningChallenge};
+use crate::types::{Coin, Epoch, Fee, MiningChallenge};
+use crate::eip712::SigningChain;
+use crate::execute_msg::ExecuteMsg;
 use crate::web3_extension::ExtensionOptionsWeb3Tx;
+use crate::tx_log::{self, TxLogEntry};
+use crate::signed_tx_log;
+use crate::tls::{self, ConnectivityReport, TlsOptions};
+use crate::msg_execute_contract_compat::format_funds;
 use serde_json::json;
 use std::error::Error;
-use blake2::{Blake2b512, Digest};
+use std::path::PathBuf;
+use crate::partition::calculate_nonce_range;
 use log;
 use base64::{Engine as _, engine::general_purpose};
 
 // Use official Injective testnet LCD endpoint
 const TESTNET_REST_URL: &str = "https://testnet.sentry.lcd.injective.network:443";
-const MINING_CONTRACT: &str = "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66"; // V3.5 with migration capability
+pub const MINING_CONTRACT: &str = "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66"; // V3.5 with migration capability
 
+const SUBMIT_GAS_LIMIT: u64 = 350000;
+
+/// Blocks that must remain in the reveal phase for a reveal to be worth sending
+/// (~1s blocks; same default as the desktop miner's submission buffer)
+pub const DEFAULT_REVEAL_BUFFER_BLOCKS: u64 = 8;
+
+/// Which chain, LCD endpoint and mining contract the client talks to
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkConfig {
+    pub rest_url: String,
+    pub contract_address: String,
+    pub chain: SigningChain,
+}
+
+impl NetworkConfig {
+    pub fn testnet() -> Self {
+        NetworkConfig {
+            rest_url: TESTNET_REST_URL.to_string(),
+            contract_address: MINING_CONTRACT.to_string(),
+            chain: SigningChain::testnet(),
+        }
+    }
+    
+    /// Validate runtime settings; empty values fall back to the testnet defaults
+    /// A non-testnet chain needs its own contract - the testnet one doesn't exist there
+    pub fn from_parts(rest_url: &str, chain_id: &str, contract_address: &str) -> Result<Self, String> {
+        let (rest_url, chain_id, contract_address) = (rest_url.trim(), chain_id.trim(), contract_address.trim());
+        let mut network = Self::testnet();
+        
+        if !chain_id.is_empty() {
+            network.chain = SigningChain::from_chain_id(chain_id)?;
+        }
+        
+        if !rest_url.is_empty() {
+            if !rest_url.starts_with("https://") && !rest_url.starts_with("http://") {
+                return Err(format!("Invalid REST URL '{}': expected http:// or https://", rest_url));
+            }
+            network.rest_url = rest_url.trim_end_matches('/').to_string();
+        }
+        
+        if !contract_address.is_empty() {
+            let (hrp, _) = bech32::decode(contract_address)
+                .map_err(|e| format!("Invalid contract address '{}': {}", contract_address, e))?;
+            if hrp.as_str() != "inj" {
+                return Err(format!("Invalid contract address '{}': expected an inj address", contract_address));
+            }
+            network.contract_address = contract_address.to_string();
+        } else if network.chain != SigningChain::testnet() {
+            return Err(format!("A contract address is required for chain {}", network.chain.chain_id));
+        }
+        
+        Ok(network)
+    }
+}
+
+#[derive(Clone)]
 pub struct BlockchainClient {
     agent: ureq::Agent,
+    tx_log_path: Option<PathBuf>,
+    /// Feegrant granter paying gas for submitted transactions (empty = the sender pays)
+    fee_granter: String,
+    /// Reveals are aborted when fewer blocks than this remain in the reveal phase
+    reveal_buffer_blocks: u64,
+    network: NetworkConfig,
+}
+
+/// Where the chain is in the reveal phase
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RevealWindow {
+    pub height: u64,
+    pub ends_at: u64,
+    pub blocks_remaining: u64,
 }
 
 impl BlockchainClient {
     pub fn new() -> Self {
-        // Use ureq with default TLS configuration
-        // It should work better on Android than reqwest
-        let agent = ureq::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build();
+        // ureq with explicit rustls roots - works better on Android than reqwest
+        let agent = TlsOptions::default().build_agent();
             
-        BlockchainClient { agent }
+        BlockchainClient {
+            agent,
+            tx_log_path: None,
+            fee_granter: String::new(),
+            reveal_buffer_blocks: DEFAULT_REVEAL_BUFFER_BLOCKS,
+            network: NetworkConfig::testnet(),
+        }
     }
     
-    /// Get current epoch information from contract
-    pub fn get_current_epoch(&self) -> Result<Epoch, Box<dyn Error>> {
+    /// Talk to a different chain, LCD endpoint or contract
+    pub fn set_network(&mut self, network: NetworkConfig) {
+        self.network = network;
+    }
+    
+    pub fn network(&self) -> &NetworkConfig {
+        &self.network
+    }
+    
+    /// Rebuild the HTTP agent with different TLS settings
+    pub fn set_tls_options(&mut self, options: &TlsOptions) {
+        self.agent = options.build_agent();
+    }
+    
+    /// Reach the LCD once and report how it went, including the exact TLS error
+    pub fn check_connectivity(&self) -> ConnectivityReport {
+        let url = format!("{}/cosmos/base/tendermint/v1beta1/node_info", self.network.rest_url);
+        let started = std::time::Instant::now();
+        let error = self.agent.get(&url).call().err().map(|e| tls::describe_error(&e));
+        ConnectivityReport {
+            ok: error.is_none(),
+            url,
+            latency_ms: started.elapsed().as_millis() as u64,
+            error,
+        }
+    }
+    
+    /// Record every submitted transaction to an append-only JSONL file
+    pub fn set_tx_log_path(&mut self, path: Option<PathBuf>) {
+        self.tx_log_path = path;
+    }
+    
+    /// Have a feegrant granter pay gas so the mining wallet needs no INJ (None = pay it ourselves)
+    pub fn set_fee_granter(&mut self, fee_granter: Option<String>) -> Result<(), Box<dyn Error>> {
+        if let Some(ref granter) = fee_granter {
+            let (hrp, data) = bech32::decode(granter)
+                .map_err(|e| format!("Invalid fee granter address '{}': {}", granter, e))?;
+            if hrp.as_str() != "inj" || data.len() != 20 {
+                return Err(format!("Invalid fee granter address '{}': expected a 20-byte inj address", granter).into());
+            }
+        }
+        self.fee_granter = fee_granter.unwrap_or_default();
+        Ok(())
+    }
+    
+    /// Minimum blocks left in the reveal phase for `submit_reveal` to broadcast
+    pub fn set_reveal_buffer_blocks(&mut self, blocks: u64) {
+        self.reveal_buffer_blocks = blocks;
+    }
+    
+    /// Fee the submitted transactions carry - sign over exactly this
+    pub fn fee(&self) -> Fee {
+        Fee {
+            amount: vec![Coin {
+                denom: "inj".to_string(),
+                amount: "500000000000000".to_string(),
+            }],
+            gas: SUBMIT_GAS_LIMIT.to_string(),
+            payer: String::new(),
+            granter: self.fee_granter.clone(),
+        }
+    }
+    
+    fn record_tx(&self, msg: &ExecuteMsg, result: &Result<String, Box<dyn Error>>) {
+        if let Some(path) = &self.tx_log_path {
+            let entry = TxLogEntry::from_result(msg.name(), msg.epoch(), msg.to_json(), SUBMIT_GAS_LIMIT, result);
+            if let Err(e) = tx_log::append_entry(path, &entry) {
+                log::warn!("Failed to write transaction log: {}", e);
+            }
+        }
+    }
+    
+    /// Raw `current_epoch` query response
+    fn query_current_epoch(&self) -> Result<serde_json::Value, Box<dyn Error>> {
         let query_msg = json!({
             "current_epoch": {}
         });
@@ -35,8 +187,8 @@ impl BlockchainClient {
         let query_data = general_purpose::STANDARD.encode(query_msg.to_string());
         let url = format!(
             "{}/cosmwasm/wasm/v1/contract/{}/smart/{}",
-            TESTNET_REST_URL,
-            MINING_CONTRACT,
+            self.network.rest_url,
+            self.network.contract_address,
             query_data
         );
         
@@ -46,22 +198,47 @@ impl BlockchainClient {
             Ok(response) => {
                 let result: serde_json::Value = response.into_json()?;
                 log::info!("Got epoch response: {}", result);
-                
-                // Parse the response
-                if let Some(data) = result.get("data") {
-                    let epoch: Epoch = serde_json::from_value(data.clone())?;
-                    return Ok(epoch);
-                } else {
-                    return Err("Failed to get epoch data".into());
-                }
+                Ok(result)
             }
             Err(e) => {
                 log::error!("HTTP request failed: {}", e);
-                return Err(format!("Failed to get current epoch: {}", e).into());
+                Err(format!("Failed to get current epoch: {}", e).into())
             }
         }
     }
     
+    /// Get current epoch information from contract
+    pub fn get_current_epoch(&self) -> Result<Epoch, Box<dyn Error>> {
+        let epoch = parse_epoch_response(&self.query_current_epoch()?);
+        if let Err(ref e) = epoch {
+            if is_contract_not_ready(e.as_ref()) {
+                log::info!("{} - retry later", e);
+            }
+        }
+        epoch
+    }
+    
+    /// Current height and the end of the reveal phase; errors outside the reveal phase
+    pub fn get_reveal_window(&self) -> Result<RevealWindow, Box<dyn Error>> {
+        let ends_at = parse_reveal_ends_at(&self.query_current_epoch()?)?;
+        let height = self.get_latest_block_height()?;
+        Ok(RevealWindow { height, ends_at, blocks_remaining: ends_at.saturating_sub(height) })
+    }
+    
+    /// Fail unless enough of the reveal phase remains for a reveal to land -
+    /// one broadcast into a closing window only burns gas
+    pub fn check_reveal_window(&self) -> Result<RevealWindow, Box<dyn Error>> {
+        let window = self.get_reveal_window()?;
+        if window.blocks_remaining < self.reveal_buffer_blocks {
+            return Err(format!(
+                "Reveal window closing: {} blocks left (height {}, ends at {}), need at least {} - aborting reveal",
+                window.blocks_remaining, window.height, window.ends_at, self.reveal_buffer_blocks
+            ).into());
+        }
+        log::info!("Reveal window: {} blocks left (height {}, ends at {})", window.blocks_remaining, window.height, window.ends_at);
+        Ok(window)
+    }
+    
     /// Get current mining challenge for wallet
     pub fn get_mining_challenge(&self, wallet_address: &str) -> Result<MiningChallenge, Box<dyn Error>> {
         // First get current epoch
@@ -70,14 +247,8 @@ impl BlockchainClient {
         // Calculate nonce range for this wallet
         let (nonce_start, nonce_end) = calculate_nonce_range(wallet_address, epoch.epoch_number);
         
-        // Convert target_hash to challenge array
-        let mut challenge = [0u8; 32];
-        if epoch.target_hash.len() >= 32 {
-            challenge.copy_from_slice(&epoch.target_hash[..32]);
-        } else {
-            // Pad with zeros if needed
-            challenge[..epoch.target_hash.len()].copy_from_slice(&epoch.target_hash);
-        }
+        // The challenge is the target_hash verbatim - reject anything but 32 bytes
+        let challenge = epoch.challenge()?;
         
         Ok(MiningChallenge {
             challenge,
@@ -92,7 +263,7 @@ impl BlockchainClient {
     pub fn get_account_info(&self, address: &str) -> Result<(u64, u64), Box<dyn Error>> {
         let url = format!(
             "{}/cosmos/auth/v1beta1/accounts/{}",
-            TESTNET_REST_URL,
+            self.network.rest_url,
             address
         );
         
@@ -123,7 +294,7 @@ impl BlockchainClient {
     pub fn get_latest_block_height(&self) -> Result<u64, Box<dyn Error>> {
         let url = format!(
             "{}/cosmos/base/tendermint/v1beta1/blocks/latest",
-            TESTNET_REST_URL
+            self.network.rest_url
         );
         
         log::info!("Fetching latest block from: {}", url);
@@ -151,9 +322,9 @@ impl BlockchainClient {
         }
     }
     
-    /// Submit a mining commitment using Injective's JSON format
-    pub fn submit_commitment(&self, commitment: &str, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
-        log::info!("submit_commitment called with:");
+    /// Unlogged body of `submit_msg`
+    fn submit_msg_inner(&self, msg: &ExecuteMsg, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
+        log::info!("submit {} called with:", msg.name());
         log::info!("  from_address: {}", from_address);
         log::info!("  signature: {}", signature);
         log::info!("  pub_key: {}", pub_key);
@@ -166,18 +337,14 @@ impl BlockchainClient {
                     "messages": [{
                         "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
                         "sender": from_address,
-                        "contract": MINING_CONTRACT,
-                        "msg": json!({
-                            "commit_solution": {
-                                "commitment": commitment
-                            }
-                        }).to_string(),
-                        "funds": "0"
+                        "contract": self.network.contract_address,
+                        "msg": msg.to_contract_json(),
+                        "funds": format_funds(&[])
                     }],
                     "memo": "",
                     "timeout_height": "0",
                     "extension_options": [
-                        ExtensionOptionsWeb3Tx::new_for_testnet().to_any()?
+                        ExtensionOptionsWeb3Tx::for_chain(self.network.chain.eth_chain_id).to_any()?
                     ],
                     "non_critical_extension_options": []
                 },
@@ -201,7 +368,7 @@ impl BlockchainClient {
                         }],
                         "gas_limit": "350000",
                         "payer": "",
-                        "granter": ""
+                        "granter": self.fee_granter
                     }
                 },
                 "signatures": [signature.trim_start_matches("0x")]
@@ -209,10 +376,10 @@ impl BlockchainClient {
             "mode": "BROADCAST_MODE_SYNC"
         });
         
-        let url = format!("{}/cosmos/tx/v1beta1/txs", TESTNET_REST_URL);
+        let url = format!("{}/cosmos/tx/v1beta1/txs", self.network.rest_url);
         
-        log::info!("Submitting transaction to: {}", url);
-        log::debug!("Transaction payload: {}", serde_json::to_string_pretty(&tx).unwrap_or_default());
+        log::info!("Submitting {} transaction to: {}", msg.name(), url);
+        signed_tx_log::log_tx_json(msg.name(), &tx);
         
         let response = match self.agent.post(&url).send_json(&tx) {
             Ok(resp) => resp,
@@ -226,7 +393,7 @@ impl BlockchainClient {
             
         let result: serde_json::Value = response.into_json()?;
         
-        log::info!("Transaction response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
+        log::info!("{} transaction response: {}", msg.name(), serde_json::to_string_pretty(&result).unwrap_or_default());
         
         // Check for tx hash
         if let Some(tx_response) = result.get("tx_response") {
@@ -244,17 +411,18 @@ impl BlockchainClient {
             }
         }
         
-        Err("Failed to broadcast transaction".into())
+        Err(format!("Failed to broadcast {} transaction", msg.name()).into())
     }
     
     /// Broadcast signed transaction
     pub fn broadcast_tx(&self, tx_bytes: &str) -> Result<String, Box<dyn Error>> {
-        let url = format!("{}/cosmos/tx/v1beta1/txs", TESTNET_REST_URL);
+        let url = format!("{}/cosmos/tx/v1beta1/txs", self.network.rest_url);
         
         let body = json!({
             "tx_bytes": tx_bytes,
             "mode": "BROADCAST_MODE_SYNC"
         });
+        signed_tx_log::log_tx_json("broadcast", &body);
         
         let response = self.agent.post(&url)
             .send_json(&body)
@@ -281,393 +449,82 @@ impl BlockchainClient {
         Err("Failed to broadcast transaction".into())
     }
     
+    /// Submit a contract message using Injective's JSON format, signed over `msg` by `Eip712Signer::sign_msg`
+    pub fn submit_msg(&self, msg: &ExecuteMsg, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
+        let result = self.submit_msg_inner(msg, from_address, signature, pub_key, account_number, sequence);
+        self.record_tx(msg, &result);
+        result
+    }
+    
+    /// Submit a mining commitment using Injective's JSON format
+    pub fn submit_commitment(&self, commitment: &str, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
+        let msg = ExecuteMsg::CommitSolution { commitment: json!(commitment) };
+        self.submit_msg(&msg, from_address, signature, pub_key, account_number, sequence)
+    }
+    
     /// Submit a reveal solution using Injective's JSON format
+    /// Aborts without broadcasting if the reveal phase is over or about to end
     pub fn submit_reveal(&self, nonce: &str, digest: &str, salt: &str, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
-        log::info!("submit_reveal called");
-        
-        // Construct the full transaction in Injective's expected format
-        let tx = json!({
-            "tx": {
-                "body": {
-                    "messages": [{
-                        "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                        "sender": from_address,
-                        "contract": MINING_CONTRACT,
-                        "msg": json!({
-                            "reveal_solution": {
-                                "nonce": nonce,
-                                "digest": digest,
-                                "salt": salt
-                            }
-                        }).to_string(),
-                        "funds": "0"
-                    }],
-                    "memo": "",
-                    "timeout_height": "0",
-                    "extension_options": [
-                        ExtensionOptionsWeb3Tx::new_for_testnet().to_any()?
-                    ],
-                    "non_critical_extension_options": []
-                },
-                "auth_info": {
-                    "signer_infos": [{
-                        "public_key": {
-                            "@type": "/injective.crypto.v1beta1.ethsecp256k1.PubKey",
-                            "key": pub_key
-                        },
-                        "mode_info": {
-                            "single": {
-                                "mode": "SIGN_MODE_LEGACY_AMINO_JSON"
-                            }
-                        },
-                        "sequence": sequence.to_string()
-                    }],
-                    "fee": {
-                        "amount": [{
-                            "denom": "inj",
-                            "amount": "500000000000000"
-                        }],
-                        "gas_limit": "350000",
-                        "payer": "",
-                        "granter": ""
-                    }
-                },
-                "signatures": [signature.trim_start_matches("0x")]
-            },
-            "mode": "BROADCAST_MODE_SYNC"
-        });
-        
-        let url = format!("{}/cosmos/tx/v1beta1/txs", TESTNET_REST_URL);
-        
-        log::info!("Submitting reveal transaction to: {}", url);
-        
-        let response = match self.agent.post(&url).send_json(&tx) {
-            Ok(resp) => resp,
-            Err(ureq::Error::Status(code, response)) => {
-                let error_body = response.into_string().unwrap_or_else(|_| "Unable to read error body".to_string());
-                return Err(format!("HTTP request failed: {} - {}: {}", url, code, error_body).into());
-            }
-            Err(e) => return Err(format!("HTTP request failed: {}", e).into()),
-        };
-            
-        let result: serde_json::Value = response.into_json()?;
-        
-        log::info!("Reveal transaction response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
-        
-        // Check for tx hash
-        if let Some(tx_response) = result.get("tx_response") {
-            if let Some(txhash) = tx_response.get("txhash").and_then(|v| v.as_str()) {
-                // Check for errors
-                if let Some(code) = tx_response.get("code").and_then(|v| v.as_u64()) {
-                    if code != 0 {
-                        let raw_log = tx_response.get("raw_log")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("Unknown error");
-                        return Err(format!("Transaction failed: {}", raw_log).into());
-                    }
-                }
-                return Ok(txhash.to_string());
-            }
-        }
-        
-        Err("Failed to broadcast reveal transaction".into())
+        self.check_reveal_window()?;
+        let msg = ExecuteMsg::RevealSolution { nonce: json!(nonce), digest: json!(digest), salt: json!(salt) };
+        self.submit_msg(&msg, from_address, signature, pub_key, account_number, sequence)
     }
     
     /// Submit advance epoch transaction
     pub fn submit_advance_epoch(&self, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
-        log::info!("submit_advance_epoch called");
-        
-        // Construct the full transaction in Injective's expected format
-        let tx = json!({
-            "tx": {
-                "body": {
-                    "messages": [{
-                        "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                        "sender": from_address,
-                        "contract": MINING_CONTRACT,
-                        "msg": json!({
-                            "advance_epoch": {}
-                        }).to_string(),
-                        "funds": "0"
-                    }],
-                    "memo": "",
-                    "timeout_height": "0",
-                    "extension_options": [
-                        ExtensionOptionsWeb3Tx::new_for_testnet().to_any()?
-                    ],
-                    "non_critical_extension_options": []
-                },
-                "auth_info": {
-                    "signer_infos": [{
-                        "public_key": {
-                            "@type": "/injective.crypto.v1beta1.ethsecp256k1.PubKey",
-                            "key": pub_key
-                        },
-                        "mode_info": {
-                            "single": {
-                                "mode": "SIGN_MODE_LEGACY_AMINO_JSON"
-                            }
-                        },
-                        "sequence": sequence.to_string()
-                    }],
-                    "fee": {
-                        "amount": [{
-                            "denom": "inj",
-                            "amount": "500000000000000"
-                        }],
-                        "gas_limit": "350000",
-                        "payer": "",
-                        "granter": ""
-                    }
-                },
-                "signatures": [signature.trim_start_matches("0x")]
-            },
-            "mode": "BROADCAST_MODE_SYNC"
-        });
-        
-        let url = format!("{}/cosmos/tx/v1beta1/txs", TESTNET_REST_URL);
-        
-        log::info!("Submitting advance epoch transaction to: {}", url);
-        
-        let response = match self.agent.post(&url).send_json(&tx) {
-            Ok(resp) => resp,
-            Err(ureq::Error::Status(code, response)) => {
-                let error_body = response.into_string().unwrap_or_else(|_| "Unable to read error body".to_string());
-                return Err(format!("HTTP request failed: {} - {}: {}", url, code, error_body).into());
-            }
-            Err(e) => return Err(format!("HTTP request failed: {}", e).into()),
-        };
-            
-        let result: serde_json::Value = response.into_json()?;
-        
-        log::info!("Advance epoch transaction response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
-        
-        // Check for tx hash
-        if let Some(tx_response) = result.get("tx_response") {
-            if let Some(txhash) = tx_response.get("txhash").and_then(|v| v.as_str()) {
-                // Check for errors
-                if let Some(code) = tx_response.get("code").and_then(|v| v.as_u64()) {
-                    if code != 0 {
-                        let raw_log = tx_response.get("raw_log")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("Unknown error");
-                        return Err(format!("Transaction failed: {}", raw_log).into());
-                    }
-                }
-                return Ok(txhash.to_string());
-            }
-        }
-        
-        Err("Failed to broadcast advance epoch transaction".into())
+        self.submit_msg(&ExecuteMsg::AdvanceEpoch {}, from_address, signature, pub_key, account_number, sequence)
     }
     
     /// Submit finalize epoch transaction
     pub fn submit_finalize_epoch(&self, epoch_number: u64, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
-        log::info!("submit_finalize_epoch called for epoch {}", epoch_number);
-        
-        // Construct the full transaction in Injective's expected format
-        let tx = json!({
-            "tx": {
-                "body": {
-                    "messages": [{
-                        "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                        "sender": from_address,
-                        "contract": MINING_CONTRACT,
-                        "msg": json!({
-                            "finalize_epoch": {
-                                "epoch_number": epoch_number
-                            }
-                        }).to_string(),
-                        "funds": "0"
-                    }],
-                    "memo": "",
-                    "timeout_height": "0",
-                    "extension_options": [
-                        ExtensionOptionsWeb3Tx::new_for_testnet().to_any()?
-                    ],
-                    "non_critical_extension_options": []
-                },
-                "auth_info": {
-                    "signer_infos": [{
-                        "public_key": {
-                            "@type": "/injective.crypto.v1beta1.ethsecp256k1.PubKey",
-                            "key": pub_key
-                        },
-                        "mode_info": {
-                            "single": {
-                                "mode": "SIGN_MODE_LEGACY_AMINO_JSON"
-                            }
-                        },
-                        "sequence": sequence.to_string()
-                    }],
-                    "fee": {
-                        "amount": [{
-                            "denom": "inj",
-                            "amount": "500000000000000"
-                        }],
-                        "gas_limit": "350000",
-                        "payer": "",
-                        "granter": ""
-                    }
-                },
-                "signatures": [signature.trim_start_matches("0x")]
-            },
-            "mode": "BROADCAST_MODE_SYNC"
-        });
-        
-        let url = format!("{}/cosmos/tx/v1beta1/txs", TESTNET_REST_URL);
-        
-        log::info!("Submitting finalize epoch transaction to: {}", url);
-        
-        let response = match self.agent.post(&url).send_json(&tx) {
-            Ok(resp) => resp,
-            Err(ureq::Error::Status(code, response)) => {
-                let error_body = response.into_string().unwrap_or_else(|_| "Unable to read error body".to_string());
-                return Err(format!("HTTP request failed: {} - {}: {}", url, code, error_body).into());
-            }
-            Err(e) => return Err(format!("HTTP request failed: {}", e).into()),
-        };
-            
-        let result: serde_json::Value = response.into_json()?;
-        
-        log::info!("Finalize epoch transaction response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
-        
-        // Check for tx hash
-        if let Some(tx_response) = result.get("tx_response") {
-            if let Some(txhash) = tx_response.get("txhash").and_then(|v| v.as_str()) {
-                // Check for errors
-                if let Some(code) = tx_response.get("code").and_then(|v| v.as_u64()) {
-                    if code != 0 {
-                        let raw_log = tx_response.get("raw_log")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("Unknown error");
-                        return Err(format!("Transaction failed: {}", raw_log).into());
-                    }
-                }
-                return Ok(txhash.to_string());
-            }
-        }
-        
-        Err("Failed to broadcast finalize epoch transaction".into())
+        self.submit_msg(&ExecuteMsg::FinalizeEpoch { epoch_number }, from_address, signature, pub_key, account_number, sequence)
     }
     
     /// Submit claim reward transaction
     pub fn submit_claim_reward(&self, epoch_number: u64, from_address: &str, signature: &str, pub_key: &str, account_number: u64, sequence: u64) -> Result<String, Box<dyn Error>> {
-        log::info!("submit_claim_reward called for epoch {}", epoch_number);
-        
-        // Construct the full transaction in Injective's expected format
-        let tx = json!({
-            "tx": {
-                "body": {
-                    "messages": [{
-                        "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                        "sender": from_address,
-                        "contract": MINING_CONTRACT,
-                        "msg": json!({
-                            "claim_reward": {
-                                "epoch_number": epoch_number
-                            }
-                        }).to_string(),
-                        "funds": "0"
-                    }],
-                    "memo": "",
-                    "timeout_height": "0",
-                    "extension_options": [
-                        ExtensionOptionsWeb3Tx::new_for_testnet().to_any()?
-                    ],
-                    "non_critical_extension_options": []
-                },
-                "auth_info": {
-                    "signer_infos": [{
-                        "public_key": {
-                            "@type": "/injective.crypto.v1beta1.ethsecp256k1.PubKey",
-                            "key": pub_key
-                        },
-                        "mode_info": {
-                            "single": {
-                                "mode": "SIGN_MODE_LEGACY_AMINO_JSON"
-                            }
-                        },
-                        "sequence": sequence.to_string()
-                    }],
-                    "fee": {
-                        "amount": [{
-                            "denom": "inj",
-                            "amount": "500000000000000"
-                        }],
-                        "gas_limit": "350000",
-                        "payer": "",
-                        "granter": ""
-                    }
-                },
-                "signatures": [signature.trim_start_matches("0x")]
-            },
-            "mode": "BROADCAST_MODE_SYNC"
-        });
-        
-        let url = format!("{}/cosmos/tx/v1beta1/txs", TESTNET_REST_URL);
-        
-        log::info!("Submitting claim reward transaction to: {}", url);
-        
-        let response = match self.agent.post(&url).send_json(&tx) {
-            Ok(resp) => resp,
-            Err(ureq::Error::Status(code, response)) => {
-                let error_body = response.into_string().unwrap_or_else(|_| "Unable to read error body".to_string());
-                return Err(format!("HTTP request failed: {} - {}: {}", url, code, error_body).into());
-            }
-            Err(e) => return Err(format!("HTTP request failed: {}", e).into()),
-        };
-            
-        let result: serde_json::Value = response.into_json()?;
-        
-        log::info!("Claim reward transaction response: {}", serde_json::to_string_pretty(&result).unwrap_or_default());
-        
-        // Check for tx hash
-        if let Some(tx_response) = result.get("tx_response") {
-            if let Some(txhash) = tx_response.get("txhash").and_then(|v| v.as_str()) {
-                // Check for errors
-                if let Some(code) = tx_response.get("code").and_then(|v| v.as_u64()) {
-                    if code != 0 {
-                        let raw_log = tx_response.get("raw_log")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("Unknown error");
-                        return Err(format!("Transaction failed: {}", raw_log).into());
-                    }
-                }
-                return Ok(txhash.to_string());
-            }
-        }
-        
-        Err("Failed to broadcast claim reward transaction".into())
+        self.submit_msg(&ExecuteMsg::ClaimReward { epoch_number }, from_address, signature, pub_key, account_number, sequence)
     }
 }
 
-/// Calculate nonce range for a wallet address
-/// This must match the contract's drillx_utils::get_nonce_range_for_address
-fn calculate_nonce_range(address: &str, epoch: u64) -> (u64, u64) {
-    // Create commitment using Blake2b512
-    let mut hasher = Blake2b512::new();
-    hasher.update(address.as_bytes());
-    hasher.update(epoch.to_le_bytes());
-    let commitment = hasher.finalize();
-    
-    // Extract first 8 bytes as u64
-    let mut bytes = [0u8; 8];
-    bytes.copy_from_slice(&commitment[..8]);
-    let hash_value = u64::from_le_bytes(bytes);
-    
-    // Calculate partition (1/1000th of nonce space)
-    let partition_size = u64::MAX / 1000;
-    let partition_index = hash_value % 1000;
-    
-    let nonce_start = partition_index * partition_size;
-    let nonce_end = if partition_index == 999 {
-        u64::MAX
-    } else {
-        (partition_index + 1) * partition_size - 1
+/// Fields without which an epoch response can't be used at all
+const REQUIRED_EPOCH_FIELDS: [&str; 2] = ["epoch_number", "target_hash"];
+
+/// Parse the `data` of a `current_epoch` LCD response
+/// Empty or partial data (contract migration, node resync) is a "contract not ready"
+/// error the caller should retry with backoff; anything else unparseable is malformed
+fn parse_epoch_response(result: &serde_json::Value) -> Result<Epoch, Box<dyn Error>> {
+    let data = result.get("data").unwrap_or(&serde_json::Value::Null);
+    let missing: Vec<&str> = match data {
+        serde_json::Value::Object(map) => REQUIRED_EPOCH_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| map.get(*field).map_or(true, |v| v.is_null()))
+            .collect(),
+        serde_json::Value::Null => REQUIRED_EPOCH_FIELDS.to_vec(),
+        other => return Err(format!("Malformed epoch response: expected an object, got {}", other).into()),
     };
+    if !missing.is_empty() {
+        return Err(format!("Contract not ready: epoch response is missing {}", missing.join(", ")).into());
+    }
     
-    (nonce_start, nonce_end)
+    serde_json::from_value(data.clone()).map_err(|e| format!("Malformed epoch response: {}", e).into())
+}
+
+/// `ends_at` of the reveal phase from a `current_epoch` response (`"phase": {"reveal": {"ends_at": N}}`)
+fn parse_reveal_ends_at(result: &serde_json::Value) -> Result<u64, Box<dyn Error>> {
+    let phase = result.get("data").and_then(|d| d.get("phase")).ok_or("Epoch response has no phase")?;
+    match phase.get("reveal").and_then(|r| r.get("ends_at")).and_then(|e| e.as_u64()) {
+        Some(ends_at) => Ok(ends_at),
+        None => {
+            let name = phase.as_object().and_then(|p| p.keys().next().cloned()).unwrap_or_else(|| phase.to_string());
+            Err(format!("Not in the reveal phase (phase: {})", name).into())
+        }
+    }
+}
+
+/// Whether an error from `get_current_epoch` means the contract is temporarily unavailable
+pub fn is_contract_not_ready(error: &dyn Error) -> bool {
+    error.to_string().starts_with("Contract not ready")
 }
 
 #[cfg(test)]
@@ -685,4 +542,66 @@ mod tests {
         assert!(end - start >= u64::MAX / 1000 - 1);
         assert!(end - start <= u64::MAX / 1000 + 1);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_fee_granter() {
+        let mut client = BlockchainClient::new();
+        assert_eq!(client.fee().granter, "");
+        
+        client.set_fee_granter(Some("inj1h2rq8q2ly6mwgwv4jcd5qpjvfqwvwee5v9n032".to_string())).unwrap();
+        assert_eq!(client.fee().granter, "inj1h2rq8q2ly6mwgwv4jcd5qpjvfqwvwee5v9n032");
+        assert_eq!(client.fee().gas, SUBMIT_GAS_LIMIT.to_string());
+        
+        assert!(client.set_fee_granter(Some("cosmos1npvwllfr9dqr8erajqqr6s0vxnk2ak55fsjtl6".to_string())).is_err());
+        assert!(client.set_fee_granter(Some("inj1short".to_string())).is_err());
+    }
+    
+    #[test]
+    fn test_network_from_parts() {
+        assert_eq!(NetworkConfig::from_parts("", "", "").unwrap(), NetworkConfig::testnet());
+        
+        let network = NetworkConfig::from_parts(
+            " https://lcd.example.org/ ", "injective-1", "inj1h2rq8q2ly6mwgwv4jcd5qpjvfqwvwee5v9n032",
+        ).unwrap();
+        assert_eq!(network.rest_url, "https://lcd.example.org");
+        assert_eq!(network.chain, SigningChain::mainnet());
+        assert_eq!(network.contract_address, "inj1h2rq8q2ly6mwgwv4jcd5qpjvfqwvwee5v9n032");
+        
+        assert!(NetworkConfig::from_parts("lcd.example.org", "", "").is_err());
+        assert!(NetworkConfig::from_parts("", "injective-999", "").is_err());
+        assert!(NetworkConfig::from_parts("", "", "cosmos1npvwllfr9dqr8erajqqr6s0vxnk2ak55fsjtl6").is_err());
+        // The testnet contract doesn't exist on mainnet
+        assert!(NetworkConfig::from_parts("", "injective-1", "").is_err());
+    }
+    
+    #[test]
+    fn test_parse_reveal_ends_at() {
+        let reveal = json!({"data": {"epoch_number": 12, "phase": {"reveal": {"ends_at": 460}}}});
+        assert_eq!(parse_reveal_ends_at(&reveal).unwrap(), 460);
+        
+        let settlement = json!({"data": {"epoch_number": 12, "phase": {"settlement": {"ends_at": 480}}}});
+        let err = parse_reveal_ends_at(&settlement).unwrap_err();
+        assert!(err.to_string().contains("settlement"), "{}", err);
+        
+        assert!(parse_reveal_ends_at(&json!({"data": {"epoch_number": 12}})).is_err());
+    }
+    
+    #[test]
+    fn test_parse_partial_epoch_response() {
+        // Empty or partial data while the contract migrates
+        for response in [json!({}), json!({"data": null}), json!({"data": {"epoch_number": 12}})] {
+            let err = parse_epoch_response(&response).unwrap_err();
+            assert!(is_contract_not_ready(err.as_ref()), "{}", err);
+        }
+        
+        // Present but wrongly typed is malformed, not "not ready"
+        let err = parse_epoch_response(&json!({"data": {"epoch_number": "x", "target_hash": []}})).unwrap_err();
+        assert!(!is_contract_not_ready(err.as_ref()));
+        
+        let epoch = parse_epoch_response(&json!({
+            "data": {"epoch_number": 12, "start_block": 100, "difficulty": 9, "target_hash": [1, 2]}
+        })).unwrap();
+        assert_eq!(epoch.epoch_number, 12);
+        assert_eq!(epoch.target_hash, vec![1, 2]);
+    }
+}
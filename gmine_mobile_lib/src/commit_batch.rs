@@ -0,0 +1,120 @@
+//! Commit batching - solutions for different epochs found within a short window are
+//! committed together off a single account query, with the sequence advanced locally
+//! per accepted commit. The contract takes one commit per miner per epoch, so a batch
+//! holds the best solution of each epoch waiting (see `SolutionQueue::take_batch`).
+//! Besides saving LCD round trips, this avoids re-querying between back-to-back
+//! commits, where a lagging LCD can hand back the sequence of the commit just sent
+use std::time::{Duration, Instant};
+
+/// Longest batch window accepted - every solution in the batch waits up to this long
+pub const MAX_COMMIT_BATCH_WINDOW: Duration = Duration::from_secs(5);
+
+/// Most epochs committed per batch; solutions for later epochs wait for the next poll
+pub const MAX_COMMIT_BATCH_EPOCHS: usize = 5;
+
+/// Whether a batch whose first solution was queued at `started` is due for submission
+/// A zero window disables batching: every solution is submitted as soon as it's polled
+pub fn batch_due(started: Option<Instant>, window: Duration, now: Instant) -> bool {
+    match started {
+        Some(started) => now.saturating_duration_since(started) >= window.min(MAX_COMMIT_BATCH_WINDOW),
+        None => true,
+    }
+}
+
+/// Account number and sequence the commits of a batch are signed with: queried for
+/// the first commit, then advanced locally for each commit the chain accepts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchSequence {
+    batching: bool,
+    next: Option<(u64, u64)>,
+}
+
+impl BatchSequence {
+    /// With `batching` off every commit queries the account itself
+    pub fn new(batching: bool) -> Self {
+        BatchSequence { batching, next: None }
+    }
+
+    /// Account number and sequence to sign the next commit with, calling `query` only
+    /// when no earlier commit in the batch left a known sequence
+    pub fn account<E>(&mut self, query: impl FnOnce() -> Result<(u64, u64), E>) -> Result<(u64, u64), E> {
+        match self.next {
+            Some(account) => Ok(account),
+            None => query(),
+        }
+    }
+
+    /// The commit signed with `account_number`/`sequence` was accepted
+    pub fn accepted(&mut self, account_number: u64, sequence: u64) {
+        if self.batching {
+            self.next = Some((account_number, sequence + 1));
+        }
+    }
+
+    /// Unknown whether the failed commit consumed the sequence - query again
+    pub fn failed(&mut self) {
+        self.next = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sign and "broadcast" one commit per epoch the way `process_pending_solutions`
+    /// does, returning the account queries made and the (account, sequence) each
+    /// commit was signed with
+    fn commit_epochs(batching: bool, epochs: &[u64], rejected: &[u64]) -> (u32, Vec<(u64, u64)>) {
+        let mut queries = 0;
+        let mut chain_sequence = 7;
+        let mut signed_with = Vec::new();
+        let mut batch = BatchSequence::new(batching);
+        for epoch in epochs {
+            let account = batch.account(|| {
+                queries += 1;
+                Ok::<_, String>((42, chain_sequence))
+            });
+            let (account_number, sequence) = account.unwrap();
+            signed_with.push((account_number, sequence));
+            if rejected.contains(epoch) {
+                batch.failed();
+            } else {
+                chain_sequence += 1;
+                batch.accepted(account_number, sequence);
+            }
+        }
+        (queries, signed_with)
+    }
+
+    #[test]
+    fn test_batch_commits_are_signed_from_one_account_query() {
+        let (queries, signed_with) = commit_epochs(true, &[10, 11, 12], &[]);
+        assert_eq!(queries, 1);
+        assert_eq!(signed_with, vec![(42, 7), (42, 8), (42, 9)]);
+
+        // Without batching each commit queries the account itself
+        let (queries, signed_with) = commit_epochs(false, &[10, 11, 12], &[]);
+        assert_eq!(queries, 3);
+        assert_eq!(signed_with, vec![(42, 7), (42, 8), (42, 9)]);
+    }
+
+    #[test]
+    fn test_failed_commit_requeries_the_account() {
+        // Epoch 11's commit failed without consuming the sequence: the next commit
+        // re-queries instead of signing with a sequence one too high
+        let (queries, signed_with) = commit_epochs(true, &[10, 11, 12, 13], &[11]);
+        assert_eq!(queries, 2);
+        assert_eq!(signed_with, vec![(42, 7), (42, 8), (42, 8), (42, 9)]);
+    }
+
+    #[test]
+    fn test_batch_window_is_bounded() {
+        let now = Instant::now();
+        let started = now - Duration::from_millis(500);
+
+        assert!(batch_due(None, Duration::from_secs(1), now));
+        assert!(batch_due(Some(started), Duration::ZERO, now));
+        assert!(!batch_due(Some(started), Duration::from_secs(1), now));
+        assert!(batch_due(Some(now - MAX_COMMIT_BATCH_WINDOW), Duration::from_secs(3600), now));
+    }
+}
@@ -1,15 +1,47 @@
 use crate::types::{Fee, SigningResult};
+use crate::execute_msg::ExecuteMsg;
+use crate::msg_execute_contract_compat::format_funds;
 use k256::ecdsa::{SigningKey, Signature, signature::Signer};
 use sha3::{Digest, Keccak256};
 use serde_json::json;
 use std::error::Error;
 
-const CHAIN_ID: &str = "injective-888";
-const MINING_CONTRACT: &str = "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66"; // V3.5 with migration capability
+/// Chain a signature is bound to: the Cosmos chain id in the signed message and the
+/// Ethereum chain id in the EIP-712 domain (and the Web3 extension)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigningChain {
+    pub chain_id: String,
+    pub eth_chain_id: u64,
+}
+
+impl SigningChain {
+    pub fn testnet() -> Self {
+        Self { chain_id: "injective-888".to_string(), eth_chain_id: 1439 }
+    }
+    
+    pub fn mainnet() -> Self {
+        Self { chain_id: "injective-1".to_string(), eth_chain_id: 1 }
+    }
+    
+    /// Known Injective chain by its Cosmos chain id
+    pub fn from_chain_id(chain_id: &str) -> Result<Self, String> {
+        [Self::testnet(), Self::mainnet()]
+            .into_iter()
+            .find(|chain| chain.chain_id == chain_id)
+            .ok_or_else(|| format!("Unknown chain id '{}' (expected injective-888 or injective-1)", chain_id))
+    }
+}
+
+impl Default for SigningChain {
+    fn default() -> Self {
+        Self::testnet()
+    }
+}
 
 pub struct Eip712Signer {
     signing_key: SigningKey,
     public_key: Vec<u8>,
+    chain: SigningChain,
 }
 
 impl Eip712Signer {
@@ -19,30 +51,55 @@ impl Eip712Signer {
         Ok(Eip712Signer {
             signing_key,
             public_key: public_key.to_vec(),
+            chain: SigningChain::default(),
         })
     }
     
-    /// Sign a transaction message using EIP-712
+    /// Sign for this chain instead of testnet
+    pub fn set_chain(&mut self, chain: SigningChain) {
+        self.chain = chain;
+    }
+    
+    /// Sign a transaction message given by type name and body (as the JNI API passes it)
     pub fn sign_transaction(
         &self,
         msg_type: &str,
         msg_data: &serde_json::Value,
         sender_address: &str,
+        contract_address: &str,
         account_number: u64,
         sequence: u64,
         fee: Option<Fee>,
         memo: &str,
     ) -> Result<SigningResult, Box<dyn Error>> {
-        eprintln!("EIP712::sign_transaction called with msg_type: {}", msg_type);
+        let msg = ExecuteMsg::from_parts(msg_type, msg_data)?;
+        self.sign_msg(&msg, sender_address, contract_address, account_number, sequence, fee, memo)
+    }
+    
+    /// Sign a transaction message using EIP-712
+    /// `contract_address` must be the contract the broadcast message executes on -
+    /// the signature covers it, so signing over any other address is rejected by the chain
+    pub fn sign_msg(
+        &self,
+        msg: &ExecuteMsg,
+        sender_address: &str,
+        contract_address: &str,
+        account_number: u64,
+        sequence: u64,
+        fee: Option<Fee>,
+        memo: &str,
+    ) -> Result<SigningResult, Box<dyn Error>> {
+        eprintln!("EIP712::sign_msg called with msg_type: {}", msg.name());
         // Use default fee if not provided
         let fee = fee.unwrap_or_default();
         
         eprintln!("EIP712: Building typed data...");
         // Build EIP-712 typed data
         let typed_data = match build_typed_data(
-            msg_type,
-            msg_data,
+            &self.chain,
+            msg,
             sender_address,
+            contract_address,
             account_number,
             sequence,
             &fee,
@@ -61,6 +118,7 @@ impl Eip712Signer {
         // Hash the typed data
         let hash = hash_typed_data(&typed_data)?;
         eprintln!("EIP712: Hash computed, length: {}", hash.len());
+        crate::signed_tx_log::log_sign_hash("EIP-712", msg.name(), account_number, sequence, &hash);
         
         // Sign using secp256k1 for recoverable signatures
         // Convert k256 key to secp256k1 format
@@ -122,152 +180,20 @@ impl Eip712Signer {
 
 /// Build EIP-712 typed data for Injective
 fn build_typed_data(
-    msg_type: &str,
-    msg_data: &serde_json::Value,
+    chain: &SigningChain,
+    msg: &ExecuteMsg,
     sender_address: &str,
+    contract_address: &str,
     account_number: u64,
     sequence: u64,
     fee: &Fee,
     memo: &str,
 ) -> Result<serde_json::Value, Box<dyn Error>> {
-    // Map message type to proper Injective format matching what transaction_manager sends
-    let (injective_msg_type, formatted_msg) = match msg_type {
-        "commit" | "commit_solution" => {
-            log::info!("Processing commit message. msg_data: {}", msg_data);
-            // Keep commitment as is - don't convert to base64
-            let commitment = msg_data.get("commitment")
-                .ok_or("Missing commitment field")?;
-            (
-                "wasmx/MsgExecuteContractCompat",
-                json!({
-                    "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                    "contract": MINING_CONTRACT,
-                    "msg": {
-                        "commit_solution": {
-                            "commitment": commitment
-                        }
-                    },
-                    "sender": sender_address,
-                    "funds": ""
-                })
-            )
-        },
-        "reveal" | "reveal_solution" => {
-            // Keep arrays as is - don't convert to base64
-            let nonce = msg_data.get("nonce")
-                .ok_or("Missing nonce field")?;
-            let digest = msg_data.get("digest")
-                .ok_or("Missing digest field")?;
-            let salt = msg_data.get("salt")
-                .ok_or("Missing salt field")?;
-            
-            (
-                "wasmx/MsgExecuteContractCompat",
-                json!({
-                    "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                    "contract": MINING_CONTRACT,
-                    "msg": {
-                        "reveal_solution": {
-                            "nonce": nonce,
-                            "digest": digest,
-                            "salt": salt
-                        }
-                    },
-                    "sender": sender_address,
-                    "funds": ""
-                })
-            )
-        },
-        "claim_rewards" | "claim_reward" => {
-            // Handle both claim_rewards (what rust_signer sends) and claim_reward (what should be sent)
-            let epoch_number = msg_data.get("epoch_number")
-                .and_then(|v| v.as_u64());
-            
-            let msg_content = if let Some(epoch) = epoch_number {
-                json!({
-                    "claim_reward": {
-                        "epoch_number": epoch
-                    }
-                })
-            } else {
-                // Fallback for backward compatibility
-                json!({
-                    "claim_reward": {}
-                })
-            };
-            
-            (
-                "wasmx/MsgExecuteContractCompat",
-                json!({
-                    "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                    "contract": MINING_CONTRACT,
-                    "msg": msg_content,
-                    "sender": sender_address,
-                    "funds": ""
-                })
-            )
-        },
-        "advance_epoch" => {
-            (
-                "wasmx/MsgExecuteContractCompat",
-                json!({
-                    "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                    "contract": MINING_CONTRACT,
-                    "msg": {
-                        "advance_epoch": {}
-                    },
-                    "sender": sender_address,
-                    "funds": ""
-                })
-            )
-        },
-        "finalize_epoch" => {
-            let epoch_number = msg_data.get("epoch_number")
-                .and_then(|v| v.as_u64())
-                .ok_or("Missing epoch_number for finalize_epoch")?;
-            (
-                "wasmx/MsgExecuteContractCompat",
-                json!({
-                    "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
-                    "contract": MINING_CONTRACT,
-                    "msg": {
-                        "finalize_epoch": {
-                            "epoch_number": epoch_number
-                        }
-                    },
-                    "sender": sender_address,
-                    "funds": ""
-                })
-            )
-        },
-        _ => return Err(format!("Unknown message type: {} with data: {}", msg_type, msg_data).into()),
-    };
-    
-    // Get msg as object for EIP-712 (will be stringified later for protobuf)
-    let msg_content_obj = formatted_msg.get("msg").ok_or("Missing msg field")?.clone();
-    
-    // For MsgExecuteContractCompat, funds is a string field ("" for empty, matching Node.js)
-    let funds_str = if let Some(funds_array) = formatted_msg.get("funds").and_then(|v| v.as_array()) {
-        if funds_array.is_empty() {
-            "0".to_string()  // Chain expects "0" for empty funds
-        } else {
-            // Format as comma-separated string like "100inj,200usdt"
-            funds_array.iter()
-                .filter_map(|coin| {
-                    let denom = coin.get("denom")?.as_str()?;
-                    let amount = coin.get("amount")?.as_str()?;
-                    Some(format!("{}{}", amount, denom))
-                })
-                .collect::<Vec<_>>()
-                .join(",")
-        }
-    } else {
-        "0".to_string()  // Chain expects "0" for empty funds
-    };
+    // Injective signs the contract message as a string, exactly as broadcast
+    let msg_str = msg.to_contract_json();
     
-    // Convert msg to string for EIP-712 signing (Injective expects this)
-    let msg_str = serde_json::to_string(&msg_content_obj)
-        .map_err(|e| format!("Failed to stringify msg: {}", e))?;
+    // For MsgExecuteContractCompat, funds is a string field - the miner never sends funds
+    let funds_str = format_funds(&[]);
     
     // CRITICAL: Field order must match the type definition for EIP-712!
     // The MsgValue type defines fields in order: sender, contract, msg, funds
@@ -275,8 +201,8 @@ fn build_typed_data(
     // Using a Vec to maintain order instead of json! macro which doesn't guarantee order
     let mut msg_value_map = serde_json::Map::new();
     // Insert in the exact order required by the type definition
-    msg_value_map.insert("sender".to_string(), formatted_msg.get("sender").unwrap_or(&json!("")).clone());
-    msg_value_map.insert("contract".to_string(), formatted_msg.get("contract").unwrap_or(&json!(MINING_CONTRACT)).clone());
+    msg_value_map.insert("sender".to_string(), json!(sender_address));
+    msg_value_map.insert("contract".to_string(), json!(contract_address));
     msg_value_map.insert("msg".to_string(), json!(msg_str));            // String for EIP-712 (Injective requirement)
     msg_value_map.insert("funds".to_string(), json!(funds_str));        // String to match protobuf
     let msg_value = serde_json::Value::Object(msg_value_map);
@@ -306,10 +232,19 @@ fn build_typed_data(
         { "name": "timeout_height", "type": "string" }
     ]));
     
-    types.insert("Fee".to_string(), json!([
-        { "name": "amount", "type": "Coin[]" },
-        { "name": "gas", "type": "string" }
-    ]));
+    // With a feegrant the chain signs over the granter as `feePayer`, listed first
+    if fee.granter.is_empty() {
+        types.insert("Fee".to_string(), json!([
+            { "name": "amount", "type": "Coin[]" },
+            { "name": "gas", "type": "string" }
+        ]));
+    } else {
+        types.insert("Fee".to_string(), json!([
+            { "name": "feePayer", "type": "string" },
+            { "name": "amount", "type": "Coin[]" },
+            { "name": "gas", "type": "string" }
+        ]));
+    }
     
     types.insert("Coin".to_string(), json!([
         { "name": "denom", "type": "string" },
@@ -329,26 +264,31 @@ fn build_typed_data(
         { "name": "funds", "type": "string" }
     ]));
     
+    let mut fee_value = json!({
+        "amount": fee.amount.iter().map(|c| json!({
+            "denom": c.denom,
+            "amount": c.amount
+        })).collect::<Vec<_>>(),
+        "gas": fee.gas
+    });
+    if !fee.granter.is_empty() {
+        fee_value["feePayer"] = json!(fee.granter);
+    }
+    
     let typed_data = json!({
         "types": types,
         "primaryType": "Tx",
         "domain": {
             "name": "Injective Web3",
             "version": "1.0.0",
-            "chainId": "0x59f", // Hex format for 1439 (Ethereum chain ID that chain expects)
+            "chainId": format!("0x{:x}", chain.eth_chain_id), // Hex, e.g. 0x59f for testnet's 1439
             "verifyingContract": "cosmos",
             "salt": "0"
         },
         "message": {
             "account_number": account_number.to_string(),
-            "chain_id": CHAIN_ID,
-            "fee": {
-                "amount": fee.amount.iter().map(|c| json!({
-                    "denom": c.denom,
-                    "amount": c.amount
-                })).collect::<Vec<_>>(),
-                "gas": fee.gas
-            },
+            "chain_id": chain.chain_id,
+            "fee": fee_value,
             "memo": memo,
             "msgs": [{
                 "type": "wasmx/MsgExecuteContractCompat", // Use wasmx amino type
@@ -722,6 +662,7 @@ mod tests {
             "commit",
             &msg_data,
             "inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz",
+            "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66",
             36669,
             35849,
             Some(fee),
@@ -735,4 +676,56 @@ mod tests {
         // The signature should be a 132-character hex string (65 bytes * 2 + "0x")
         assert!(result.signature.unwrap().len() == 132);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_signed_msg_and_funds_match_broadcast() {
+        use crate::msg_execute_contract_compat::MsgExecuteContractCompat;
+        
+        let msgs = [
+            ExecuteMsg::CommitSolution { commitment: json!("ab01") },
+            ExecuteMsg::RevealSolution { nonce: json!([1]), digest: json!([2]), salt: json!([3]) },
+            ExecuteMsg::ClaimReward { epoch_number: 7 },
+            ExecuteMsg::FinalizeEpoch { epoch_number: 7 },
+            ExecuteMsg::AdvanceEpoch {},
+        ];
+        for msg in msgs {
+            // Every message type signs the same message and empty-funds strings the submit paths broadcast
+            let broadcast = MsgExecuteContractCompat::new("inj1a".to_string(), "inj1b".to_string(), msg.to_json(), vec![]);
+            let typed_data = build_typed_data(&SigningChain::testnet(), &msg, "inj1a", "inj1b", 1, 2, &Fee::default(), "").unwrap();
+            let signed = &typed_data["message"]["msgs"][0]["value"];
+            assert_eq!(signed["msg"], json!(broadcast.msg));
+            assert_eq!(signed["funds"], json!(broadcast.funds));
+        }
+    }
+    
+    #[test]
+    fn test_signs_over_target_contract() {
+        let private_key = hex::decode("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").unwrap();
+        let public_key = hex::decode("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9").unwrap();
+        let signer = Eip712Signer::new(&private_key, &public_key).unwrap();
+        let sender = "inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz";
+        let msg_data = json!({"epoch_number": 7});
+        let msg = ExecuteMsg::FinalizeEpoch { epoch_number: 7 };
+        
+        for contract in ["inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66", "inj1h2rq8q2ly6mwgwv4jcd5qpjvfqwvwee5v9n032"] {
+            let typed_data = build_typed_data(&SigningChain::testnet(), &msg, sender, contract, 1, 2, &Fee::default(), "").unwrap();
+            assert_eq!(typed_data["message"]["msgs"][0]["value"]["contract"], json!(contract));
+        }
+        
+        // A feegrant granter is signed over as the fee payer
+        let granted = Fee { granter: "inj1h2rq8q2ly6mwgwv4jcd5qpjvfqwvwee5v9n032".to_string(), ..Fee::default() };
+        let typed_data = build_typed_data(&SigningChain::testnet(), &msg, sender, "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66", 1, 2, &granted, "").unwrap();
+        assert_eq!(typed_data["message"]["fee"]["feePayer"], json!(granted.granter));
+        assert_eq!(typed_data["types"]["Fee"][0]["name"], json!("feePayer"));
+        
+        // The contract is part of the signed payload
+        let sign = |contract: &str| signer
+            .sign_transaction("finalize_epoch", &msg_data, sender, contract, 1, 2, None, "")
+            .unwrap()
+            .signature;
+        assert_ne!(
+            sign("inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66"),
+            sign("inj1h2rq8q2ly6mwgwv4jcd5qpjvfqwvwee5v9n032")
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Mining contract execute messages, typed once and used for both the EIP-712
+//! signature and the broadcast transaction, so the two can't drift apart
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A mining contract `ExecuteMsg`. Byte fields keep the JSON form the caller built
+/// them in (number arrays or strings) - the contract parses exactly what is signed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    CommitSolution { commitment: Value },
+    RevealSolution { nonce: Value, digest: Value, salt: Value },
+    ClaimReward { epoch_number: u64 },
+    FinalizeEpoch { epoch_number: u64 },
+    AdvanceEpoch {},
+    UnstakeTokens { amount: String },
+}
+
+impl ExecuteMsg {
+    /// Contract message name, e.g. `commit_solution`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExecuteMsg::CommitSolution { .. } => "commit_solution",
+            ExecuteMsg::RevealSolution { .. } => "reveal_solution",
+            ExecuteMsg::ClaimReward { .. } => "claim_reward",
+            ExecuteMsg::FinalizeEpoch { .. } => "finalize_epoch",
+            ExecuteMsg::AdvanceEpoch {} => "advance_epoch",
+            ExecuteMsg::UnstakeTokens { .. } => "unstake_tokens",
+        }
+    }
+
+    /// Epoch the message is about, if it names one
+    pub fn epoch(&self) -> Option<u64> {
+        match self {
+            ExecuteMsg::ClaimReward { epoch_number } | ExecuteMsg::FinalizeEpoch { epoch_number } => Some(*epoch_number),
+            _ => None,
+        }
+    }
+
+    /// Parse a message from its type name and body, accepting the short names older
+    /// callers use (`commit`, `reveal`, `claim_rewards`)
+    pub fn from_parts(msg_type: &str, body: &Value) -> Result<Self, String> {
+        let name = match msg_type {
+            "commit" => "commit_solution",
+            "reveal" => "reveal_solution",
+            "claim_rewards" => "claim_reward",
+            other => other,
+        };
+        let mut wrapped = serde_json::Map::new();
+        wrapped.insert(name.to_string(), body.clone());
+        serde_json::from_value(Value::Object(wrapped))
+            .map_err(|e| format!("Unsupported or malformed {} message {}: {}", msg_type, body, e))
+    }
+
+    /// Parse a single-key contract message, e.g. `{"claim_reward": {"epoch_number": 3}}`
+    pub fn from_json(msg: &Value) -> Result<Self, String> {
+        match msg.as_object() {
+            Some(map) if map.len() == 1 => {
+                let (msg_type, body) = map.iter().next().unwrap();
+                Self::from_parts(msg_type, body)
+            }
+            _ => Err(format!("Expected a single-key contract message, got: {}", msg)),
+        }
+    }
+
+    /// The message as JSON, `{name: body}`
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// The JSON string `MsgExecuteContractCompat.msg` carries - sign and broadcast exactly this
+    pub fn to_contract_json(&self) -> String {
+        self.to_json().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_messages_serialize_as_the_contract_expects() {
+        let cases = [
+            (ExecuteMsg::CommitSolution { commitment: json!("ab01") }, json!({"commit_solution": {"commitment": "ab01"}})),
+            (
+                ExecuteMsg::RevealSolution { nonce: json!([1, 2]), digest: json!([3]), salt: json!([4]) },
+                json!({"reveal_solution": {"nonce": [1, 2], "digest": [3], "salt": [4]}}),
+            ),
+            (ExecuteMsg::ClaimReward { epoch_number: 42 }, json!({"claim_reward": {"epoch_number": 42}})),
+            (ExecuteMsg::FinalizeEpoch { epoch_number: 42 }, json!({"finalize_epoch": {"epoch_number": 42}})),
+            (ExecuteMsg::AdvanceEpoch {}, json!({"advance_epoch": {}})),
+            (ExecuteMsg::UnstakeTokens { amount: "5000000".to_string() }, json!({"unstake_tokens": {"amount": "5000000"}})),
+        ];
+        for (msg, expected) in cases {
+            assert_eq!(msg.to_json(), expected);
+            assert_eq!(msg.to_contract_json(), expected.to_string());
+            assert_eq!(ExecuteMsg::from_json(&expected).unwrap(), msg);
+            assert_eq!(Some(msg.name()), expected.as_object().unwrap().keys().next().map(String::as_str));
+        }
+    }
+
+    #[test]
+    fn test_parses_short_names_and_rejects_malformed_messages() {
+        let msg = ExecuteMsg::from_parts("commit", &json!({"commitment": "ab01"})).unwrap();
+        assert_eq!(msg, ExecuteMsg::CommitSolution { commitment: json!("ab01") });
+        let msg = ExecuteMsg::from_parts("claim_rewards", &json!({"epoch_number": 3})).unwrap();
+        assert_eq!(msg.epoch(), Some(3));
+
+        assert!(ExecuteMsg::from_parts("claim_reward", &json!({})).is_err());
+        assert!(ExecuteMsg::from_parts("transfer", &json!({})).is_err());
+        assert!(ExecuteMsg::from_json(&json!({"advance_epoch": {}, "finalize_epoch": {}})).is_err());
+    }
+}
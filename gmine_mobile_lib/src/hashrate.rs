@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Per-worker hashrate slots summed on read
+///
+/// Each worker only ever writes its own slot, so concurrent updates can't
+/// overwrite each other and the reported total is the sum across workers.
+#[derive(Debug, Default)]
+pub struct HashrateAggregator {
+    slots: Vec<AtomicU64>,
+}
+
+impl HashrateAggregator {
+    pub fn new(workers: usize) -> Self {
+        HashrateAggregator {
+            slots: (0..workers).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// Record the latest hashrate (H/s) measured by a worker
+    pub fn record(&self, worker: usize, hashrate: u64) {
+        if let Some(slot) = self.slots.get(worker) {
+            slot.store(hashrate, Ordering::Relaxed);
+        }
+    }
+
+    /// Hashrate of a single worker
+    pub fn worker(&self, worker: usize) -> u64 {
+        self.slots
+            .get(worker)
+            .map(|slot| slot.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// Aggregate hashrate across all workers
+    pub fn total(&self) -> u64 {
+        self.slots.iter().map(|slot| slot.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Zero all slots (e.g. when mining stops)
+    pub fn reset(&self) {
+        for slot in &self.slots {
+            slot.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_total_is_sum_of_workers() {
+        let aggregator = Arc::new(HashrateAggregator::new(4));
+
+        let handles: Vec<_> = (0..4)
+            .map(|id| {
+                let aggregator = aggregator.clone();
+                thread::spawn(move || {
+                    // Each worker reports repeatedly; the last value wins per slot
+                    for _ in 0..1000 {
+                        aggregator.record(id, (id as u64 + 1) * 100);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(aggregator.total(), 100 + 200 + 300 + 400);
+        assert_eq!(aggregator.worker(2), 300);
+    }
+
+    #[test]
+    fn test_out_of_range_and_reset() {
+        let aggregator = HashrateAggregator::new(2);
+        aggregator.record(0, 50);
+        aggregator.record(5, 1000); // ignored
+        assert_eq!(aggregator.total(), 50);
+
+        aggregator.reset();
+        assert_eq!(aggregator.total(), 0);
+    }
+}
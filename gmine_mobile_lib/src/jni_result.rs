@@ -0,0 +1,83 @@
+use jni::JNIEnv;
+use jni::objects::JString;
+use jni::sys::jstring;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// Result envelope every fallible JNI entry point returns as JSON:
+/// `{"success": bool, "error": string|null, "data": ...}`
+/// so the app can show why a call failed instead of getting "" or null
+#[derive(Debug, Serialize)]
+pub struct JniResult<T: Serialize> {
+    pub success: bool,
+    pub error: Option<String>,
+    pub data: Option<T>,
+}
+
+impl<T: Serialize> JniResult<T> {
+    pub fn ok(data: T) -> Self {
+        Self { success: true, error: None, data: Some(data) }
+    }
+
+    pub fn err(error: impl Display) -> Self {
+        Self { success: false, error: Some(error.to_string()), data: None }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!(r#"{{"success":false,"error":"Failed to serialize result: {}","data":null}}"#, e))
+    }
+
+    /// Hand the envelope to Java, logging the error if any
+    pub fn into_jstring(self, env: &JNIEnv) -> jstring {
+        if let Some(ref error) = self.error {
+            log::error!("{}", error);
+        }
+        match env.new_string(self.to_json()) {
+            Ok(jstr) => jstr.into_raw(),
+            Err(e) => {
+                log::error!("Failed to create Java string: {:?}", e);
+                std::ptr::null_mut()
+            }
+        }
+    }
+}
+
+impl<T: Serialize, E: Display> From<Result<T, E>> for JniResult<T> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(data) => Self::ok(data),
+            Err(e) => Self::err(e),
+        }
+    }
+}
+
+/// Read a string argument, naming it in the error
+pub fn read_string(env: &mut JNIEnv, value: &JString, name: &str) -> Result<String, String> {
+    env.get_string(value)
+        .map(Into::into)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))
+}
+
+/// Return a result to Java as its JSON envelope
+pub fn to_jstring<T: Serialize, E: Display>(env: &JNIEnv, result: Result<T, E>) -> jstring {
+    JniResult::from(result).into_jstring(env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_shape() {
+        let ok: JniResult<&str> = Ok::<_, String>("inj1miner").into();
+        assert_eq!(ok.to_json(), r#"{"success":true,"error":null,"data":"inj1miner"}"#);
+
+        // Error text is escaped, not spliced into the JSON
+        let err: JniResult<()> = JniResult::err(r#"Invalid mnemonic: unknown word "foo""#);
+        let parsed: serde_json::Value = serde_json::from_str(&err.to_json()).unwrap();
+        assert_eq!(parsed["success"], false);
+        assert_eq!(parsed["error"], r#"Invalid mnemonic: unknown word "foo""#);
+        assert!(parsed["data"].is_null());
+    }
+}
@@ -2,7 +2,6 @@ use jni::JNIEnv;
 use jni::objects::{JClass, JString, JObject};
 use jni::sys::{jboolean, jint, jlong, jstring, jdouble, JNI_VERSION_1_6};
 use std::sync::Mutex;
-use blake2::{Blake2b512, Digest};
 use drillx;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
@@ -22,6 +21,19 @@ pub mod transaction;
 pub mod tx_proto;
 // pub mod wasmx;  // Using msg_execute_contract_compat instead
 pub mod msg_execute_contract_compat;
+pub mod execute_msg;
+pub mod tx_log;
+pub mod signed_tx_log;
+pub mod tls;
+pub mod jni_result;
+pub mod hashrate;
+pub mod solution_queue;
+pub mod commit_batch;
+pub mod nonce_strategy;
+pub mod workers;
+pub mod partition;
+pub mod byte_order;
+pub mod proof;
 
 #[cfg(test)]
 mod test_eip712;
@@ -42,9 +54,15 @@ mod test_tx_proto;
 mod test_proto_debug;
 
 use crate::mobile_wallet::MobileWallet as Wallet;
+use crate::execute_msg::ExecuteMsg;
 use crate::types::*;
 use crate::blockchain::BlockchainClient;
 use crate::eip712::Eip712Signer;
+use crate::hashrate::HashrateAggregator;
+use crate::solution_queue::SolutionQueue;
+use crate::commit_batch::BatchSequence;
+use crate::nonce_strategy::{NonceSampler, NonceStrategy};
+use crate::jni_result::{JniResult, read_string, to_jstring};
 
 // Activity log entry
 #[derive(serde::Serialize, Clone)]
@@ -78,19 +96,107 @@ static MINING_STATE: Mutex<Option<MiningState>> = Mutex::new(None);
 struct MiningState {
     is_mining: Arc<AtomicBool>,
     solutions_found: Arc<AtomicU64>,
-    hashrate: Arc<AtomicU64>,
+    hashrate: Arc<HashrateAggregator>,
+    /// Lifetime hashes per worker slot, kept across runs (worker i of every run counts into slot i)
+    worker_hashes: Vec<Arc<AtomicU64>>,
     epoch: u64,
     wallet: Wallet,
     blockchain_client: BlockchainClient,
     signer: Eip712Signer,
     threads: Vec<thread::JoinHandle<()>>,
     current_challenge: Option<MiningChallenge>,
-    pending_solutions: Arc<Mutex<VecDeque<Solution>>>,
+    pending_solutions: Arc<Mutex<SolutionQueue>>,
     start_time: Instant,
     last_commit_hash: Option<String>,
+    /// Epoch of the last accepted commitment (the contract allows one per miner per epoch)
+    last_committed_epoch: Option<u64>,
     activity_logs: Arc<Mutex<VecDeque<ActivityLog>>>,
     battery_info: Option<BatteryInfo>,
     thermal_info: Option<ThermalInfo>,
+    /// Period over which workers are started one at a time (0 = all at once)
+    ramp_up_ms: u64,
+    /// Periodic progress/hashrate logging level for mining workers
+    log_verbosity: LogVerbosity,
+    /// Order workers hash their nonce range in
+    nonce_strategy: NonceStrategy,
+    /// Fraction of the range sequential workers start rotated by (0.0 = from the bottom)
+    nonce_offset: f64,
+    /// Solutions for different epochs found within this long of each other are committed off one account query (0 = off)
+    commit_batch_window: Duration,
+}
+
+impl MiningState {
+    fn new(wallet: Wallet) -> Result<Self, Box<dyn std::error::Error>> {
+        // Create EIP-712 signer with compressed public key (33 bytes)
+        let compressed_key = wallet.public_key_compressed()?;
+        let signer = Eip712Signer::new(wallet.private_key_bytes(), &compressed_key)?;
+        
+        Ok(MiningState {
+            is_mining: Arc::new(AtomicBool::new(false)),
+            solutions_found: Arc::new(AtomicU64::new(0)),
+            hashrate: Arc::new(HashrateAggregator::default()),
+            worker_hashes: Vec::new(),
+            epoch: 0, // Will be updated from blockchain
+            wallet,
+            blockchain_client: BlockchainClient::new(),
+            signer,
+            threads: Vec::new(),
+            current_challenge: None,
+            pending_solutions: Arc::new(Mutex::new(SolutionQueue::default())),
+            start_time: Instant::now(),
+            last_commit_hash: None,
+            last_committed_epoch: None,
+            activity_logs: Arc::new(Mutex::new(VecDeque::new())),
+            battery_info: None,
+            thermal_info: None,
+            ramp_up_ms: 0,
+            log_verbosity: LogVerbosity::default(),
+            nonce_strategy: NonceStrategy::default(),
+            nonce_offset: 0.0,
+            commit_batch_window: Duration::ZERO,
+        })
+    }
+}
+
+impl Drop for MiningState {
+    fn drop(&mut self) {
+        // Workers hold their own clone of `is_mining` - without this they'd keep
+        // hashing against a challenge nobody will ever collect
+        let threads = std::mem::take(&mut self.threads);
+        if !threads.is_empty() {
+            log::info!("Stopping {} mining workers before releasing mining state", threads.len());
+        }
+        workers::stop_workers(&self.is_mining, threads, workers::WORKER_STOP_TIMEOUT);
+    }
+}
+
+/// Run `f` on the mining state, failing if `initialize` hasn't been called
+fn with_mining_state<R>(f: impl FnOnce(&mut MiningState) -> Result<R, String>) -> Result<R, String> {
+    let mut state = MINING_STATE.lock()
+        .map_err(|e| format!("Failed to acquire MINING_STATE lock: {:?}", e))?;
+    match state.as_mut() {
+        Some(mining_state) => f(mining_state),
+        None => Err("Mining engine not initialized - call initialize first".to_string()),
+    }
+}
+
+/// Network set with `configureNetwork`, or testnet before `initialize`
+fn configured_network() -> blockchain::NetworkConfig {
+    MINING_STATE.lock().ok()
+        .and_then(|state| state.as_ref().map(|mining_state| mining_state.blockchain_client.network().clone()))
+        .unwrap_or_else(blockchain::NetworkConfig::testnet)
+}
+
+/// Drop the mining state, stopping and joining its workers outside the lock
+fn cleanup_mining_state() {
+    let state = match MINING_STATE.lock() {
+        Ok(mut state) => state.take(),
+        Err(_) => {
+            log::error!("Failed to acquire MINING_STATE lock for cleanup");
+            return;
+        }
+    };
+    drop(state);
 }
 
 // Called when the library is loaded
@@ -105,79 +211,321 @@ pub extern "system" fn JNI_OnLoad(_vm: jni::JavaVM, _: *mut std::os::raw::c_void
     JNI_VERSION_1_6
 }
 
-// Initialize with mnemonic
+// Initialize with mnemonic; data is the wallet address
 #[no_mangle]
 pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_initialize(
     mut env: JNIEnv,
     _class: JClass,
     mnemonic: JString,
-) -> jboolean {
-    let mnemonic_str: String = match env.get_string(&mnemonic) {
-        Ok(s) => s.into(),
-        Err(_) => {
-            log::error!("Failed to get mnemonic string from JNI");
-            return 0;
-        }
-    };
-    
+) -> jstring {
+    let result = read_string(&mut env, &mnemonic, "mnemonic")
+        .and_then(|mnemonic| initialize(&mnemonic));
+    to_jstring(&env, result)
+}
+
+fn initialize(mnemonic: &str) -> Result<String, String> {
     log::info!("MiningEngine::initializeNative called with REAL blockchain integration");
     
     // Create real wallet from mnemonic
-    let wallet = match Wallet::from_mnemonic_no_passphrase(&mnemonic_str) {
-        Ok(w) => w,
-        Err(e) => {
-            log::error!("Failed to create wallet: {}", e);
-            return 0;
-        }
-    };
-    
-    log::info!("Wallet address: {}", wallet.address);
+    let wallet = Wallet::from_mnemonic_no_passphrase(mnemonic)
+        .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+    let address = wallet.address.clone();
+    log::info!("Wallet address: {}", address);
     
-    // Create blockchain client
-    let blockchain_client = BlockchainClient::new();
+    let new_state = MiningState::new(wallet)
+        .map_err(|e| format!("Failed to create signer: {}", e))?;
     
-    // Create EIP-712 signer with compressed public key (33 bytes)
-    let compressed_key = match wallet.public_key_compressed() {
-        Ok(key) => key,
-        Err(e) => {
-            log::error!("Failed to get compressed public key: {}", e);
-            return 0;
-        }
+    // Drop any previous state (which stops its workers) after releasing the lock
+    let previous = MINING_STATE.lock()
+        .map_err(|e| format!("Failed to acquire MINING_STATE lock: {:?}", e))?
+        .replace(new_state);
+    drop(previous);
+    Ok(address)
+}
+
+// Have a feegrant granter pay gas so the mining wallet needs no INJ (empty address disables it)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setFeeGranter(
+    mut env: JNIEnv,
+    _class: JClass,
+    granter: JString,
+) -> jstring {
+    let result = read_string(&mut env, &granter, "fee granter").and_then(|granter| {
+        let fee_granter = if granter.is_empty() { None } else { Some(granter) };
+        with_mining_state(|mining_state| {
+            mining_state.blockchain_client.set_fee_granter(fee_granter.clone())
+                .map_err(|e| e.to_string())?;
+            log::info!("Fee granter set to {:?}", fee_granter);
+            Ok(())
+        })
+    });
+    to_jstring(&env, result)
+}
+
+// Point the miner at another chain, LCD endpoint or mining contract (empty values keep
+// the testnet defaults); data = {rest_url, chain_id, contract_address} as applied
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_configureNetwork(
+    mut env: JNIEnv,
+    _class: JClass,
+    rest_url: JString,
+    chain_id: JString,
+    contract_address: JString,
+) -> jstring {
+    let parts = (|| Ok::<_, String>((
+        read_string(&mut env, &rest_url, "REST URL")?,
+        read_string(&mut env, &chain_id, "chain id")?,
+        read_string(&mut env, &contract_address, "contract address")?,
+    )))();
+    let result = parts
+        .and_then(|(rest_url, chain_id, contract_address)| {
+            blockchain::NetworkConfig::from_parts(&rest_url, &chain_id, &contract_address)
+        })
+        .and_then(|network| {
+            with_mining_state(|mining_state| {
+                log::info!(
+                    "Network set to {} via {} (contract {})",
+                    network.chain.chain_id, network.rest_url, network.contract_address
+                );
+                mining_state.signer.set_chain(network.chain.clone());
+                mining_state.blockchain_client.set_network(network.clone());
+                Ok(json!({
+                    "rest_url": network.rest_url,
+                    "chain_id": network.chain.chain_id,
+                    "contract_address": network.contract_address,
+                }))
+            })
+        });
+    to_jstring(&env, result)
+}
+
+// Minimum blocks that must remain in the reveal phase before a reveal is broadcast
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setRevealBufferBlocks(
+    env: JNIEnv,
+    _class: JClass,
+    blocks: jint,
+) -> jstring {
+    let result = if blocks < 0 {
+        Err(format!("Invalid reveal buffer: {} blocks", blocks))
+    } else {
+        with_mining_state(|mining_state| {
+            mining_state.blockchain_client.set_reveal_buffer_blocks(blocks as u64);
+            log::info!("Reveal buffer set to {} blocks", blocks);
+            Ok(())
+        })
     };
-    let signer = match Eip712Signer::new(wallet.private_key_bytes(), &compressed_key) {
-        Ok(s) => s,
+    to_jstring(&env, result)
+}
+
+// Check that enough of the reveal phase remains before signing a reveal
+// (data = {height, ends_at, blocks_remaining}; fails outside the phase or when it is closing)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_checkRevealWindow(
+    env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    let result = with_mining_state(|mining_state| {
+        mining_state.blockchain_client.check_reveal_window().map_err(|e| e.to_string())
+    });
+    to_jstring(&env, result)
+}
+
+// TLS settings for the LCD connection: skip SNI and/or connect to a fixed "ip[:port]" (empty = resolve normally)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setTlsOptions(
+    mut env: JNIEnv,
+    _class: JClass,
+    disable_sni: jboolean,
+    connect_address: JString,
+) -> jstring {
+    let result = read_string(&mut env, &connect_address, "connect address")
+        .and_then(|connect_address| {
+            tls::TlsOptions::parse(disable_sni != 0, &connect_address).map_err(|e| e.to_string())
+        })
+        .and_then(|options| with_mining_state(|mining_state| {
+            mining_state.blockchain_client.set_tls_options(&options);
+            log::info!("TLS options set: {:?}", options);
+            Ok(())
+        }));
+    to_jstring(&env, result)
+}
+
+// Connectivity self-test against the LCD; data is {ok, url, latency_ms, error}
+// `error` carries the full failure chain (e.g. the certificate or TLS alert) for support reports
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_testConnectivity(
+    env: JNIEnv,
+    _class: JClass,
+) -> jstring {
+    // Clone the client so the lock isn't held across the network call
+    let client = match MINING_STATE.lock() {
+        Ok(state) => match state.as_ref() {
+            Some(mining_state) => mining_state.blockchain_client.clone(),
+            None => BlockchainClient::new(),
+        },
         Err(e) => {
-            log::error!("Failed to create signer: {}", e);
-            return 0;
+            return to_jstring::<(), _>(&env, Err(format!("Failed to acquire MINING_STATE lock: {:?}", e)));
         }
     };
+    let report = client.check_connectivity();
+    if report.ok {
+        log::info!("LCD reachable in {}ms", report.latency_ms);
+    }
     
-    match MINING_STATE.lock() {
-        Ok(mut state) => {
-            *state = Some(MiningState {
-        is_mining: Arc::new(AtomicBool::new(false)),
-        solutions_found: Arc::new(AtomicU64::new(0)),
-        hashrate: Arc::new(AtomicU64::new(0)),
-        epoch: 0, // Will be updated from blockchain
-        wallet,
-        blockchain_client,
-        signer,
-        threads: Vec::new(),
-        current_challenge: None,
-        pending_solutions: Arc::new(Mutex::new(VecDeque::new())),
-        start_time: Instant::now(),
-        last_commit_hash: None,
-        activity_logs: Arc::new(Mutex::new(VecDeque::new())),
-        battery_info: None,
-        thermal_info: None,
+    let result = JniResult {
+        success: report.ok,
+        error: report.error.as_ref().map(|error| format!("LCD connectivity check failed: {}", error)),
+        data: Some(report),
+    };
+    result.into_jstring(&env)
+}
+
+// Enable the append-only transaction audit log (empty path disables it)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setTxLogPath(
+    mut env: JNIEnv,
+    _class: JClass,
+    path: JString,
+) -> jstring {
+    let result = read_string(&mut env, &path, "tx log path").and_then(|path| {
+        with_mining_state(|mining_state| {
+            let tx_log_path = if path.is_empty() {
+                None
+            } else {
+                Some(std::path::PathBuf::from(&path))
+            };
+            log::info!("Transaction log path set to {:?}", tx_log_path);
+            mining_state.blockchain_client.set_tx_log_path(tx_log_path);
+            Ok(())
+        })
     });
-            1 // true
-        }
-        Err(e) => {
-            log::error!("Failed to acquire MINING_STATE lock: {:?}", e);
-            0 // false
-        }
-    }
+    to_jstring(&env, result)
+}
+
+// Bound the pending solution queue; lowest-difficulty solutions are dropped when full
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setSolutionQueueCapacity(
+    env: JNIEnv,
+    _class: JClass,
+    capacity: jint,
+) -> jstring {
+    let result = if capacity <= 0 {
+        Err(format!("Invalid solution queue capacity: {}", capacity))
+    } else {
+        with_mining_state(|mining_state| {
+            let mut solutions = mining_state.pending_solutions.lock()
+                .map_err(|_| "Failed to lock pending_solutions".to_string())?;
+            solutions.set_capacity(capacity as usize);
+            log::info!("Solution queue capacity set to {}", capacity);
+            Ok(())
+        })
+    };
+    to_jstring(&env, result)
+}
+
+// Hold found solutions for up to this long so those for different epochs found together
+// are committed off one account query (0 disables; at most 5000ms)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setCommitBatchWindow(
+    env: JNIEnv,
+    _class: JClass,
+    window_ms: jlong,
+) -> jstring {
+    let max_ms = commit_batch::MAX_COMMIT_BATCH_WINDOW.as_millis() as jlong;
+    let result = if !(0..=max_ms).contains(&window_ms) {
+        Err(format!("Invalid commit batch window: {}ms (expected 0-{}ms)", window_ms, max_ms))
+    } else {
+        with_mining_state(|mining_state| {
+            mining_state.commit_batch_window = Duration::from_millis(window_ms as u64);
+            log::info!("Commit batch window set to {}ms", window_ms);
+            Ok(())
+        })
+    };
+    to_jstring(&env, result)
+}
+
+// Start workers gradually over the given period to avoid a thermal spike (0 disables)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setRampUp(
+    env: JNIEnv,
+    _class: JClass,
+    ramp_up_ms: jlong,
+) -> jstring {
+    let result = with_mining_state(|mining_state| {
+        mining_state.ramp_up_ms = ramp_up_ms.max(0) as u64;
+        log::info!("Worker ramp-up set to {}ms", mining_state.ramp_up_ms);
+        Ok(())
+    });
+    to_jstring(&env, result)
+}
+
+// Control periodic worker logging: 0 = quiet, 1 = combined hashrate only (default), 2 = per-worker
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setLogVerbosity(
+    env: JNIEnv,
+    _class: JClass,
+    level: jint,
+) -> jstring {
+    let result = with_mining_state(|mining_state| {
+        mining_state.log_verbosity = LogVerbosity::from_level(level);
+        log::info!("Mining log verbosity set to {:?}", mining_state.log_verbosity);
+        Ok(())
+    });
+    to_jstring(&env, result)
+}
+
+// How workers walk their nonce range: "sequential" (default) or "random" - random keeps
+// frequent restarts from re-hashing the start of the range; applies from the next startMining
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setNonceStrategy(
+    mut env: JNIEnv,
+    _class: JClass,
+    strategy: JString,
+) -> jstring {
+    let result = read_string(&mut env, &strategy, "nonce strategy")
+        .and_then(|name| NonceStrategy::from_name(&name))
+        .and_then(|strategy| {
+            with_mining_state(|mining_state| {
+                mining_state.nonce_strategy = strategy;
+                log::info!("Nonce strategy set to {:?}", strategy);
+                Ok(())
+            })
+        });
+    to_jstring(&env, result)
+}
+
+// Start sequential workers this fraction (0.0-1.0) into the nonce range, so devices
+// mining with one wallet don't hash the same nonces first; applies from the next startMining
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setNonceOffset(
+    env: JNIEnv,
+    _class: JClass,
+    offset: jdouble,
+) -> jstring {
+    let result = if (0.0..1.0).contains(&offset) {
+        with_mining_state(|mining_state| {
+            mining_state.nonce_offset = offset;
+            log::info!("Nonce offset set to {}", offset);
+            Ok(())
+        })
+    } else {
+        Err(format!("Nonce offset must be from 0.0 up to (not including) 1.0, got {}", offset))
+    };
+    to_jstring(&env, result)
+}
+
+// Log the signed hash and signed transaction of every submission at debug level
+// (for diagnosing signature verification failures; off by default)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_setLogSignedTx(
+    env: JNIEnv,
+    _class: JClass,
+    enabled: jboolean,
+) -> jstring {
+    signed_tx_log::set_enabled(enabled != 0);
+    log::info!("Signed transaction logging {}", if enabled != 0 { "enabled" } else { "disabled" });
+    to_jstring(&env, Ok::<_, String>(()))
 }
 
 // Helper function to add activity log - safe version that doesn't deadlock
@@ -218,92 +566,111 @@ fn add_activity_log(level: &str, message: String, worker: Option<u32>, difficult
     }
 }
 
-// Start REAL mining
+// Start REAL mining; data is the epoch and difficulty being mined
 #[no_mangle]
 pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_startMining(
-    _env: JNIEnv,
+    env: JNIEnv,
     _class: JClass,
     thread_count: jint,
-) -> jboolean {
+) -> jstring {
     log::info!("Starting REAL mining with {} threads", thread_count);
     
-    match MINING_STATE.lock() {
-        Ok(mut state) => {
-            if let Some(mining_state) = state.as_mut() {
+    let result = with_mining_state(|mining_state| {
         if mining_state.is_mining.load(Ordering::Relaxed) {
-            log::warn!("Mining already running");
-            return 0;
+            return Err("Mining already running".to_string());
         }
         
         // Fetch current challenge from blockchain
         log::info!("Fetching mining challenge for wallet: {}", mining_state.wallet.address);
-        match mining_state.blockchain_client.get_mining_challenge(&mining_state.wallet.address) {
-            Ok(challenge) => {
-                log::info!("Successfully got challenge for epoch {}, difficulty: {}, nonce range: {}-{}", 
-                    challenge.epoch, challenge.difficulty, challenge.nonce_start, challenge.nonce_end);
-                
-                // Add to activity log - use direct version since we're holding the lock
-                add_activity_log_direct(
-                    &mining_state.activity_logs,
-                    "info",
-                    format!("Mining started for epoch {} (difficulty: {})", challenge.epoch, challenge.difficulty),
-                    None,
-                    Some(challenge.difficulty),
-                    None
-                );
-                
-                mining_state.current_challenge = Some(challenge.clone());
-                mining_state.epoch = challenge.epoch;
-                
-                mining_state.is_mining.store(true, Ordering::Relaxed);
-                
-                log::info!("About to create {} mining threads", thread_count);
-                
-                // Create real mining threads with blockchain challenge
-                for i in 0..thread_count {
-                    let is_mining = mining_state.is_mining.clone();
-                    let solutions_found = mining_state.solutions_found.clone();
-                    let hashrate = mining_state.hashrate.clone();
-                    // CRITICAL FIX: Use the SAME Arc reference that MINING_STATE holds
-                    // This ensures JNI calls see the same queue instance
-                    let pending_solutions = Arc::clone(&mining_state.pending_solutions);
-                    let activity_logs = mining_state.activity_logs.clone();
-                    let challenge = challenge.clone();
-                    
-                    let handle = thread::spawn(move || {
-                        mine_worker(
-                            i as usize, 
-                            is_mining, 
-                            solutions_found, 
-                            hashrate,
-                            pending_solutions,
-                            activity_logs,
-                            challenge
-                        );
-                    });
-                    
-                    mining_state.threads.push(handle);
-                }
-                
-                log::info!("All {} mining threads created, returning from startMining", thread_count);
-                return 1;
-            }
-            Err(e) => {
-                log::error!("Failed to get mining challenge from blockchain: {:?}", e);
-                log::error!("Wallet address: {}", mining_state.wallet.address);
-                log::error!("This likely means the blockchain connection failed");
-                return 0;
-            }
-            }
-            } else {
-                0 // false
-            }
+        let challenge = mining_state.blockchain_client.get_mining_challenge(&mining_state.wallet.address)
+            .map_err(|e| format!(
+                "Failed to get mining challenge for {} (blockchain connection failed?): {}",
+                mining_state.wallet.address, e
+            ))?;
+        log::info!("Successfully got challenge for epoch {}, difficulty: {}, nonce range: {}-{}", 
+            challenge.epoch, challenge.difficulty, challenge.nonce_start, challenge.nonce_end);
+        
+        // Add to activity log - use direct version since we're holding the lock
+        add_activity_log_direct(
+            &mining_state.activity_logs,
+            "info",
+            format!("Mining started for epoch {} (difficulty: {})", challenge.epoch, challenge.difficulty),
+            None,
+            Some(challenge.difficulty),
+            None
+        );
+        
+        mining_state.current_challenge = Some(challenge.clone());
+        mining_state.epoch = challenge.epoch;
+        
+        // Fresh flag per run: workers detached by a timed-out stop keep seeing
+        // their old (false) flag instead of being revived by this run
+        mining_state.is_mining = Arc::new(AtomicBool::new(true));
+        
+        // Fresh per-worker hashrate slots for this run
+        mining_state.hashrate = Arc::new(HashrateAggregator::new(thread_count.max(0) as usize));
+        while mining_state.worker_hashes.len() < thread_count.max(0) as usize {
+            mining_state.worker_hashes.push(Arc::new(AtomicU64::new(0)));
         }
-        Err(e) => {
-            log::error!("Failed to acquire MINING_STATE lock: {:?}", e);
-            0 // false
+        
+        log::info!("About to create {} mining threads", thread_count);
+        
+        // Worker i waits i * step before hashing so load climbs gradually
+        let ramp_step_ms = mining_state.ramp_up_ms / thread_count.max(1) as u64;
+        if ramp_step_ms > 0 {
+            log::info!("Ramping up {} workers over {}ms", thread_count, mining_state.ramp_up_ms);
         }
-    }
+        
+        // Create real mining threads with blockchain challenge
+        for i in 0..thread_count {
+            let is_mining = mining_state.is_mining.clone();
+            let solutions_found = mining_state.solutions_found.clone();
+            let hashrate = mining_state.hashrate.clone();
+            let total_hashes = Arc::clone(&mining_state.worker_hashes[i as usize]);
+            // CRITICAL FIX: Use the SAME Arc reference that MINING_STATE holds
+            // This ensures JNI calls see the same queue instance
+            let pending_solutions = Arc::clone(&mining_state.pending_solutions);
+            let activity_logs = mining_state.activity_logs.clone();
+            let challenge = challenge.clone();
+            let verbosity = mining_state.log_verbosity;
+            let nonce_strategy = mining_state.nonce_strategy;
+            let nonce_offset = mining_state.nonce_offset;
+            
+            let delay_ms = ramp_step_ms * i as u64;
+            
+            let handle = thread::spawn(move || {
+                if delay_ms > 0 {
+                    let deadline = Instant::now() + Duration::from_millis(delay_ms);
+                    while Instant::now() < deadline {
+                        if !is_mining.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        thread::sleep(Duration::from_millis(50));
+                    }
+                    log::info!("Ramp-up: worker {}/{} started", i + 1, thread_count);
+                }
+                mine_worker(
+                    i as usize, 
+                    is_mining, 
+                    solutions_found, 
+                    hashrate,
+                    total_hashes,
+                    pending_solutions,
+                    activity_logs,
+                    challenge,
+                    verbosity,
+                    nonce_strategy,
+                    nonce_offset,
+                );
+            });
+            
+            mining_state.threads.push(handle);
+        }
+        
+        log::info!("All {} mining threads created, returning from startMining", thread_count);
+        Ok(json!({ "epoch": challenge.epoch, "difficulty": challenge.difficulty }))
+    });
+    to_jstring(&env, result)
 }
 
 // Real mining worker
@@ -311,10 +678,14 @@ fn mine_worker(
     id: usize,
     is_mining: Arc<AtomicBool>,
     solutions_found: Arc<AtomicU64>,
-    hashrate: Arc<AtomicU64>,
-    pending_solutions: Arc<Mutex<VecDeque<Solution>>>,
+    hashrate: Arc<HashrateAggregator>,
+    total_hashes: Arc<AtomicU64>,
+    pending_solutions: Arc<Mutex<SolutionQueue>>,
     activity_logs: Arc<Mutex<VecDeque<ActivityLog>>>,
     challenge: MiningChallenge,
+    verbosity: LogVerbosity,
+    nonce_strategy: NonceStrategy,
+    nonce_offset: f64,
 ) {
     log::info!("Mining worker {} started for epoch {}", id, challenge.epoch);
     
@@ -336,22 +707,27 @@ fn mine_worker(
         });
     }
     
-    // Start from assigned nonce range
-    let mut nonce = challenge.nonce_start + (id as u64 * 1000);
+    // Walk the assigned nonce range
+    let mut nonces = NonceSampler::new(nonce_strategy, id, challenge.nonce_start, challenge.nonce_end, nonce_offset);
+    // Hashes since the last hashrate update; total_hashes keeps the running count
     let mut hashes = 0u64;
     let mut last_update = Instant::now();
     
     // Pre-allocate memory for equix
     let mut solver_memory = equix::SolverMemory::new();
     
-    while is_mining.load(Ordering::Relaxed) && nonce <= challenge.nonce_end {
-        let nonce_bytes = nonce.to_le_bytes();
+    while is_mining.load(Ordering::Relaxed) {
+        let Some(nonce) = nonces.next() else {
+            break;
+        };
+        let nonce_bytes = byte_order::nonce_bytes(nonce);
         
         // Log mining progress every 10000 hashes
-        if hashes % 10000 == 0 && hashes > 0 {
+        let worker_total = total_hashes.load(Ordering::Relaxed);
+        if verbosity == LogVerbosity::Verbose && worker_total % 10000 == 0 && worker_total > 0 {
             log::info!(
                 "Worker {}: Mining epoch {} | Nonce: {} | Hashes: {} | Looking for difficulty >= {}",
-                id, challenge.epoch, nonce, hashes, challenge.difficulty
+                id, challenge.epoch, nonce, worker_total, challenge.difficulty
             );
         }
         
@@ -387,15 +763,20 @@ fn mine_worker(
                     // Store solution for submission
                     let solution = Solution {
                         nonce,
-                        hash: hash.h.to_vec(),  // drillx Hash has h field for the hash bytes
+                        hash: hash.d.to_vec(),  // drillx digest - what the commitment and reveal carry
                         difficulty: hash_difficulty,
                         epoch: challenge.epoch,
                     };
                     
                     if let Ok(mut solutions) = pending_solutions.lock() {
                         let queue_size_before = solutions.len();
-                        solutions.push_back(solution);
-                        log::info!("Added solution to queue. Queue size: {} -> {}", queue_size_before, solutions.len());
+                        match solutions.push(solution) {
+                            Some(dropped) => log::warn!(
+                                "Solution queue full ({}), dropped difficulty {} solution (nonce {}). Total dropped: {}",
+                                solutions.capacity(), dropped.difficulty, dropped.nonce, solutions.dropped()
+                            ),
+                            None => log::info!("Added solution to queue. Queue size: {} -> {}", queue_size_before, solutions.len()),
+                        }
                     } else {
                         log::error!("Failed to lock pending_solutions queue in worker!");
                     }
@@ -409,39 +790,51 @@ fn mine_worker(
             }
         }
         
-        nonce += 1;
         hashes += 1;
+        total_hashes.fetch_add(1, Ordering::Relaxed);
         
         // Update hashrate every second
-        if last_update.elapsed() >= Duration::from_secs(1) {
-            let thread_count = thread::available_parallelism()
-                .map(|n| n.get())
-                .unwrap_or(4) as u64;
-            let current_hashrate = hashes * thread_count;
-            hashrate.store(current_hashrate, Ordering::Relaxed);
+        let elapsed = last_update.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            // Each worker reports only its own rate; the aggregator sums them
+            let current_hashrate = (hashes as f64 / elapsed.as_secs_f64()) as u64;
+            hashrate.record(id, current_hashrate);
             
-            // Log hashrate like a real miner
-            log::info!(
-                "Worker {} | Hashrate: {} H/s | Epoch: {} | Range: {}-{}",
-                id, current_hashrate, challenge.epoch, challenge.nonce_start, challenge.nonce_end
-            );
-            
-            // Log hashrate to activity feed
-            if let Ok(mut logs) = activity_logs.lock() {
-                let timestamp = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as u64;
+            // Per-worker lines in verbose mode; otherwise worker 0 reports the combined rate
+            let report = match verbosity {
+                LogVerbosity::Verbose => Some((Some(id as u32), current_hashrate)),
+                LogVerbosity::Normal if id == 0 => Some((None, hashrate.total())),
+                _ => None,
+            };
+            if let Some((worker, reported_hashrate)) = report {
+                match worker {
+                    Some(_) => log::info!(
+                        "Worker {} | Hashrate: {} H/s | Epoch: {} | Range: {}-{}",
+                        id, reported_hashrate, challenge.epoch, challenge.nonce_start, challenge.nonce_end
+                    ),
+                    None => log::info!(
+                        "Hashrate: {} H/s | Epoch: {} | Range: {}-{}",
+                        reported_hashrate, challenge.epoch, challenge.nonce_start, challenge.nonce_end
+                    ),
+                }
                 
-                logs.push_front(ActivityLog {
-                    timestamp,
-                    level: "info".to_string(),
-                    message: format!("Hashrate: {} H/s", current_hashrate),
-                    worker: Some(id as u32),
-                    difficulty: None,
-                    nonce: None,
-                    hashrate: Some(current_hashrate),
-                });
+                // Log hashrate to activity feed
+                if let Ok(mut logs) = activity_logs.lock() {
+                    let timestamp = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis() as u64;
+                    
+                    logs.push_front(ActivityLog {
+                        timestamp,
+                        level: "info".to_string(),
+                        message: format!("Hashrate: {} H/s", reported_hashrate),
+                        worker,
+                        difficulty: None,
+                        nonce: None,
+                        hashrate: Some(reported_hashrate),
+                    });
+                }
             }
             
             last_update = Instant::now();
@@ -449,6 +842,7 @@ fn mine_worker(
         }
     }
     
+    hashrate.record(id, 0);
     log::info!("Mining worker {} stopped", id);
 }
 
@@ -460,21 +854,25 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_stopMining(
 ) {
     log::info!("Stopping mining");
     
-    if let Ok(mut state) = MINING_STATE.lock() {
-        if let Some(mining_state) = state.as_mut() {
-        mining_state.is_mining.store(false, Ordering::Relaxed);
-        
-        // Wait for threads to finish
-        while let Some(handle) = mining_state.threads.pop() {
-            let _ = handle.join();
-        }
+    // Take the handles and release the lock before waiting, so stats/status calls
+    // aren't blocked while workers wind down
+    let (is_mining, threads) = match MINING_STATE.lock() {
+        Ok(mut state) => match state.as_mut() {
+            Some(mining_state) => (mining_state.is_mining.clone(), std::mem::take(&mut mining_state.threads)),
+            None => return,
+        },
+        Err(_) => {
+            log::error!("Failed to acquire MINING_STATE lock to stop mining");
+            return;
         }
-    } else {
-        log::error!("Failed to acquire MINING_STATE lock to stop mining");
+    };
+    
+    if workers::stop_workers(&is_mining, threads, workers::WORKER_STOP_TIMEOUT) {
+        log::info!("All mining workers stopped");
     }
 }
 
-// Get mining stats
+// Get mining stats (all zero before initialize)
 #[no_mangle]
 pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_getMiningStats(
     env: JNIEnv,
@@ -484,12 +882,15 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_getMiningStats(
         Ok(state) => {
             if let Some(mining_state) = state.as_ref() {
         let is_mining = mining_state.is_mining.load(Ordering::Relaxed);
-        let hashrate = mining_state.hashrate.load(Ordering::Relaxed);
+        let hashrate = mining_state.hashrate.total();
         let solutions = mining_state.solutions_found.load(Ordering::Relaxed);
         let uptime = mining_state.start_time.elapsed().as_secs();
+        let worker_hashes: Vec<u64> = mining_state.worker_hashes.iter()
+            .map(|total| total.load(Ordering::Relaxed))
+            .collect();
         
-        log::info!("getMiningStats: solutions_found = {}, is_mining = {}, hashrate = {}", 
-                   solutions, is_mining, hashrate);
+        log::info!("getMiningStats: solutions_found = {}, is_mining = {}, hashrate = {}, total_hashes = {}", 
+                   solutions, is_mining, hashrate, worker_hashes.iter().sum::<u64>());
         
         MiningStats {
             is_mining,
@@ -504,6 +905,16 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_getMiningStats(
                 .map(|c| hex::encode(&c.challenge)),
             difficulty: mining_state.current_challenge.as_ref()
                 .map(|c| c.difficulty),
+            target_hash: mining_state.current_challenge.as_ref()
+                .map(|c| hex::encode(c.target_hash())),
+            nonce_range_start: mining_state.current_challenge.as_ref()
+                .map(|c| c.nonce_start.to_string()),
+            nonce_range_end: mining_state.current_challenge.as_ref()
+                .map(|c| c.nonce_end.to_string()),
+            estimated_time_to_solution: mining_state.current_challenge.as_ref()
+                .and_then(|c| estimate_time_to_solution(c.difficulty, hashrate)),
+            total_hashes: worker_hashes.iter().sum(),
+            worker_hashes,
         }
             } else {
                 MiningStats {
@@ -517,35 +928,21 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_getMiningStats(
             wallet_address: None,
             current_challenge: None,
             difficulty: None,
+            target_hash: None,
+            nonce_range_start: None,
+            nonce_range_end: None,
+            estimated_time_to_solution: None,
+            total_hashes: 0,
+            worker_hashes: Vec::new(),
         }
             }
         }
         Err(e) => {
-            log::error!("Failed to acquire MINING_STATE lock: {:?}", e);
-            MiningStats {
-                is_mining: false,
-                hashrate: 0,
-                solutions_found: 0,
-                uptime_seconds: 0,
-                epoch: 0,
-                last_solution_time: None,
-                real_mining: true,
-                wallet_address: None,
-                current_challenge: None,
-                difficulty: None,
-            }
+            return to_jstring::<(), _>(&env, Err(format!("Failed to acquire MINING_STATE lock: {:?}", e)));
         }
     };
     
-    let stats_json = serde_json::to_string(&stats).unwrap_or_else(|_| "{}".to_string());
-    
-    match env.new_string(stats_json) {
-        Ok(jstr) => jstr.into_raw(),
-        Err(e) => {
-            log::error!("Failed to create Java string: {:?}", e);
-            std::ptr::null_mut()
-        }
-    }
+    to_jstring(&env, Ok::<_, String>(stats))
 }
 
 // Get activity logs
@@ -555,36 +952,73 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_getActivityLogs
     _class: JClass,
     max_count: jint,
 ) -> jstring {
-    let logs: Vec<ActivityLog> = match MINING_STATE.lock() {
-        Ok(state) => {
-            if let Some(mining_state) = state.as_ref() {
-        if let Ok(activity_logs) = mining_state.activity_logs.lock() {
-            activity_logs.iter()
-                .take(max_count as usize)
-                .cloned()
-                .collect()
-        } else {
-            Vec::new()
-        }
-            } else {
-                Vec::new()
-            }
-        }
-        Err(e) => {
-            log::error!("Failed to acquire MINING_STATE lock: {:?}", e);
-            Vec::new()
-        }
+    let result = match MINING_STATE.lock() {
+        Ok(state) => match state.as_ref() {
+            Some(mining_state) => mining_state.activity_logs.lock()
+                .map(|activity_logs| activity_logs.iter()
+                    .take(max_count.max(0) as usize)
+                    .cloned()
+                    .collect::<Vec<ActivityLog>>())
+                .map_err(|_| "Failed to lock activity logs".to_string()),
+            // Nothing logged before initialize
+            None => Ok(Vec::new()),
+        },
+        Err(e) => Err(format!("Failed to acquire MINING_STATE lock: {:?}", e)),
+    };
+    to_jstring(&env, result)
+}
+
+// Partition index and nonce range for an address/epoch (for verifying assignments)
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_getPartitionInfo(
+    mut env: JNIEnv,
+    _class: JClass,
+    address: JString,
+    epoch: jlong,
+) -> jstring {
+    let result = read_string(&mut env, &address, "address").map(|address| {
+        let epoch = epoch as u64;
+        let (nonce_start, nonce_end) = partition::calculate_nonce_range(&address, epoch);
+        
+        // u64 values as strings - they don't fit in a JSON/Java double
+        serde_json::json!({
+            "address": address,
+            "epoch": epoch,
+            "partition_index": partition::partition_index(&address, epoch),
+            "nonce_start": nonce_start.to_string(),
+            "nonce_end": nonce_end.to_string(),
+        })
+    });
+    to_jstring(&env, result)
+}
+
+// Export a found solution as a JSON proof anyone can verify offline with drillx
+// `nonce` is the solution's nonce (as shown in the activity feed) for the current epoch
+#[no_mangle]
+pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_exportSolution(
+    env: JNIEnv,
+    _class: JClass,
+    nonce: jlong,
+) -> jstring {
+    let nonce = nonce as u64;
+    let proof = match MINING_STATE.lock() {
+        Ok(state) => match state.as_ref() {
+            Some(mining_state) => match mining_state.current_challenge.as_ref() {
+                Some(challenge) => proof::SolutionProof::from_nonce(
+                    challenge.epoch,
+                    &mining_state.wallet.address,
+                    &challenge.challenge,
+                    nonce,
+                ),
+                None => Err("No mining challenge - start mining first".into()),
+            },
+            None => Err("Mining engine not initialized".into()),
+        },
+        Err(_) => Err("Failed to lock mining state".into()),
     };
     
-    let logs_json = serde_json::to_string(&logs).unwrap_or_else(|_| "[]".to_string());
-    
-    match env.new_string(logs_json) {
-        Ok(jstr) => jstr.into_raw(),
-        Err(e) => {
-            log::error!("Failed to create Java string: {:?}", e);
-            std::ptr::null_mut()
-        }
-    }
+    let result = proof.map_err(|e| format!("Failed to export solution for nonce {}: {}", nonce, e));
+    to_jstring(&env, result)
 }
 
 // Process solutions - submit to blockchain
@@ -594,15 +1028,7 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_processMiningSo
     _class: JClass,
 ) -> jstring {
     log::info!("processMiningSolutions JNI called");
-    let solutions_json = process_pending_solutions();
-    
-    match env.new_string(solutions_json) {
-        Ok(jstr) => jstr.into_raw(),
-        Err(e) => {
-            log::error!("Failed to create Java string: {:?}", e);
-            std::ptr::null_mut()
-        }
-    }
+    to_jstring(&env, process_pending_solutions())
 }
 
 #[no_mangle]
@@ -616,31 +1042,26 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_processSolution
 
 
 // Internal function to process and submit solutions
-fn process_pending_solutions() -> String {
+fn process_pending_solutions() -> Result<Vec<serde_json::Value>, String> {
     log::info!("process_pending_solutions called");
-    match MINING_STATE.lock() {
-        Ok(mut state) => {
-            if let Some(mining_state) = state.as_mut() {
+    with_mining_state(|mining_state| {
         // Get pending solutions
         let solutions_to_process = {
             let mut solutions = match mining_state.pending_solutions.lock() {
                 Ok(s) => s,
-                Err(_) => {
-                    log::error!("Failed to lock pending_solutions");
-                    return "[]".to_string();
-                },
+                Err(_) => return Err("Failed to lock pending_solutions".to_string()),
             };
             
             log::info!("Pending solutions queue size: {}", solutions.len());
             
-            let mut to_process = Vec::new();
-            while let Some(solution) = solutions.pop_front() {
-                to_process.push(solution);
-                if to_process.len() >= 5 { // Process up to 5 at a time
-                    break;
-                }
+            // Wait for the rest of the batch - the next poll picks it up
+            if !commit_batch::batch_due(solutions.waiting_since(), mining_state.commit_batch_window, Instant::now()) {
+                log::info!("Holding {} solutions for the commit batch window", solutions.len());
+                return Ok(Vec::new());
             }
-            to_process
+            
+            // Best solution of each epoch still waiting - one commit per epoch is accepted
+            solutions.take_batch(commit_batch::MAX_COMMIT_BATCH_EPOCHS, mining_state.last_committed_epoch)
         };
         
         // Create JSON array of processed solutions
@@ -648,8 +1069,17 @@ fn process_pending_solutions() -> String {
         
         log::info!("Processing {} solutions", solutions_to_process.len());
         
+        // With batching on, one account query serves the whole batch
+        let mut batch_sequence = BatchSequence::new(!mining_state.commit_batch_window.is_zero());
+        
         // Process each solution
         for (idx, solution) in solutions_to_process.iter().enumerate() {
+            if mining_state.last_committed_epoch == Some(solution.epoch) {
+                log::info!("Already committed for epoch {}, skipping solution with nonce {}", 
+                          solution.epoch, solution.nonce);
+                continue;
+            }
+            
             log::info!("Processing solution {}/{} for epoch {} with nonce {}", 
                       idx + 1, solutions_to_process.len(), solution.epoch, solution.nonce);
             
@@ -661,31 +1091,36 @@ fn process_pending_solutions() -> String {
             // 4. Submit reveal transaction
             
             // Create commitment - must match contract's format:
-            // Blake2b512(wallet_address || nonce_bytes || hash_digest || epoch_bytes)
-            let mut hasher = Blake2b512::new();
-            hasher.update(&mining_state.wallet.address.as_bytes());
-            hasher.update(&solution.nonce.to_le_bytes());
-            hasher.update(&solution.hash); // The drillx hash digest
-            hasher.update(&solution.epoch.to_le_bytes());
-            let commitment = hasher.finalize();
-            let commitment_hex = hex::encode(&commitment);
+            // Blake2b512(wallet_address || nonce_le || hash_digest || epoch_be), first 32 bytes
+            let Ok(digest) = <[u8; 16]>::try_from(solution.hash.as_slice()) else {
+                log::error!("Solution with nonce {} has a {}-byte digest, expected 16; skipping", solution.nonce, solution.hash.len());
+                continue;
+            };
+            let commitment = byte_order::unsalted_commitment(
+                &mining_state.wallet.address,
+                solution.epoch,
+                byte_order::nonce_bytes(solution.nonce),
+                digest,
+            );
+            let commitment_hex = hex::encode(commitment);
             
             log::info!("Submitting commitment: {} for nonce: {}", commitment_hex, solution.nonce);
             
             // Submit via EIP-712 signed transaction
-            let msg_data = json!({
-                "commitment": commitment_hex.clone()
-            });
+            let msg = ExecuteMsg::CommitSolution { commitment: json!(commitment_hex) };
             
-            match mining_state.blockchain_client.get_account_info(&mining_state.wallet.address) {
+            let account = batch_sequence.account(|| {
+                mining_state.blockchain_client.get_account_info(&mining_state.wallet.address)
+            });
+            match account {
                 Ok((account_number, sequence)) => {
-                    match mining_state.signer.sign_transaction("commit", &msg_data, &mining_state.wallet.address, account_number, sequence, None, "") {
+                    match mining_state.signer.sign_msg(&msg, &mining_state.wallet.address, &mining_state.blockchain_client.network().contract_address, account_number, sequence, Some(mining_state.blockchain_client.fee()), "") {
                         Ok(signing_result) => {
                             if let Some(signature) = signing_result.signature {
                                 if let Some(pub_key) = signing_result.pub_key {
-                                    // Use the new submit_commitment method that constructs full transaction
-                                    match mining_state.blockchain_client.submit_commitment(
-                                        &commitment_hex,
+                                    // Broadcast exactly the message that was signed
+                                    match mining_state.blockchain_client.submit_msg(
+                                        &msg,
                                         &mining_state.wallet.address,
                                         &signature,
                                         &pub_key,
@@ -694,8 +1129,10 @@ fn process_pending_solutions() -> String {
                                     ) {
                                         Ok(tx_hash) => {
                                             log::info!("✅ Commitment submitted! TX: {}", tx_hash);
+                                            batch_sequence.accepted(account_number, sequence);
                                             // Store for later reveal
                                             mining_state.last_commit_hash = Some(commitment_hex.clone());
+                                            mining_state.last_committed_epoch = Some(solution.epoch);
                                             
                                             // Add success log to activity
                                             if let Ok(mut logs) = mining_state.activity_logs.lock() {
@@ -715,6 +1152,10 @@ fn process_pending_solutions() -> String {
                                         }
                                         Err(e) => {
                                             log::error!("Failed to submit commitment: {:?}", e);
+                                            batch_sequence.failed();
+                                            if e.to_string().to_lowercase().contains("already committed") {
+                                                mining_state.last_committed_epoch = Some(solution.epoch);
+                                            }
                                             
                                             // Add error log to activity feed
                                             if let Ok(mut logs) = mining_state.activity_logs.lock() {
@@ -827,17 +1268,8 @@ fn process_pending_solutions() -> String {
             }));
         }
         
-        // Return JSON string of processed solutions
-        serde_json::to_string(&processed_solutions).unwrap_or_else(|_| "[]".to_string())
-            } else {
-                "[]".to_string()
-            }
-        }
-        Err(e) => {
-            log::error!("Failed to acquire MINING_STATE lock: {:?}", e);
-            "[]".to_string()
-        }
-    }
+        Ok(processed_solutions)
+    })
 }
 
 // Cleanup
@@ -847,11 +1279,7 @@ pub extern "system" fn Java_io_gelotto_gmine_mining_MiningEngine_cleanup(
     _class: JClass,
 ) {
     log::info!("cleanup called");
-    if let Ok(mut state) = MINING_STATE.lock() {
-        *state = None;
-    } else {
-        log::error!("Failed to acquire MINING_STATE lock for cleanup");
-    }
+    cleanup_mining_state();
 }
 
 // Battery state update from Android
@@ -966,25 +1394,12 @@ pub extern "system" fn Java_io_gelotto_gmine_wallet_WalletManager_generateMnemon
     env: JNIEnv,
     _class: JClass,
 ) -> jstring {
-    match Wallet::generate_mnemonic() {
-        Ok(mnemonic) => {
-            log::info!("Generated new mnemonic");
-            match env.new_string(mnemonic) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(e) => {
-                    log::error!("Failed to create Java string: {:?}", e);
-                    std::ptr::null_mut()
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("Failed to generate mnemonic: {}", e);
-            match env.new_string("") {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            }
-        }
+    let result = Wallet::generate_mnemonic()
+        .map_err(|e| format!("Failed to generate mnemonic: {}", e));
+    if result.is_ok() {
+        log::info!("Generated new mnemonic");
     }
+    to_jstring(&env, result)
 }
 
 #[no_mangle]
@@ -1011,34 +1426,15 @@ pub extern "system" fn Java_io_gelotto_gmine_wallet_WalletManager_deriveAddress(
     _class: JClass,
     mnemonic: JString,
 ) -> jstring {
-    let mnemonic_str: String = match env.get_string(&mnemonic) {
-        Ok(s) => s.into(),
-        Err(_) => {
-            return env.new_string("")
-                .expect("Couldn't create java string!")
-                .into_raw();
-        }
-    };
-    
-    match Wallet::from_mnemonic_no_passphrase(&mnemonic_str) {
-        Ok(wallet) => {
-            log::info!("Derived real address: {}", wallet.address);
-            match env.new_string(&wallet.address) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(e) => {
-                    log::error!("Failed to create Java string: {:?}", e);
-                    std::ptr::null_mut()
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("Failed to derive address: {}", e);
-            match env.new_string("") {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            }
-        }
-    }
+    let result = read_string(&mut env, &mnemonic, "mnemonic").and_then(|mnemonic| {
+        let wallet = Wallet::from_mnemonic_no_passphrase(&mnemonic)
+            .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+        Wallet::validate_address(&wallet.address)
+            .map_err(|e| format!("Derived an invalid address: {}", e))?;
+        log::info!("Derived real address: {}", wallet.address);
+        Ok(wallet.address)
+    });
+    to_jstring(&env, result)
 }
 
 #[no_mangle]
@@ -1069,33 +1465,21 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let mnemonic_str: String = match env.get_string(&mnemonic) {
         Ok(s) => s.into(),
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid mnemonic: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid mnemonic: {}", e)).into_jstring(&env);
         }
     };
     
     let msg_type_str: String = match env.get_string(&msg_type) {
         Ok(s) => s.into(),
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid msg_type: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid msg_type: {}", e)).into_jstring(&env);
         }
     };
     
     let msg_data_str: String = match env.get_string(&msg_data_json) {
         Ok(s) => s.into(),
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid msg_data: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid msg_data: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1113,11 +1497,7 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let wallet = match Wallet::from_mnemonic_no_passphrase(&mnemonic_str) {
         Ok(w) => w,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid mnemonic: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid mnemonic: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1125,21 +1505,13 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let compressed_key = match wallet.public_key_compressed() {
         Ok(key) => key,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Failed to get compressed public key: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Failed to get compressed public key: {}", e)).into_jstring(&env);
         }
     };
-    let signer = match crate::eip712::Eip712Signer::new(wallet.private_key_bytes(), &compressed_key) {
+    let mut signer = match crate::eip712::Eip712Signer::new(wallet.private_key_bytes(), &compressed_key) {
         Ok(s) => s,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Failed to create signer: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Failed to create signer: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1147,11 +1519,7 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let msg_data: serde_json::Value = match serde_json::from_str(&msg_data_str) {
         Ok(d) => d,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid JSON in msg_data: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid JSON in msg_data: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1165,11 +1533,14 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
         None
     };
     
-    // Sign the transaction with sender address
+    // Sign the transaction with sender address, for the configured network
+    let network = configured_network();
+    signer.set_chain(network.chain);
     match signer.sign_transaction(
         &msg_type_str,
         &msg_data,
         &wallet.address,
+        &network.contract_address,
         account_number as u64,
         sequence as u64,
         fee,
@@ -1188,11 +1559,7 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
             }
         }
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Signing failed: {}"}}"#, e);
-            match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            }
+            JniResult::<()>::err(format!("Signing failed: {}", e)).into_jstring(&env)
         }
     }
 }
@@ -1215,33 +1582,21 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let mnemonic_str: String = match env.get_string(&mnemonic) {
         Ok(s) => s.into(),
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid mnemonic: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid mnemonic: {}", e)).into_jstring(&env);
         }
     };
     
     let msg_type_str: String = match env.get_string(&msg_type) {
         Ok(s) => s.into(),
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid msg_type: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid msg_type: {}", e)).into_jstring(&env);
         }
     };
     
     let msg_data_str: String = match env.get_string(&msg_data_json) {
         Ok(s) => s.into(),
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid msg_data: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid msg_data: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1259,11 +1614,7 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let wallet = match Wallet::from_mnemonic_no_passphrase(&mnemonic_str) {
         Ok(w) => w,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Failed to create wallet: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Failed to create wallet: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1271,21 +1622,13 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let compressed_key = match wallet.public_key_compressed() {
         Ok(key) => key,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Failed to get compressed public key: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Failed to get compressed public key: {}", e)).into_jstring(&env);
         }
     };
-    let signer = match Eip712Signer::new(wallet.private_key_bytes(), &compressed_key) {
+    let mut signer = match Eip712Signer::new(wallet.private_key_bytes(), &compressed_key) {
         Ok(s) => s,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Failed to create signer: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Failed to create signer: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1293,11 +1636,7 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
     let msg_data: serde_json::Value = match serde_json::from_str(&msg_data_str) {
         Ok(d) => d,
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Invalid JSON in msg_data: {}"}}"#, e);
-            return match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            };
+            return JniResult::<()>::err(format!("Invalid JSON in msg_data: {}", e)).into_jstring(&env);
         }
     };
     
@@ -1311,11 +1650,14 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
         None
     };
     
-    // Sign the transaction with sender address
+    // Sign the transaction with sender address, for the configured network
+    let network = configured_network();
+    signer.set_chain(network.chain);
     match signer.sign_transaction(
         &msg_type_str,
         &msg_data,
         &wallet.address,
+        &network.contract_address,
         account_number as u64,
         sequence as u64,
         fee,
@@ -1334,11 +1676,7 @@ pub extern "system" fn Java_io_gelotto_gmine_bridge_EipBridgeService_signTransac
             }
         }
         Err(e) => {
-            let err = format!(r#"{{"success":false,"error":"Signing failed: {}"}}"#, e);
-            match env.new_string(err) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(_) => std::ptr::null_mut()
-            }
+            JniResult::<()>::err(format!("Signing failed: {}", e)).into_jstring(&env)
         }
     }
 }
@@ -1369,4 +1707,37 @@ pub extern "system" fn Java_io_gelotto_gmine_thermal_AndroidThermalManager_updat
     });
     
     log::debug!("Updated thermal state: {:.1}°C, critical: {}", temperature, is_critical != 0);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_stops_worker_threads() {
+        let wallet = Wallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let mut state = MiningState::new(wallet).unwrap();
+        state.is_mining.store(true, Ordering::Relaxed);
+        
+        let alive = Arc::new(AtomicU64::new(0));
+        for _ in 0..3 {
+            let is_mining = state.is_mining.clone();
+            let alive = alive.clone();
+            alive.fetch_add(1, Ordering::SeqCst);
+            state.threads.push(thread::spawn(move || {
+                while is_mining.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(5));
+                }
+                alive.fetch_sub(1, Ordering::SeqCst);
+            }));
+        }
+        *MINING_STATE.lock().unwrap() = Some(state);
+        
+        cleanup_mining_state();
+        
+        assert!(MINING_STATE.lock().unwrap().is_none());
+        assert_eq!(alive.load(Ordering::SeqCst), 0);
+    }
+}
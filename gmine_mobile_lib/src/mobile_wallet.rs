@@ -1,13 +1,13 @@
 use anyhow::{Result, bail, anyhow};
 use bip39::Mnemonic;
-use bip32::{XPrv, ChildNumber};
+use bip32::{XPrv, DerivationPath};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 use tiny_keccak::{Hasher, Keccak};
 use bech32::{self, Hrp};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 use log::{info, warn, debug};
 
-const INJECTIVE_HD_PATH: &str = "m/44'/60'/0'/0/0"; // Ethereum-style HD path for Injective
+pub const INJECTIVE_HD_PATH: &str = "m/44'/60'/0'/0/0"; // Ethereum-style HD path for Injective
 const INJECTIVE_PREFIX: &str = "inj";
 
 /// Mobile-optimized secure wallet for Injective blockchain
@@ -29,7 +29,12 @@ impl MobileWallet {
     /// Create a wallet from a BIP39 mnemonic phrase with optional passphrase
     /// For mobile security, this should only be used during wallet setup
     pub fn from_mnemonic(mnemonic_str: &str, passphrase: &str) -> Result<Self> {
-        info!("Creating mobile wallet from mnemonic");
+        Self::from_mnemonic_with_path(mnemonic_str, passphrase, INJECTIVE_HD_PATH)
+    }
+    
+    /// Create a wallet from a BIP39 mnemonic using a custom BIP32 derivation path
+    pub fn from_mnemonic_with_path(mnemonic_str: &str, passphrase: &str, path: &str) -> Result<Self> {
+        info!("Creating mobile wallet from mnemonic (path {})", path);
         
         // Parse and validate mnemonic
         let mnemonic = Mnemonic::parse(mnemonic_str)?;
@@ -38,7 +43,7 @@ impl MobileWallet {
         let seed = mnemonic.to_seed(passphrase);
         
         // Derive private key using proper BIP32 HD derivation
-        let private_key = derive_private_key_bip32(&seed, INJECTIVE_HD_PATH)?;
+        let private_key = derive_private_key_bip32(&seed, path)?;
         
         // Get public key from private key
         let secp = Secp256k1::new();
@@ -123,6 +128,12 @@ impl MobileWallet {
     
     /// Get the public key as uncompressed bytes (65 bytes with 0x04 prefix)
     pub fn public_key_bytes(&self) -> [u8; 65] {
+        self.public_key_uncompressed()
+    }
+    
+    /// Get the public key as uncompressed bytes (65 bytes: 0x04 + X + Y)
+    /// This is the form the address is derived from
+    pub fn public_key_uncompressed(&self) -> [u8; 65] {
         self.public_key_bytes
     }
     
@@ -248,23 +259,17 @@ impl Default for MobileTransactionSigner {
 }
 
 /// Derive a private key using proper BIP32 HD derivation
-fn derive_private_key_bip32(seed: &[u8], _path_str: &str) -> Result<[u8; 32]> {
-    // Create extended private key from seed
-    let xprv = XPrv::new(seed)
-        .map_err(|e| anyhow!("Failed to create XPrv from seed: {}", e))?;
-    
-    // Derive using Ethereum HD path: m/44'/60'/0'/0/0
+fn derive_private_key_bip32(seed: &[u8], path_str: &str) -> Result<[u8; 32]> {
+    // Parse the HD path, e.g. Ethereum-style m/44'/60'/0'/0/0
     // 44' = BIP44 purpose (hardened)
     // 60' = Ethereum coin type (hardened) 
-    // 0' = Account 0 (hardened)
+    // 0' = Account (hardened)
     // 0 = External chain
-    // 0 = Address index 0
-    let derived = xprv
-        .derive_child(ChildNumber::new(44, true)?)  // 44'
-        .and_then(|k| k.derive_child(ChildNumber::new(60, true)?))  // 60'
-        .and_then(|k| k.derive_child(ChildNumber::new(0, true)?))   // 0'
-        .and_then(|k| k.derive_child(ChildNumber::new(0, false)?))  // 0
-        .and_then(|k| k.derive_child(ChildNumber::new(0, false)?))  // 0
+    // 0 = Address index
+    let path: DerivationPath = path_str.parse()
+        .map_err(|e| anyhow!("Invalid derivation path '{}': {}", path_str, e))?;
+    
+    let derived = XPrv::derive_from_path(seed, &path)
         .map_err(|e| anyhow!("Failed to derive key: {}", e))?;
     
     // Return the private key bytes
@@ -318,6 +323,14 @@ mod tests {
         
         // Verify address validation
         assert!(MobileWallet::validate_address(&wallet.address).is_ok());
+        
+        // Same address as the desktop wallet (0x9858EfFD232B4033E47d90003D41EC34EcaEda94)
+        assert_eq!(wallet.address, "inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz");
+        
+        // Compressed and uncompressed forms are the same key
+        let compressed = wallet.public_key_compressed().unwrap();
+        assert_eq!(compressed[1..], wallet.public_key_uncompressed()[1..33]);
+        assert_eq!(PublicKey::from_slice(&compressed).unwrap().serialize_uncompressed(), wallet.public_key_uncompressed());
     }
     
     #[test]
@@ -29,16 +29,6 @@ impl MsgExecuteContractCompat {
         msg: serde_json::Value,
         funds: Vec<crate::types::Coin>,
     ) -> Self {
-        // Format funds as comma-separated string
-        let funds_str = if funds.is_empty() {
-            "0".to_string()
-        } else {
-            funds.iter()
-                .map(|coin| format!("{}{}", coin.amount, coin.denom))
-                .collect::<Vec<_>>()
-                .join(",")
-        };
-        
         Self {
             sender,
             contract,
@@ -47,7 +37,7 @@ impl MsgExecuteContractCompat {
             // with caution or refactored to use a builder pattern where the
             // `msg` field can be held as a `serde_json::Value` until final encoding.
             msg: msg.to_string(),
-            funds: funds_str,
+            funds: format_funds(&funds),
         }
     }
     
@@ -55,4 +45,37 @@ impl MsgExecuteContractCompat {
     pub fn type_url() -> &'static str {
         "/injective.wasmx.v1.MsgExecuteContractCompat"
     }
-}
\ No newline at end of file
+}
+
+/// The one way to render `funds` for MsgExecuteContractCompat: "100inj,200usdt", or "0"
+/// when empty. The EIP-712 payload and the broadcast message must both use this - if the
+/// signed string differs from the broadcast one by a byte, the signature is invalid
+pub fn format_funds(funds: &[crate::types::Coin]) -> String {
+    if funds.is_empty() {
+        "0".to_string()
+    } else {
+        funds.iter()
+            .map(|coin| format!("{}{}", coin.amount, coin.denom))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Coin;
+
+    #[test]
+    fn test_funds_format() {
+        assert_eq!(format_funds(&[]), "0");
+        let funds = vec![
+            Coin { denom: "inj".to_string(), amount: "100".to_string() },
+            Coin { denom: "usdt".to_string(), amount: "200".to_string() },
+        ];
+        assert_eq!(format_funds(&funds), "100inj,200usdt");
+        
+        let msg = MsgExecuteContractCompat::new("inj1a".to_string(), "inj1b".to_string(), serde_json::json!({}), funds.clone());
+        assert_eq!(msg.funds, format_funds(&funds));
+    }
+}
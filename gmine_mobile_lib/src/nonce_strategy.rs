@@ -0,0 +1,155 @@
+//! Order in which a worker walks its nonce range
+//!
+//! Hashing is deterministic, so a worker that restarts (app backgrounded, laptop
+//! asleep) and searches sequentially again re-hashes the low end of its range it
+//! already found nothing in. Random sampling draws fresh nonces after every restart,
+//! so work done before a restart isn't repeated. `cargo bench --bench nonce_strategy`
+//! compares time-to-solution for both under frequent restarts
+//!
+//! Several devices mining with one wallet share a nonce range, so sequential workers
+//! can also be rotated by a fraction of the range (`setNonceOffset`) to keep devices
+//! from hashing the same nonces first
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonceStrategy {
+    /// Walk the range upwards from a per-worker offset
+    #[default]
+    Sequential,
+    /// Uniform samples from the whole range, from a per-worker PRNG
+    Random,
+}
+
+impl NonceStrategy {
+    /// Parse the JNI/config name ("sequential" or "random")
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "sequential" => Ok(NonceStrategy::Sequential),
+            "random" => Ok(NonceStrategy::Random),
+            other => Err(format!("Unknown nonce strategy '{}' (expected sequential or random)", other)),
+        }
+    }
+}
+
+/// Nonces for one worker to hash, in strategy order
+#[derive(Debug)]
+pub enum NonceSampler {
+    /// `wrap` is the part of the range below a rotated starting point, walked after `end`
+    Sequential { next: u64, end: u64, exhausted: bool, wrap: Option<(u64, u64)> },
+    Random { range: RangeInclusive<u64>, rng: StdRng },
+}
+
+impl NonceSampler {
+    /// Sampler for worker `id` over `start..=end`, with sequential workers rotated by
+    /// `offset` (a fraction of the range); random samplers are seeded from OS entropy
+    pub fn new(strategy: NonceStrategy, id: usize, start: u64, end: u64, offset: f64) -> Self {
+        match strategy {
+            NonceStrategy::Sequential => Self::sequential(id, start, end, offset),
+            NonceStrategy::Random => Self::random(start, end, StdRng::from_entropy()),
+        }
+    }
+
+    /// Worker `id` starts `id * 1000` nonces past `offset` of the way into the range.
+    /// A rotated worker continues from `start` after reaching `end`, up to the rotation point
+    pub fn sequential(id: usize, start: u64, end: u64, offset: f64) -> Self {
+        let span = end.saturating_sub(start);
+        let rotation = if offset.is_finite() && offset > 0.0 {
+            ((span as f64 * offset.rem_euclid(1.0)) as u64).min(span)
+        } else {
+            0
+        };
+        let wrap = (rotation > 0).then(|| (start, start + rotation - 1));
+        let next = (start + rotation).saturating_add(id as u64 * 1000);
+        if next > end {
+            // Started past the top: only the wrapped part is left
+            return match wrap {
+                Some((from, to)) => NonceSampler::Sequential { next: from, end: to, exhausted: false, wrap: None },
+                None => NonceSampler::Sequential { next, end, exhausted: true, wrap: None },
+            };
+        }
+        NonceSampler::Sequential { next, end, exhausted: false, wrap }
+    }
+
+    pub fn random(start: u64, end: u64, rng: StdRng) -> Self {
+        NonceSampler::Random { range: start..=end, rng }
+    }
+}
+
+impl Iterator for NonceSampler {
+    type Item = u64;
+
+    /// None once a sequential worker passes the end of its range; random never runs out
+    fn next(&mut self) -> Option<u64> {
+        match self {
+            NonceSampler::Sequential { next, end, exhausted, wrap } => {
+                if *exhausted {
+                    return None;
+                }
+                let nonce = *next;
+                if nonce == *end {
+                    match wrap.take() {
+                        Some((from, to)) => {
+                            *next = from;
+                            *end = to;
+                        }
+                        None => *exhausted = true,
+                    }
+                } else {
+                    *next += 1;
+                }
+                Some(nonce)
+            }
+            NonceSampler::Random { range, rng } => Some(rng.gen_range(range.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(NonceStrategy::from_name("Random").unwrap(), NonceStrategy::Random);
+        assert_eq!(NonceStrategy::from_name(" sequential ").unwrap(), NonceStrategy::Sequential);
+        assert!(NonceStrategy::from_name("spiral").is_err());
+    }
+
+    #[test]
+    fn test_sequential_stays_in_range() {
+        let nonces: Vec<u64> = NonceSampler::sequential(1, 0, 1002, 0.0).collect();
+        assert_eq!(nonces, vec![1000, 1001, 1002]);
+        assert_eq!(NonceSampler::sequential(0, u64::MAX - 1, u64::MAX, 0.0).count(), 2);
+        assert_eq!(NonceSampler::sequential(5, 0, 100, 0.0).next(), None);
+    }
+
+    #[test]
+    fn test_sequential_offset_wraps() {
+        // Half way into 0..=9, then back round to just below the rotation point
+        let nonces: Vec<u64> = NonceSampler::sequential(0, 0, 9, 0.5).collect();
+        assert_eq!(nonces, vec![4, 5, 6, 7, 8, 9, 0, 1, 2, 3]);
+
+        // A worker whose stagger lands past the top only has the wrapped part left
+        let nonces: Vec<u64> = NonceSampler::sequential(1, 0, 1199, 0.5).collect();
+        assert_eq!(nonces.len(), 599);
+        assert_eq!(nonces.first(), Some(&0));
+    }
+
+    #[test]
+    fn test_random_samples_whole_range() {
+        let (start, end) = (5_000, 5_009);
+        let mut seen = [false; 10];
+        for nonce in NonceSampler::random(start, end, StdRng::seed_from_u64(7)).take(1_000) {
+            assert!((start..=end).contains(&nonce));
+            seen[(nonce - start) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        // Each worker gets its own stream
+        let a: Vec<u64> = NonceSampler::random(0, u64::MAX, StdRng::seed_from_u64(1)).take(4).collect();
+        let b: Vec<u64> = NonceSampler::random(0, u64::MAX, StdRng::seed_from_u64(2)).take(4).collect();
+        assert_ne!(a, b);
+    }
+}
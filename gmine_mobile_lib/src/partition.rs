@@ -0,0 +1,62 @@
+use blake2::{Blake2b512, Digest};
+
+use crate::byte_order::epoch_bytes;
+
+/// Number of nonce partitions per epoch; each miner searches one of them
+pub const PARTITION_COUNT: u64 = 1000;
+
+/// Partitions are rotated by `epoch * EPOCH_ROTATION` so miners can't grind a favourable slot
+const EPOCH_ROTATION: u64 = 37;
+
+/// Seed derived from Blake2b512(address || epoch big-endian), as the contract does
+fn partition_seed(miner_address: &str, epoch_number: u64) -> u64 {
+    let mut hasher = Blake2b512::new();
+    hasher.update(miner_address.as_bytes());
+    hasher.update(epoch_bytes(epoch_number));
+    let hash = hasher.finalize();
+    u64::from_be_bytes([
+        hash[0], hash[1], hash[2], hash[3], hash[4], hash[5], hash[6], hash[7],
+    ])
+}
+
+/// Index (0..1000) of the partition assigned to a miner for an epoch, after rotation
+pub fn partition_index(miner_address: &str, epoch_number: u64) -> u64 {
+    let base = partition_seed(miner_address, epoch_number) % PARTITION_COUNT;
+    (base + epoch_number.wrapping_mul(EPOCH_ROTATION) % PARTITION_COUNT) % PARTITION_COUNT
+}
+
+/// Nonce range `(start, end)` a miner may reveal for an epoch
+/// Mirrors the contract's calculate_nonce_range. The rotated offset wraps around
+/// u64::MAX, so a partition whose base + rotation passes 1000 starts a few hundred
+/// nonces below `partition_index * (u64::MAX / 1000)` (and for index 0, `end < start`)
+pub fn calculate_nonce_range(miner_address: &str, epoch_number: u64) -> (u64, u64) {
+    let nonce_space = u64::MAX / PARTITION_COUNT;
+    let partition_offset = (partition_seed(miner_address, epoch_number) % PARTITION_COUNT) * nonce_space;
+    let epoch_rotation = epoch_number.wrapping_mul(EPOCH_ROTATION) % PARTITION_COUNT;
+    let rotated_offset = partition_offset.wrapping_add(epoch_rotation * nonce_space);
+
+    (rotated_offset, rotated_offset.wrapping_add(nonce_space))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_index_matches_range() {
+        let address = "inj1testaddress123456789";
+        assert_eq!(partition_index(address, 1287), 983);
+
+        let (start, end) = calculate_nonce_range(address, 1287);
+        let nonce_space = u64::MAX / PARTITION_COUNT;
+        assert_eq!(start, 983 * nonce_space);
+        assert_eq!(end - start, nonce_space);
+    }
+
+    #[test]
+    fn test_partition_index_in_bounds() {
+        for epoch in 0..200 {
+            assert!(partition_index("inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz", epoch) < PARTITION_COUNT);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// Format version of exported proofs
+pub const PROOF_VERSION: u32 = 1;
+/// Hash algorithm proofs are checked with
+pub const PROOF_ALGORITHM: &str = "drillx";
+
+/// Self-contained record of a found solution that anyone can re-check offline with drillx,
+/// without trusting the miner's logs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolutionProof {
+    pub version: u32,
+    pub algorithm: String,
+    pub epoch: u64,
+    /// Address of the miner that found the solution
+    pub miner: String,
+    /// Epoch challenge (the contract's target_hash), hex
+    pub challenge: String,
+    /// Nonce as a decimal string - u64 doesn't fit in a JSON/Java double
+    #[serde(with = "u64_string")]
+    pub nonce: u64,
+    /// drillx digest (16 bytes), hex
+    pub digest: String,
+    /// Difficulty (leading zero bits) the miner claims the solution reaches
+    pub difficulty: u32,
+}
+
+mod u64_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl SolutionProof {
+    pub fn new(epoch: u64, miner: &str, challenge: &[u8; 32], nonce: u64, digest: &[u8; 16], difficulty: u32) -> Self {
+        Self {
+            version: PROOF_VERSION,
+            algorithm: PROOF_ALGORITHM.to_string(),
+            epoch,
+            miner: miner.to_string(),
+            challenge: hex::encode(challenge),
+            nonce,
+            digest: hex::encode(digest),
+            difficulty,
+        }
+    }
+
+    /// Build a proof for `nonce` by hashing it against the challenge
+    /// Fails if the nonce has no drillx solution for this challenge
+    pub fn from_nonce(epoch: u64, miner: &str, challenge: &[u8; 32], nonce: u64) -> Result<Self, Box<dyn Error>> {
+        let hash = drillx::hash_with_memory(&mut equix::SolverMemory::new(), challenge, &crate::byte_order::nonce_bytes(nonce))
+            .map_err(|e| format!("Nonce {} has no drillx solution for this challenge: {:?}", nonce, e))?;
+        Ok(Self::new(epoch, miner, challenge, nonce, &hash.d, hash.difficulty()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Recompute the drillx hash and check the proof's digest and claimed difficulty
+    /// Returns the difficulty the solution actually reaches
+    pub fn verify(&self) -> Result<u32, Box<dyn Error>> {
+        if !self.algorithm.eq_ignore_ascii_case(PROOF_ALGORITHM) {
+            return Err(format!("Unsupported algorithm '{}' (expected {})", self.algorithm, PROOF_ALGORITHM).into());
+        }
+        let challenge: [u8; 32] = hex::decode(&self.challenge)?
+            .try_into()
+            .map_err(|_| "Challenge must be 32 bytes")?;
+        let digest: [u8; 16] = hex::decode(&self.digest)?
+            .try_into()
+            .map_err(|_| "Digest must be 16 bytes")?;
+
+        let hash = drillx::hash_with_memory(&mut equix::SolverMemory::new(), &challenge, &crate::byte_order::nonce_bytes(self.nonce))
+            .map_err(|e| format!("Nonce {} has no drillx solution for this challenge: {:?}", self.nonce, e))?;
+        if hash.d != digest {
+            return Err(format!(
+                "Digest mismatch: proof has {}, drillx computes {}",
+                self.digest, hex::encode(hash.d)
+            ).into());
+        }
+        let actual = hash.difficulty();
+        if actual < self.difficulty {
+            return Err(format!(
+                "Solution reaches difficulty {}, below the claimed {}",
+                actual, self.difficulty
+            ).into());
+        }
+        Ok(actual)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// First nonce with a drillx solution for the challenge
+    fn solvable_proof(challenge: &[u8; 32]) -> SolutionProof {
+        (0..1000u64)
+            .find_map(|nonce| SolutionProof::from_nonce(42, "inj1testaddress123456789", challenge, nonce).ok())
+            .expect("no solvable nonce in 0..1000")
+    }
+
+    #[test]
+    fn test_proof_roundtrip_and_verify() {
+        let proof = solvable_proof(&[7u8; 32]);
+        assert_eq!(proof.verify().unwrap(), proof.difficulty);
+
+        let json = proof.to_json();
+        // The nonce survives consumers that parse numbers as doubles
+        assert!(json.contains(&format!("\"nonce\": \"{}\"", proof.nonce)));
+        assert_eq!(SolutionProof::from_json(&json).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_tampered_proofs_fail() {
+        let proof = solvable_proof(&[7u8; 32]);
+
+        let mut overclaimed = proof.clone();
+        overclaimed.difficulty = proof.difficulty + 1;
+        assert!(overclaimed.verify().unwrap_err().to_string().contains("below the claimed"));
+
+        let mut other_challenge = proof.clone();
+        other_challenge.challenge = hex::encode([8u8; 32]);
+        assert!(other_challenge.verify().is_err());
+
+        let mut bad_digest = proof;
+        bad_digest.digest = hex::encode([0u8; 16]);
+        assert!(bad_digest.verify().is_err());
+    }
+}
@@ -0,0 +1,67 @@
+//! Opt-in dump of exactly what was signed and broadcast, for diagnosing "signature
+//! verification failed" rejections: the hash the key signed (EIP-712 digest or
+//! SIGN_MODE_DIRECT sign doc hash) and the signed transaction as submitted.
+//! Off by default - nothing here is a secret, but it ties the wallet's account,
+//! sequence and messages to a log that tends to get pasted into bug reports
+use base64::{Engine as _, engine::general_purpose};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn signed transaction logging on or off (process-wide)
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Log the hash the key signed for `msg_type`
+pub fn log_sign_hash(kind: &str, msg_type: &str, account_number: u64, sequence: u64, hash: &[u8]) {
+    if enabled() {
+        log::debug!(
+            "Signed {} (account {}, sequence {}): {} hash 0x{}",
+            msg_type, account_number, sequence, kind, hex::encode(hash)
+        );
+    }
+}
+
+/// Log signed protobuf transaction bytes, base64 like the broadcast `tx_bytes` field
+pub fn log_tx_bytes(msg_type: &str, tx_bytes: &[u8]) {
+    if enabled() {
+        log::debug!("Signed {} tx: {}", msg_type, describe_tx_bytes(tx_bytes));
+    }
+}
+
+/// Log a signed transaction submitted as JSON (the mobile REST paths)
+pub fn log_tx_json(msg_type: &str, tx: &serde_json::Value) {
+    if enabled() {
+        log::debug!("Signed {} tx: {}", msg_type, tx);
+    }
+}
+
+/// Hash (as the chain reports it) and base64 of a signed transaction
+fn describe_tx_bytes(tx_bytes: &[u8]) -> String {
+    format!(
+        "hash {}, {} bytes, base64 {}",
+        hex::encode_upper(Sha256::digest(tx_bytes)),
+        tx_bytes.len(),
+        general_purpose::STANDARD.encode(tx_bytes)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_tx_bytes() {
+        // The tx hash the chain reports is the upper-case SHA-256 of the raw bytes
+        assert_eq!(
+            describe_tx_bytes(b"abc"),
+            "hash BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD, 3 bytes, base64 YWJj"
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::types::Solution;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Default number of solutions held before backpressure kicks in
+pub const DEFAULT_SOLUTION_QUEUE_CAPACITY: usize = 64;
+
+/// Bounded FIFO of found solutions awaiting submission
+///
+/// When full, the lowest-difficulty solution (queued or incoming) is dropped,
+/// since only the best solutions are worth committing anyway.
+#[derive(Debug)]
+pub struct SolutionQueue {
+    solutions: VecDeque<Solution>,
+    capacity: usize,
+    dropped: u64,
+    /// When the queue last went from empty to non-empty
+    waiting_since: Option<Instant>,
+}
+
+impl SolutionQueue {
+    pub fn new(capacity: usize) -> Self {
+        SolutionQueue {
+            solutions: VecDeque::new(),
+            capacity: capacity.max(1),
+            dropped: 0,
+            waiting_since: None,
+        }
+    }
+
+    /// Queue a solution, returning whichever solution was dropped if the queue was full
+    pub fn push(&mut self, solution: Solution) -> Option<Solution> {
+        if self.solutions.is_empty() {
+            self.waiting_since = Some(Instant::now());
+        }
+        if self.solutions.len() < self.capacity {
+            self.solutions.push_back(solution);
+            return None;
+        }
+
+        self.dropped += 1;
+        // Oldest of the lowest-difficulty entries
+        let (lowest_idx, lowest) = self
+            .solutions
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, s)| s.difficulty)
+            .map(|(i, s)| (i, s.difficulty))?;
+
+        if solution.difficulty <= lowest {
+            return Some(solution);
+        }
+        let evicted = self.solutions.remove(lowest_idx);
+        self.solutions.push_back(solution);
+        evicted
+    }
+
+    pub fn pop_front(&mut self) -> Option<Solution> {
+        let solution = self.solutions.pop_front();
+        if self.solutions.is_empty() {
+            self.waiting_since = None;
+        }
+        solution
+    }
+
+    /// Take the best solution of each of up to `max_epochs` epochs (oldest first) to
+    /// commit together. The contract takes one commit per miner per epoch, so solutions
+    /// for `committed` and the runners-up of the taken epochs are dropped; solutions
+    /// for epochs past the limit stay queued
+    pub fn take_batch(&mut self, max_epochs: usize, committed: Option<u64>) -> Vec<Solution> {
+        let mut epochs: Vec<u64> = self
+            .solutions
+            .iter()
+            .map(|s| s.epoch)
+            .filter(|epoch| Some(*epoch) != committed)
+            .collect();
+        epochs.sort_unstable();
+        epochs.dedup();
+        epochs.truncate(max_epochs);
+
+        let mut batch: Vec<Solution> = Vec::new();
+        let mut waiting = VecDeque::new();
+        for solution in self.solutions.drain(..) {
+            if Some(solution.epoch) == committed {
+                continue;
+            }
+            if !epochs.contains(&solution.epoch) {
+                waiting.push_back(solution);
+                continue;
+            }
+            match batch.iter_mut().find(|best| best.epoch == solution.epoch) {
+                Some(best) if best.difficulty >= solution.difficulty => {}
+                Some(best) => *best = solution,
+                None => batch.push(solution),
+            }
+        }
+        self.solutions = waiting;
+        if self.solutions.is_empty() {
+            self.waiting_since = None;
+        }
+        batch.sort_by_key(|s| s.epoch);
+        batch
+    }
+
+    /// When the oldest solution still waiting was queued (None if empty)
+    pub fn waiting_since(&self) -> Option<Instant> {
+        self.waiting_since
+    }
+
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity, dropping lowest-difficulty solutions if now over it
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.solutions.len() > self.capacity {
+            if let Some((idx, _)) = self
+                .solutions
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, s)| s.difficulty)
+            {
+                self.solutions.remove(idx);
+                self.dropped += 1;
+            }
+        }
+    }
+
+    /// Total solutions dropped due to backpressure
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+impl Default for SolutionQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_SOLUTION_QUEUE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(nonce: u64, difficulty: u8) -> Solution {
+        solution_for(1, nonce, difficulty)
+    }
+
+    fn solution_for(epoch: u64, nonce: u64, difficulty: u8) -> Solution {
+        Solution {
+            nonce,
+            hash: vec![0; 16],
+            difficulty,
+            epoch,
+        }
+    }
+
+    #[test]
+    fn test_batch_takes_best_solution_per_epoch() {
+        let mut queue = SolutionQueue::new(16);
+        for (epoch, nonce, difficulty) in [(12, 1, 9), (10, 2, 8), (11, 3, 7), (12, 4, 14), (9, 5, 20), (13, 6, 8)] {
+            queue.push(solution_for(epoch, nonce, difficulty));
+        }
+
+        // Epoch 9 is already committed; only two epochs fit in this batch
+        let batch = queue.take_batch(2, Some(9));
+        let taken: Vec<(u64, u64)> = batch.iter().map(|s| (s.epoch, s.nonce)).collect();
+        assert_eq!(taken, vec![(10, 2), (11, 3)]);
+        assert!(queue.waiting_since().is_some());
+
+        // The best of epoch 12 is taken, the runner-up dropped
+        let batch = queue.take_batch(5, Some(11));
+        let taken: Vec<(u64, u64)> = batch.iter().map(|s| (s.epoch, s.nonce)).collect();
+        assert_eq!(taken, vec![(12, 4), (13, 6)]);
+        assert!(queue.is_empty());
+        assert!(queue.waiting_since().is_none());
+    }
+
+    #[test]
+    fn test_full_queue_keeps_highest_difficulty() {
+        let mut queue = SolutionQueue::new(3);
+        assert!(queue.push(solution(1, 10)).is_none());
+        assert!(queue.push(solution(2, 8)).is_none());
+        assert!(queue.push(solution(3, 12)).is_none());
+
+        // Better than the worst queued -> evicts difficulty 8
+        assert_eq!(queue.push(solution(4, 15)).unwrap().nonce, 2);
+        // Worse than everything queued -> incoming is dropped
+        assert_eq!(queue.push(solution(5, 9)).unwrap().nonce, 5);
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.dropped(), 2);
+        let mut difficulties: Vec<u8> = std::iter::from_fn(|| queue.pop_front())
+            .map(|s| s.difficulty)
+            .collect();
+        difficulties.sort();
+        assert_eq!(difficulties, vec![10, 12, 15]);
+    }
+
+    #[test]
+    fn test_shrinking_capacity() {
+        let mut queue = SolutionQueue::new(4);
+        for (nonce, difficulty) in [(1, 9), (2, 11), (3, 7), (4, 13)] {
+            queue.push(solution(nonce, difficulty));
+        }
+        queue.set_capacity(2);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop_front().unwrap().difficulty, 11);
+        assert_eq!(queue.pop_front().unwrap().difficulty, 13);
+    }
+}
@@ -24,6 +24,7 @@ mod tests {
             "commit_solution",
             &msg_data,
             "inj1hkhdaj2a2clmq5jq6mspsggqs32vynpk228q3r",  // Test address
+            "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66",  // Mining contract
             12345,  // account_number
             0,      // sequence
             Some(Fee::default()),
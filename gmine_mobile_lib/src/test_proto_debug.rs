@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::tx_proto::ProtoTransactionBuilder;
+    use crate::execute_msg::ExecuteMsg;
     use crate::mobile_wallet::MobileWallet;
     use serde_json::json;
 
@@ -22,15 +23,15 @@ mod tests {
         ).unwrap();
         
         // Test advance_epoch message (simplest case)
-        let msg = json!({"_msg_type": "advance_epoch"});
+        let msg = ExecuteMsg::AdvanceEpoch {};
         
-        println!("Building transaction with message: {}", msg);
+        println!("Building transaction with message: {}", msg.to_contract_json());
         
         // Build transaction
         match builder.build_transaction(
             &address,
             "inj1mdq8lej6n35lp977w9nvc7mglwc3tqh5cms42y",
-            msg,
+            &msg,
             741150,  // account_number from Node.js test
             9104,    // sequence from Node.js test
             None,
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::transaction::Eip712TransactionBuilder;
+    use crate::execute_msg::ExecuteMsg;
     use crate::mobile_wallet::MobileWallet;
     use crate::types::Fee;
     use serde_json::json;
@@ -42,17 +43,15 @@ mod tests {
         ).unwrap();
         
         // Test commitment message
-        let msg = json!({
-            "commit_solution": {
-                "commitment": base64::Engine::encode(&base64::engine::general_purpose::STANDARD, b"test commitment data")
-            }
-        });
+        let msg = ExecuteMsg::CommitSolution {
+            commitment: json!(base64::Engine::encode(&base64::engine::general_purpose::STANDARD, b"test commitment data")),
+        };
         
         // Build transaction
         let tx = builder.build_transaction(
             &address,
             "inj1mdq8lej6n35lp977w9nvc7mglwc3tqh5cms42y",  // Mining contract
-            msg,
+            &msg,
             account_number,
             sequence,
             Some(Fee::default()),
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::transaction::Eip712TransactionBuilder;
+    use crate::execute_msg::ExecuteMsg;
     use crate::types::Fee;
     use serde_json::json;
 
@@ -16,17 +17,13 @@ mod tests {
         let builder = Eip712TransactionBuilder::new(&private_key, &public_key, "testnet").unwrap();
         
         // Test commitment message
-        let msg = json!({
-            "commit_solution": {
-                "commitment": "dGVzdCBjb21taXRtZW50IGRhdGE="  // base64 encoded
-            }
-        });
+        let msg = ExecuteMsg::CommitSolution { commitment: json!("dGVzdCBjb21taXRtZW50IGRhdGE=") };  // base64 encoded
         
         // Build transaction
         let tx = builder.build_transaction(
             "inj1hkhdaj2a2clmq5jq6mspsggqs32vynpk228q3r",  // Test address
             "inj1mdq8lej6n35lp977w9nvc7mglwc3tqh5cms42y",  // Mining contract
-            msg,
+            &msg,
             12345,  // account_number
             0,      // sequence
             Some(Fee::default()),
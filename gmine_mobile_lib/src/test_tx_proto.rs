@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::tx_proto::ProtoTransactionBuilder;
+    use crate::execute_msg::ExecuteMsg;
     use crate::mobile_wallet::MobileWallet;
     use crate::types::Fee;
     use serde_json::json;
@@ -23,17 +24,13 @@ mod tests {
         ).unwrap();
         
         // Test commitment message
-        let msg = json!({
-            "commit_solution": {
-                "commitment": "dGVzdCBjb21taXRtZW50IGRhdGE="
-            }
-        });
+        let msg = ExecuteMsg::CommitSolution { commitment: json!("dGVzdCBjb21taXRtZW50IGRhdGE=") };
         
         // Build transaction
         match builder.build_transaction(
             &address,
             "inj1mdq8lej6n35lp977w9nvc7mglwc3tqh5cms42y",
-            msg,
+            &msg,
             0,  // account_number
             0,  // sequence
             Some(Fee::default()),
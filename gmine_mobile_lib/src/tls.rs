@@ -0,0 +1,110 @@
+use serde::Serialize;
+use std::error::Error;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Port used when a connect address override has none
+const DEFAULT_HTTPS_PORT: u16 = 443;
+
+/// TLS settings for the LCD connection
+/// Roots always come from the bundled Mozilla store (webpki-roots): Android's system
+/// store varies by version and ROM, and isn't reachable from native code anyway
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsOptions {
+    /// Send the server name (SNI) in the handshake. Some carrier proxies and custom
+    /// ROMs reset connections on it
+    pub enable_sni: bool,
+    /// Connect here instead of resolving the LCD host (for broken DNS); the certificate
+    /// is still checked against the LCD host name
+    pub connect_addr: Option<SocketAddr>,
+}
+
+impl Default for TlsOptions {
+    fn default() -> Self {
+        Self {
+            enable_sni: true,
+            connect_addr: None,
+        }
+    }
+}
+
+impl TlsOptions {
+    /// Options from JNI arguments; `connect_addr` is "ip" or "ip:port", empty for none
+    pub fn parse(disable_sni: bool, connect_addr: &str) -> Result<Self, Box<dyn Error>> {
+        let connect_addr = match connect_addr.trim() {
+            "" => None,
+            addr => Some(addr.parse::<SocketAddr>()
+                .or_else(|_| addr.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, DEFAULT_HTTPS_PORT)))
+                .map_err(|_| format!("Invalid connect address '{}': expected ip or ip:port", addr))?),
+        };
+        Ok(Self { enable_sni: !disable_sni, connect_addr })
+    }
+
+    /// HTTP agent for the LCD with these settings
+    pub fn build_agent(&self) -> ureq::Agent {
+        let mut roots = rustls::RootCertStore::empty();
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        let mut config = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        config.enable_sni = self.enable_sni;
+
+        let mut builder = ureq::builder()
+            .timeout(Duration::from_secs(30))
+            .tls_config(Arc::new(config));
+        if let Some(addr) = self.connect_addr {
+            builder = builder.resolver(move |_netloc: &str| Ok(vec![addr]));
+        }
+        builder.build()
+    }
+}
+
+/// Result of a connectivity self-test, reported to the app as JSON
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectivityReport {
+    pub ok: bool,
+    pub url: String,
+    pub latency_ms: u64,
+    /// Full error chain on failure, down to the TLS alert or certificate problem
+    pub error: Option<String>,
+}
+
+/// Flatten a ureq error and its sources into one line, e.g.
+/// "Connection Failed: tls connection init failed: invalid peer certificate: UnknownIssuer"
+pub fn describe_error(err: &ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(code, _) => format!("HTTP {}", code),
+        ureq::Error::Transport(transport) => {
+            let mut description = transport.kind().to_string();
+            if let Some(message) = transport.message() {
+                description.push_str(": ");
+                description.push_str(message);
+            }
+            let mut source = transport.source();
+            while let Some(cause) = source {
+                description.push_str(": ");
+                description.push_str(&cause.to_string());
+                source = cause.source();
+            }
+            description
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(TlsOptions::parse(false, "").unwrap(), TlsOptions::default());
+
+        let options = TlsOptions::parse(true, "34.1.2.3").unwrap();
+        assert!(!options.enable_sni);
+        assert_eq!(options.connect_addr, Some("34.1.2.3:443".parse().unwrap()));
+        assert_eq!(TlsOptions::parse(false, "34.1.2.3:8443").unwrap().connect_addr, Some("34.1.2.3:8443".parse().unwrap()));
+
+        assert!(TlsOptions::parse(false, "lcd.example.com").is_err());
+    }
+}
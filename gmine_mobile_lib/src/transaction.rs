@@ -1,4 +1,5 @@
 use crate::eip712::Eip712Signer;
+use crate::execute_msg::ExecuteMsg;
 use crate::web3_extension::ExtensionOptionsWeb3Tx;
 use crate::types::{Fee, Coin};
 use serde_json::{json, Value};
@@ -46,7 +47,7 @@ impl Eip712TransactionBuilder {
         &self,
         sender_address: &str,
         contract_address: &str,
-        msg: Value,
+        msg: &ExecuteMsg,
         account_number: u64,
         sequence: u64,
         fee: Option<Fee>,
@@ -57,69 +58,14 @@ impl Eip712TransactionBuilder {
             "@type": "/injective.wasmx.v1.MsgExecuteContractCompat",
             "sender": sender_address,
             "contract": contract_address,
-            "msg": general_purpose::STANDARD.encode(msg.to_string()),
+            "msg": general_purpose::STANDARD.encode(msg.to_contract_json()),
             "funds": ""  // Empty string for Injective
         });
         
-        // Sign the message using EIP-712 - detect the actual message type
-        let msg_type = if msg.get("commit").is_some() || msg.get("commit_solution").is_some() {
-            "commit_solution"
-        } else if msg.get("reveal").is_some() || msg.get("reveal_solution").is_some() {
-            "reveal_solution"
-        } else if msg.get("claim_rewards").is_some() || msg.get("claim_reward").is_some() {
-            "claim_reward"
-        } else if msg.get("advance_epoch").is_some() {
-            "advance_epoch"
-        } else if msg.get("finalize_epoch").is_some() {
-            "finalize_epoch"
-        } else {
-            return Err(format!("Unknown message type in: {}", msg).into());
-        };
-        
-        // Extract the inner message data for signing
-        let signing_data = match msg_type {
-            "commit_solution" => {
-                if let Some(commit_msg) = msg.get("commit_solution") {
-                    commit_msg.clone()
-                } else if let Some(commit_msg) = msg.get("commit") {
-                    commit_msg.clone()
-                } else {
-                    return Err("Missing commit_solution data".into());
-                }
-            }
-            "reveal_solution" => {
-                if let Some(reveal_msg) = msg.get("reveal_solution") {
-                    reveal_msg.clone()
-                } else if let Some(reveal_msg) = msg.get("reveal") {
-                    reveal_msg.clone()
-                } else {
-                    return Err("Missing reveal_solution data".into());
-                }
-            }
-            "claim_reward" => {
-                if let Some(claim_msg) = msg.get("claim_reward") {
-                    claim_msg.clone()
-                } else if let Some(claim_msg) = msg.get("claim_rewards") {
-                    claim_msg.clone()
-                } else {
-                    json!({})
-                }
-            }
-            "advance_epoch" => json!({}),
-            "finalize_epoch" => {
-                if let Some(finalize_msg) = msg.get("finalize_epoch") {
-                    finalize_msg.clone()
-                } else {
-                    return Err("Missing finalize_epoch data".into());
-                }
-            }
-            _ => return Err(format!("Unknown message type: {}", msg_type).into()),
-        };
-        
-        let signing_result = self.signer.sign_transaction(
-            msg_type,
-            &signing_data,
+        let signing_result = self.signer.sign_msg(
+            msg,
             sender_address,
+            contract_address,
             account_number,
             sequence,
             fee.clone(),
@@ -241,16 +187,12 @@ mod tests {
         let builder = Eip712TransactionBuilder::new(&private_key, &public_key, "testnet")
             .unwrap();
         
-        let msg = json!({
-            "commit": {
-                "commitment": "0".repeat(64)
-            }
-        });
+        let msg = ExecuteMsg::CommitSolution { commitment: json!("0".repeat(64)) };
         
         let tx = builder.build_transaction(
             "inj1test...",
             "inj1mdq8lej6n35lp977w9nvc7mglwc3tqh5cms42y",
-            msg,
+            &msg,
             12345,
             0,
             None,
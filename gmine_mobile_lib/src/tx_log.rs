@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One broadcast transaction in the append-only audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxLogEntry {
+    /// Unix timestamp (milliseconds)
+    pub timestamp: u64,
+    /// Message type, e.g. "commit_solution"
+    pub tx_type: String,
+    /// Epoch number if known
+    pub epoch: Option<u64>,
+    /// Execute message sent to the contract
+    pub msg: serde_json::Value,
+    /// Gas limit requested
+    pub gas: u64,
+    /// Transaction hash on success
+    pub tx_hash: Option<String>,
+    /// Result code (0 on success)
+    pub code: Option<u32>,
+    /// Raw log / error message on failure
+    pub raw_log: Option<String>,
+}
+
+impl TxLogEntry {
+    pub fn from_result(
+        tx_type: &str,
+        epoch: Option<u64>,
+        msg: serde_json::Value,
+        gas: u64,
+        result: &Result<String, Box<dyn Error>>,
+    ) -> Self {
+        let (tx_hash, code, raw_log) = match result {
+            Ok(hash) => (Some(hash.clone()), Some(0), None),
+            Err(e) => (None, None, Some(e.to_string())),
+        };
+        TxLogEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            tx_type: tx_type.to_string(),
+            epoch,
+            msg,
+            gas,
+            tx_hash,
+            code,
+            raw_log,
+        }
+    }
+}
+
+/// Append an entry as a JSON line, flushing to disk immediately
+pub fn append_entry(path: &Path, entry: &TxLogEntry) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    file.flush()?;
+    file.sync_data()?;
+    Ok(())
+}
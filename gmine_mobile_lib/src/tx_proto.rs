@@ -1,7 +1,8 @@
 use crate::eip712::Eip712Signer;
+use crate::execute_msg::ExecuteMsg;
 use crate::web3_extension::ExtensionOptionsWeb3Tx;
 use crate::types::{Fee, Coin};
-use serde_json::{Value, json};
+use serde_json::Value;
 use std::error::Error;
 use base64::{Engine as _, engine::general_purpose};
 use prost::Message;
@@ -13,13 +14,15 @@ use cosmos_sdk_proto::cosmos::tx::v1beta1::{
 };
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
 use cosmos_sdk_proto::Any;
-use crate::msg_execute_contract_compat::MsgExecuteContractCompat;
+use crate::msg_execute_contract_compat::{format_funds, MsgExecuteContractCompat};
 
 /// Build a protobuf transaction for Injective
 pub struct ProtoTransactionBuilder {
     signer: Eip712Signer,
     rest_url: String,
     chain_id: String,
+    /// Proxy URL for REST calls (e.g. `socks5h://127.0.0.1:9050`)
+    proxy: Option<String>,
 }
 
 impl ProtoTransactionBuilder {
@@ -47,62 +50,52 @@ impl ProtoTransactionBuilder {
             signer,
             rest_url,
             chain_id,
+            proxy: None,
         })
     }
     
+    /// Broadcast to a different LCD endpoint than the network default (e.g. a local devnet)
+    pub fn with_rest_url(mut self, rest_url: Option<String>) -> Self {
+        if let Some(rest_url) = rest_url {
+            self.rest_url = rest_url.trim_end_matches('/').to_string();
+        }
+        self
+    }
+    
+    /// Send REST calls through a proxy
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+    
+    /// HTTP client for REST calls, proxied if configured
+    fn http_client(&self) -> Result<reqwest::Client, Box<dyn Error>> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(ref proxy) = self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+        Ok(builder.build()?)
+    }
+    
     /// Build and sign a transaction, returning protobuf bytes
     pub fn build_transaction(
         &self,
         sender_address: &str,
         contract_address: &str,
-        msg: Value,
+        msg: &ExecuteMsg,
         account_number: u64,
         sequence: u64,
         fee: Option<Fee>,
         memo: &str,
     ) -> Result<Vec<u8>, Box<dyn Error>> {
-        // The msg parameter contains the inner message data
-        // We need to wrap it in the proper contract message format with PascalCase
-        // Create clean message without _msg_type for contract
-        let clean_msg = if msg.get("_msg_type").is_some() {
-            let mut cleaned = msg.clone();
-            if let Some(obj) = cleaned.as_object_mut() {
-                obj.remove("_msg_type");
-            }
-            cleaned
-        } else {
-            msg.clone()
-        };
-        
-        let contract_msg = if msg.get("_msg_type").and_then(|v| v.as_str()) == Some("advance_epoch") {
-            json!({"advance_epoch": {}})
-        } else if msg.get("commitment").is_some() {
-            json!({"commit_solution": clean_msg})
-        } else if msg.get("nonce").is_some() {
-            json!({"reveal_solution": clean_msg})
-        } else if msg.get("epoch_number").is_some() {
-            // Check the hint first to distinguish between finalize_epoch and claim_reward
-            if msg.get("_msg_type").and_then(|v| v.as_str()) == Some("claim_reward") {
-                // For claim_reward, only include epoch_number
-                let epoch_number = msg.get("epoch_number").unwrap();
-                json!({"claim_reward": {"epoch_number": epoch_number}})
-            } else {
-                json!({"finalize_epoch": clean_msg})
-            }
-        } else if msg.is_object() && msg.as_object().unwrap().is_empty() {
-            json!({"claim_reward": {}})
-        } else {
-            clean_msg
-        };
+        let contract_msg = msg.to_contract_json();
         
         // Debug log what we're sending to the contract
-        log::info!("Contract message being sent: {}", serde_json::to_string_pretty(&contract_msg).unwrap_or_default());
+        log::info!("Contract message being sent: {}", contract_msg);
         
         // Additional debugging - show exact bytes
-        let msg_bytes = contract_msg.to_string().as_bytes().to_vec();
-        log::debug!("Message as bytes: {:?}", msg_bytes);
-        log::debug!("Message byte length: {}", msg_bytes.len());
-        log::debug!("Message as hex: {}", hex::encode(&msg_bytes));
+        log::debug!("Message byte length: {}", contract_msg.len());
+        log::debug!("Message as hex: {}", hex::encode(contract_msg.as_bytes()));
         
         // Create the MsgExecuteContractCompat (Injective-specific)
         // IMPORTANT: The msg field expects a JSON string. The contract will parse this
@@ -110,8 +103,8 @@ impl ProtoTransactionBuilder {
         let execute_msg = MsgExecuteContractCompat {
             sender: sender_address.to_string(),
             contract: contract_address.to_string(),
-            msg: contract_msg.to_string(), // This creates the JSON string
-            funds: "0".to_string(), // Empty funds as "0" string
+            msg: contract_msg, // Exactly the string that is signed
+            funds: format_funds(&[]), // Must match the signed EIP-712 funds string
         };
         
         // Wrap in Any
@@ -140,44 +133,10 @@ impl ProtoTransactionBuilder {
         };
         
         // Sign the transaction using EIP-712
-        // The msg parameter here is the contract execute message content, not wrapped
-        log::info!("ProtoTransactionBuilder: determining message type from: {}", msg);
-        let msg_type = if msg.get("commitment").is_some() {
-            "commit_solution"
-        } else if msg.get("nonce").is_some() {
-            "reveal_solution"
-        } else if msg.get("_msg_type").is_some() {
-            // Handle hint from rust_signer first
-            msg.get("_msg_type").unwrap().as_str().unwrap()
-        } else if msg.get("epoch_number").is_some() {
-            "finalize_epoch"
-        } else if msg.is_object() && msg.as_object().unwrap().is_empty() {
-            // Empty object defaults to advance_epoch
-            "advance_epoch"
-        } else {
-            return Err(format!("Unknown message type in: {}", msg).into());
-        };
-        
-        // Remove _msg_type hint before passing to signer
-        let msg_for_signing = if msg.get("_msg_type").is_some() {
-            let mut cleaned = msg.clone();
-            if let Some(obj) = cleaned.as_object_mut() {
-                obj.remove("_msg_type");
-            }
-            // If it's now empty (was just _msg_type), return empty object
-            if cleaned.as_object().map(|o| o.is_empty()).unwrap_or(false) {
-                json!({})
-            } else {
-                cleaned
-            }
-        } else {
-            msg.clone()
-        };
-        
-        let signing_result = self.signer.sign_transaction(
-            msg_type,
-            &msg_for_signing,
+        let signing_result = self.signer.sign_msg(
+            msg,
             sender_address,
+            contract_address,
             account_number,
             sequence,
             fee.clone(),
@@ -296,6 +255,7 @@ impl ProtoTransactionBuilder {
         println!("TxRaw bytes (hex): {}", hex::encode(&tx_raw_bytes));
         println!("TxRaw bytes length: {}", tx_raw_bytes.len());
         println!("=== END PROTOBUF DEBUGGING ===");
+        crate::signed_tx_log::log_tx_bytes(msg_type, &tx_raw_bytes);
         
         // Encode to protobuf bytes
         Ok(tx_raw_bytes)
@@ -303,7 +263,7 @@ impl ProtoTransactionBuilder {
     
     /// Submit a transaction to the blockchain
     pub async fn submit_transaction(&self, tx_bytes: Vec<u8>) -> Result<String, Box<dyn Error>> {
-        let client = reqwest::Client::new();
+        let client = self.http_client()?;
         let url = format!("{}/cosmos/tx/v1beta1/txs", self.rest_url);
         
         // Create the broadcast request
@@ -399,7 +359,7 @@ impl ProtoTransactionBuilder {
         const TIMEOUT_MS: u64 = 60000; // 60 seconds
         const MAX_ATTEMPTS: u32 = (TIMEOUT_MS / POLL_INTERVAL_MS) as u32;
         
-        let client = reqwest::Client::new();
+        let client = self.http_client()?;
         let url = format!("{}/cosmos/tx/v1beta1/txs/{}", self.rest_url, tx_hash);
         
         log::info!("Polling for transaction confirmation: {}", tx_hash);
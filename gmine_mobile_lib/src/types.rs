@@ -9,6 +9,19 @@ pub struct Epoch {
     pub target_hash: Vec<u8>,
 }
 
+impl Epoch {
+    /// The epoch's target_hash as the 32-byte mining challenge. Anything else is a
+    /// malformed contract response - padding or truncating it would mine a challenge
+    /// the contract never issued
+    pub fn challenge(&self) -> Result<[u8; 32], String> {
+        <[u8; 32]>::try_from(self.target_hash.as_slice()).map_err(|_| format!(
+            "Malformed target_hash for epoch {}: expected 32 bytes, got {}",
+            self.epoch_number,
+            self.target_hash.len()
+        ))
+    }
+}
+
 /// Mining challenge for current epoch
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MiningChallenge {
@@ -19,10 +32,36 @@ pub struct MiningChallenge {
     pub nonce_end: u64,
 }
 
+impl MiningChallenge {
+    /// Threshold a hash must be below: `difficulty` leading zero bits, all ones after
+    pub fn target_hash(&self) -> [u8; 32] {
+        let mut target = [0xffu8; 32];
+        let bits = (self.difficulty as usize).min(256);
+        for byte in target.iter_mut().take(bits / 8) {
+            *byte = 0;
+        }
+        if bits < 256 && bits % 8 != 0 {
+            target[bits / 8] = 0xff >> (bits % 8);
+        }
+        target
+    }
+}
+
+/// Expected seconds until a solution at `difficulty` is found at `hashrate` H/s
+/// (on average 2^difficulty hashes are needed). None while the hashrate is unknown.
+pub fn estimate_time_to_solution(difficulty: u8, hashrate: u64) -> Option<u64> {
+    if hashrate == 0 {
+        return None;
+    }
+    let expected_hashes = 2f64.powi(difficulty as i32);
+    Some((expected_hashes / hashrate as f64).ceil().min(u64::MAX as f64) as u64)
+}
+
 /// Solution found by miner
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Solution {
     pub nonce: u64,
+    /// drillx digest (`Hash::d`, 16 bytes)
     pub hash: Vec<u8>,
     pub difficulty: u8,
     pub epoch: u64,
@@ -104,4 +143,94 @@ pub struct MiningStats {
     #[serde(rename = "currentChallenge")]
     pub current_challenge: Option<String>,
     pub difficulty: Option<u8>,
-}
\ No newline at end of file
+    /// Hash threshold for the current difficulty (hex)
+    #[serde(rename = "targetHash", default)]
+    pub target_hash: Option<String>,
+    /// Assigned nonce range as decimal strings (values can exceed a Java long)
+    #[serde(rename = "nonceRangeStart", default)]
+    pub nonce_range_start: Option<String>,
+    #[serde(rename = "nonceRangeEnd", default)]
+    pub nonce_range_end: Option<String>,
+    /// Expected seconds to find a solution at the current hashrate
+    #[serde(rename = "estimatedTimeToSolution", default)]
+    pub estimated_time_to_solution: Option<u64>,
+    /// Hashes computed since initialize, across all runs (never reset)
+    #[serde(rename = "totalHashes", default)]
+    pub total_hashes: u64,
+    /// Lifetime hashes of each worker slot
+    #[serde(rename = "workerHashes", default)]
+    pub worker_hashes: Vec<u64>,
+}
+
+/// How much periodic per-worker logging mining produces
+/// Solution-found and error logs are always emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogVerbosity {
+    /// No periodic progress or hashrate lines
+    Quiet,
+    /// One combined hashrate line per interval
+    #[default]
+    Normal,
+    /// Per-worker progress and hashrate lines
+    Verbose,
+}
+
+impl LogVerbosity {
+    /// Map the JNI level (0 = quiet, 1 = normal, 2+ = verbose)
+    pub fn from_level(level: i32) -> Self {
+        match level {
+            i32::MIN..=0 => LogVerbosity::Quiet,
+            1 => LogVerbosity::Normal,
+            _ => LogVerbosity::Verbose,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(difficulty: u8) -> MiningChallenge {
+        MiningChallenge {
+            challenge: [0; 32],
+            difficulty,
+            epoch: 1,
+            nonce_start: 0,
+            nonce_end: 1000,
+        }
+    }
+
+    #[test]
+    fn test_target_hash() {
+        assert_eq!(hex::encode(&challenge(0).target_hash()[..2]), "ffff");
+        assert_eq!(hex::encode(&challenge(8).target_hash()[..2]), "00ff");
+        assert_eq!(hex::encode(&challenge(12).target_hash()[..3]), "000fff");
+    }
+
+    #[test]
+    fn test_epoch_challenge_length() {
+        let epoch = |len: usize| Epoch { epoch_number: 7, start_block: 0, difficulty: 8, target_hash: vec![0xab; len] };
+        assert_eq!(epoch(32).challenge().unwrap(), [0xab; 32]);
+
+        let err = epoch(31).challenge().unwrap_err();
+        assert!(err.contains("expected 32 bytes, got 31"), "{}", err);
+        assert!(epoch(33).challenge().unwrap_err().contains("got 33"));
+        assert!(epoch(0).challenge().is_err());
+    }
+
+    #[test]
+    fn test_estimate_time_to_solution() {
+        assert_eq!(estimate_time_to_solution(10, 0), None);
+        assert_eq!(estimate_time_to_solution(10, 1024), Some(1));
+        assert_eq!(estimate_time_to_solution(16, 100), Some(656));
+    }
+
+    #[test]
+    fn test_log_verbosity_from_level() {
+        assert_eq!(LogVerbosity::from_level(-1), LogVerbosity::Quiet);
+        assert_eq!(LogVerbosity::from_level(0), LogVerbosity::Quiet);
+        assert_eq!(LogVerbosity::from_level(1), LogVerbosity::Normal);
+        assert_eq!(LogVerbosity::from_level(5), LogVerbosity::Verbose);
+        assert_eq!(LogVerbosity::default(), LogVerbosity::Normal);
+    }
+}
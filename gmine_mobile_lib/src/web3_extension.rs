@@ -37,6 +37,15 @@ impl ExtensionOptionsWeb3Tx {
         }
     }
     
+    /// Create a new Web3Extension for the chain with this Ethereum chain ID (non-delegated)
+    pub fn for_chain(eth_chain_id: u64) -> Self {
+        ExtensionOptionsWeb3Tx {
+            typedDataChainID: eth_chain_id,
+            feePayer: String::new(),
+            feePayerSig: vec![],
+        }
+    }
+    
     /// Create a new Web3Extension with fee delegation for testnet
     pub fn new_for_testnet_with_fee_delegation(fee_payer: &str, fee_payer_sig: Vec<u8>) -> Self {
         ExtensionOptionsWeb3Tx {
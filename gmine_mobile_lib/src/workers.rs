@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// How long `stopMining` waits for workers before detaching them
+pub const WORKER_STOP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Signal workers to stop and wait up to `timeout` for them to exit
+///
+/// Joining happens on a separate reaper thread, so a worker stuck inside a long
+/// hash can't block the caller (the JNI/UI thread) past the timeout. Workers still
+/// running at the deadline are detached; they exit on their own once they next
+/// check `stop_flag`. Returns true if every worker exited in time.
+pub fn stop_workers(stop_flag: &AtomicBool, handles: Vec<thread::JoinHandle<()>>, timeout: Duration) -> bool {
+    stop_flag.store(false, Ordering::Relaxed);
+    if handles.is_empty() {
+        return true;
+    }
+
+    let count = handles.len();
+    let (done_tx, done_rx) = mpsc::channel();
+    thread::spawn(move || {
+        for handle in handles {
+            let _ = handle.join();
+        }
+        let _ = done_tx.send(());
+    });
+
+    match done_rx.recv_timeout(timeout) {
+        Ok(()) => true,
+        Err(_) => {
+            log::warn!("{} mining workers did not stop within {:?}, detaching", count, timeout);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    #[test]
+    fn test_stop_returns_promptly_under_load() {
+        let running = Arc::new(AtomicBool::new(true));
+        let handles = (0..4)
+            .map(|_| {
+                let running = running.clone();
+                thread::spawn(move || {
+                    while running.load(Ordering::Relaxed) {
+                        // Stand-in for one uninterruptible hash
+                        let start = Instant::now();
+                        while start.elapsed() < Duration::from_millis(20) {
+                            std::hint::spin_loop();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let start = Instant::now();
+        assert!(stop_workers(&running, handles, Duration::from_secs(2)));
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_stuck_worker_is_detached() {
+        let running = AtomicBool::new(true);
+        // Ignores the stop flag entirely
+        let stuck = thread::spawn(|| thread::sleep(Duration::from_secs(5)));
+
+        let start = Instant::now();
+        assert!(!stop_workers(&running, vec![stuck], Duration::from_millis(100)));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(!running.load(Ordering::Relaxed));
+    }
+}
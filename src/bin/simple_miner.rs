@@ -5,16 +5,44 @@
 
 use anyhow::{Result, anyhow, Context};
 use clap::{Args, Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use gmine_miner::{
-    chain::{InjectiveClient, ClientConfig, ContractAddresses, InjectiveWallet},
-    orchestrator::{MiningOrchestrator, OrchestratorConfig},
+    chain::{InjectiveClient, ClientConfig, ContractAddresses, InjectiveWallet, SigningMethod, TxLog, CallTrace, CALL_TRACE_CAPACITY, query_epoch_info},
+    chain::wallet::hd_path_for_index,
+    chain::address::validate as validate_address,
+    chain::queries::{calculate_nonce_range, partition_index, query_miner_info, detect_power_token, query_power_token_balance, PhaseInfo, PowerToken},
+    chain::queries::{query_contract_version, is_compatible_contract_version, EXPECTED_CONTRACT_MAJOR, MIN_CONTRACT_MINOR},
+    chain::queries::{query_pow_params, query_stake_info},
+    chain::messages::{CommitmentVersion, SaltSource, StakeTokensMsg, UnstakeTokensMsg, MIN_STAKE_AMOUNT},
+    config::load_layered,
+    miner::CoreCounts,
+    miner::solver_pool::{worker_memory_bytes, workers_within_budget},
+    miner::pow::{check_pow_params, check_pow_contract_version, drillx_self_test, POW_ALGORITHM, POW_PARAMS_VERSION, POW_VENDOR_VERSION},
+    chain::{ContractEventListener, GasPriceSource, HeightSource, Socks5Proxy, warm_up, DEFAULT_FEE_DENOM, DEFAULT_MAX_GAS_PRICE, DEFAULT_MIN_GAS_PRICE},
+    chain::client_real::fee_for_gas_at,
+    chain::bridge_client::{BankSend, Coin, BRIDGE_FEE},
+    chain::gas_price::validate_fee_denom,
+    chain::rust_signer::SIGNER_GAS_LIMIT,
+    chain::contract_events::{MAINNET_EVENTS_ENDPOINT, TESTNET_EVENTS_ENDPOINT},
+    orchestrator::{AggregateStats, BroadcastLimiter, AGGREGATE_STATS_INTERVAL},
+    orchestrator::{FailureAction, InstanceLock, MiningOrchestrator, MiningPhase, MiningSchedule, MiningState, OrchestratorConfig, ProfitabilityEstimate, RepeatedFailureError, SubmissionTarget, serve_health},
 };
+use gmine_mobile::proof::SolutionProof;
+use gmine_mobile::byte_order::nonce_from_bytes;
 use dialoguer::{Input, Password, Confirm};
 use serde::{Deserialize, Serialize};
 use bip39::Mnemonic;
+use cosmwasm_std::Uint128;
+use futures_util::future::join_all;
+use std::sync::Arc;
+
+/// State file for `--no-submit` runs unless one is given explicitly
+const NO_SUBMIT_STATE_FILE: &str = "gmine_miner.no_submit.state";
+
+/// State file for `--standby` runs unless one is given explicitly
+const STANDBY_STATE_FILE: &str = "gmine_miner.standby.state";
 
 /// Main CLI structure with subcommands and backward compatibility
 #[derive(Parser, Debug)]
@@ -59,6 +87,85 @@ enum Commands {
     
     /// Show mining status and statistics
     Status,
+    
+    /// Show build info and check compatibility with the deployed contract
+    Version,
+    
+    /// Watch an address's on-chain mining state (read-only, no wallet needed)
+    Monitor {
+        /// Config file to read network/endpoint from (default: ~/.gmine/config.toml)
+        #[arg(long)]
+        config: Option<PathBuf>,
+        
+        /// Injective address to watch (inj1...)
+        #[arg(long)]
+        address: String,
+        
+        /// Refresh interval in seconds
+        #[arg(long, default_value = "10")]
+        interval: u64,
+    },
+    
+    /// Show the nonce partition assigned to an address for an epoch
+    Partition {
+        /// Config file to read network/endpoint from (default: ~/.gmine/config.toml)
+        #[arg(long)]
+        config: Option<PathBuf>,
+        
+        /// Injective address to look up (inj1...)
+        #[arg(long)]
+        address: String,
+        
+        /// Epoch number (default: the current epoch on chain)
+        #[arg(long)]
+        epoch: Option<u64>,
+    },
+    
+    /// Export the last found solution as a JSON proof that can be verified offline
+    ExportSolution {
+        /// Config file to read the state file location from (default: ~/.gmine/config.toml)
+        #[arg(long)]
+        config: Option<PathBuf>,
+        
+        /// Write the proof to this file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// Verify a solution proof with drillx
+    VerifySolution {
+        /// Proof file written by export-solution
+        proof: PathBuf,
+    },
+    
+    /// Decode a state file and check its stored commitment (read-only)
+    InspectState {
+        /// State file to inspect (e.g. gmine_miner.state)
+        path: PathBuf,
+        
+        /// Miner address, to also check the commitment under the pre-V3.4 unsalted scheme
+        #[arg(long)]
+        address: Option<String>,
+    },
+    
+    /// Emergency exit: stop the miner, unstake all POWER and move it to a safe address
+    EmergencyExit {
+        /// Config file to read the network, mnemonic and safe address from (default: ~/.gmine/config.toml)
+        #[arg(long)]
+        config: Option<PathBuf>,
+        
+        /// Read the mnemonic from this file instead of the config
+        #[arg(long)]
+        mnemonic_file: Option<PathBuf>,
+        
+        /// Send the wallet's POWER here after unstaking (overrides `safe_address` in the config)
+        #[arg(long)]
+        safe_address: Option<String>,
+        
+        /// Required: confirms you want to unstake and move funds now
+        #[arg(long)]
+        confirm: bool,
+    },
 }
 
 /// Service management subcommands
@@ -95,6 +202,15 @@ struct MineArgs {
     #[arg(long)]
     workers: Option<usize>,
     
+    /// Without --workers, size the worker count from physical cores instead of hyperthreads
+    #[arg(long)]
+    use_physical_cores_only: bool,
+    
+    /// Memory budget for the mining workers in MB; fewer workers are started if they
+    /// wouldn't fit (each needs its own solver memory)
+    #[arg(long)]
+    max_memory_mb: Option<u64>,
+    
     /// Network to use (mainnet or testnet)
     #[arg(long)]
     network: Option<String>,
@@ -107,10 +223,43 @@ struct MineArgs {
     #[arg(long)]
     state_file: Option<PathBuf>,
     
+    /// Rotated state file backups to keep for recovering from a corrupt state file (0 disables)
+    #[arg(long)]
+    state_backups: Option<usize>,
+    
+    /// Write metrics in OpenMetrics text format to this file every 15s
+    #[arg(long)]
+    metrics_file: Option<String>,
+    
+    /// Rewrite this file with a timestamp, phase and epoch every loop iteration (liveness for watchdogs)
+    #[arg(long)]
+    heartbeat_file: Option<String>,
+    
+    /// POST a JSON event with the old and new difficulty to this URL when it changes
+    #[arg(long)]
+    difficulty_webhook: Option<String>,
+    
+    /// Pause mining and submissions when the block height hasn't moved for this many seconds (0 = off, default: 120)
+    #[arg(long)]
+    chain_halt_secs: Option<u64>,
+    
+    /// Run this command on solutions found, rewards claimed and errors (event, epoch and details are appended)
+    #[arg(long)]
+    notify_command: Option<String>,
+    
+    /// Also mine this mining contract at the same time (repeatable; needs the bridge signer)
+    #[arg(long = "extra-contract")]
+    extra_contracts: Vec<String>,
+    
     /// Enable debug logging
     #[arg(long)]
     debug: bool,
     
+    /// Log the signed hash and base64 signed bytes of every transaction (for diagnosing
+    /// signature verification failures - the logs then tie your wallet to every tx)
+    #[arg(long)]
+    log_signed_tx: bool,
+    
     /// Use Rust-native EIP-712 signer instead of Node.js bridge (experimental)
     #[arg(long)]
     use_rust_signer: bool,
@@ -119,6 +268,247 @@ struct MineArgs {
     /// Commits and reveals won't be submitted if less than this many blocks remain
     #[arg(long, default_value = "8")]
     submission_buffer_blocks: Option<u64>,
+    
+    /// Skip claiming rewards after reveals (for pooled/delegated setups)
+    #[arg(long)]
+    no_claim: bool,
+    
+    /// Path to an append-only JSONL log of every broadcast transaction
+    #[arg(long)]
+    tx_log: Option<PathBuf>,
+    
+    /// Keep the last 50 chain calls in memory and write them to this file on a panic
+    /// or when --max-failed-epochs trips
+    #[arg(long)]
+    crash_report: Option<PathBuf>,
+    
+    /// HD address index to derive the wallet from (m/44'/60'/0'/0/<index>)
+    #[arg(long)]
+    account_index: Option<u32>,
+    
+    /// Keep hashing into the adjacent nonce partition once ours is exhausted
+    /// (solutions outside our partition are rejected by the current contract)
+    #[arg(long)]
+    extended_nonce_search: bool,
+    
+    /// Port for /healthz and /readyz probes (for Docker/Kubernetes)
+    #[arg(long)]
+    health_port: Option<u16>,
+    
+    /// Start workers gradually over this many seconds to avoid thermal spikes
+    #[arg(long)]
+    ramp_up_secs: Option<u64>,
+    
+    /// Log gRPC calls slower than this many milliseconds as warnings
+    #[arg(long)]
+    slow_call_ms: Option<u64>,
+    
+    /// Gzip-compress gRPC requests and responses (falls back if the node rejects it)
+    #[arg(long)]
+    grpc_compression: bool,
+    
+    /// Route chain traffic through a SOCKS5 proxy, e.g. Tor at 127.0.0.1:9050 (adds latency)
+    #[arg(long)]
+    socks5_proxy: Option<String>,
+    
+    /// Have this address pay gas through a feegrant allowance, so the mining wallet needs no INJ
+    #[arg(long)]
+    fee_granter: Option<String>,
+    
+    /// Only mine inside these daily windows, local time (e.g. "22:00-06:00,12-14")
+    #[arg(long)]
+    schedule: Option<String>,
+    
+    /// Refuse to broadcast any transaction costing more than this many INJ in fees
+    #[arg(long)]
+    max_fee_inj: Option<f64>,
+    
+    /// After an "account sequence mismatch", re-query the account instead of resubmitting
+    /// at the sequence the chain says it expects
+    #[arg(long)]
+    no_sequence_recovery: bool,
+    
+    /// Fall back to plain SIGN_MODE_DIRECT if no EIP-712 signer is set up, instead of
+    /// refusing to start. Such transactions don't earn rewards on Injective
+    #[arg(long)]
+    allow_direct_signing: bool,
+    
+    /// After a restart with a pending reveal, wait for the first poll instead of revealing
+    /// right away when the reveal window is already open
+    #[arg(long)]
+    no_reveal_on_resume: bool,
+    
+    /// Gas price source: "static" (default), "node" (the node's minimum gas price) or an oracle URL
+    #[arg(long)]
+    gas_price: Option<String>,
+    
+    /// Read block heights from this endpoint instead of the main one (LCD URL, or
+    /// "rpc:<url>" for a Tendermint RPC endpoint); the main endpoint is the fallback
+    #[arg(long)]
+    height_endpoint: Option<String>,
+    
+    /// Lowest fetched gas price accepted, in base units of INJ per gas
+    #[arg(long)]
+    min_gas_price: Option<u128>,
+    
+    /// Highest fetched gas price accepted, in base units of INJ per gas
+    #[arg(long)]
+    max_gas_price: Option<u128>,
+    
+    /// Pay fees in this denom instead of INJ (e.g. a peggy0x... token the chain accepts for gas)
+    #[arg(long)]
+    fee_denom: Option<String>,
+    
+    /// Commitment hashing scheme: "salted" (contract v3.4+) or "unsalted" (older contracts)
+    #[arg(long)]
+    commitment_version: Option<String>,
+    
+    /// Commitment salt: "random" or "derived" from the wallet key (reveal survives a lost state file)
+    #[arg(long)]
+    salt_source: Option<String>,
+    
+    /// Require the same contract phase on this many consecutive polls before committing
+    /// or revealing (2 recommended; 1 acts on the first reading)
+    #[arg(long)]
+    phase_confirmations: Option<u32>,
+    
+    /// Also POST found solutions to this pool endpoint (the pool does the commit/reveal)
+    #[arg(long)]
+    pool_url: Option<String>,
+    
+    /// Bearer token for --pool-url
+    #[arg(long)]
+    pool_auth: Option<String>,
+    
+    /// Only submit solutions to the pool, never commit on-chain (requires --pool-url)
+    #[arg(long)]
+    pool_only: bool,
+    
+    /// Contract messages the Rust signer may sign (comma-separated, e.g. "commit,reveal")
+    #[arg(long, value_delimiter = ',')]
+    allowed_messages: Option<Vec<String>>,
+    
+    /// Human-readable name for this miner, shown in logs and telemetry (e.g. "rig-basement-01")
+    #[arg(long)]
+    label: Option<String>,
+    
+    /// Fixed telemetry miner id (UUID); by default one is generated once and persisted
+    #[arg(long)]
+    miner_id: Option<String>,
+    
+    /// Delay the first mining start by a random 0..=N seconds (desynchronizes fleets)
+    #[arg(long)]
+    startup_jitter: Option<u64>,
+    
+    /// Pause or exit after this many consecutive epochs fail to commit/reveal (0 = never)
+    #[arg(long)]
+    max_failed_epochs: Option<u32>,
+    
+    /// Give up on an epoch's reveal after the contract rejected it this many times (0 = retry until the window closes)
+    #[arg(long)]
+    max_reveal_attempts: Option<u32>,
+    
+    /// Give up on claiming an epoch after this many attempts found it not claimable, backing off between them (0 = keep trying)
+    #[arg(long)]
+    max_claim_attempts: Option<u32>,
+    
+    /// Seconds to wait for a commit transaction to be confirmed (default: 120)
+    #[arg(long)]
+    commit_confirmation_timeout: Option<u64>,
+    
+    /// Seconds to wait for a reveal transaction to be confirmed before retrying it (default: 30)
+    #[arg(long)]
+    reveal_confirmation_timeout: Option<u64>,
+    
+    /// Seconds to wait for a claim transaction to be confirmed (default: 300)
+    #[arg(long)]
+    claim_confirmation_timeout: Option<u64>,
+    
+    /// Milliseconds between status checks while waiting for a transaction (default: 500)
+    #[arg(long)]
+    confirmation_poll_ms: Option<u64>,
+    
+    /// Send queued transactions in the order they were queued instead of reveals first,
+    /// then commits, then claims
+    #[arg(long)]
+    fifo_transactions: bool,
+    
+    /// When the epoch advances before a solution is committed, go idle until the next
+    /// poll instead of restarting the workers on the new epoch straight away
+    #[arg(long)]
+    no_restart_on_epoch_advance: bool,
+    
+    /// Skip the startup check that the contract's epoch, miner and config responses
+    /// have the expected shape
+    #[arg(long)]
+    no_warmup: bool,
+    
+    /// What to do when --max-failed-epochs is reached: "pause" (30 min) or "exit" (nonzero code)
+    #[arg(long)]
+    on_repeated_failure: Option<String>,
+    
+    /// Subscribe to contract events to react instantly to epoch changes
+    #[arg(long)]
+    contract_events: bool,
+    
+    /// Tendermint websocket endpoint for contract events (defaults per network)
+    #[arg(long)]
+    events_endpoint: Option<String>,
+    
+    /// Adjust worker count per epoch from difficulty, CPU temperature and battery
+    #[arg(long)]
+    adaptive_threads: bool,
+    
+    /// Minimum workers when --adaptive-threads is set
+    #[arg(long)]
+    min_threads: Option<usize>,
+    
+    /// Maximum workers when --adaptive-threads is set (default: --workers)
+    #[arg(long)]
+    max_threads: Option<usize>,
+    
+    /// Sign the reveal during the commit phase so revealing is a single broadcast (Rust signer only)
+    #[arg(long)]
+    prepare_reveal: bool,
+    
+    /// Epochs tracked at once; above 1, claims run while the next epoch is mined
+    #[arg(long)]
+    max_tracked_epochs: Option<usize>,
+    
+    /// Mine live epochs but never submit a transaction (hardware load testing, no funds needed)
+    #[arg(long)]
+    no_submit: bool,
+    
+    /// Warm standby for a primary miner on the same wallet: only commit if the primary hasn't
+    #[arg(long, conflicts_with = "no_submit")]
+    standby: bool,
+    
+    /// Blocks before the commit window closes (beyond the submission buffer) at which a standby takes over
+    #[arg(long)]
+    standby_takeover_blocks: Option<u64>,
+    
+    /// Seconds to wait after a claim before mining again
+    #[arg(long)]
+    post_claim_cooldown: Option<u64>,
+    
+    /// Never send advance_epoch - wait for another miner to start the next epoch
+    #[arg(long)]
+    no_advance: bool,
+    
+    /// Start the workers this far into the nonce partition (fraction 0.0-1.0), for
+    /// several machines mining with one wallet
+    #[arg(long)]
+    nonce_offset: Option<f64>,
+    
+    /// Stake the wallet's POWER with each claim, locked for this many days
+    /// (0, 30, 90, 180, 365 or 730); not available with --use-rust-signer
+    #[arg(long)]
+    auto_stake_days: Option<u64>,
+    
+    /// Re-fetch the target hash and difficulty every N seconds while mining and restart
+    /// the workers on a mid-epoch retarget (adds RPC load; off by default)
+    #[arg(long)]
+    target_refresh: Option<u64>,
 }
 
 /// Configuration file structure
@@ -135,10 +525,142 @@ struct MinerConfig {
 struct MiningConfig {
     mnemonic: Option<String>,
     workers: Option<usize>,
+    #[serde(default)]
+    use_physical_cores_only: bool,
+    #[serde(default)]
+    max_memory_mb: Option<u64>,
     network: String,
     grpc_endpoint: Option<String>,
     state_file: Option<String>,
     use_rust_signer: bool,
+    #[serde(default = "default_claim_enabled")]
+    claim_enabled: bool,
+    #[serde(default)]
+    tx_log: Option<String>,
+    #[serde(default)]
+    crash_report: Option<String>,
+    #[serde(default)]
+    account_index: Option<u32>,
+    #[serde(default)]
+    extended_nonce_search: bool,
+    #[serde(default)]
+    health_port: Option<u16>,
+    #[serde(default)]
+    ramp_up_secs: Option<u64>,
+    #[serde(default)]
+    startup_jitter_seconds: Option<u64>,
+    #[serde(default)]
+    max_failed_epochs: Option<u32>,
+    #[serde(default)]
+    max_reveal_attempts: Option<u32>,
+    #[serde(default)]
+    max_claim_attempts: Option<u32>,
+    #[serde(default)]
+    commit_confirmation_timeout: Option<u64>,
+    #[serde(default)]
+    reveal_confirmation_timeout: Option<u64>,
+    #[serde(default)]
+    claim_confirmation_timeout: Option<u64>,
+    #[serde(default)]
+    confirmation_poll_ms: Option<u64>,
+    #[serde(default)]
+    fifo_transactions: bool,
+    #[serde(default)]
+    no_restart_on_epoch_advance: bool,
+    #[serde(default)]
+    no_warmup: bool,
+    #[serde(default)]
+    on_repeated_failure: Option<String>,
+    #[serde(default)]
+    miner_label: Option<String>,
+    #[serde(default)]
+    miner_id: Option<String>,
+    #[serde(default)]
+    max_fee_inj: Option<f64>,
+    #[serde(default)]
+    no_sequence_recovery: bool,
+    #[serde(default)]
+    allow_direct_signing: bool,
+    #[serde(default)]
+    no_reveal_on_resume: bool,
+    #[serde(default)]
+    gas_price: Option<String>,
+    #[serde(default)]
+    height_endpoint: Option<String>,
+    #[serde(default)]
+    min_gas_price: Option<u128>,
+    #[serde(default)]
+    max_gas_price: Option<u128>,
+    #[serde(default)]
+    fee_denom: Option<String>,
+    #[serde(default)]
+    allowed_messages: Option<Vec<String>>,
+    #[serde(default)]
+    commitment_version: Option<String>,
+    #[serde(default)]
+    salt_source: Option<String>,
+    #[serde(default)]
+    phase_confirmations: Option<u32>,
+    #[serde(default)]
+    pool_url: Option<String>,
+    #[serde(default)]
+    pool_auth: Option<String>,
+    #[serde(default)]
+    pool_only: bool,
+    #[serde(default)]
+    schedule: Option<String>,
+    #[serde(default)]
+    slow_call_ms: Option<u64>,
+    #[serde(default)]
+    grpc_compression: bool,
+    #[serde(default)]
+    socks5_proxy: Option<String>,
+    #[serde(default)]
+    fee_granter: Option<String>,
+    #[serde(default)]
+    contract_events: bool,
+    #[serde(default)]
+    events_endpoint: Option<String>,
+    #[serde(default)]
+    adaptive_threads: bool,
+    #[serde(default)]
+    min_threads: Option<usize>,
+    #[serde(default)]
+    max_threads: Option<usize>,
+    #[serde(default)]
+    prepare_reveal: bool,
+    #[serde(default)]
+    standby: bool,
+    #[serde(default)]
+    standby_takeover_blocks: Option<u64>,
+    #[serde(default)]
+    post_claim_cooldown: Option<u64>,
+    #[serde(default)]
+    no_advance: bool,
+    #[serde(default)]
+    nonce_offset: Option<f64>,
+    #[serde(default)]
+    auto_stake_days: Option<u64>,
+    #[serde(default)]
+    target_refresh: Option<u64>,
+    #[serde(default)]
+    max_tracked_epochs: Option<usize>,
+    #[serde(default)]
+    state_backups: Option<usize>,
+    #[serde(default)]
+    metrics_file: Option<String>,
+    #[serde(default)]
+    heartbeat_file: Option<String>,
+    #[serde(default)]
+    difficulty_webhook: Option<String>,
+    #[serde(default)]
+    extra_contracts: Vec<String>,
+    #[serde(default)]
+    safe_address: Option<String>,
+    #[serde(default)]
+    chain_halt_secs: Option<u64>,
+    #[serde(default)]
+    notify_command: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -146,15 +668,85 @@ struct TelemetryConfig {
     enabled: bool,
 }
 
+fn default_claim_enabled() -> bool {
+    true
+}
+
 impl Default for MiningConfig {
     fn default() -> Self {
         Self {
             mnemonic: None,
             workers: None,
+            use_physical_cores_only: false,
+            max_memory_mb: None,
             network: "testnet".to_string(),
             grpc_endpoint: None,
             state_file: None,
             use_rust_signer: false,
+            claim_enabled: true,
+            tx_log: None,
+            crash_report: None,
+            account_index: None,
+            extended_nonce_search: false,
+            health_port: None,
+            ramp_up_secs: None,
+            startup_jitter_seconds: None,
+            max_failed_epochs: None,
+            max_reveal_attempts: None,
+            max_claim_attempts: None,
+            commit_confirmation_timeout: None,
+            reveal_confirmation_timeout: None,
+            claim_confirmation_timeout: None,
+            confirmation_poll_ms: None,
+            fifo_transactions: false,
+            no_restart_on_epoch_advance: false,
+            no_warmup: false,
+            on_repeated_failure: None,
+            miner_label: None,
+            miner_id: None,
+            max_fee_inj: None,
+            no_sequence_recovery: false,
+            allow_direct_signing: false,
+            no_reveal_on_resume: false,
+            gas_price: None,
+            height_endpoint: None,
+            min_gas_price: None,
+            max_gas_price: None,
+            fee_denom: None,
+            allowed_messages: None,
+            commitment_version: None,
+            salt_source: None,
+            phase_confirmations: None,
+            pool_url: None,
+            pool_auth: None,
+            pool_only: false,
+            schedule: None,
+            slow_call_ms: None,
+            grpc_compression: false,
+            socks5_proxy: None,
+            fee_granter: None,
+            contract_events: false,
+            events_endpoint: None,
+            adaptive_threads: false,
+            min_threads: None,
+            max_threads: None,
+            prepare_reveal: false,
+            standby: false,
+            standby_takeover_blocks: None,
+            post_claim_cooldown: None,
+            no_advance: false,
+            nonce_offset: None,
+            auto_stake_days: None,
+            target_refresh: None,
+            max_tracked_epochs: None,
+            state_backups: None,
+            metrics_file: None,
+            heartbeat_file: None,
+            difficulty_webhook: None,
+            extra_contracts: Vec::new(),
+            safe_address: None,
+            chain_halt_secs: None,
+            notify_command: None,
         }
     }
 }
@@ -178,6 +770,15 @@ async fn main() -> Result<()> {
         Some(Commands::Service { action }) => cmd_service(action).await,
         Some(Commands::Logs { lines, follow }) => cmd_logs(lines, follow).await,
         Some(Commands::Status) => cmd_status().await,
+        Some(Commands::Version) => cmd_version().await,
+        Some(Commands::Monitor { config, address, interval }) => cmd_monitor(config, address, interval).await,
+        Some(Commands::Partition { config, address, epoch }) => cmd_partition(config, address, epoch).await,
+        Some(Commands::ExportSolution { config, output }) => cmd_export_solution(config, output),
+        Some(Commands::VerifySolution { proof }) => cmd_verify_solution(proof),
+        Some(Commands::InspectState { path, address }) => cmd_inspect_state(path, address),
+        Some(Commands::EmergencyExit { config, mnemonic_file, safe_address, confirm }) => {
+            cmd_emergency_exit(config, mnemonic_file, safe_address, confirm).await
+        }
         None => {
             // No subcommand provided - run mining with backward compatibility
             cmd_mine(cli.mine_args).await
@@ -231,11 +832,11 @@ async fn cmd_init() -> Result<()> {
     println!("\n✅ Wallet address: {}", wallet.address);
     
     // Get CPU info and suggest workers
-    let cpu_count = num_cpus::get();
-    let suggested_workers = if cpu_count > 1 { cpu_count - 1 } else { 1 };
+    let cores = CoreCounts::detect();
+    let suggested_workers = cores.default_workers(false);
     
     println!("\n💻 System Configuration");
-    println!("Detected {} CPU cores", cpu_count);
+    println!("Detected {} CPU cores ({} physical)", cores.logical, cores.physical);
     
     let workers: usize = Input::new()
         .with_prompt("Number of mining workers")
@@ -271,10 +872,76 @@ async fn cmd_init() -> Result<()> {
         mining: MiningConfig {
             mnemonic: Some(mnemonic_str),
             workers: Some(workers),
+            use_physical_cores_only: false,
+            max_memory_mb: None,
             network: network.clone(),
             grpc_endpoint: None,
             state_file: Some("gmine_miner.state".to_string()),
             use_rust_signer,
+            claim_enabled: true,
+            tx_log: None,
+            crash_report: None,
+            account_index: None,
+            extended_nonce_search: false,
+            health_port: None,
+            ramp_up_secs: None,
+            startup_jitter_seconds: None,
+            max_failed_epochs: None,
+            max_reveal_attempts: None,
+            max_claim_attempts: None,
+            commit_confirmation_timeout: None,
+            reveal_confirmation_timeout: None,
+            claim_confirmation_timeout: None,
+            confirmation_poll_ms: None,
+            fifo_transactions: false,
+            no_restart_on_epoch_advance: false,
+            no_warmup: false,
+            on_repeated_failure: None,
+            miner_label: None,
+            miner_id: None,
+            max_fee_inj: None,
+            no_sequence_recovery: false,
+            allow_direct_signing: false,
+            no_reveal_on_resume: false,
+            gas_price: None,
+            height_endpoint: None,
+            min_gas_price: None,
+            max_gas_price: None,
+            fee_denom: None,
+            allowed_messages: None,
+            commitment_version: None,
+            salt_source: None,
+            phase_confirmations: None,
+            pool_url: None,
+            pool_auth: None,
+            pool_only: false,
+            schedule: None,
+            slow_call_ms: None,
+            grpc_compression: false,
+            socks5_proxy: None,
+            fee_granter: None,
+            contract_events: false,
+            events_endpoint: None,
+            adaptive_threads: false,
+            min_threads: None,
+            max_threads: None,
+            prepare_reveal: false,
+            standby: false,
+            standby_takeover_blocks: None,
+            post_claim_cooldown: None,
+            no_advance: false,
+            nonce_offset: None,
+            auto_stake_days: None,
+            target_refresh: None,
+            max_tracked_epochs: None,
+            state_backups: None,
+            metrics_file: None,
+            heartbeat_file: None,
+            difficulty_webhook: None,
+            extra_contracts: Vec::new(),
+            safe_address: None,
+            chain_halt_secs: None,
+            notify_command: None,
         },
         telemetry: TelemetryConfig {
             enabled: true,
@@ -308,19 +975,9 @@ async fn cmd_init() -> Result<()> {
 
 /// Run the miner
 async fn cmd_mine(args: MineArgs) -> Result<()> {
-    // Load config file first (lowest priority)
+    // Config file over defaults, GMINE_* environment variables over both; CLI args win
     let config_path = get_config_dir()?.join("config.toml");
-    let mut config = if config_path.exists() {
-        let content = fs::read_to_string(&config_path)
-            .context("Failed to read config file")?;
-        toml::from_str::<MinerConfig>(&content)
-            .context("Failed to parse config file")?
-    } else {
-        MinerConfig {
-            mining: MiningConfig::default(),
-            telemetry: TelemetryConfig::default(),
-        }
-    };
+    let (mut config, env_overrides) = load_miner_config(&config_path)?;
     
     // Override with command line args (highest priority)
     if args.mnemonic.is_some() {
@@ -329,6 +986,12 @@ async fn cmd_mine(args: MineArgs) -> Result<()> {
     if args.workers.is_some() {
         config.mining.workers = args.workers;
     }
+    if args.use_physical_cores_only {
+        config.mining.use_physical_cores_only = true;
+    }
+    if args.max_memory_mb.is_some() {
+        config.mining.max_memory_mb = args.max_memory_mb;
+    }
     if args.network.is_some() {
         config.mining.network = args.network.unwrap();
     }
@@ -337,69 +1000,409 @@ async fn cmd_mine(args: MineArgs) -> Result<()> {
     }
     if args.state_file.is_some() {
         config.mining.state_file = Some(args.state_file.unwrap().to_string_lossy().to_string());
+    } else if args.no_submit {
+        // A load test keeps its own state so it never marks epochs in the real miner's file
+        config.mining.state_file = Some(NO_SUBMIT_STATE_FILE.to_string());
+    } else if args.standby {
+        // The primary on the same host must not share (and overwrite) the standby's state
+        config.mining.state_file = Some(STANDBY_STATE_FILE.to_string());
     }
     if args.use_rust_signer {
         config.mining.use_rust_signer = true;
     }
-    
-    // Initialize logging
-    if args.debug {
-        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug")).init();
-    } else {
-        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    if args.no_claim {
+        config.mining.claim_enabled = false;
     }
-    
-    log::info!("=== GMINE Mining Client v0.1.0 ===");
-    log::info!("Network: {}", config.mining.network);
-    
-    // Load wallet
-    let mnemonic = if let Some(mnemonic) = config.mining.mnemonic {
-        mnemonic
-    } else if let Some(path) = args.mnemonic_file {
-        fs::read_to_string(path)?
-            .trim()
-            .to_string()
-    } else {
-        return Err(anyhow!("No mnemonic provided. Use --mnemonic, --mnemonic-file, or run 'gmine init'"));
-    };
-    
-    let wallet = InjectiveWallet::from_mnemonic_no_passphrase(&mnemonic)?;
-    log::info!("Wallet address: {}", wallet.address);
-    
-    // Get workers count
-    let workers = config.mining.workers.unwrap_or_else(|| {
-        let cpu_count = num_cpus::get();
-        if cpu_count > 1 { cpu_count - 1 } else { 1 }
-    });
-    log::info!("Workers: {}", workers);
-    log::info!("Submission buffer: {} blocks", args.submission_buffer_blocks.unwrap_or(8));
-    
-    // Configure client
-    let client_config = if config.mining.network == "mainnet" {
-        ClientConfig {
-            grpc_endpoint: config.mining.grpc_endpoint.unwrap_or_else(|| 
-                "https://sentry.chain.grpc.injective.network:443".to_string()
-            ),
-            connection_timeout: 10,
-            request_timeout: 30,
-            max_retries: 3,
-            chain_id: "injective-1".to_string(),
+    if args.extended_nonce_search {
+        config.mining.extended_nonce_search = true;
+    }
+    if args.health_port.is_some() {
+        config.mining.health_port = args.health_port;
+    }
+    if args.ramp_up_secs.is_some() {
+        config.mining.ramp_up_secs = args.ramp_up_secs;
+    }
+    if args.startup_jitter.is_some() {
+        config.mining.startup_jitter_seconds = args.startup_jitter;
+    }
+    if args.max_failed_epochs.is_some() {
+        config.mining.max_failed_epochs = args.max_failed_epochs;
+    }
+    if args.max_reveal_attempts.is_some() {
+        config.mining.max_reveal_attempts = args.max_reveal_attempts;
+    }
+    if args.max_claim_attempts.is_some() {
+        config.mining.max_claim_attempts = args.max_claim_attempts;
+    }
+    if args.commit_confirmation_timeout.is_some() {
+        config.mining.commit_confirmation_timeout = args.commit_confirmation_timeout;
+    }
+    if args.reveal_confirmation_timeout.is_some() {
+        config.mining.reveal_confirmation_timeout = args.reveal_confirmation_timeout;
+    }
+    if args.claim_confirmation_timeout.is_some() {
+        config.mining.claim_confirmation_timeout = args.claim_confirmation_timeout;
+    }
+    if args.confirmation_poll_ms.is_some() {
+        config.mining.confirmation_poll_ms = args.confirmation_poll_ms;
+    }
+    if args.fifo_transactions {
+        config.mining.fifo_transactions = true;
+    }
+    if args.no_restart_on_epoch_advance {
+        config.mining.no_restart_on_epoch_advance = true;
+    }
+    if args.no_warmup {
+        config.mining.no_warmup = true;
+    }
+    if args.on_repeated_failure.is_some() {
+        config.mining.on_repeated_failure = args.on_repeated_failure;
+    }
+    if args.label.is_some() {
+        config.mining.miner_label = args.label;
+    }
+    if args.miner_id.is_some() {
+        config.mining.miner_id = args.miner_id;
+    }
+    if args.max_fee_inj.is_some() {
+        config.mining.max_fee_inj = args.max_fee_inj;
+    }
+    if args.no_sequence_recovery {
+        config.mining.no_sequence_recovery = true;
+    }
+    if args.allow_direct_signing {
+        config.mining.allow_direct_signing = true;
+    }
+    if args.no_reveal_on_resume {
+        config.mining.no_reveal_on_resume = true;
+    }
+    if args.gas_price.is_some() {
+        config.mining.gas_price = args.gas_price;
+    }
+    if args.height_endpoint.is_some() {
+        config.mining.height_endpoint = args.height_endpoint;
+    }
+    if args.min_gas_price.is_some() {
+        config.mining.min_gas_price = args.min_gas_price;
+    }
+    if args.fee_denom.is_some() {
+        config.mining.fee_denom = args.fee_denom.clone();
+    }
+    if args.max_gas_price.is_some() {
+        config.mining.max_gas_price = args.max_gas_price;
+    }
+    if args.allowed_messages.is_some() {
+        config.mining.allowed_messages = args.allowed_messages;
+    }
+    if args.commitment_version.is_some() {
+        config.mining.commitment_version = args.commitment_version;
+    }
+    if args.salt_source.is_some() {
+        config.mining.salt_source = args.salt_source;
+    }
+    if args.phase_confirmations.is_some() {
+        config.mining.phase_confirmations = args.phase_confirmations;
+    }
+    if args.pool_url.is_some() {
+        config.mining.pool_url = args.pool_url;
+    }
+    if args.pool_auth.is_some() {
+        config.mining.pool_auth = args.pool_auth;
+    }
+    if args.pool_only {
+        config.mining.pool_only = true;
+    }
+    if args.schedule.is_some() {
+        config.mining.schedule = args.schedule;
+    }
+    if args.slow_call_ms.is_some() {
+        config.mining.slow_call_ms = args.slow_call_ms;
+    }
+    if args.grpc_compression {
+        config.mining.grpc_compression = true;
+    }
+    if args.socks5_proxy.is_some() {
+        config.mining.socks5_proxy = args.socks5_proxy;
+    }
+    if args.fee_granter.is_some() {
+        config.mining.fee_granter = args.fee_granter;
+    }
+    if args.contract_events {
+        config.mining.contract_events = true;
+    }
+    if args.events_endpoint.is_some() {
+        config.mining.events_endpoint = args.events_endpoint;
+    }
+    if args.adaptive_threads {
+        config.mining.adaptive_threads = true;
+    }
+    if args.prepare_reveal {
+        config.mining.prepare_reveal = true;
+    }
+    if args.standby {
+        config.mining.standby = true;
+    }
+    if args.standby_takeover_blocks.is_some() {
+        config.mining.standby_takeover_blocks = args.standby_takeover_blocks;
+    }
+    if args.no_advance {
+        config.mining.no_advance = true;
+    }
+    if args.nonce_offset.is_some() {
+        config.mining.nonce_offset = args.nonce_offset;
+    }
+    if args.auto_stake_days.is_some() {
+        config.mining.auto_stake_days = args.auto_stake_days;
+    }
+    if let Some(offset) = config.mining.nonce_offset {
+        if !(0.0..1.0).contains(&offset) {
+            return Err(anyhow!("--nonce-offset must be a fraction from 0.0 up to (not including) 1.0, got {}", offset));
         }
+    }
+    if args.post_claim_cooldown.is_some() {
+        config.mining.post_claim_cooldown = args.post_claim_cooldown;
+    }
+    if args.target_refresh.is_some() {
+        config.mining.target_refresh = args.target_refresh;
+    }
+    if args.max_tracked_epochs.is_some() {
+        config.mining.max_tracked_epochs = args.max_tracked_epochs;
+    }
+    if args.state_backups.is_some() {
+        config.mining.state_backups = args.state_backups;
+    }
+    if args.metrics_file.is_some() {
+        config.mining.metrics_file = args.metrics_file.clone();
+    }
+    if args.heartbeat_file.is_some() {
+        config.mining.heartbeat_file = args.heartbeat_file.clone();
+    }
+    if args.difficulty_webhook.is_some() {
+        config.mining.difficulty_webhook = args.difficulty_webhook.clone();
+    }
+    if !args.extra_contracts.is_empty() {
+        config.mining.extra_contracts = args.extra_contracts.clone();
+    }
+    if args.chain_halt_secs.is_some() {
+        config.mining.chain_halt_secs = args.chain_halt_secs;
+    }
+    if args.notify_command.is_some() {
+        config.mining.notify_command = args.notify_command.clone();
+    }
+    if args.min_threads.is_some() {
+        config.mining.min_threads = args.min_threads;
+    }
+    if args.max_threads.is_some() {
+        config.mining.max_threads = args.max_threads;
+    }
+    if args.account_index.is_some() {
+        config.mining.account_index = args.account_index;
+    }
+    if args.tx_log.is_some() {
+        config.mining.tx_log = Some(args.tx_log.unwrap().to_string_lossy().to_string());
+    }
+    if args.crash_report.is_some() {
+        config.mining.crash_report = Some(args.crash_report.unwrap().to_string_lossy().to_string());
+    }
+    
+    // Initialize logging
+    let default_level = if args.debug { "debug" } else { "info" };
+    let mut logger = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level));
+    // Prefix every line with the miner label so fleet logs can be told apart
+    if let Some(label) = config.mining.miner_label.clone() {
+        logger.format(move |buf, record| {
+            use std::io::Write;
+            writeln!(buf, "[{} {} {}] [{}] {}", buf.timestamp(), record.level(), record.target(), label, record.args())
+        });
+    }
+    if args.log_signed_tx {
+        // Logged at debug level - make sure it shows without debug logging everywhere
+        logger.filter_module("gmine_mobile::signed_tx_log", log::LevelFilter::Debug);
+    }
+    logger.init();
+    gmine_mobile::signed_tx_log::set_enabled(args.log_signed_tx);
+    
+    log::info!("=== GMINE Mining Client v0.1.0 ===");
+    if !env_overrides.is_empty() {
+        log::info!("Config overrides from environment: {}", env_overrides.join(", "));
+    }
+    log::info!("Network: {}", config.mining.network);
+    
+    // Load wallet
+    let mnemonic = if let Some(mnemonic) = config.mining.mnemonic {
+        mnemonic
+    } else if let Some(path) = args.mnemonic_file {
+        fs::read_to_string(path)?
+            .trim()
+            .to_string()
     } else {
-        ClientConfig {
-            grpc_endpoint: config.mining.grpc_endpoint.unwrap_or_else(|| 
-                "https://testnet.sentry.chain.grpc.injective.network:443".to_string()
-            ),
-            connection_timeout: 10,
-            request_timeout: 30,
-            max_retries: 3,
-            chain_id: "injective-888".to_string(),
-        }
+        return Err(anyhow!("No mnemonic provided. Use --mnemonic, --mnemonic-file, or run 'gmine init'"));
+    };
+    
+    let hd_path = hd_path_for_index(config.mining.account_index.unwrap_or(0));
+    let wallet = InjectiveWallet::from_mnemonic_with_path(&mnemonic, "", &hd_path)?;
+    validate_address(&wallet.address)?;
+    log::info!("Wallet address: {}", wallet.address);
+    if config.mining.account_index.unwrap_or(0) != 0 {
+        log::info!("Derivation path: {}", hd_path);
+    }
+    
+    let state_file = config.mining.state_file
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("gmine_miner.state"));
+    
+    // A second miner on this wallet would fight this one over sequence numbers.
+    // No-submit mode sends nothing, so it can run alongside the real miner; a
+    // standby only transacts when the primary hasn't, and takes its own lock
+    let instance_heartbeat = if args.no_submit {
+        None
+    } else {
+        let lock_dir = match state_file.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        Some(InstanceLock::acquire(&lock_dir, &wallet.address, config.mining.standby)?.spawn_heartbeat())
     };
     
+    // Get workers count
+    let cores = CoreCounts::detect();
+    log::info!("CPU cores: {} logical / {} physical", cores.logical, cores.physical);
+    let mut workers = config.mining.workers
+        .unwrap_or_else(|| cores.default_workers(config.mining.use_physical_cores_only));
+    let per_worker_bytes = worker_memory_bytes();
+    // Too many workers for the memory would end in an OOM kill, so fewer is the safe side
+    let memory_worker_cap = config.mining.max_memory_mb
+        .map(|mb| workers_within_budget(usize::MAX, mb.saturating_mul(1024 * 1024), per_worker_bytes));
+    if let Some(cap) = memory_worker_cap.filter(|&cap| cap < workers) {
+        log::warn!(
+            "Reducing workers from {} to {}: {} workers need ~{} MB, over the --max-memory-mb budget of {} MB",
+            workers, cap, workers, workers as u64 * per_worker_bytes / (1024 * 1024),
+            config.mining.max_memory_mb.unwrap_or_default()
+        );
+        workers = cap;
+    }
+    log::info!("Workers: {}", workers);
+    log::info!(
+        "Mining memory: ~{} MB ({} workers x ~{:.1} MB solver memory and stack)",
+        workers as u64 * per_worker_bytes / (1024 * 1024), workers, per_worker_bytes as f64 / (1024.0 * 1024.0)
+    );
+    if cores.has_smt() && workers > cores.physical {
+        log::info!("Workers exceed physical cores - equix is memory-bound, so compare hashrate with --use-physical-cores-only");
+    }
+    log::info!("Submission buffer: {} blocks", args.submission_buffer_blocks.unwrap_or(8));
+    if !config.mining.claim_enabled {
+        log::info!("Claims: disabled (reveal-only mode)");
+    }
+    if config.mining.extended_nonce_search {
+        log::warn!("Extended nonce search enabled: solutions outside your partition will be rejected unless the contract allows them");
+    }
+    
+    let commitment_version: CommitmentVersion = config.mining.commitment_version
+        .as_deref()
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let salt_source: SaltSource = config.mining.salt_source
+        .as_deref()
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or_default();
+    let failure_action: FailureAction = config.mining.on_repeated_failure
+        .as_deref()
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or_default();
+    
+    // Configure client
+    let mut client_config = client_config_for(&config.mining.network, config.mining.grpc_endpoint);
+    if let Some(max_fee_inj) = config.mining.max_fee_inj {
+        log::info!("Fee cap: refusing transactions above {} INJ", max_fee_inj);
+        client_config.max_fee_inj = Some(max_fee_inj);
+    }
+    client_config.sequence_recovery = !config.mining.no_sequence_recovery;
+    client_config.allow_direct_signing = config.mining.allow_direct_signing;
+    client_config.gas_price_source = config.mining.gas_price
+        .as_deref()
+        .map(str::parse)
+        .transpose()?
+        .unwrap_or_default();
+    client_config.height_source = config.mining.height_endpoint
+        .as_deref()
+        .map(str::parse::<HeightSource>)
+        .transpose()?;
+    if let Some(ref source) = client_config.height_source {
+        // It's queried directly, so anything but a local node would bypass the proxy
+        if config.mining.socks5_proxy.is_some() && !source.is_loopback() {
+            return Err(anyhow!(
+                "--height-endpoint {} is not on this machine and would bypass the SOCKS5 proxy - use a local node or drop it",
+                source
+            ));
+        }
+        log::info!("Block height source: {} (main endpoint as fallback)", source);
+    }
+    if let Some(ref fee_denom) = config.mining.fee_denom {
+        validate_fee_denom(fee_denom)?;
+        client_config.fee_denom = fee_denom.clone();
+    }
+    // The default bounds are INJ prices - meaningless for another denom
+    let inj_fees = client_config.fee_denom == DEFAULT_FEE_DENOM;
+    client_config.min_gas_price = config.mining.min_gas_price
+        .unwrap_or(if inj_fees { DEFAULT_MIN_GAS_PRICE } else { 0 });
+    client_config.max_gas_price = config.mining.max_gas_price
+        .unwrap_or(if inj_fees { DEFAULT_MAX_GAS_PRICE } else { u128::MAX });
+    if client_config.min_gas_price > client_config.max_gas_price {
+        return Err(anyhow!(
+            "--min-gas-price ({}) is above --max-gas-price ({})",
+            client_config.min_gas_price, client_config.max_gas_price
+        ));
+    }
+    if let Some(slow_call_ms) = config.mining.slow_call_ms {
+        client_config.slow_call_threshold_ms = slow_call_ms;
+    }
+    client_config.grpc_compression = config.mining.grpc_compression;
+    if let Some(ref proxy) = config.mining.socks5_proxy {
+        let proxy: Socks5Proxy = proxy.parse()?;
+        log::warn!(
+            "Routing chain traffic through SOCKS5 proxy {}. Tor adds seconds to every call - \
+             reveals can miss their window; keep the proxy close or use a low-latency circuit",
+            proxy.addr()
+        );
+        if !config.mining.use_rust_signer {
+            log::warn!("The Node.js bridge signer broadcasts on its own connection, bypassing the proxy - use --use-rust-signer");
+        }
+        if config.mining.contract_events {
+            log::warn!("Contract events are not proxied; disabling them and polling through the proxy instead");
+            config.mining.contract_events = false;
+        }
+        client_config.socks5_proxy = Some(proxy);
+    }
+    if let Some(ref granter) = config.mining.fee_granter {
+        validate_address(granter).context("Invalid --fee-granter")?;
+        log::info!("Fee granter: {} pays gas through its feegrant allowance", granter);
+        client_config.fee_granter = Some(granter.clone());
+    }
+    if let Some(days) = config.mining.auto_stake_days {
+        StakeTokensMsg::new(Uint128::new(MIN_STAKE_AMOUNT), days).context("Invalid --auto-stake-days")?;
+        if config.mining.use_rust_signer {
+            return Err(anyhow!("--auto-stake-days needs the bridge signer - the Rust signer only signs mining contract messages"));
+        }
+    }
+    if config.mining.prepare_reveal && !config.mining.use_rust_signer {
+        log::warn!("--prepare-reveal needs the Rust signer (--use-rust-signer); reveals will be signed at submit time");
+        config.mining.prepare_reveal = false;
+    }
+    
     // Create client (wallet will be moved)
-    let wallet_for_client = InjectiveWallet::from_mnemonic_no_passphrase(&mnemonic)?;
+    let wallet_for_client = InjectiveWallet::from_mnemonic_with_path(&mnemonic, "", &hd_path)?;
     let mut client = InjectiveClient::new(client_config, wallet_for_client);
+    if let Some(ref tx_log_path) = config.mining.tx_log {
+        client.set_tx_log(TxLog::new(tx_log_path));
+    }
+    if let Some(ref crash_report_path) = config.mining.crash_report {
+        let call_trace = CallTrace::new(crash_report_path, CALL_TRACE_CAPACITY);
+        call_trace.dump_on_panic();
+        client.set_call_trace(call_trace);
+    }
     
     // Connect to chain
     log::info!("Connecting to Injective...");
@@ -415,19 +1418,106 @@ async fn cmd_mine(args: MineArgs) -> Result<()> {
     };
     
     log::info!("Mining contract: {}", contracts.mining_contract);
-    log::info!("Power token: {}", contracts.power_token);
+    for extra in &config.mining.extra_contracts {
+        validate_address(extra).with_context(|| format!("Invalid --extra-contract {}", extra))?;
+        if *extra == contracts.mining_contract || config.mining.extra_contracts.iter().filter(|c| *c == extra).count() > 1 {
+            return Err(anyhow!("Contract {} is listed more than once", extra));
+        }
+        log::info!("Also mining contract: {}", extra);
+    }
+    if !config.mining.extra_contracts.is_empty() && config.mining.use_rust_signer {
+        return Err(anyhow!("--extra-contract needs the bridge signer - the Rust signer only signs for one mining contract"));
+    }
+    match detect_power_token(&client, &contracts.power_token).await {
+        Ok(PowerToken::Bank { denom }) if config.mining.auto_stake_days.is_some() => {
+            return Err(anyhow!("POWER is bank denom {} - --auto-stake-days only supports a CW20 POWER token", denom));
+        }
+        Ok(token) => log::info!("Power token: {}", token),
+        Err(e) => log::warn!("Power token: {} (could not tell CW20 from bank denom: {})", contracts.power_token, e),
+    }
+    
+    // Catch protocol mismatches up front instead of as silent tx rejections
+    let deployed_version = query_contract_version(&client, &contracts.mining_contract).await;
+    match &deployed_version {
+        Ok(deployed) if !is_compatible_contract_version(&deployed.version) => {
+            log::warn!(
+                "Deployed contract v{} may be incompatible with this miner (expects v{}.{}+) - run 'gmine version'",
+                deployed.version, EXPECTED_CONTRACT_MAJOR, MIN_CONTRACT_MINOR
+            );
+        }
+        Ok(deployed) => log::info!("Contract version: {} v{}", deployed.contract, deployed.version),
+        Err(e) => log::debug!("Could not query contract version: {}", e),
+    }
+    
+    // A commitment the contract can't reproduce makes every reveal fail
+    if let Ok(deployed) = &deployed_version {
+        if !commitment_version.supports_contract(&deployed.version) {
+            return Err(anyhow!(
+                "Commitment version '{}' does not match contract v{} - set --commitment-version {}",
+                commitment_version,
+                deployed.version,
+                if commitment_version == CommitmentVersion::Salted { CommitmentVersion::Unsalted } else { CommitmentVersion::Salted }
+            ));
+        }
+    }
+    log::info!("Commitment version: {}", commitment_version);
+    
+    // A drillx build whose output changed would mine nothing but rejected solutions
+    let drillx_vector = drillx_self_test()?;
+    log::info!("drillx self-test passed ({})", drillx_vector);
+    
+    // Mining with PoW parameters the contract doesn't verify with only produces rejected
+    // solutions, so this is fatal rather than a warning
+    match query_pow_params(&client, &contracts.mining_contract).await {
+        Ok(params) => {
+            check_pow_params(&params)?;
+            log::info!("PoW parameters: {} v{} (matches contract)", params.algorithm, params.version);
+        }
+        Err(e) => {
+            log::debug!("Contract does not report PoW parameters ({}), using version pin", e);
+            if let Ok(deployed) = &deployed_version {
+                check_pow_contract_version(&deployed.version)?;
+            }
+        }
+    }
+    
+    // Garbage from a contract mid-migration would otherwise only show up as failed
+    // commits and reveals once mining is underway
+    if config.mining.no_warmup {
+        log::warn!("Skipping the contract warm-up check (--no-warmup)");
+    } else {
+        for contract in std::iter::once(&contracts.mining_contract).chain(&config.mining.extra_contracts) {
+            let warm = warm_up(&client, contract, Some(&wallet.address)).await
+                .context("Refusing to mine against unexpected contract data (--no-warmup to skip this check)")?;
+            log::info!(
+                "Contract warm-up passed for {}: epoch {} at difficulty {}{}",
+                contract,
+                warm.epoch.epoch_number,
+                warm.epoch.difficulty,
+                if warm.config.is_some() { ", config ok" } else { "" }
+            );
+        }
+    }
     
     // Set up EIP-712 signing
-    if config.mining.use_rust_signer {
+    if args.no_submit {
+        log::info!("No-submit mode: skipping signer setup, no transactions will be sent");
+    } else if config.mining.use_rust_signer {
         log::info!("Using Rust-native EIP-712 signer...");
-        client.enable_rust_signer(&mnemonic, &contracts.mining_contract)?;
+        client.enable_rust_signer_with_path(&mnemonic, &hd_path, &contracts.mining_contract)?;
+        if let Some(allowed) = &config.mining.allowed_messages {
+            client.set_signer_allowed_messages(allowed)?;
+        }
         log::info!("Rust-native EIP-712 signer enabled successfully!");
     } else {
+        if config.mining.allowed_messages.is_some() {
+            log::warn!("--allowed-messages only applies to the Rust signer (--use-rust-signer); ignoring");
+        }
         log::info!("Setting up EIP-712 bridge for Injective compatibility...");
         let mut bridge_manager = gmine_miner::BridgeManager::new(
             mnemonic.clone(),
             config.mining.network.clone()
-        )?;
+        )?.with_derivation_path(Some(hd_path.clone()));
         
         bridge_manager.start()?;
         
@@ -443,28 +1533,191 @@ async fn cmd_mine(args: MineArgs) -> Result<()> {
         log::info!("EIP-712 bridge configured successfully!");
     }
     
+    // Exactly one signer, and no silent SIGN_MODE_DIRECT fallback that never earns
+    if !args.no_submit {
+        let signing_method = client.signing_method()?;
+        if signing_method == SigningMethod::Direct {
+            log::warn!("Signing with {} (--allow-direct-signing) - transactions may not earn rewards", signing_method);
+        } else {
+            log::info!("Signing with {}", signing_method);
+        }
+    }
+    
+    // Signers are set up, so a fetched price reaches whichever one is in use
+    let fetched_gas_price = match client.fetch_gas_price().await {
+        Ok(Some(price)) => {
+            log::info!("Gas price source: {} ({} {}/gas)", config.mining.gas_price.as_deref().unwrap_or("static"), price, client.fee_denom());
+            client.set_gas_price(price);
+            Some(price)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("Could not fetch the gas price, using the static price until the next refresh: {}", e);
+            None
+        }
+    };
+    
+    // The static price is in INJ, so another fee denom needs its own price and balance
+    let mut fee_denom_min_balance = None;
+    if client.fee_denom() != DEFAULT_FEE_DENOM && !args.no_submit {
+        match fee_denom_gas_price(&client, fetched_gas_price).await {
+            Ok((price, required)) => {
+                client.set_gas_price(price);
+                fee_denom_min_balance = Some(required);
+            }
+            Err(e) => {
+                log::warn!("{:#} - paying fees in INJ instead", e);
+                client.fall_back_to_inj();
+            }
+        }
+    }
+    
     // Configure orchestrator
-    let state_file = config.mining.state_file
-        .map(PathBuf::from)
-        .unwrap_or_else(|| PathBuf::from("gmine_miner.state"));
+    let schedule = config.mining.schedule.as_deref()
+        .map(str::parse::<MiningSchedule>)
+        .transpose()
+        .context("Invalid mining schedule")?;
+    if let Some(ref schedule) = schedule {
+        log::info!("Mining schedule: {} (local time)", schedule);
+    }
+    
+    let miner_id = config.mining.miner_id
+        .as_deref()
+        .map(uuid::Uuid::parse_str)
+        .transpose()
+        .context("Invalid miner id (expected a UUID)")?;
     
-    let orchestrator_config = OrchestratorConfig {
+    let mut submission_targets = Vec::new();
+    if !config.mining.pool_only {
+        submission_targets.push(SubmissionTarget::OnChain);
+    }
+    match config.mining.pool_url.clone() {
+        Some(url) => submission_targets.push(SubmissionTarget::Pool { url, auth: config.mining.pool_auth.clone() }),
+        None if config.mining.pool_only => return Err(anyhow!("--pool-only requires --pool-url")),
+        None => {}
+    }
+    
+    let mut orchestrator_config = OrchestratorConfig {
         state_file,
         epoch_poll_interval: 5,
         reveal_wait_interval: 10,
         max_retries: 3,
         retry_delay_ms: 1000,
         contract_address: contracts.mining_contract.clone(),
+        additional_contracts: config.mining.extra_contracts.clone(),
+        power_token: contracts.power_token.clone(),
+        auto_stake_days: config.mining.auto_stake_days,
         worker_count: workers,
         submission_buffer_blocks: args.submission_buffer_blocks.unwrap_or(8),  // Conservative default: 8 blocks (~8 seconds)
+        claim_enabled: config.mining.claim_enabled,
+        extended_nonce_search: config.mining.extended_nonce_search,
+        worker_ramp_up_secs: config.mining.ramp_up_secs.unwrap_or(0),
+        nonce_offset: config.mining.nonce_offset.unwrap_or(0.0),
+        startup_jitter_seconds: config.mining.startup_jitter_seconds.unwrap_or(0),
+        schedule,
+        miner_label: config.mining.miner_label.clone(),
+        miner_id,
+        commitment_version,
+        salt_source,
+        failure_action,
+        max_failed_epochs: config.mining.max_failed_epochs.unwrap_or(5),
+        max_reveal_attempts: config.mining.max_reveal_attempts.unwrap_or(5),
+        max_claim_attempts: config.mining.max_claim_attempts.unwrap_or(10),
+        commit_confirmation_timeout_secs: config.mining.commit_confirmation_timeout.unwrap_or(120),
+        reveal_confirmation_timeout_secs: config.mining.reveal_confirmation_timeout.unwrap_or(30),
+        claim_confirmation_timeout_secs: config.mining.claim_confirmation_timeout.unwrap_or(300),
+        confirmation_poll_ms: config.mining.confirmation_poll_ms.unwrap_or(500),
+        prioritize_transactions: !config.mining.fifo_transactions,
+        restart_on_epoch_advance: !config.mining.no_restart_on_epoch_advance,
+        phase_confirmations: config.mining.phase_confirmations.unwrap_or(1),
+        submission_targets,
+        adaptive_threads: config.mining.adaptive_threads,
+        min_threads: config.mining.min_threads.unwrap_or(1),
+        max_threads: config.mining.max_threads.unwrap_or(workers).min(memory_worker_cap.unwrap_or(usize::MAX)),
+        prepare_reveal: config.mining.prepare_reveal,
+        max_tracked_epochs: config.mining.max_tracked_epochs.unwrap_or(1).max(1),
+        state_backups: config.mining.state_backups.unwrap_or(3),
+        metrics_file: config.mining.metrics_file.clone().map(PathBuf::from),
+        heartbeat_file: config.mining.heartbeat_file.clone().map(PathBuf::from),
+        difficulty_webhook: config.mining.difficulty_webhook.clone(),
+        no_advance: config.mining.no_advance,
+        no_submit: args.no_submit,
+        standby: config.mining.standby && !args.no_submit,
+        standby_takeover_blocks: config.mining.standby_takeover_blocks.unwrap_or(20),
+        post_claim_cooldown_secs: config.mining.post_claim_cooldown.unwrap_or(5),
+        target_refresh_secs: config.mining.target_refresh.filter(|&secs| secs > 0),
+        chain_halt_secs: config.mining.chain_halt_secs.unwrap_or(120),
+        notify_command: config.mining.notify_command.clone(),
+        reveal_on_resume: !config.mining.no_reveal_on_resume,
+        ..OrchestratorConfig::default()
     };
+    if let Some(required) = fee_denom_min_balance {
+        orchestrator_config.fee_denom_min_balance = required;
+    }
+    if config.mining.fee_granter.is_some() || args.no_submit {
+        // The granter pays gas (or nothing is sent) - an empty wallet is expected, not a readiness problem
+        orchestrator_config.min_gas_balance = 0;
+        orchestrator_config.fee_denom_min_balance = 0;
+    }
+    
+    // Create the orchestrators - one per contract, sharing the client and taking turns
+    // broadcasting, so their transactions don't collide on the account sequence
+    let client = Arc::new(tokio::sync::RwLock::new(client));
+    let limiter = Arc::new(BroadcastLimiter::new(orchestrator_config.max_in_flight_per_signer));
+    let mut orchestrators = Vec::new();
+    let mut wallet = Some(wallet);
+    for contract_config in orchestrator_config.per_contract() {
+        let contract = contract_config.contract_address.clone();
+        let wallet = match wallet.take() {
+            Some(wallet) => wallet,
+            None => InjectiveWallet::from_mnemonic_with_path(&mnemonic, "", &hd_path)?,
+        };
+        if !orchestrator_config.additional_contracts.is_empty() {
+            log::info!("{}: {} workers, state file {}", contract, contract_config.worker_count, contract_config.state_file.display());
+        }
+        let mut orchestrator = MiningOrchestrator::new_shared(contract_config, client.clone(), wallet, limiter.clone()).await?;
+        
+        // Wake the orchestrator on epoch advance/finalize instead of waiting for the next poll
+        if config.mining.contract_events {
+            let endpoint = config.mining.events_endpoint.clone().unwrap_or_else(|| {
+                if config.mining.network == "mainnet" {
+                    MAINNET_EVENTS_ENDPOINT.to_string()
+                } else {
+                    TESTNET_EVENTS_ENDPOINT.to_string()
+                }
+            });
+            let listener = ContractEventListener::new(endpoint, contract.clone());
+            orchestrator.set_event_listener(listener.spawn());
+        }
+        orchestrators.push((contract, orchestrator));
+    }
+    
+    // Log combined stats; each orchestrator still logs its own
+    if orchestrators.len() > 1 {
+        let collectors: Vec<_> = orchestrators.iter().map(|(_, o)| o.get_stats_collector()).collect();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(AGGREGATE_STATS_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let mut stats = Vec::with_capacity(collectors.len());
+                for collector in &collectors {
+                    stats.push(collector.lock().await.get_stats().await);
+                }
+                log::info!("All {}", AggregateStats::from_stats(&stats));
+            }
+        });
+    }
     
-    // Create and run orchestrator
-    let mut orchestrator = MiningOrchestrator::new(
-        orchestrator_config,
-        client,
-        wallet,
-    ).await?;
+    // Expose liveness/readiness probes for container runtimes (the first contract's)
+    if let Some(port) = config.mining.health_port {
+        let health = orchestrators[0].1.health_state();
+        tokio::spawn(async move {
+            if let Err(e) = serve_health(&format!("0.0.0.0:{}", port), health).await {
+                log::error!("Health probe server failed: {}", e);
+            }
+        });
+    }
     
     log::info!("Starting mining orchestrator...");
     log::info!("Press Ctrl+C to stop mining");
@@ -472,12 +1725,24 @@ async fn cmd_mine(args: MineArgs) -> Result<()> {
     // Set up graceful shutdown
     let shutdown = tokio::signal::ctrl_c();
     
-    // Run mining loop
+    // Run the mining loops; one contract stopping leaves the others mining
+    let multi_contract = orchestrators.len() > 1;
+    let runs = orchestrators.iter_mut().map(|(contract, orchestrator)| async move {
+        let result = orchestrator.run().await;
+        match &result {
+            Ok(_) if multi_contract => log::info!("Mining {} completed successfully", contract),
+            Ok(_) => log::info!("Mining completed successfully"),
+            Err(e) if multi_contract => log::error!("Mining {} stopped: {}", contract, e),
+            Err(e) if e.is::<RepeatedFailureError>() => log::error!("Mining stopped: {}", e),
+            Err(e) => log::error!("Mining error: {}", e),
+        }
+        result
+    });
     tokio::select! {
-        result = orchestrator.run() => {
-            match result {
-                Ok(_) => log::info!("Mining completed successfully"),
-                Err(e) => log::error!("Mining error: {}", e),
+        results = join_all(runs) => {
+            // Exit nonzero so a supervisor restarts us or someone looks at it
+            if let Some(e) = results.into_iter().filter_map(Result::err).find(|e| e.is::<RepeatedFailureError>()) {
+                return Err(e);
             }
         }
         _ = shutdown => {
@@ -485,10 +1750,42 @@ async fn cmd_mine(args: MineArgs) -> Result<()> {
         }
     }
     
+    // Release the instance lock
+    if let Some(heartbeat) = instance_heartbeat {
+        heartbeat.abort();
+    }
     log::info!("Mining stopped");
     Ok(())
 }
 
+/// Gas price in the configured non-INJ fee denom, and the balance of it one transaction
+/// needs. Fails if the wallet can't pay a fee in it, so the caller falls back to INJ
+async fn fee_denom_gas_price(client: &InjectiveClient, fetched_gas_price: Option<u128>) -> Result<(u128, u128)> {
+    let denom = client.fee_denom();
+    match client.node_fee_denoms().await {
+        Ok(denoms) if !denoms.iter().any(|d| d == denom) => log::warn!(
+            "Fee denom {} is not among the node's fee denoms ({}) - transactions may be rejected",
+            denom, denoms.join(", ")
+        ),
+        Ok(_) => {}
+        Err(e) => log::warn!("Could not check whether the node accepts fees in {}: {}", denom, e),
+    }
+    
+    let price = match fetched_gas_price {
+        Some(price) => price,
+        None => client.node_gas_price().await?
+            .ok_or_else(|| anyhow!("No gas price known for fee denom {} - set --gas-price to an oracle for it", denom))?,
+    };
+    let required = fee_for_gas_at(SIGNER_GAS_LIMIT, price);
+    let balance = client.query_bank_balance(client.address(), denom).await
+        .with_context(|| format!("Could not check the {} balance", denom))?;
+    if balance < required {
+        return Err(anyhow!("Balance of {} ({}) is below one transaction fee ({})", denom, balance, required));
+    }
+    log::info!("Paying fees in {} at {} per gas (balance {})", denom, price, balance);
+    Ok((price, required))
+}
+
 /// Manage service
 async fn cmd_service(action: ServiceAction) -> Result<()> {
     match action {
@@ -602,9 +1899,597 @@ async fn cmd_status() -> Result<()> {
         println!("Service status not supported on this platform");
     }
     
+    print_profitability();
+    
+    Ok(())
+}
+
+/// Build the gRPC client configuration for a network
+fn client_config_for(network: &str, grpc_endpoint: Option<String>) -> ClientConfig {
+    if network == "mainnet" {
+        ClientConfig {
+            grpc_endpoint: grpc_endpoint.unwrap_or_else(|| 
+                "https://sentry.chain.grpc.injective.network:443".to_string()
+            ),
+            connection_timeout: 10,
+            request_timeout: 30,
+            query_timeout: 5,
+            broadcast_timeout: 15,
+            max_retries: 3,
+            chain_id: "injective-1".to_string(),
+            max_fee_inj: None,
+            slow_call_threshold_ms: 2000,
+            lcd_endpoint: "https://sentry.lcd.injective.network:443".to_string(),
+            grpc_compression: false,
+            socks5_proxy: None,
+            fee_granter: None,
+            gas_price_source: GasPriceSource::Static,
+            min_gas_price: DEFAULT_MIN_GAS_PRICE,
+            max_gas_price: DEFAULT_MAX_GAS_PRICE,
+            fee_denom: DEFAULT_FEE_DENOM.to_string(),
+            sequence_recovery: true,
+            allow_direct_signing: false,
+            height_source: None,
+        }
+    } else {
+        ClientConfig {
+            grpc_endpoint: grpc_endpoint.unwrap_or_else(|| 
+                "https://testnet.sentry.chain.grpc.injective.network:443".to_string()
+            ),
+            connection_timeout: 10,
+            request_timeout: 30,
+            query_timeout: 5,
+            broadcast_timeout: 15,
+            max_retries: 3,
+            chain_id: "injective-888".to_string(),
+            max_fee_inj: None,
+            slow_call_threshold_ms: 2000,
+            lcd_endpoint: "https://testnet.sentry.lcd.injective.network:443".to_string(),
+            grpc_compression: false,
+            socks5_proxy: None,
+            fee_granter: None,
+            gas_price_source: GasPriceSource::Static,
+            min_gas_price: DEFAULT_MIN_GAS_PRICE,
+            max_gas_price: DEFAULT_MAX_GAS_PRICE,
+            fee_denom: DEFAULT_FEE_DENOM.to_string(),
+            sequence_recovery: true,
+            allow_direct_signing: false,
+            height_source: None,
+        }
+    }
+}
+
+/// Defaults, overridden by the config file if it exists, overridden by `GMINE_*`
+/// environment variables. Also returns the names of the variables applied
+fn load_miner_config(config_path: &Path) -> Result<(MinerConfig, Vec<String>)> {
+    let defaults = MinerConfig {
+        mining: MiningConfig::default(),
+        telemetry: TelemetryConfig::default(),
+    };
+    load_layered(&defaults, Some(config_path).filter(|path| path.exists()))
+}
+
+/// The mining section of the layered config (see `load_miner_config`)
+fn load_mining_config(config_path: &PathBuf) -> Result<MiningConfig> {
+    Ok(load_miner_config(config_path)?.0.mining)
+}
+
+/// Print build info and check the deployed contract's protocol version
+async fn cmd_version() -> Result<()> {
+    let mining = load_mining_config(&get_config_dir()?.join("config.toml"))?;
+    let client_config = client_config_for(&mining.network, mining.grpc_endpoint.clone());
+    let contracts = ContractAddresses::testnet();
+    
+    println!("gmine {}", env!("CARGO_PKG_VERSION"));
+    println!("Git commit:       {}", env!("GMINE_GIT_COMMIT"));
+    println!("Algorithm:        {}/equix v{} ({})", POW_ALGORITHM, POW_PARAMS_VERSION, POW_VENDOR_VERSION);
+    println!("Network:          {} ({})", mining.network, client_config.chain_id);
+    println!("Mining contract:  {}", contracts.mining_contract);
+    println!("Expected protocol: v{}.{}+", EXPECTED_CONTRACT_MAJOR, MIN_CONTRACT_MINOR);
+    
+    if mining.network == "mainnet" {
+        println!("\n⚠️  Mainnet contracts are not yet supported");
+        return Ok(());
+    }
+    
+    let mut client = InjectiveClient::new_read_only(client_config)?;
+    if let Err(e) = client.connect().await {
+        println!("\n⚠️  Could not connect to check contract version: {}", e);
+        return Ok(());
+    }
+    
+    match query_contract_version(&client, &contracts.mining_contract).await {
+        Ok(deployed) => {
+            println!("Deployed contract: {} v{}", deployed.contract, deployed.version);
+            if is_compatible_contract_version(&deployed.version) {
+                println!("\n✅ Miner is compatible with the deployed contract");
+            } else {
+                println!(
+                    "\n❌ Deployed contract v{} does not match the protocol this miner implements (v{}.{}+)",
+                    deployed.version, EXPECTED_CONTRACT_MAJOR, MIN_CONTRACT_MINOR
+                );
+                println!("   Commits/reveals may be rejected - update the miner before mining");
+            }
+        }
+        Err(e) => println!("\n⚠️  Could not query contract version: {}", e),
+    }
+    
+    if let Ok(params) = query_pow_params(&client, &contracts.mining_contract).await {
+        match check_pow_params(&params) {
+            Ok(()) => println!("✅ PoW parameters match: {} v{}", params.algorithm, params.version),
+            Err(e) => println!("❌ {}", e),
+        }
+    }
+    
+    match warm_up(&client, &contracts.mining_contract, None).await {
+        Ok(warm) => println!(
+            "✅ Contract responses look valid: epoch {} at difficulty {}{}",
+            warm.epoch.epoch_number,
+            warm.epoch.difficulty,
+            if warm.config.is_some() { ", config ok" } else { "" }
+        ),
+        Err(e) => {
+            println!("❌ {}", e);
+            println!("   The miner will refuse to start against this contract (--no-warmup to override)");
+        }
+    }
+    
+    Ok(())
+}
+
+/// Print the partition index and nonce range for an address and epoch
+async fn cmd_partition(config_path: Option<PathBuf>, address: String, epoch: Option<u64>) -> Result<()> {
+    validate_address(&address)?;
+    
+    let epoch = match epoch {
+        Some(epoch) => epoch,
+        None => {
+            let config_path = match config_path {
+                Some(path) => path,
+                None => get_config_dir()?.join("config.toml"),
+            };
+            let mining = load_mining_config(&config_path)?;
+            if mining.network == "mainnet" {
+                return Err(anyhow!("Mainnet not yet supported"));
+            }
+            let mut client = InjectiveClient::new_read_only(client_config_for(&mining.network, mining.grpc_endpoint.clone()))?;
+            client.connect().await?;
+            query_epoch_info(&client, &ContractAddresses::testnet().mining_contract).await
+                .context("Failed to query current epoch (pass --epoch to skip)")?
+                .epoch_number
+        }
+    };
+    
+    let (start, end) = calculate_nonce_range(&address, epoch);
+    println!("Address:      {}", address);
+    println!("Epoch:        {}", epoch);
+    println!("Partition:    {} of 1000", partition_index(&address, epoch));
+    println!("Nonce range:  {} to {}", start, end);
+    
+    Ok(())
+}
+
+/// Print (or save) the proof of the last solution found by the miner
+fn cmd_export_solution(config_path: Option<PathBuf>, output: Option<PathBuf>) -> Result<()> {
+    let config_path = match config_path {
+        Some(path) => path,
+        None => get_config_dir()?.join("config.toml"),
+    };
+    let state_file = load_mining_config(&config_path)?
+        .state_file
+        .unwrap_or_else(|| "gmine_miner.state".to_string());
+    
+    let path = MiningOrchestrator::solution_proof_file(&PathBuf::from(state_file));
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("No solution proof at {} - has this miner found a solution yet?", path.display()))?;
+    let proof = SolutionProof::from_json(&content)
+        .map_err(|e| anyhow!("Invalid solution proof in {}: {}", path.display(), e))?;
+    
+    match output {
+        Some(output) => {
+            fs::write(&output, proof.to_json())
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+            println!("Exported solution for epoch {} (nonce {}) to {}", proof.epoch, proof.nonce, output.display());
+        }
+        None => println!("{}", proof.to_json()),
+    }
+    Ok(())
+}
+
+/// Re-check a solution proof with drillx, independently of the miner that produced it
+fn cmd_verify_solution(path: PathBuf) -> Result<()> {
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let proof = SolutionProof::from_json(&content)
+        .map_err(|e| anyhow!("Invalid solution proof: {}", e))?;
+    
+    println!("Miner:       {}", proof.miner);
+    println!("Epoch:       {}", proof.epoch);
+    println!("Challenge:   {}", proof.challenge);
+    println!("Nonce:       {}", proof.nonce);
+    println!("Digest:      {}", proof.digest);
+    println!("Difficulty:  {} (claimed)", proof.difficulty);
+    
+    let actual = proof.verify().map_err(|e| anyhow!("❌ Invalid solution: {}", e))?;
+    println!("✅ Valid {} solution, difficulty {}", proof.algorithm, actual);
+    Ok(())
+}
+
+/// Pretty-print a state file, with the stored solution in hex and a check that its
+/// nonce, digest and salt still hash to the stored commitment. Only reads the file -
+/// unlike the miner's loader it doesn't fall back to (or rotate) backups
+fn cmd_inspect_state(path: PathBuf, address: Option<String>) -> Result<()> {
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let state: MiningState = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a valid state file", path.display()))?;
+    
+    println!("State file:      {}", path.display());
+    println!("Phase:           {}", state.phase.name());
+    println!("Epoch:           {}", state.epoch);
+    let saved = chrono::DateTime::from_timestamp(state.last_saved as i64, 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| "invalid timestamp".to_string());
+    println!("Last saved:      {} ({})", state.last_saved, saved);
+    println!("Committed:       {:?}", state.committed_epochs);
+    println!("Pending claims:  {:?}", state.pending_claims);
+    println!(
+        "Failure streak:  {} failed epochs{}",
+        state.failure_streak.count,
+        state.failure_streak.pending.map(|epoch| format!(", epoch {} pending", epoch)).unwrap_or_default()
+    );
+    if let Some(ref checkpoint) = state.search_checkpoint {
+        let remaining: u64 = checkpoint.ranges.iter().map(|(start, end)| end - start).sum();
+        println!(
+            "Search progress: epoch {}, {} nonces left in {} slices",
+            checkpoint.epoch, remaining, checkpoint.ranges.len()
+        );
+    }
+    if let MiningPhase::Claiming(epoch) = state.phase {
+        println!("Claiming epoch:  {}", epoch);
+    }
+    
+    let Some(data) = state.phase.commitment_data() else {
+        return Ok(());
+    };
+    println!();
+    println!("Solution epoch:  {}", data.epoch);
+    println!("Nonce:           0x{} ({})", hex::encode(data.nonce), nonce_from_bytes(data.nonce));
+    println!("Digest:          0x{}", hex::encode(data.digest));
+    println!("Salt:            0x{}", hex::encode(data.salt));
+    println!("Commitment:      0x{}", hex::encode(data.commitment));
+    if data.prepared_reveal.is_some() {
+        println!("Prepared reveal: yes");
+    }
+    
+    let salted = data.is_consistent(CommitmentVersion::Salted, "");
+    println!("Salted (V3.4+):  {}", if salted { "✅ matches" } else { "❌ does not match" });
+    match address {
+        Some(address) => {
+            let unsalted = data.is_consistent(CommitmentVersion::Unsalted, &address);
+            println!("Unsalted:        {}", if unsalted { "✅ matches" } else { "❌ does not match" });
+            if !salted && !unsalted {
+                println!("\n⚠️  The stored commitment doesn't match the stored solution - its reveal will be rejected");
+            }
+        }
+        None if !salted => {
+            println!("\n⚠️  Not a salted commitment - pass --address to check it as an unsalted (pre-V3.4) one");
+        }
+        None => {}
+    }
     Ok(())
 }
 
+/// Gas limit for emergency exit transactions - generous, since they skip simulation
+const EMERGENCY_GAS_LIMIT: u64 = 600_000;
+
+/// Stop the miner, unstake the full staked balance and send the wallet's POWER and INJ
+/// to a safe address, as fast as possible (no simulation) and reporting each tx hash.
+/// Fails if any funds are left in the wallet
+async fn cmd_emergency_exit(
+    config_path: Option<PathBuf>,
+    mnemonic_file: Option<PathBuf>,
+    safe_address: Option<String>,
+    confirm: bool,
+) -> Result<()> {
+    if !confirm {
+        return Err(anyhow!(
+            "emergency-exit unstakes everything and moves funds - re-run with --confirm if you mean it"
+        ));
+    }
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+    
+    let config_path = match config_path {
+        Some(path) => path,
+        None => get_config_dir()?.join("config.toml"),
+    };
+    let mining = load_mining_config(&config_path)?;
+    let safe_address = safe_address.or(mining.safe_address.clone());
+    if let Some(ref address) = safe_address {
+        validate_address(address).context("Invalid safe address")?;
+    }
+    
+    let mnemonic = match (mnemonic_file, mining.mnemonic.clone()) {
+        (Some(path), _) => fs::read_to_string(path)?.trim().to_string(),
+        (None, Some(mnemonic)) => mnemonic,
+        (None, None) => return Err(anyhow!("No mnemonic in the config - pass --mnemonic-file")),
+    };
+    let hd_path = hd_path_for_index(mining.account_index.unwrap_or(0));
+    let wallet = InjectiveWallet::from_mnemonic_with_path(&mnemonic, "", &hd_path)?;
+    println!("🚨 Emergency exit for {}", wallet.address);
+    if safe_address.as_deref() == Some(wallet.address.as_str()) {
+        return Err(anyhow!("The safe address is the miner wallet itself"));
+    }
+    
+    let contracts = if mining.network == "mainnet" {
+        return Err(anyhow!("Mainnet not yet supported"));
+    } else {
+        ContractAddresses::testnet()
+    };
+    
+    // Step 1: stop mining, so the miner's transactions don't race ours for the sequence
+    if let Err(e) = service_stop().await {
+        println!("Mining service: not stopped ({}) - stop any foreground miner with Ctrl+C", e);
+    }
+    let state_file = PathBuf::from(mining.state_file.clone().unwrap_or_else(|| "gmine_miner.state".to_string()));
+    let lock_dir = match state_file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    // Held until we're done, so a restarting miner can't get in between
+    let _lock = match InstanceLock::acquire(&lock_dir, &wallet.address, false) {
+        Ok(lock) => Some(lock),
+        Err(e) => {
+            println!("⚠️  {} - continuing anyway; its transactions may collide with ours", e);
+            None
+        }
+    };
+    
+    // The bridge signs arbitrary contract messages and bank sends (the Rust signer only mining messages)
+    let mut client = InjectiveClient::new(
+        client_config_for(&mining.network, mining.grpc_endpoint.clone()),
+        InjectiveWallet::from_mnemonic_with_path(&mnemonic, "", &hd_path)?,
+    );
+    client.connect().await?;
+    let mut bridge_manager = gmine_miner::BridgeManager::new(mnemonic.clone(), mining.network.clone())?
+        .with_derivation_path(Some(hd_path.clone()));
+    bridge_manager.start()?;
+    bridge_manager.ensure_healthy().await?;
+    client.set_bridge_client(gmine_miner::chain::bridge_client::BridgeClient::new(
+        bridge_manager.get_url(),
+        Some(bridge_manager.get_api_key()),
+    ));
+    
+    // Step 2: unstake everything
+    // Whatever can't be moved to safety; any of it makes the command fail
+    let mut left_behind = Vec::new();
+    let stake = query_stake_info(&client, &contracts.mining_contract, &wallet.address).await
+        .context("Could not query the stake")?;
+    let staked = stake.staked_amount()?;
+    let mut unstake_hash = None;
+    if staked == 0 {
+        println!("Unstake:      nothing staked");
+    } else {
+        let msg = UnstakeTokensMsg::new(Uint128::new(staked));
+        match client.execute_message_fast(&contracts.mining_contract, &msg, vec![], EMERGENCY_GAS_LIMIT).await {
+            Ok(hash) => {
+                println!("Unstake:      {:.6} POWER, tx {}", staked as f64 / 1_000_000.0, hash);
+                unstake_hash = Some(hash);
+            }
+            Err(e) => {
+                println!(
+                    "Unstake:      FAILED for {:.6} POWER (locked until block {}): {}",
+                    staked as f64 / 1_000_000.0, stake.lock_until, e
+                );
+                left_behind.push(format!("{:.6} staked POWER", staked as f64 / 1_000_000.0));
+            }
+        }
+    }
+    
+    // Step 3: move the POWER and INJ to the safe address
+    let Some(safe_address) = safe_address else {
+        println!("Transfer:     skipped (no safe address configured)");
+        left_behind.push("the wallet's POWER and INJ (no safe address)".to_string());
+        return emergency_exit_result(&left_behind);
+    };
+    if let Some(ref hash) = unstake_hash {
+        // The unstaked POWER is only in the wallet once the unstake has succeeded
+        if let Err(e) = wait_for_tx(&client, hash).await {
+            println!("Unstake:      FAILED: {:#}", e);
+            left_behind.push(format!("{:.6} staked POWER", staked as f64 / 1_000_000.0));
+        }
+    }
+    let power_token = detect_power_token(&client, &contracts.power_token).await?;
+    let balance = query_power_token_balance(&client, &power_token, &wallet.address).await?;
+    if balance == 0 {
+        println!("Transfer:     no POWER in the wallet");
+    } else {
+        let sent = match &power_token {
+            PowerToken::Cw20 { contract } => {
+                let transfer = cw20::Cw20ExecuteMsg::Transfer { recipient: safe_address.clone(), amount: Uint128::new(balance) };
+                client.execute_contract_fast(contract, serde_json::to_value(&transfer)?, vec![], EMERGENCY_GAS_LIMIT).await
+            }
+            PowerToken::Bank { denom } => {
+                let send = BankSend {
+                    to_address: safe_address.clone(),
+                    amount: vec![Coin { denom: denom.clone(), amount: balance.to_string() }],
+                };
+                client.send_tokens_fast(send, EMERGENCY_GAS_LIMIT).await
+            }
+        };
+        // Wait for it, so the INJ balance below is net of its fee
+        let sent = match sent {
+            Ok(hash) => wait_for_tx(&client, &hash).await.map(|_| hash),
+            Err(e) => Err(e),
+        };
+        match sent {
+            Ok(hash) => println!("Transfer:     {:.6} POWER to {}, tx {}", balance as f64 / 1_000_000.0, safe_address, hash),
+            Err(e) => {
+                println!("Transfer:     FAILED for {:.6} POWER: {:#}", balance as f64 / 1_000_000.0, e);
+                left_behind.push(format!("{:.6} POWER", balance as f64 / 1_000_000.0));
+            }
+        }
+    }
+    
+    // INJ last, keeping back the fee of the send itself
+    let inj = client.query_bank_balance(&wallet.address, "inj").await
+        .context("Could not query the INJ balance")?;
+    if inj <= BRIDGE_FEE {
+        println!("INJ:          nothing to sweep ({:.6} INJ covers at most the fee)", inj as f64 / 1e18);
+    } else {
+        let amount = inj - BRIDGE_FEE;
+        let send = BankSend {
+            to_address: safe_address.clone(),
+            amount: vec![Coin { denom: "inj".to_string(), amount: amount.to_string() }],
+        };
+        let sent = match client.send_tokens_fast(send, EMERGENCY_GAS_LIMIT).await {
+            Ok(hash) => wait_for_tx(&client, &hash).await.map(|_| hash),
+            Err(e) => Err(e),
+        };
+        match sent {
+            Ok(hash) => println!("INJ:          {:.6} INJ to {}, tx {}", amount as f64 / 1e18, safe_address, hash),
+            Err(e) => {
+                println!("INJ:          FAILED for {:.6} INJ: {:#}", amount as f64 / 1e18, e);
+                left_behind.push(format!("{:.6} INJ", amount as f64 / 1e18));
+            }
+        }
+    }
+    emergency_exit_result(&left_behind)
+}
+
+/// Wait up to 30s for a transaction to be included, failing if the chain rejected it
+async fn wait_for_tx(client: &InjectiveClient, hash: &str) -> Result<()> {
+    for _ in 0..30 {
+        match client.find_tx(hash).await {
+            Ok(Some(tx)) if tx.code == 0 => return Ok(()),
+            Ok(Some(tx)) => return Err(anyhow!("tx {} failed with code {}: {}", hash, tx.code, tx.raw_log)),
+            Ok(None) | Err(_) => tokio::time::sleep(std::time::Duration::from_secs(1)).await,
+        }
+    }
+    Err(anyhow!("tx {} was not included within 30s", hash))
+}
+
+/// Fail the emergency exit (non-zero exit code) if any funds stayed in the wallet
+fn emergency_exit_result(left_behind: &[String]) -> Result<()> {
+    match left_behind {
+        [] => Ok(()),
+        _ => Err(anyhow!("Funds left in the wallet: {} - move them by hand", left_behind.join(", "))),
+    }
+}
+
+/// Continuously display an address's on-chain mining state without a wallet
+async fn cmd_monitor(config_path: Option<PathBuf>, address: String, interval: u64) -> Result<()> {
+    validate_address(&address)?;
+    
+    let config_path = match config_path {
+        Some(path) => path,
+        None => get_config_dir()?.join("config.toml"),
+    };
+    let mining = load_mining_config(&config_path)?;
+    
+    let contracts = if mining.network == "mainnet" {
+        return Err(anyhow!("Mainnet not yet supported"));
+    } else {
+        ContractAddresses::testnet()
+    };
+    
+    let mut client = InjectiveClient::new_read_only(client_config_for(&mining.network, mining.grpc_endpoint.clone()))?;
+    client.connect().await?;
+    
+    println!("👀 Monitoring {} on {} (read-only, Ctrl+C to stop)", address, mining.network);
+    let power_token = detect_power_token(&client, &contracts.power_token).await;
+    
+    loop {
+        println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        match query_epoch_info(&client, &contracts.mining_contract).await {
+            Ok(info) => {
+                match &info.phase {
+                    PhaseInfo::Commit { ends_at } => println!("Epoch:        {} (Commit phase, ends at block {})", info.epoch_number, ends_at),
+                    PhaseInfo::Reveal { ends_at } => println!("Epoch:        {} (Reveal phase, ends at block {})", info.epoch_number, ends_at),
+                    PhaseInfo::Settlement { ends_at } => println!("Epoch:        {} (Settlement phase, ends at block {})", info.epoch_number, ends_at),
+                    PhaseInfo::Unknown(name) => println!("Epoch:        {} (unrecognized phase '{}')", info.epoch_number, name),
+                }
+                println!("Difficulty:   {}", info.difficulty);
+                let (start, end) = calculate_nonce_range(&address, info.epoch_number);
+                println!("Partition:    {} of 1000", partition_index(&address, info.epoch_number));
+                println!("Nonce range:  {} to {}", start, end);
+            }
+            Err(e) => println!("Epoch:        query failed ({})", e),
+        }
+        
+        match client.get_latest_block_height().await {
+            Ok(height) => println!("Block height: {}", height),
+            Err(e) => println!("Block height: query failed ({})", e),
+        }
+        
+        match client.query_bank_balance(&address, "inj").await {
+            Ok(balance) => println!("INJ balance:  {:.6}", balance as f64 / 1e18),
+            Err(e) => println!("INJ balance:  query failed ({})", e),
+        }
+        
+        match &power_token {
+            Ok(token) => match query_power_token_balance(&client, token, &address).await {
+                Ok(balance) => println!("POWER:        {:.6}", balance as f64 / 1_000_000.0),
+                Err(e) => println!("POWER:        query failed ({})", e),
+            },
+            Err(e) => println!("POWER:        unknown token type ({})", e),
+        }
+        
+        match query_miner_info(&client, &contracts.mining_contract, &address, None).await {
+            Ok(stats) => {
+                let earned = stats.total_rewards_earned.parse::<u128>().unwrap_or(0) as f64 / 1_000_000.0;
+                println!("Mined:        {}/{} successful, {:.6} POWER earned", stats.successful_mines, stats.total_attempts, earned);
+            }
+            Err(_) => println!("Mined:        no mining history for this address"),
+        }
+        
+        tokio::select! {
+            _ = tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("\nStopped monitoring");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Print the latest profitability estimate written by the running miner
+fn print_profitability() {
+    let config_path = match get_config_dir() {
+        Ok(dir) => dir.join("config.toml"),
+        Err(_) => return,
+    };
+    let state_file = load_miner_config(&config_path)
+        .ok()
+        .and_then(|(config, _)| config.mining.state_file)
+        .unwrap_or_else(|| "gmine_miner.state".to_string());
+    
+    let path = MiningOrchestrator::profitability_file(&PathBuf::from(state_file));
+    let estimate = match fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<ProfitabilityEstimate>(&content).ok())
+    {
+        Some(estimate) => estimate,
+        None => return,
+    };
+    
+    println!("\n💰 Profitability");
+    println!("Difficulty: {}", estimate.difficulty);
+    println!("Hashrate: {:.2} MH/s", estimate.hashrate / 1_000_000.0);
+    println!("Win probability per epoch: {:.2}%", estimate.win_probability * 100.0);
+    match estimate.expected_reward_power {
+        Some(reward) => println!("Expected reward per attempt: {:.4} POWER", reward),
+        None => println!("Expected reward per attempt: n/a (no claims yet)"),
+    }
+    if let Some(per_hour) = estimate.reward_per_hour_power {
+        println!("Estimated reward: {:.4} POWER/hour", per_hour);
+    }
+    println!("Gas cost per attempt: {:.6} {}", estimate.gas_cost, estimate.fee_unit);
+    if let Some(break_even) = estimate.break_even_per_power {
+        println!("Break-even price: {:.6} {} per POWER", break_even, estimate.fee_unit);
+    }
+}
+
 /// Service installation
 async fn service_install() -> Result<()> {
     #[cfg(target_os = "linux")]
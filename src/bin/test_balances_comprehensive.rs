@@ -2,7 +2,7 @@
 /// Tests balance queries for various token types and precision handling
 
 use anyhow::Result;
-use gmine_miner::chain::{InjectiveClient, ClientConfig};
+use gmine_miner::chain::{InjectiveClient, ClientConfig, GasPriceSource, DEFAULT_FEE_DENOM, DEFAULT_MAX_GAS_PRICE, DEFAULT_MIN_GAS_PRICE};
 use gmine_miner::chain::wallet::InjectiveWallet;
 
 const INJECTIVE_TESTNET_ENDPOINT: &str = "https://testnet.sentry.chain.grpc.injective.network:443";
@@ -47,8 +47,23 @@ async fn main() -> Result<()> {
         grpc_endpoint: INJECTIVE_TESTNET_ENDPOINT.to_string(),
         connection_timeout: 10,
         request_timeout: 30,
+        query_timeout: 5,
+        broadcast_timeout: 15,
         max_retries: 3,
         chain_id: INJECTIVE_TESTNET_CHAIN_ID.to_string(),
+        max_fee_inj: None,
+        slow_call_threshold_ms: 2000,
+        lcd_endpoint: "https://testnet.sentry.lcd.injective.network:443".to_string(),
+        grpc_compression: false,
+        socks5_proxy: None,
+        fee_granter: None,
+        gas_price_source: GasPriceSource::Static,
+        min_gas_price: DEFAULT_MIN_GAS_PRICE,
+        max_gas_price: DEFAULT_MAX_GAS_PRICE,
+        fee_denom: DEFAULT_FEE_DENOM.to_string(),
+        sequence_recovery: true,
+        allow_direct_signing: false,
+        height_source: None,
     };
 
     // Create and connect client
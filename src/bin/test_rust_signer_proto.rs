@@ -26,14 +26,8 @@ async fn main() -> Result<()> {
     let account_number = 1234u64;
     let sequence = 0u64;
     
-    // Test fee
-    let fee = Some(vec![gmine_miner::chain::Coin {
-        denom: "inj".to_string(),
-        amount: "100000000000000000".to_string(), // 0.1 INJ
-    }]);
-    
     // Try to sign (won't broadcast since we're using test account)
-    match signer.sign_and_broadcast_commit(&commitment, account_number, sequence, fee).await {
+    match signer.sign_and_broadcast_commit(&commitment, account_number, sequence).await {
         Ok(tx_hash) => {
             println!("✅ Transaction would be submitted with hash: {}", tx_hash);
         }
@@ -53,7 +47,7 @@ async fn main() -> Result<()> {
     let nonce = vec![1u8, 2, 3, 4, 5, 6, 7, 8];
     let digest = vec![1u8; 16];
     let salt = vec![1u8; 32];
-    match signer.sign_and_broadcast_reveal(nonce, digest, salt, account_number, sequence + 1, None).await {
+    match signer.sign_and_broadcast_reveal(nonce, digest, salt, account_number, sequence + 1).await {
         Ok(_) => println!("✅ Reveal message format OK"),
         Err(e) => {
             if e.to_string().contains("HTTP") || e.to_string().contains("broadcast") {
@@ -65,7 +59,7 @@ async fn main() -> Result<()> {
     }
     
     // Test claim
-    match signer.sign_and_broadcast_claim(account_number, sequence + 2, None).await {
+    match signer.sign_and_broadcast_claim(account_number, sequence + 2).await {
         Ok(_) => println!("✅ Claim message format OK"),
         Err(e) => {
             if e.to_string().contains("HTTP") || e.to_string().contains("broadcast") {
@@ -77,7 +71,7 @@ async fn main() -> Result<()> {
     }
     
     // Test advance_epoch
-    match signer.sign_and_broadcast_advance_epoch(account_number, sequence + 3, None).await {
+    match signer.sign_and_broadcast_advance_epoch(account_number, sequence + 3).await {
         Ok(_) => println!("✅ Advance epoch message format OK"),
         Err(e) => {
             if e.to_string().contains("HTTP") || e.to_string().contains("broadcast") {
@@ -89,7 +83,7 @@ async fn main() -> Result<()> {
     }
     
     // Test finalize_epoch
-    match signer.sign_and_broadcast_finalize_epoch(1, account_number, sequence + 4, None).await {
+    match signer.sign_and_broadcast_finalize_epoch(1, account_number, sequence + 4).await {
         Ok(_) => println!("✅ Finalize epoch message format OK"),
         Err(e) => {
             if e.to_string().contains("HTTP") || e.to_string().contains("broadcast") {
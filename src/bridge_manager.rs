@@ -11,6 +11,7 @@ pub struct BridgeManager {
     mnemonic: String,
     network: String,
     port: u16,
+    derivation_path: Option<String>,
 }
 
 impl BridgeManager {
@@ -24,8 +25,15 @@ impl BridgeManager {
             mnemonic,
             network,
             port: 8080,
+            derivation_path: None,
         })
     }
+    
+    /// Use a custom HD derivation path for the bridge's signing key
+    pub fn with_derivation_path(mut self, path: Option<String>) -> Self {
+        self.derivation_path = path;
+        self
+    }
 
     /// Find the Node.js bridge script in various locations
     fn find_bridge_executable() -> Result<PathBuf> {
@@ -102,6 +110,7 @@ impl BridgeManager {
                 .env("NETWORK", &self.network)
                 .env("PORT", self.port.to_string())
                 .env("BRIDGE_API_KEY", "gmine-internal-key")
+                .envs(self.derivation_path.iter().map(|p| ("HD_PATH", p)))
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
                 .spawn()?
@@ -113,6 +122,7 @@ impl BridgeManager {
                 .env("NETWORK", &self.network)
                 .env("PORT", self.port.to_string())
                 .env("BRIDGE_API_KEY", "gmine-internal-key")
+                .envs(self.derivation_path.iter().map(|p| ("HD_PATH", p)))
                 .current_dir("../gmine-bridge/bridge_nodejs")
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
@@ -0,0 +1,55 @@
+/// Injective address validation
+/// Catches typo'd addresses at the boundary instead of deep inside a transaction
+
+use anyhow::{Result, anyhow};
+use bech32::Hrp;
+
+const INJECTIVE_HRP: &str = "inj";
+/// Injective account addresses are 20-byte (Ethereum-style) hashes
+const ADDRESS_LEN: usize = 20;
+
+/// Verify the `inj` prefix, bech32 checksum and payload length of an address
+pub fn validate(address: &str) -> Result<()> {
+    let (hrp, data) = bech32::decode(address)
+        .map_err(|e| anyhow!("Invalid Injective address '{}': {}", address, e))?;
+
+    if hrp != Hrp::parse(INJECTIVE_HRP)? {
+        return Err(anyhow!(
+            "Invalid Injective address '{}': expected 'inj' prefix, got '{}'",
+            address, hrp
+        ));
+    }
+    if data.len() != ADDRESS_LEN {
+        return Err(anyhow!(
+            "Invalid Injective address '{}': expected {} bytes, got {}",
+            address, ADDRESS_LEN, data.len()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_addresses() {
+        assert!(validate("inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz").is_ok());
+        assert!(validate("inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        // Corrupted checksum (last character changed)
+        assert!(validate("inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dy").is_err());
+        // Single-character typo in the payload
+        assert!(validate("inj1npvwllfr9dqr8erajqqr6s0vxnk2ak56re90dz").is_err());
+        // Valid bech32 but wrong prefix
+        let err = validate("cosmos1npvwllfr9dqr8erajqqr6s0vxnk2ak55fsjtl6").unwrap_err();
+        assert!(err.to_string().contains("prefix"));
+        // Not bech32 at all
+        assert!(validate("").is_err());
+        assert!(validate("inj1short").is_err());
+    }
+}
@@ -4,26 +4,42 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use reqwest::Client;
 
+/// Fee the bridge pays per transaction, whatever the gas (0.0005 INJ)
+pub const BRIDGE_FEE: u128 = 500_000_000_000_000;
+
 #[derive(Debug, Serialize)]
 pub struct SignRequest {
     pub chain_id: String,
     pub account_number: u64,
     pub sequence: u64,
     pub messages: Vec<MessageData>,
+    /// Bank sends, signed after `messages` (omitted when there are none)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sends: Vec<BankSend>,
     pub gas_limit: u64,
     pub gas_price: String,
     pub memo: String,
     pub request_id: String,
+    /// Feegrant granter paying the fee (omitted when the signer pays)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_granter: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MessageData {
     pub contract: String,
     pub msg: serde_json::Value,
     pub funds: Vec<Coin>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A bank `MsgSend` from the signer's address
+#[derive(Debug, Clone, Serialize)]
+pub struct BankSend {
+    pub to_address: String,
+    pub amount: Vec<Coin>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Coin {
     pub denom: String,
     pub amount: String,
@@ -42,6 +58,10 @@ pub struct BridgeClient {
     client: Client,
     base_url: String,
     api_key: Option<String>,
+    fee_granter: Option<String>,
+    /// Gas price in base units of `fee_denom` per gas
+    gas_price: u128,
+    fee_denom: String,
 }
 
 impl BridgeClient {
@@ -55,9 +75,27 @@ impl BridgeClient {
             client,
             base_url,
             api_key,
+            fee_granter: None,
+            gas_price: crate::chain::client_real::GAS_PRICE,
+            fee_denom: crate::chain::DEFAULT_FEE_DENOM.to_string(),
         }
     }
 
+    /// Have a feegrant granter pay the fee for every transaction
+    pub fn set_fee_granter(&mut self, fee_granter: Option<String>) {
+        self.fee_granter = fee_granter;
+    }
+
+    /// Gas price sent with every sign request
+    pub fn set_gas_price(&mut self, gas_price: u128) {
+        self.gas_price = gas_price;
+    }
+
+    /// Denom the fee is paid in
+    pub fn set_fee_denom(&mut self, fee_denom: String) {
+        self.fee_denom = fee_denom;
+    }
+
     /// Check if the bridge service is healthy
     pub async fn health_check(&self) -> Result<bool> {
         let url = format!("{}/health", self.base_url);
@@ -78,6 +116,48 @@ impl BridgeClient {
         msg: serde_json::Value,
         funds: Vec<Coin>,
         gas_limit: u64,
+    ) -> Result<String> {
+        let message = MessageData {
+            contract: contract_address.to_string(),
+            msg,
+            funds,
+        };
+        self.sign_and_broadcast_messages(chain_id, account_number, sequence, vec![message], gas_limit).await
+    }
+
+    /// Sign and broadcast several contract messages as one transaction: they all
+    /// succeed or fail together
+    pub async fn sign_and_broadcast_messages(
+        &self,
+        chain_id: String,
+        account_number: u64,
+        sequence: u64,
+        messages: Vec<MessageData>,
+        gas_limit: u64,
+    ) -> Result<String> {
+        self.sign_and_broadcast_request(chain_id, account_number, sequence, messages, Vec::new(), gas_limit).await
+    }
+
+    /// Sign and broadcast a bank send of `send.amount` to `send.to_address`
+    pub async fn send_tokens(
+        &self,
+        chain_id: String,
+        account_number: u64,
+        sequence: u64,
+        send: BankSend,
+        gas_limit: u64,
+    ) -> Result<String> {
+        self.sign_and_broadcast_request(chain_id, account_number, sequence, Vec::new(), vec![send], gas_limit).await
+    }
+
+    async fn sign_and_broadcast_request(
+        &self,
+        chain_id: String,
+        account_number: u64,
+        sequence: u64,
+        messages: Vec<MessageData>,
+        sends: Vec<BankSend>,
+        gas_limit: u64,
     ) -> Result<String> {
         let request_id = uuid::Uuid::new_v4().to_string();
         
@@ -85,15 +165,13 @@ impl BridgeClient {
             chain_id,
             account_number,
             sequence,
-            messages: vec![MessageData {
-                contract: contract_address.to_string(),
-                msg,
-                funds,
-            }],
+            messages,
+            sends,
             gas_limit,
-            gas_price: "500000000inj".to_string(),
+            gas_price: format!("{}{}", self.gas_price, self.fee_denom),
             memo: String::new(),
             request_id: request_id.clone(),
+            fee_granter: self.fee_granter.clone(),
         };
 
         let url = format!("{}/sign-and-broadcast", self.base_url);
@@ -139,4 +217,40 @@ impl BridgeClient {
         
         Err(anyhow!("Bridge service failed to become healthy after {} attempts", max_attempts))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(sends: Vec<BankSend>) -> serde_json::Value {
+        serde_json::to_value(SignRequest {
+            chain_id: "injective-888".to_string(),
+            account_number: 1,
+            sequence: 2,
+            messages: Vec::new(),
+            sends,
+            gas_limit: 600_000,
+            gas_price: "500000000inj".to_string(),
+            memo: String::new(),
+            request_id: "r".to_string(),
+            fee_granter: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bank_sends_are_only_sent_when_present() {
+        // Older bridges never see the field unless a send is asked for
+        assert!(request(Vec::new()).get("sends").is_none());
+
+        let send = BankSend {
+            to_address: "inj1safe".to_string(),
+            amount: vec![Coin { denom: "inj".to_string(), amount: "1000".to_string() }],
+        };
+        assert_eq!(
+            request(vec![send])["sends"],
+            serde_json::json!([{"to_address": "inj1safe", "amount": [{"denom": "inj", "amount": "1000"}]}])
+        );
+    }
+}
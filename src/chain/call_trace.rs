@@ -0,0 +1,153 @@
+/// Recent chain calls for crash diagnostics
+/// The last few calls (method, parameters, outcome, timing) are kept in memory and
+/// written to a crash report on panic or when the failure breaker trips, so a wedged
+/// or crashed miner leaves a focused trace even without debug logging
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Calls kept by default
+pub const CALL_TRACE_CAPACITY: usize = 50;
+
+/// Longest parameter or error summary kept per call
+const MAX_SUMMARY_LEN: usize = 200;
+
+/// One chain call
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ChainCall {
+    /// RFC3339 timestamp of when the call finished
+    pub timestamp: String,
+    pub method: String,
+    /// Short summary of the request (contract, query type, address...)
+    pub params: String,
+    pub elapsed_ms: u64,
+    /// "ok", or the error
+    pub outcome: String,
+}
+
+impl ChainCall {
+    pub fn new(method: &str, params: &str, elapsed_ms: u64, error: Option<&str>) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            method: method.to_string(),
+            params: truncate(params),
+            elapsed_ms,
+            outcome: error.map_or_else(|| "ok".to_string(), |e| format!("error: {}", truncate(e))),
+        }
+    }
+}
+
+fn truncate(s: &str) -> String {
+    match s.char_indices().nth(MAX_SUMMARY_LEN) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_string(),
+    }
+}
+
+/// Written to the crash report file
+#[derive(Debug, Serialize)]
+struct CrashReport<'a> {
+    timestamp: String,
+    reason: &'a str,
+    calls: Vec<ChainCall>,
+}
+
+/// Ring buffer of the most recent chain calls, shared by clones
+#[derive(Debug, Clone)]
+pub struct CallTrace {
+    calls: Arc<Mutex<VecDeque<ChainCall>>>,
+    capacity: usize,
+    /// Where `dump` writes the crash report
+    report_path: PathBuf,
+}
+
+impl CallTrace {
+    pub fn new(report_path: impl Into<PathBuf>, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            calls: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+            report_path: report_path.into(),
+        }
+    }
+
+    pub fn report_path(&self) -> &Path {
+        &self.report_path
+    }
+
+    /// Add a call, dropping the oldest once full
+    pub fn record(&self, call: ChainCall) {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        if calls.len() == self.capacity {
+            calls.pop_front();
+        }
+        calls.push_back(call);
+    }
+
+    /// The kept calls, oldest first
+    pub fn calls(&self) -> Vec<ChainCall> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+
+    /// Write the kept calls to the crash report file, replacing an earlier report
+    pub fn dump(&self, reason: &str) -> Result<()> {
+        // A panic while the buffer was locked must not deadlock the panic hook
+        let calls = match self.calls.try_lock() {
+            Ok(calls) => calls.iter().cloned().collect(),
+            Err(std::sync::TryLockError::Poisoned(e)) => e.into_inner().iter().cloned().collect(),
+            Err(std::sync::TryLockError::WouldBlock) => Vec::new(),
+        };
+        let report = CrashReport { timestamp: chrono::Utc::now().to_rfc3339(), reason, calls };
+        std::fs::write(&self.report_path, serde_json::to_string_pretty(&report)?)?;
+        Ok(())
+    }
+
+    /// Dump the trace from a panic hook, ahead of the previously installed one
+    pub fn dump_on_panic(&self) {
+        let trace = self.clone();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            match trace.dump(&format!("panic: {}", info)) {
+                Ok(()) => eprintln!("Recent chain calls written to {}", trace.report_path.display()),
+                Err(e) => eprintln!("Failed to write crash report {}: {}", trace.report_path.display(), e),
+            }
+            previous(info);
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_most_recent_calls() {
+        let trace = CallTrace::new("unused.json", 3);
+        for i in 0..5 {
+            trace.record(ChainCall::new("query_contract_smart", &format!("epoch_info #{}", i), i, None));
+        }
+        let calls = trace.calls();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].params, "epoch_info #2");
+        assert_eq!(calls[2].params, "epoch_info #4");
+
+        let failed = ChainCall::new("broadcast_tx", &"x".repeat(500), 12, Some("unavailable"));
+        assert_eq!(failed.outcome, "error: unavailable");
+        assert_eq!(failed.params.len(), MAX_SUMMARY_LEN + 3);
+    }
+
+    #[test]
+    fn test_dump() {
+        let path = std::env::temp_dir().join(format!("gmine_call_trace_{}.json", std::process::id()));
+        let trace = CallTrace::new(&path, CALL_TRACE_CAPACITY);
+        trace.record(ChainCall::new("get_latest_block_height", "", 8, None));
+        trace.dump("failure breaker tripped").unwrap();
+
+        let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(report["reason"], "failure breaker tripped");
+        assert_eq!(report["calls"][0]["method"], "get_latest_block_height");
+        std::fs::remove_file(&path).ok();
+    }
+}
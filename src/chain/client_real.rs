@@ -1,22 +1,32 @@
 use anyhow::{Result, anyhow};
 use tonic::transport::{Channel, Endpoint};
 use tonic::Code;
+use tonic::codec::CompressionEncoding;
 use std::time::Duration;
 use serde_json::Value;
+use std::collections::BTreeSet;
 use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tracing::Instrument;
 
 use crate::chain::proto::{
     self,
     Coin, AuthQueryClient, QueryAccountRequest,
-    ServiceClient, SimulateRequest, BroadcastTxRequest, BroadcastMode,
+    ServiceClient, SimulateRequest, BroadcastTxRequest, BroadcastMode, GetTxRequest,
     BankQueryClient, QueryBalanceRequest,
     TendermintServiceClient, GetNodeInfoRequest, GetLatestBlockRequest
 };
 use crate::chain::wallet::InjectiveWallet;
 use crate::chain::tx_builder::ProperTxBuilder;
 use crate::chain::account_types::{Account, AccountInfo};
-use crate::chain::bridge_client::BridgeClient;
-use crate::chain::rust_signer::RustSigner;
+use crate::chain::bridge_client::{BankSend, BridgeClient, MessageData};
+use crate::chain::rust_signer::{PreparedTx, RustSigner};
+use crate::chain::messages::{ExecuteMsg, MessageBuilder};
+use crate::chain::tx_log::{TxLog, TxLogEntry};
+use crate::chain::call_trace::{CallTrace, ChainCall};
+use crate::chain::socks::Socks5Proxy;
+use crate::chain::gas_price::{self, GasPriceSource, DEFAULT_FEE_DENOM, DEFAULT_MAX_GAS_PRICE, DEFAULT_MIN_GAS_PRICE};
+use crate::chain::height_source::{HeightSource, parse_lcd_block_height};
 
 /// Configuration for the Injective gRPC client
 #[derive(Debug, Clone)]
@@ -25,12 +35,49 @@ pub struct ClientConfig {
     pub grpc_endpoint: String,
     /// Connection timeout in seconds
     pub connection_timeout: u64,
-    /// Request timeout in seconds
+    /// Request timeout in seconds (channel-wide upper bound)
     pub request_timeout: u64,
+    /// Timeout for read-only queries in seconds
+    /// Kept short so reveal-path queries fail fast and retry instead of eating the window
+    pub query_timeout: u64,
+    /// Timeout for simulate/broadcast calls in seconds
+    pub broadcast_timeout: u64,
     /// Maximum retry attempts
     pub max_retries: u32,
     /// Chain ID (e.g., "injective-888" for testnet)
     pub chain_id: String,
+    /// Refuse to broadcast any transaction whose fee (gas limit × gas price) exceeds
+    /// this many INJ. Guards against gas escalation draining the wallet. None = no cap
+    pub max_fee_inj: Option<f64>,
+    /// gRPC calls slower than this (milliseconds) are logged as warnings
+    pub slow_call_threshold_ms: u64,
+    /// LCD (REST) endpoint, used for the block height when gRPC doesn't report it
+    pub lcd_endpoint: String,
+    /// Gzip-compress gRPC requests and accept compressed responses (saves bandwidth on
+    /// metered connections). Switched off automatically if the server rejects it
+    pub grpc_compression: bool,
+    /// Route gRPC and LCD traffic through this SOCKS5 proxy (e.g. Tor). None = direct
+    pub socks5_proxy: Option<Socks5Proxy>,
+    /// Address whose feegrant allowance pays gas for this wallet's transactions
+    /// (sponsored/pooled mining). None = the wallet pays its own gas
+    pub fee_granter: Option<String>,
+    /// Where the gas price comes from. Default: the static `GAS_PRICE`
+    pub gas_price_source: GasPriceSource,
+    /// Bounds applied to fetched gas prices (base units of the fee denom per gas), so
+    /// a bad node or oracle can't push fees to absurd levels
+    pub min_gas_price: u128,
+    pub max_gas_price: u128,
+    /// Denom fees are paid in (`inj`, or e.g. a `peggy0x...` token the chain accepts for gas)
+    pub fee_denom: String,
+    /// On an "account sequence mismatch, expected N" rejection, resubmit straight away
+    /// at N instead of re-querying the account (a lagging node can return the same stale value)
+    pub sequence_recovery: bool,
+    /// Sign with plain SIGN_MODE_DIRECT when neither the Rust signer nor the bridge is
+    /// set up. Off by default: such transactions are accepted but don't earn rewards
+    pub allow_direct_signing: bool,
+    /// Read block heights from this endpoint (e.g. a local node) rather than the main
+    /// one, falling back to the main endpoint when it fails. None = main endpoint only
+    pub height_source: Option<HeightSource>,
 }
 
 impl Default for ClientConfig {
@@ -39,12 +86,249 @@ impl Default for ClientConfig {
             grpc_endpoint: "https://testnet.sentry.chain.grpc.injective.network:443".to_string(),
             connection_timeout: 10,
             request_timeout: 30,
+            query_timeout: 5,
+            broadcast_timeout: 15,
             max_retries: 3,
             chain_id: "injective-888".to_string(),
+            max_fee_inj: None,
+            slow_call_threshold_ms: DEFAULT_SLOW_CALL_THRESHOLD_MS,
+            lcd_endpoint: TESTNET_LCD_ENDPOINT.to_string(),
+            grpc_compression: false,
+            socks5_proxy: None,
+            fee_granter: None,
+            gas_price_source: GasPriceSource::Static,
+            min_gas_price: DEFAULT_MIN_GAS_PRICE,
+            max_gas_price: DEFAULT_MAX_GAS_PRICE,
+            fee_denom: DEFAULT_FEE_DENOM.to_string(),
+            sequence_recovery: true,
+            allow_direct_signing: false,
+            height_source: None,
         }
     }
 }
 
+/// Public LCD (REST) endpoints
+pub const TESTNET_LCD_ENDPOINT: &str = "https://testnet.sentry.lcd.injective.network:443";
+pub const MAINNET_LCD_ENDPOINT: &str = "https://sentry.lcd.injective.network:443";
+
+/// Default threshold for slow gRPC call warnings - a few of these eat a reveal window
+pub const DEFAULT_SLOW_CALL_THRESHOLD_MS: u64 = 2000;
+
+/// Gas price used when building transactions (base units of INJ per gas)
+pub const GAS_PRICE: u128 = 500_000_000;
+
+/// Fee in base units (1e-18 INJ) for a transaction with this gas limit at the static price
+pub fn fee_for_gas(gas_limit: u64) -> u128 {
+    fee_for_gas_at(gas_limit, GAS_PRICE)
+}
+
+/// Fee in base units (1e-18 INJ) for a transaction with this gas limit and price
+pub fn fee_for_gas_at(gas_limit: u64, gas_price: u128) -> u128 {
+    gas_limit as u128 * gas_price
+}
+
+/// Cosmos transaction hash: uppercase hex SHA-256 of the encoded tx bytes
+pub fn tx_hash(tx_bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode_upper(Sha256::digest(tx_bytes))
+}
+
+/// Whether a gRPC error means the server doesn't support compressed messages
+fn is_compression_rejected(error_msg: &str) -> bool {
+    let msg = error_msg.to_lowercase();
+    msg.contains("compressed with") || msg.contains("grpc-encoding") || msg.contains("compression")
+}
+
+/// Apply the client's gzip setting to a generated gRPC client
+macro_rules! with_compression {
+    ($self:expr, $client:expr) => {{
+        let client = $client;
+        match $self.compression() {
+            Some(encoding) => client.send_compressed(encoding).accept_compressed(encoding),
+            None => client,
+        }
+    }};
+}
+
+/// How the client signs transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningMethod {
+    /// Rust-native EIP-712 signer
+    RustSigner,
+    /// EIP-712 via the Node.js bridge service
+    Bridge,
+    /// Plain Cosmos SIGN_MODE_DIRECT - accepted by the chain, but doesn't earn rewards
+    Direct,
+}
+
+impl std::fmt::Display for SigningMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SigningMethod::RustSigner => write!(f, "Rust-native EIP-712 signer"),
+            SigningMethod::Bridge => write!(f, "EIP-712 bridge"),
+            SigningMethod::Direct => write!(f, "SIGN_MODE_DIRECT"),
+        }
+    }
+}
+
+/// A message for the `execute_*` methods: a typed mining contract message, or raw
+/// JSON for another contract (e.g. a CW20 transfer on the POWER token)
+enum ContractMsg {
+    Execute(ExecuteMsg),
+    Json(Value),
+}
+
+impl ContractMsg {
+    fn to_json(&self) -> Result<Value> {
+        match self {
+            ContractMsg::Execute(msg) => msg.to_json(),
+            ContractMsg::Json(msg) => Ok(msg.clone()),
+        }
+    }
+    
+    /// The message for the Rust signer, which only signs mining contract messages
+    fn execute_msg(&self) -> Result<&ExecuteMsg> {
+        match self {
+            ContractMsg::Execute(msg) => Ok(msg),
+            ContractMsg::Json(msg) => Err(anyhow!("The Rust signer only signs mining contract messages, not {}", msg)),
+        }
+    }
+}
+
+impl std::fmt::Display for ContractMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContractMsg::Execute(msg) => write!(f, "{:?}", msg),
+            ContractMsg::Json(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+/// The one signing method the configured signers leave. Two signers at once, a Rust
+/// signer that was switched on but never set up, or falling through to
+/// SIGN_MODE_DIRECT without `allow_direct` are configuration errors
+fn select_signing_method(use_rust_signer: bool, has_rust_signer: bool, has_bridge: bool, allow_direct: bool) -> Result<SigningMethod> {
+    match (use_rust_signer, has_rust_signer, has_bridge) {
+        (true, false, _) => Err(anyhow!("Rust signer enabled but not initialized")),
+        (true, true, true) => Err(anyhow!(
+            "Both the Rust signer and the EIP-712 bridge are configured - set up exactly one"
+        )),
+        (true, true, false) => Ok(SigningMethod::RustSigner),
+        (false, _, true) => Ok(SigningMethod::Bridge),
+        (false, _, false) if allow_direct => Ok(SigningMethod::Direct),
+        (false, _, false) => Err(anyhow!(
+            "No EIP-712 signer configured (Rust signer or bridge). Transactions would fall back to \
+             SIGN_MODE_DIRECT, which the chain accepts but which doesn't earn mining rewards - \
+             refusing to sign without allow_direct_signing"
+        )),
+    }
+}
+
+/// Error if the fee for `gas_limit` would exceed `max_fee_inj`
+pub fn check_fee_cap(gas_limit: u64, gas_price: u128, max_fee_inj: Option<f64>) -> Result<()> {
+    let Some(max_fee_inj) = max_fee_inj else {
+        return Ok(());
+    };
+    let fee = fee_for_gas_at(gas_limit, gas_price);
+    let cap = (max_fee_inj * 1e18) as u128;
+    if fee > cap {
+        return Err(anyhow!(
+            "Fee cap exceeded: gas limit {} costs {:.6} INJ, max fee is {} INJ - refusing to broadcast",
+            gas_limit, fee as f64 / 1e18, max_fee_inj
+        ));
+    }
+    Ok(())
+}
+
+/// Local account sequence tracking for back-to-back submissions
+/// The node's account index can lag a just-broadcast tx, so a fresh query may still
+/// return the pre-commit sequence. We remember the next sequence we expect and use
+/// whichever of ours or the chain's is higher, skipping sequences other broadcasts
+/// (e.g. from clones of the client) are still using.
+#[derive(Debug, Default)]
+struct SequenceTracker {
+    /// Next sequence we expect the chain to accept
+    next: Option<u64>,
+    /// Sequences handed out to transactions whose broadcast hasn't finished yet
+    in_flight: BTreeSet<u64>,
+}
+
+impl SequenceTracker {
+    /// Pick the sequence to sign with given the chain's reported value, and mark it in flight
+    fn resolve(&mut self, chain_sequence: u64) -> u64 {
+        let sequence = self.peek(chain_sequence);
+        self.in_flight.insert(sequence);
+        sequence
+    }
+    
+    /// The sequence `resolve` would pick, without marking it in flight
+    fn peek(&self, chain_sequence: u64) -> u64 {
+        let mut sequence = self.next.map_or(chain_sequence, |next| next.max(chain_sequence));
+        while self.in_flight.contains(&sequence) {
+            sequence += 1;
+        }
+        sequence
+    }
+    
+    /// The transaction signed at `sequence` was accepted - advance locally
+    fn confirm(&mut self, sequence: u64) {
+        self.in_flight.remove(&sequence);
+        self.next = Some(self.next.map_or(sequence + 1, |next| next.max(sequence + 1)));
+    }
+    
+    /// The chain rejected `sequence` and told us what it expects; anything in flight
+    /// below that has been used up already
+    fn reconcile(&mut self, sequence: u64, expected: u64) {
+        self.in_flight.remove(&sequence);
+        self.in_flight.retain(|&s| s >= expected);
+        self.next = Some(expected);
+    }
+    
+    /// The transaction signed at `sequence` failed in a way that doesn't tell whether
+    /// it used the sequence up. `next` only holds sequences the chain has confirmed,
+    /// so the next query's value decides
+    fn release(&mut self, sequence: u64) {
+        self.in_flight.remove(&sequence);
+    }
+    
+    /// A sequence from `resolve` wasn't signed with after all
+    fn unused(&mut self, sequence: u64) {
+        self.in_flight.remove(&sequence);
+    }
+    
+    /// Forget local state and trust the chain on the next query
+    fn reset(&mut self) {
+        self.in_flight.clear();
+        self.next = None;
+    }
+}
+
+/// Connection counters, shared by clones of the client
+#[derive(Debug, Default)]
+struct ConnectionCounters {
+    /// Unix timestamp of the last successful gRPC call (0 = never)
+    last_success: AtomicU64,
+    /// gRPC calls that failed (after retries)
+    errors: AtomicU64,
+    /// Successful `connect` calls
+    connects: AtomicU64,
+}
+
+/// Network-level health snapshot for the status endpoint
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ClientMetrics {
+    pub endpoint: String,
+    pub connected: bool,
+    /// Seconds since the last successful gRPC call (None if never)
+    pub last_success_age_secs: Option<u64>,
+    pub grpc_errors: u64,
+    /// Connections made after the first one
+    pub reconnects: u64,
+    /// Next account sequence we expect to sign with (None until the first broadcast)
+    pub sequence: Option<u64>,
+    pub proxied: bool,
+}
+
 /// gRPC client for interacting with Injective blockchain
 #[derive(Clone)]
 pub struct InjectiveClient {
@@ -54,8 +338,22 @@ pub struct InjectiveClient {
     bridge_client: Option<BridgeClient>,
     rust_signer: Option<RustSigner>,
     use_rust_signer: bool,
-    // No longer tracking sequence locally - always fetch fresh from chain
-    sequence_tracker: Arc<RwLock<Option<u64>>>, // Kept for compatibility
+    /// Next sequence and the ones in flight, shared so clones of the client see each
+    /// other's broadcasts
+    sequence_tracker: Arc<RwLock<SequenceTracker>>,
+    /// Optional append-only audit log of broadcast transactions
+    tx_log: Option<TxLog>,
+    /// Optional ring buffer of recent calls, for crash reports
+    call_trace: Option<CallTrace>,
+    /// Hash of the last broadcast whose outcome is unknown (may still be in the mempool)
+    last_broadcast_hash: Arc<std::sync::Mutex<Option<String>>>,
+    /// Set once the server has rejected compressed requests
+    compression_rejected: Arc<AtomicBool>,
+    counters: Arc<ConnectionCounters>,
+    /// Gas price for new transactions (base units of INJ per gas)
+    gas_price: u128,
+    /// HTTP client for the dedicated block-height source, reused across polls
+    height_client: reqwest::Client,
 }
 
 impl InjectiveClient {
@@ -68,22 +366,104 @@ impl InjectiveClient {
             bridge_client: None,
             rust_signer: None,
             use_rust_signer: false,
-            sequence_tracker: Arc::new(RwLock::new(None)),
+            sequence_tracker: Arc::new(RwLock::new(SequenceTracker::default())),
+            tx_log: None,
+            call_trace: None,
+            last_broadcast_hash: Arc::new(std::sync::Mutex::new(None)),
+            compression_rejected: Arc::new(AtomicBool::new(false)),
+            counters: Arc::new(ConnectionCounters::default()),
+            gas_price: GAS_PRICE,
+            height_client: reqwest::Client::new(),
         }
     }
     
+    /// Create a query-only client that needs no mnemonic
+    /// Backed by an ephemeral key, so any attempt to execute a transaction will fail
+    /// for lack of an on-chain account
+    pub fn new_read_only(config: ClientConfig) -> Result<Self> {
+        Ok(Self::new(config, InjectiveWallet::ephemeral()?))
+    }
+    
     /// Create a new client with default testnet configuration
     pub fn new_testnet(wallet: InjectiveWallet) -> Self {
         Self::new(ClientConfig::default(), wallet)
     }
 
+    /// Address of the wallet this client signs for
+    pub fn address(&self) -> &str {
+        &self.wallet.address
+    }
+    
+    /// Compression to use for gRPC calls, if enabled and not rejected by the server
+    fn compression(&self) -> Option<CompressionEncoding> {
+        if self.config.grpc_compression && !self.compression_rejected.load(Ordering::Relaxed) {
+            Some(CompressionEncoding::Gzip)
+        } else {
+            None
+        }
+    }
+    
+    /// Take the hash of the last broadcast that may have reached the mempool without
+    /// a definitive result (e.g. the call timed out), so a retry can check on it first
+    pub fn take_last_broadcast_hash(&self) -> Option<String> {
+        let signer_hash = self.rust_signer.as_ref().and_then(|s| s.take_last_tx_hash());
+        let direct_hash = self.last_broadcast_hash.lock().unwrap_or_else(|e| e.into_inner()).take();
+        signer_hash.or(direct_hash)
+    }
+    
     /// Set the bridge client for EIP-712 signing
-    pub fn set_bridge_client(&mut self, bridge_client: BridgeClient) {
+    pub fn set_bridge_client(&mut self, mut bridge_client: BridgeClient) {
+        bridge_client.set_fee_granter(self.config.fee_granter.clone());
+        bridge_client.set_gas_price(self.gas_price);
+        bridge_client.set_fee_denom(self.config.fee_denom.clone());
         self.bridge_client = Some(bridge_client);
     }
     
+    /// The signing method transactions will use; errors unless exactly one is usable.
+    /// Checked at startup so a miner that can never earn doesn't run
+    pub fn signing_method(&self) -> Result<SigningMethod> {
+        select_signing_method(
+            self.use_rust_signer,
+            self.rust_signer.is_some(),
+            self.bridge_client.is_some(),
+            self.config.allow_direct_signing,
+        )
+    }
+    
+    /// Record every broadcast transaction to an append-only JSONL file
+    pub fn set_tx_log(&mut self, tx_log: TxLog) {
+        log::info!("Transaction audit log: {}", tx_log.path().display());
+        self.tx_log = Some(tx_log);
+    }
+    
+    /// Keep the most recent calls in memory for crash reports
+    pub fn set_call_trace(&mut self, call_trace: CallTrace) {
+        log::info!("Crash report (recent chain calls): {}", call_trace.report_path().display());
+        self.call_trace = Some(call_trace);
+    }
+    
+    pub fn call_trace(&self) -> Option<&CallTrace> {
+        self.call_trace.as_ref()
+    }
+    
+    /// Append the outcome of a contract execution to the audit log (if enabled)
+    fn record_tx(&self, msg: &Value, gas_limit: u64, result: &Result<String>) {
+        if let Some(tx_log) = &self.tx_log {
+            let fee = format!("{}{}", fee_for_gas_at(gas_limit, self.gas_price), self.config.fee_denom);
+            let entry = TxLogEntry::from_result(msg, gas_limit, fee, result);
+            if let Err(e) = tx_log.append(&entry) {
+                log::warn!("Failed to write transaction log: {}", e);
+            }
+        }
+    }
+    
     /// Enable Rust-native EIP-712 signing
     pub fn enable_rust_signer(&mut self, mnemonic: &str, contract_address: &str) -> Result<()> {
+        self.enable_rust_signer_with_path(mnemonic, crate::chain::wallet::INJECTIVE_HD_PATH, contract_address)
+    }
+    
+    /// Enable Rust-native EIP-712 signing with a custom HD derivation path
+    pub fn enable_rust_signer_with_path(&mut self, mnemonic: &str, derivation_path: &str, contract_address: &str) -> Result<()> {
         // Convert chain ID to network name for the mobile signer
         let network = if self.config.chain_id == "injective-888" {
             "testnet"
@@ -93,17 +473,38 @@ impl InjectiveClient {
             return Err(anyhow!("Unknown chain ID: {}", self.config.chain_id));
         };
         
-        let rust_signer = RustSigner::new(
+        let mut rust_signer = RustSigner::new_with_path(
             mnemonic,
+            derivation_path,
             network,
             contract_address
         )?;
+        if let Some(ref proxy) = self.config.socks5_proxy {
+            rust_signer.set_proxy(Some(proxy.url()));
+        }
+        // Broadcast to the same LCD the client queries (matters for devnets and private nodes)
+        rust_signer.set_rest_url(Some(self.config.lcd_endpoint.clone()));
+        rust_signer.set_fee_granter(self.config.fee_granter.clone())?;
+        rust_signer.set_fee_denom(self.config.fee_denom.clone());
+        if rust_signer.address() != self.wallet.address {
+            return Err(anyhow!("Rust signer address {} does not match wallet address {}", 
+                rust_signer.address(), self.wallet.address));
+        }
         self.rust_signer = Some(rust_signer);
         self.use_rust_signer = true;
         log::info!("Enabled Rust-native EIP-712 signer");
         Ok(())
     }
     
+    /// Restrict which contract messages the Rust signer will sign
+    pub fn set_signer_allowed_messages<S: AsRef<str>>(&mut self, messages: &[S]) -> Result<()> {
+        let rust_signer = self.rust_signer.as_mut()
+            .ok_or_else(|| anyhow!("Rust signer must be enabled before setting its allowed messages"))?;
+        rust_signer.set_allowed_messages(messages)?;
+        log::info!("Rust signer allowed messages: {}", rust_signer.allowed_messages().join(", "));
+        Ok(())
+    }
+    
     /// Connect to the gRPC endpoint
     pub async fn connect(&mut self) -> Result<()> {
         log::info!("Connecting to Injective at {}", self.config.grpc_endpoint);
@@ -114,8 +515,15 @@ impl InjectiveClient {
             .timeout(Duration::from_secs(self.config.request_timeout))
             .connect_timeout(Duration::from_secs(self.config.connection_timeout));
         
-        let channel = endpoint.connect().await?;
+        let channel = match self.config.socks5_proxy {
+            Some(ref proxy) => {
+                log::info!("Routing gRPC through SOCKS5 proxy {}", proxy.addr());
+                endpoint.connect_with_connector(proxy.connector()).await?
+            }
+            None => endpoint.connect().await?,
+        };
         self.channel = Some(channel);
+        self.counters.connects.fetch_add(1, Ordering::Relaxed);
         
         log::info!("Connected to Injective blockchain");
         Ok(())
@@ -126,6 +534,38 @@ impl InjectiveClient {
         self.channel.is_some()
     }
     
+    /// Snapshot of the connection counters
+    pub fn metrics(&self) -> ClientMetrics {
+        let last_success = self.counters.last_success.load(Ordering::Relaxed);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        ClientMetrics {
+            endpoint: self.config.grpc_endpoint.clone(),
+            connected: self.is_connected(),
+            last_success_age_secs: (last_success > 0).then(|| now.saturating_sub(last_success)),
+            grpc_errors: self.counters.errors.load(Ordering::Relaxed),
+            reconnects: self.counters.connects.load(Ordering::Relaxed).saturating_sub(1),
+            sequence: self.sequence_tracker.read().ok().and_then(|tracker| tracker.next),
+            proxied: self.config.socks5_proxy.is_some(),
+        }
+    }
+    
+    /// Wrap a read-only query message with the (short) query timeout
+    fn query_request<T>(&self, msg: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(msg);
+        request.set_timeout(Duration::from_secs(self.config.query_timeout));
+        request
+    }
+    
+    /// Wrap a simulate/broadcast message with the broadcast timeout
+    fn broadcast_request<T>(&self, msg: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(msg);
+        request.set_timeout(Duration::from_secs(self.config.broadcast_timeout));
+        request
+    }
+    
     /// Get the channel for making gRPC calls
     fn channel(&self) -> Result<Channel> {
         self.channel
@@ -133,21 +573,57 @@ impl InjectiveClient {
             .ok_or_else(|| anyhow!("Client not connected. Call connect() first."))
     }
     
-    /// Get the next sequence number, always fetching fresh from chain
-    /// This avoids race conditions and sequence drift issues
-    async fn get_next_sequence(&self, address: &str) -> Result<u64> {
-        // Always query fresh sequence from chain to avoid drift and race conditions
+    /// Get the account info and the next sequence number from a single account query
+    /// The sequence is fresh from chain, but never goes below the locally tracked value so a
+    /// lagging node index can't hand back the sequence of a tx we just broadcast
+    async fn account_and_sequence(&self, address: &str) -> Result<(AccountInfo, u64)> {
         let account_info = self.query_account(address).await?;
-        let sequence = account_info.sequence;
+        let chain_sequence = account_info.sequence;
+        
+        let sequence = match self.sequence_tracker.write() {
+            Ok(mut tracker) => tracker.resolve(chain_sequence),
+            Err(_) => chain_sequence,
+        };
         
-        log::info!("Fetched current sequence from chain: {}", sequence);
-        Ok(sequence)
+        if sequence != chain_sequence {
+            log::info!("Using locally tracked sequence {} (chain reports {})", sequence, chain_sequence);
+        } else {
+            log::info!("Fetched current sequence from chain: {}", sequence);
+        }
+        Ok((account_info, sequence))
     }
     
-    /// Reset sequence tracking - No longer needed since we always fetch fresh
-    /// Kept for compatibility but does nothing
+    /// Reset sequence tracking so the next transaction trusts the chain's value
     fn reset_sequence_tracking(&self) {
-        log::debug!("reset_sequence_tracking called (no-op - always fetching fresh)");
+        if let Ok(mut tracker) = self.sequence_tracker.write() {
+            tracker.reset();
+        }
+    }
+    
+    /// Settle the broadcast signed at `sequence` from its outcome
+    fn finish_sequence(&self, sequence: u64, result: &Result<String>) {
+        let error = result.as_ref().err().map(|e| e.to_string());
+        self.settle_sequence(sequence, error.as_deref());
+    }
+    
+    /// Settle the broadcast signed at `sequence`: accepted (no `error`), rejected with
+    /// the sequence the chain expects, or failed some other way
+    fn settle_sequence(&self, sequence: u64, error: Option<&str>) {
+        let mut tracker = match self.sequence_tracker.write() {
+            Ok(tracker) => tracker,
+            Err(_) => return,
+        };
+        match error {
+            None => tracker.confirm(sequence),
+            Some(error) => match self.parse_sequence_error(error) {
+                Some(expected) => {
+                    log::info!("Reconciling local sequence to chain's expected {}", expected);
+                    tracker.reconcile(sequence, expected);
+                }
+                // Unknown failure - we can't tell if the sequence was consumed
+                None => tracker.release(sequence),
+            },
+        }
     }
     
     /// Parse sequence error and extract expected sequence number
@@ -165,13 +641,57 @@ impl InjectiveClient {
         None
     }
     
+    /// Log the sequence the chain expects when a signed tx was rejected for a stale one
+    fn log_sequence_error(&self, result: &Result<String>) {
+        if let Err(e) = result {
+            let error_msg = e.to_string();
+            if error_msg.contains("account sequence") || 
+               error_msg.contains("expected") && error_msg.contains("got") {
+                log::warn!("Sequence mismatch detected: {}", error_msg);
+                
+                // Just log the sequence error - we'll fetch fresh on next attempt
+                if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
+                    log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
+                }
+            }
+        }
+    }
+    
+    /// The sequence to resubmit at after the broadcast at `failed` was rejected with a
+    /// sequence mismatch: the one the chain expects (or the next one not in flight),
+    /// marked in flight so the resubmission is tracked like any other broadcast.
+    /// None if recovery is disabled or `error_msg` isn't a sequence mismatch; `failed`
+    /// is left for the caller to settle then
+    fn recover_sequence(&self, failed: u64, error_msg: &str) -> Option<u64> {
+        if !self.config.sequence_recovery || !error_msg.contains("sequence") {
+            return None;
+        }
+        let expected = self.parse_sequence_error(error_msg)?;
+        let sequence = match self.sequence_tracker.write() {
+            Ok(mut tracker) => {
+                tracker.reconcile(failed, expected);
+                tracker.resolve(expected)
+            }
+            Err(_) => expected,
+        };
+        log::info!("Resubmitting at sequence {} (chain expects {}) instead of re-querying", sequence, expected);
+        Some(sequence)
+    }
+    
+    /// `recover_sequence` for a failed broadcast
+    fn recover_sequence_from(&self, failed: u64, result: &Result<String>) -> Option<u64> {
+        result.as_ref().err().and_then(|e| self.recover_sequence(failed, &e.to_string()))
+    }
+    
     /// Query account information - REAL IMPLEMENTATION with polymorphic account support
     /// Returns default account info (sequence=0, account_number=0) for new accounts
     pub async fn query_account(&self, address: &str) -> Result<AccountInfo> {
-        let response = self.with_retry(|| async {
+        crate::chain::address::validate(address)?;
+        
+        let response = self.with_retry("query_account", address, || async {
             let channel = self.channel()?;
-            let mut client = AuthQueryClient::new(channel);
-            let request = tonic::Request::new(QueryAccountRequest {
+            let mut client = with_compression!(self, AuthQueryClient::new(channel));
+            let request = self.query_request(QueryAccountRequest {
                 address: address.to_string(),
             });
             client.account(request).await
@@ -231,11 +751,11 @@ impl InjectiveClient {
     
     /// Simulate a transaction - REAL IMPLEMENTATION
     pub async fn simulate_tx(&self, tx_bytes: Vec<u8>) -> Result<SimulateResponse> {
-        let response = self.with_retry(|| async {
+        let response = self.with_retry("simulate_tx", &format!("{} bytes", tx_bytes.len()), || async {
             let channel = self.channel()?;
-            let mut client = ServiceClient::new(channel);
+            let mut client = with_compression!(self, ServiceClient::new(channel));
             let tx_bytes = tx_bytes.clone();
-            let request = tonic::Request::new(SimulateRequest {
+            let request = self.broadcast_request(SimulateRequest {
                 tx: None,  // Deprecated field
                 tx_bytes,
             });
@@ -256,11 +776,14 @@ impl InjectiveClient {
     /// Broadcast a transaction - REAL IMPLEMENTATION
     pub async fn broadcast_tx(&self, tx_bytes: Vec<u8>) -> Result<BroadcastResponse> {
         log::info!("broadcast_tx called with {} bytes", tx_bytes.len());
-        let response = self.with_retry(|| async {
+        // Remember the hash until the node gives a definitive answer
+        let hash = tx_hash(&tx_bytes);
+        *self.last_broadcast_hash.lock().unwrap_or_else(|e| e.into_inner()) = Some(hash.clone());
+        let response = self.with_retry("broadcast_tx", &hash, || async {
             let channel = self.channel()?;
-            let mut client = ServiceClient::new(channel);
+            let mut client = with_compression!(self, ServiceClient::new(channel));
             let tx_bytes = tx_bytes.clone();
-            let request = tonic::Request::new(BroadcastTxRequest {
+            let request = self.broadcast_request(BroadcastTxRequest {
                 tx_bytes,
                 mode: BroadcastMode::Sync as i32,
             });
@@ -270,6 +793,10 @@ impl InjectiveClient {
         
         let tx_response = response.into_inner().tx_response
             .ok_or_else(|| anyhow!("No tx response in broadcast response"))?;
+        if tx_response.code != 0 {
+            // Rejected by CheckTx - it never entered the mempool
+            self.last_broadcast_hash.lock().unwrap_or_else(|e| e.into_inner()).take();
+        }
         
         Ok(BroadcastResponse {
             tx_hash: tx_response.txhash,
@@ -279,201 +806,128 @@ impl InjectiveClient {
     }
     
     
+    /// Fetch a committed transaction by hash (including its events)
+    /// Fails with NotFound until the tx has been included in a block
+    pub async fn get_tx(&self, tx_hash: &str) -> Result<proto::TxResponse> {
+        self.traced("get_tx", tx_hash, async {
+            let channel = self.channel()?;
+            let mut client = with_compression!(self, ServiceClient::new(channel));
+            let request = self.query_request(GetTxRequest {
+                hash: tx_hash.to_string(),
+            });
+            let response = client.get_tx(request).await
+                .map_err(|e| anyhow!("Failed to get tx {}: {}", tx_hash, e))?;
+            response.into_inner().tx_response
+                .ok_or_else(|| anyhow!("No tx response for {}", tx_hash))
+        }).await
+    }
+    
+    /// Look up a transaction by hash, returning None while it isn't in a block yet
+    /// (still in the mempool, or never accepted)
+    pub async fn find_tx(&self, tx_hash: &str) -> Result<Option<proto::TxResponse>> {
+        self.traced("get_tx", tx_hash, async {
+            let channel = self.channel()?;
+            let mut client = with_compression!(self, ServiceClient::new(channel));
+            let request = self.query_request(GetTxRequest {
+                hash: tx_hash.to_string(),
+            });
+            match client.get_tx(request).await {
+                Ok(response) => Ok(response.into_inner().tx_response),
+                Err(e) if e.code() == Code::NotFound => Ok(None),
+                Err(e) => Err(anyhow!("Failed to get tx {}: {}", tx_hash, e)),
+            }
+        }).await
+    }
+    
+    
     /// Execute a contract message on the Injective blockchain - REAL IMPLEMENTATION
     /// Now includes automatic retry on sequence errors and EIP-712 bridge support
+    /// Raw JSON is for contracts other than the mining contract (e.g. a CW20 transfer):
+    /// mining contract messages go through `execute_message`
     pub async fn execute_contract(
         &mut self,
         contract_address: &str,
         msg: Value,
         funds: Vec<Coin>,
         gas_limit: u64,
+    ) -> Result<String> {
+        self.execute(contract_address, ContractMsg::Json(msg), funds, gas_limit).await
+    }
+    
+    /// Execute a typed contract message (see `execute_contract`)
+    pub async fn execute_message(
+        &mut self,
+        contract_address: &str,
+        msg: &dyn MessageBuilder,
+        funds: Vec<Coin>,
+        gas_limit: u64,
+    ) -> Result<String> {
+        self.execute(contract_address, ContractMsg::Execute(msg.build_msg()), funds, gas_limit).await
+    }
+    
+    async fn execute(
+        &mut self,
+        contract_address: &str,
+        msg: ContractMsg,
+        funds: Vec<Coin>,
+        gas_limit: u64,
+    ) -> Result<String> {
+        let logged_msg = msg.to_json()?;
+        let span = tracing::info_span!("execute_contract", contract = contract_address, gas_limit);
+        let mut result = self.execute_contract_inner(contract_address, &msg, funds.clone(), gas_limit).instrument(span.clone()).await;
+        if self.fall_back_to_inj_fee(&result) {
+            result = self.execute_contract_inner(contract_address, &msg, funds, gas_limit).instrument(span).await;
+        }
+        self.record_tx(&logged_msg, gas_limit, &result);
+        result
+    }
+    
+    async fn execute_contract_inner(
+        &mut self,
+        contract_address: &str,
+        msg: &ContractMsg,
+        funds: Vec<Coin>,
+        gas_limit: u64,
     ) -> Result<String> {
         log::info!("execute_contract called for {} with msg: {}", contract_address, msg);
+        check_fee_cap(gas_limit, self.gas_price, self.max_fee_inj())?;
         
         // Use Rust signer if enabled (preferred for performance and reliability)
         if self.use_rust_signer {
             if let Some(rust_signer) = &self.rust_signer {
                 log::info!("Using Rust-native EIP-712 signer for transaction");
-                let account = self.query_account(&self.wallet.address).await?;
-                let sequence = self.get_next_sequence(&self.wallet.address).await?;
-                
-                // Convert funds to chain format
-                let chain_funds: Vec<crate::chain::Coin> = funds.into_iter()
-                    .map(|coin| crate::chain::Coin {
-                        denom: coin.denom,
-                        amount: coin.amount,
-                    })
-                    .collect();
-                
-                // Determine message type and call appropriate method
-                // Handle both "commit" and "commit_solution" message types
                 log::debug!("Rust signer received message: {}", msg);
-                if msg.get("commit").is_some() || msg.get("commit_solution").is_some() {
-                    let msg_key = if msg.get("commit").is_some() { "commit" } else { "commit_solution" };
-                    // The commitment might be an array of bytes, not a string
-                    let commitment = if let Some(comm_arr) = msg[msg_key]["commitment"].as_array() {
-                        // Keep as byte array, don't convert to hex
-                        comm_arr.iter()
-                            .filter_map(|v| v.as_u64().map(|n| n as u8))
-                            .collect::<Vec<u8>>()
-                    } else {
-                        return Err(anyhow!("Missing or invalid commitment in {} message", msg_key));
-                    };
-                    let result = rust_signer.sign_and_broadcast_commit(
-                        commitment,
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("reveal").is_some() || msg.get("reveal_solution").is_some() {
-                    let msg_key = if msg.get("reveal").is_some() { "reveal" } else { "reveal_solution" };
-                    let nonce = msg[msg_key]["nonce"].as_array()
-                        .ok_or_else(|| anyhow!("Missing nonce in {} message", msg_key))?
-                        .iter()
-                        .filter_map(|v| v.as_u64())
-                        .map(|n| n as u8)
-                        .collect::<Vec<u8>>();
-                    let digest = msg[msg_key]["digest"].as_array()
-                        .ok_or_else(|| anyhow!("Missing digest in {} message", msg_key))?
-                        .iter()
-                        .filter_map(|v| v.as_u64())
-                        .map(|n| n as u8)
-                        .collect::<Vec<u8>>();
-                    let salt = msg[msg_key]["salt"].as_array()
-                        .ok_or_else(|| anyhow!("Missing salt in {} message", msg_key))?
-                        .iter()
-                        .filter_map(|v| v.as_u64())
-                        .map(|n| n as u8)
-                        .collect::<Vec<u8>>();
-                    let result = rust_signer.sign_and_broadcast_reveal(
-                        nonce,
-                        digest,
-                        salt,
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("claim_rewards").is_some() || msg.get("claim_reward").is_some() {
-                    // Extract epoch_number from the message
-                    let msg_key = if msg.get("claim_rewards").is_some() { "claim_rewards" } else { "claim_reward" };
-                    let epoch_number = msg[msg_key]["epoch_number"]
-                        .as_u64()
-                        .ok_or_else(|| anyhow!("Missing epoch_number in {} message", msg_key))?;
-                    
-                    let result = rust_signer.sign_and_broadcast_claim(
-                        epoch_number,
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("advance_epoch").is_some() {
-                    let result = rust_signer.sign_and_broadcast_advance_epoch(
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("finalize_epoch").is_some() {
-                    let epoch_number = msg["finalize_epoch"]["epoch_number"].as_u64()
-                        .ok_or_else(|| anyhow!("Missing epoch_number in finalize_epoch message"))?;
-                    let result = rust_signer.sign_and_broadcast_finalize_epoch(
-                        epoch_number,
+                let execute_msg = msg.execute_msg()?;
+                let (account, mut sequence) = self.account_and_sequence(&self.wallet.address).await?;
+                
+                let mut result = rust_signer.sign_and_broadcast_msg(
+                    execute_msg,
+                    account.account_number,
+                    sequence,
+                ).await;
+                self.log_sequence_error(&result);
+                if let Some(expected) = self.recover_sequence_from(sequence, &result) {
+                    sequence = expected;
+                    result = rust_signer.sign_and_broadcast_msg(
+                        execute_msg,
                         account.account_number,
                         sequence,
-                        Some(chain_funds),
                     ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else {
-                    return Err(anyhow!("Unsupported message type for Rust signer: {}", msg));
                 }
+                self.finish_sequence(sequence, &result);
+                return result;
             } else {
                 return Err(anyhow!("Rust signer enabled but not initialized"));
             }
         }
         
+        // The bridge and SIGN_MODE_DIRECT sign the message's JSON
+        let msg = msg.to_json()?;
+        
         // Use bridge if available (EIP-712 signing)
         if let Some(bridge) = &self.bridge_client {
             log::info!("Using EIP-712 bridge for transaction signing");
-            let account = self.query_account(&self.wallet.address).await?;
-            let sequence = self.get_next_sequence(&self.wallet.address).await?;
+            let (account, mut sequence) = self.account_and_sequence(&self.wallet.address).await?;
             
             let bridge_funds: Vec<crate::chain::bridge_client::Coin> = funds.into_iter()
                 .map(|coin| crate::chain::bridge_client::Coin {
@@ -482,13 +936,13 @@ impl InjectiveClient {
                 })
                 .collect();
             
-            let result = bridge.sign_and_broadcast(
+            let mut result = bridge.sign_and_broadcast(
                 self.config.chain_id.clone(),
                 account.account_number,
                 sequence,
                 contract_address,
-                msg,
-                bridge_funds,
+                msg.clone(),
+                bridge_funds.clone(),
                 gas_limit,
             ).await;
             
@@ -497,106 +951,143 @@ impl InjectiveClient {
                 let error_msg = e.to_string();
                 if error_msg.contains("account sequence") || 
                    error_msg.contains("expected") && error_msg.contains("got") {
-                    log::warn!("Sequence mismatch detected: {} (will fetch fresh on retry)", error_msg);
+                    log::warn!("Sequence mismatch detected: {}", error_msg);
                 }
             }
+            if let Some(expected) = self.recover_sequence_from(sequence, &result) {
+                sequence = expected;
+                result = bridge.sign_and_broadcast(
+                    self.config.chain_id.clone(),
+                    account.account_number,
+                    sequence,
+                    contract_address,
+                    msg,
+                    bridge_funds,
+                    gas_limit,
+                ).await;
+            }
+            self.finish_sequence(sequence, &result);
             
             return result;
         }
         
-        // Fall back to old SIGN_MODE_DIRECT (not recommended for Injective)
+        // Fall back to old SIGN_MODE_DIRECT (not recommended for Injective) - only when allowed
+        self.signing_method()?;
         log::warn!("Bridge not configured, using SIGN_MODE_DIRECT (may not earn rewards on Injective)");
         
         let max_retries = 3;
         let mut last_error = None;
+        // Account with the sequence the chain said it expects - used as is on the next attempt
+        let mut recovered: Option<AccountInfo> = None;
         
         for attempt in 0..max_retries {
-            if attempt > 0 {
-                log::info!("Retry attempt {} after sequence error", attempt);
-                tokio::time::sleep(Duration::from_millis(500)).await;
-            }
-            
             // 1. Query account for sequence and account number (fresh on each attempt)
-            let account = self.query_account(&self.wallet.address).await?;
+            let account = match recovered.take() {
+                Some(account) => account,
+                None => {
+                    if attempt > 0 {
+                        log::info!("Retry attempt {} after sequence error", attempt);
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                    }
+                    let (account, sequence) = self.account_and_sequence(&self.wallet.address).await?;
+                    AccountInfo { sequence, ..account }
+                }
+            };
             log::debug!("Account sequence: {}, account_number: {} (attempt {})", 
                 account.sequence, account.account_number, attempt + 1);
             
-            // 2. Create transaction with ProperTxBuilder
-            let builder = ProperTxBuilder::new(
-                self.config.chain_id.clone(),
-                account.account_number,
-                account.sequence,
-                &*self.wallet,
-            );
-            
-            let tx_bytes = builder.with_gas_limit(gas_limit)
-                .build_execute_contract_tx(
-                    contract_address,
-                    serde_json::to_vec(&msg)?,
-                    funds.clone(),
-                )?;
-            
-            // 3. Simulate for gas estimation (skip on retries to save time)
-            let final_tx_bytes = if attempt == 0 {
-                match self.simulate_tx(tx_bytes.clone()).await {
-                    Ok(sim_result) => {
-                        let adjusted_gas = (sim_result.gas_used * 120) / 100;
-                        log::debug!("Gas simulation: used={}, adjusted={}", sim_result.gas_used, adjusted_gas);
-                        
-                        if adjusted_gas > gas_limit {
-                            log::info!("Rebuilding transaction with adjusted gas: {} (requested: {})", adjusted_gas, gas_limit);
-                            let builder = ProperTxBuilder::new(
-                                self.config.chain_id.clone(),
-                                account.account_number,
-                                account.sequence,
-                                &*self.wallet,
-                            );
-                            
-                            builder.with_gas_limit(adjusted_gas)
-                                .build_execute_contract_tx(
-                                    contract_address,
-                                    serde_json::to_vec(&msg)?,
-                                    funds.clone(),
-                                )?
-                        } else {
-                            tx_bytes
-                        }
-                    }
-                    Err(e) => {
-                        log::warn!("Gas simulation failed: {}, using default gas", e);
-                        tx_bytes
-                    }
-                }
-            } else {
-                // On retries, skip simulation and use fixed gas
+            let broadcast = async {
+                // 2. Create transaction with ProperTxBuilder
                 let builder = ProperTxBuilder::new(
                     self.config.chain_id.clone(),
                     account.account_number,
                     account.sequence,
                     &*self.wallet,
-                ).with_gas_limit(300000);
+                ).with_fee_granter(self.config.fee_granter.clone())
+                .with_gas_price(self.gas_price, &self.config.fee_denom);
                 
-                builder.build_execute_contract_tx(
-                    contract_address,
-                    serde_json::to_vec(&msg)?,
-                    funds.clone(),
-                )?
-            };
-            
-            // 4. Broadcast transaction
-            match self.broadcast_tx(final_tx_bytes).await {
+                let tx_bytes = builder.with_gas_limit(gas_limit)
+                    .build_execute_contract_tx(
+                        contract_address,
+                        serde_json::to_vec(&msg)?,
+                        funds.clone(),
+                    )?;
+                
+                // 3. Simulate for gas estimation (skip on retries to save time)
+                let final_tx_bytes = if attempt == 0 {
+                    match self.simulate_tx(tx_bytes.clone()).await {
+                        Ok(sim_result) => {
+                            let adjusted_gas = (sim_result.gas_used * 120) / 100;
+                            log::debug!("Gas simulation: used={}, adjusted={}", sim_result.gas_used, adjusted_gas);
+                            
+                            if adjusted_gas > gas_limit {
+                                check_fee_cap(adjusted_gas, self.gas_price, self.max_fee_inj())?;
+                                log::info!("Rebuilding transaction with adjusted gas: {} (requested: {})", adjusted_gas, gas_limit);
+                                let builder = ProperTxBuilder::new(
+                                    self.config.chain_id.clone(),
+                                    account.account_number,
+                                    account.sequence,
+                                    &*self.wallet,
+                                ).with_fee_granter(self.config.fee_granter.clone())
+                                .with_gas_price(self.gas_price, &self.config.fee_denom);
+                                
+                                builder.with_gas_limit(adjusted_gas)
+                                    .build_execute_contract_tx(
+                                        contract_address,
+                                        serde_json::to_vec(&msg)?,
+                                        funds.clone(),
+                                    )?
+                            } else {
+                                tx_bytes
+                            }
+                        }
+                        Err(e) => {
+                            log::warn!("Gas simulation failed: {}, using default gas", e);
+                            tx_bytes
+                        }
+                    }
+                } else {
+                    // On retries, skip simulation and use fixed gas
+                    check_fee_cap(300000, self.gas_price, self.max_fee_inj())?;
+                    let builder = ProperTxBuilder::new(
+                        self.config.chain_id.clone(),
+                        account.account_number,
+                        account.sequence,
+                        &*self.wallet,
+                    ).with_gas_limit(300000)
+                    .with_fee_granter(self.config.fee_granter.clone())
+                    .with_gas_price(self.gas_price, &self.config.fee_denom);
+                    
+                    builder.build_execute_contract_tx(
+                        contract_address,
+                        serde_json::to_vec(&msg)?,
+                        funds.clone(),
+                    )?
+                };
+                
+                // 4. Broadcast transaction
+                self.broadcast_tx(final_tx_bytes).await
+            }.await;
+            match broadcast {
                 Ok(response) => {
                     if response.code == 0 {
                         log::info!("Transaction successful: {}", response.tx_hash);
+                        self.settle_sequence(account.sequence, None);
                         return Ok(response.tx_hash);
                     } else if response.raw_log.contains("account sequence") || 
                               response.raw_log.contains("signature verification failed") ||
                               response.raw_log.contains("account number") {
                         log::warn!("Account mismatch detected (attempt {}): {}", attempt + 1, response.raw_log);
-                        // Sequence error - will fetch fresh on retry
+                        // Sequence error - retry at the expected sequence, or fetch fresh
+                        recovered = self.recover_sequence(account.sequence, &response.raw_log)
+                            .map(|sequence| AccountInfo { sequence, ..account.clone() });
+                        if recovered.is_none() {
+                            self.settle_sequence(account.sequence, Some(&response.raw_log));
+                        }
                         last_error = Some(anyhow!("Account error: {}", response.raw_log));
                         continue;
                     } else {
+                        self.settle_sequence(account.sequence, Some(&response.raw_log));
                         return Err(anyhow!("Transaction failed: {}", response.raw_log));
                     }
                 }
@@ -606,10 +1097,16 @@ impl InjectiveClient {
                        error_str.contains("signature verification failed") ||
                        error_str.contains("account number") {
                         log::warn!("Account error in broadcast (attempt {}): {}", attempt + 1, e);
-                        // Sequence error - will fetch fresh on retry
+                        // Sequence error - retry at the expected sequence, or fetch fresh
+                        recovered = self.recover_sequence(account.sequence, &error_str)
+                            .map(|sequence| AccountInfo { sequence, ..account.clone() });
+                        if recovered.is_none() {
+                            self.settle_sequence(account.sequence, Some(&error_str));
+                        }
                         last_error = Some(e);
                         continue;
                     } else {
+                        self.settle_sequence(account.sequence, Some(&error_str));
                         return Err(e);
                     }
                 }
@@ -627,14 +1124,15 @@ impl InjectiveClient {
     ) -> Result<serde_json::Value> {
         
         
-        let response = self.with_retry(|| async {
+        let params = format!("{} {}", contract_address, String::from_utf8_lossy(&query_msg));
+        let response = self.with_retry("query_contract_smart", &params, || async {
             let channel = self.channel()?;
             
             // Create the wasm query client
-            let mut client = proto::cosmwasm::wasm::v1::query_client::QueryClient::new(channel);
+            let mut client = with_compression!(self, proto::cosmwasm::wasm::v1::query_client::QueryClient::new(channel));
             
             // Create the request with the contract address and query data
-            let request = tonic::Request::new(
+            let request = self.query_request(
                 proto::cosmwasm::wasm::v1::QuerySmartContractStateRequest {
                     address: contract_address.to_string(),
                     query_data: query_msg.clone(),
@@ -653,6 +1151,10 @@ impl InjectiveClient {
         
         // The response data contains the JSON result
         let response_bytes = response.into_inner().data;
+        // An empty body (e.g. mid-migration) is left for the caller to interpret
+        if response_bytes.is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
         
         // The gRPC endpoint for smart contract queries returns raw bytes, which are
         // expected to be a JSON-encoded string from the contract. We parse it
@@ -663,12 +1165,33 @@ impl InjectiveClient {
         Ok(json_value)
     }
     
+    /// Query a single key from a contract's raw store
+    /// Returns an empty vector if the key is not set
+    pub async fn query_contract_raw(&self, contract_address: &str, key: &[u8]) -> Result<Vec<u8>> {
+        let response = self.with_retry("query_contract_raw", &format!("{} key {}", contract_address, hex::encode(key)), || async {
+            let channel = self.channel()?;
+            let mut client = with_compression!(self, proto::cosmwasm::wasm::v1::query_client::QueryClient::new(channel));
+            let request = self.query_request(
+                proto::cosmwasm::wasm::v1::QueryRawContractStateRequest {
+                    address: contract_address.to_string(),
+                    query_data: key.to_vec(),
+                }
+            );
+            client.raw_contract_state(request).await
+                .map_err(|e| anyhow!("Failed to query raw contract state: {}", e))
+        }).await?;
+        
+        Ok(response.into_inner().data)
+    }
+    
     /// Query bank balance - REAL IMPLEMENTATION
     pub async fn query_bank_balance(&self, address: &str, denom: &str) -> Result<u128> {
-        let response = self.with_retry(|| async {
+        crate::chain::address::validate(address)?;
+        
+        let response = self.with_retry("query_bank_balance", &format!("{} {}", address, denom), || async {
             let channel = self.channel()?;
-            let mut client = BankQueryClient::new(channel);
-            let request = tonic::Request::new(QueryBalanceRequest {
+            let mut client = with_compression!(self, BankQueryClient::new(channel));
+            let request = self.query_request(QueryBalanceRequest {
                 address: address.to_string(),
                 denom: denom.to_string(),
             });
@@ -685,15 +1208,47 @@ impl InjectiveClient {
     }
     
     /// Get the latest block height from the chain - REAL IMPLEMENTATION
-    /// Uses the x-cosmos-block-height header from the response metadata
+    /// Asks the dedicated height source first if one is configured, then the main
+    /// endpoint via the x-cosmos-block-height header from the response metadata
     pub async fn get_latest_block_height(&self) -> Result<u64> {
+        if let Some(ref source) = self.config.height_source {
+            match self.query_height_source(source).await {
+                Ok(height) => return Ok(height),
+                Err(e) => log::warn!("Block height source {} failed, using the main endpoint: {:#}", source, e),
+            }
+        }
+        self.traced("get_latest_block_height", "", self.get_latest_block_height_inner()).await
+    }
+    
+    /// Latest block height from the dedicated height source. Queried directly, not
+    /// through the SOCKS5 proxy, so with a proxy only a loopback source is used
+    async fn query_height_source(&self, source: &HeightSource) -> Result<u64> {
+        if self.config.socks5_proxy.is_some() && !source.is_loopback() {
+            return Err(anyhow!("{} is not on this machine and would bypass the SOCKS5 proxy", source));
+        }
+        let url = source.url();
+        let response: Value = self.height_client
+            .get(&url)
+            .timeout(Duration::from_secs(self.config.query_timeout))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let height = source.parse(&response)
+            .ok_or_else(|| anyhow!("No block height in response from {}", url))?;
+        log::debug!("Current chain block height from {}: {}", source, height);
+        Ok(height)
+    }
+    
+    async fn get_latest_block_height_inner(&self) -> Result<u64> {
         log::debug!("Querying latest block height from chain...");
         
         // We can use any simple query to get the block height from headers
         // Using GetNodeInfo as it's lightweight and already implemented
         let channel = self.channel()?;
-        let mut client = TendermintServiceClient::new(channel);
-        let request = tonic::Request::new(GetNodeInfoRequest {});
+        let mut client = with_compression!(self, TendermintServiceClient::new(channel));
+        let request = self.query_request(GetNodeInfoRequest {});
         
         // Execute the request to get the response with metadata
         let response = client.get_node_info(request).await
@@ -717,7 +1272,7 @@ impl InjectiveClient {
             log::warn!("No x-cosmos-block-height header, trying GetLatestBlock...");
             
             let mut client = TendermintServiceClient::new(self.channel()?);
-            let request = tonic::Request::new(GetLatestBlockRequest {});
+            let request = self.query_request(GetLatestBlockRequest {});
             
             match client.get_latest_block(request).await {
                 Ok(response) => {
@@ -731,215 +1286,395 @@ impl InjectiveClient {
                         log::info!("Current chain block height from GetLatestBlock header: {}", height);
                         return Ok(height);
                     }
-                    
-                    // If no header, return a reasonable fallback
-                    log::warn!("No block height in headers, using fallback");
-                    Ok(87636000) // Recent testnet height as fallback
-                }
-                Err(e) => {
-                    log::error!("Failed to get latest block: {}, using fallback", e);
-                    Ok(87636000) // Recent testnet height as fallback
+                    log::warn!("No block height in GetLatestBlock headers, trying LCD");
                 }
+                Err(e) => log::warn!("Failed to get latest block: {}, trying LCD", e),
             }
+            
+            // Last resort - a guessed height would silently break all block-relative timing
+            self.query_lcd_block_height().await
+        }
+    }
+    
+    /// Gas price new transactions are built with (base units of INJ per gas)
+    pub fn gas_price(&self) -> u128 {
+        self.gas_price
+    }
+    
+    /// Fetch the current gas price from the configured source, clamped to
+    /// `[min_gas_price, max_gas_price]`; None when the source is static
+    pub async fn fetch_gas_price(&self) -> Result<Option<u128>> {
+        let url = match self.config.gas_price_source {
+            GasPriceSource::Static => return Ok(None),
+            GasPriceSource::Node => self.node_config_url(),
+            GasPriceSource::Oracle(ref url) => url.clone(),
+        };
+        let response = self.fetch_json(&url).await
+            .map_err(|e| anyhow!("Gas price query failed: {}", e))?;
+        let price = match self.config.gas_price_source {
+            GasPriceSource::Node => gas_price::parse_node_gas_price(&response, &self.config.fee_denom),
+            _ => gas_price::parse_oracle_gas_price(&response),
         }
+        .ok_or_else(|| anyhow!("No gas price in response from {}", url))?;
+        Ok(Some(gas_price::clamp_gas_price(price, self.config.min_gas_price, self.config.max_gas_price)))
+    }
+    
+    /// LCD endpoint reporting the node's minimum gas prices
+    fn node_config_url(&self) -> String {
+        format!("{}/cosmos/base/node/v1beta1/config", self.config.lcd_endpoint.trim_end_matches('/'))
+    }
+    
+    /// GET a JSON document over plain HTTP(S), through the proxy if one is configured
+    async fn fetch_json(&self, url: &str) -> Result<Value> {
+        let mut client = reqwest::Client::builder();
+        if let Some(ref proxy) = self.config.socks5_proxy {
+            client = client.proxy(reqwest::Proxy::all(proxy.url())?);
+        }
+        Ok(client.build()?
+            .get(url)
+            .timeout(Duration::from_secs(self.config.query_timeout))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+    
+    /// Denom fees are currently paid in
+    pub fn fee_denom(&self) -> &str {
+        &self.config.fee_denom
+    }
+    
+    /// Denoms the node accepts fees in, from its minimum gas prices
+    pub async fn node_fee_denoms(&self) -> Result<Vec<String>> {
+        let response = self.fetch_json(&self.node_config_url()).await?;
+        Ok(gas_price::node_fee_denoms(&response))
+    }
+    
+    /// The node's minimum gas price in the fee denom, if it lists one
+    pub async fn node_gas_price(&self) -> Result<Option<u128>> {
+        let response = self.fetch_json(&self.node_config_url()).await?;
+        Ok(gas_price::parse_node_gas_price(&response, &self.config.fee_denom))
+    }
+    
+    /// Pay fees in `fee_denom` at `gas_price` (base units of that denom per gas),
+    /// whichever signer is in use
+    pub fn set_fee_denom(&mut self, fee_denom: &str, gas_price: u128) {
+        log::info!("Fee denom: {} -> {} ({} per gas)", self.config.fee_denom, fee_denom, gas_price);
+        self.config.fee_denom = fee_denom.to_string();
+        if let Some(ref mut rust_signer) = self.rust_signer {
+            rust_signer.set_fee_denom(fee_denom.to_string());
+        }
+        if let Some(ref mut bridge_client) = self.bridge_client {
+            bridge_client.set_fee_denom(fee_denom.to_string());
+        }
+        self.set_gas_price(gas_price);
+    }
+    
+    /// Whether fees are paid in something other than INJ
+    fn pays_fees_in_other_denom(&self) -> bool {
+        self.config.fee_denom != DEFAULT_FEE_DENOM
+    }
+    
+    /// The INJ fee cap, which can't apply to fees paid in another denom
+    fn max_fee_inj(&self) -> Option<f64> {
+        if self.pays_fees_in_other_denom() { None } else { self.config.max_fee_inj }
+    }
+    
+    /// Pay fees in INJ at the static price, within the default INJ gas price bounds
+    /// (bounds set for another denom mean nothing in INJ)
+    pub fn fall_back_to_inj(&mut self) {
+        self.config.min_gas_price = DEFAULT_MIN_GAS_PRICE;
+        self.config.max_gas_price = DEFAULT_MAX_GAS_PRICE;
+        self.set_fee_denom(DEFAULT_FEE_DENOM, GAS_PRICE);
+    }
+    
+    /// Switch back to paying fees in INJ at the static price after the chain rejected
+    /// the configured denom; true if there was anything to switch
+    fn fall_back_to_inj_fee(&mut self, result: &Result<String>) -> bool {
+        match result {
+            Err(e) if self.pays_fees_in_other_denom() && gas_price::is_fee_denom_rejection(&format!("{:#}", e)) => {
+                log::warn!("Fee in {} was rejected ({:#}), paying fees in INJ from now on", self.config.fee_denom, e);
+                self.fall_back_to_inj();
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    /// Build new transactions with this gas price, whichever signer is in use
+    pub fn set_gas_price(&mut self, gas_price: u128) {
+        if gas_price != self.gas_price {
+            log::info!("Gas price: {} -> {} {}/gas", self.gas_price, gas_price, self.config.fee_denom);
+        }
+        self.gas_price = gas_price;
+        if let Some(ref mut rust_signer) = self.rust_signer {
+            rust_signer.set_gas_price(Some(gas_price));
+        }
+        if let Some(ref mut bridge_client) = self.bridge_client {
+            bridge_client.set_gas_price(gas_price);
+        }
+    }
+    
+    /// Latest block height from the LCD `blocks/latest` endpoint
+    async fn query_lcd_block_height(&self) -> Result<u64> {
+        let url = format!(
+            "{}/cosmos/base/tendermint/v1beta1/blocks/latest",
+            self.config.lcd_endpoint.trim_end_matches('/')
+        );
+        let mut client = reqwest::Client::builder();
+        if let Some(ref proxy) = self.config.socks5_proxy {
+            client = client.proxy(reqwest::Proxy::all(proxy.url())?);
+        }
+        let response: Value = client.build()?
+            .get(&url)
+            .timeout(Duration::from_secs(self.config.query_timeout))
+            .send()
+            .await
+            .map_err(|e| anyhow!("LCD block height query failed: {}", e))?
+            .error_for_status()
+            .map_err(|e| anyhow!("LCD block height query failed: {}", e))?
+            .json()
+            .await
+            .map_err(|e| anyhow!("Invalid LCD block response: {}", e))?;
+        
+        let height = parse_lcd_block_height(&response)
+            .ok_or_else(|| anyhow!("No block height in LCD response from {}", url))?;
+        log::info!("Current chain block height from LCD: {}", height);
+        Ok(height)
     }
     
     /// Execute a contract message WITHOUT gas simulation - for time-critical transactions
     /// This is used for reveals where the 30-second window doesn't allow time for simulation
     /// Now includes automatic retry on sequence errors and EIP-712 bridge support
+    /// Raw JSON is for contracts other than the mining contract, as with `execute_contract`
     pub async fn execute_contract_fast(
         &mut self,
         contract_address: &str,
         msg: Value,
         funds: Vec<Coin>,
         gas_limit: u64,
+    ) -> Result<String> {
+        self.execute_fast(contract_address, ContractMsg::Json(msg), funds, gas_limit).await
+    }
+    
+    /// Execute a typed contract message without gas simulation (see `execute_contract_fast`)
+    pub async fn execute_message_fast(
+        &mut self,
+        contract_address: &str,
+        msg: &dyn MessageBuilder,
+        funds: Vec<Coin>,
+        gas_limit: u64,
+    ) -> Result<String> {
+        self.execute_fast(contract_address, ContractMsg::Execute(msg.build_msg()), funds, gas_limit).await
+    }
+    
+    async fn execute_fast(
+        &mut self,
+        contract_address: &str,
+        msg: ContractMsg,
+        funds: Vec<Coin>,
+        gas_limit: u64,
+    ) -> Result<String> {
+        let logged_msg = msg.to_json()?;
+        let span = tracing::info_span!("execute_contract_fast", contract = contract_address, gas_limit);
+        let mut result = self.execute_contract_fast_inner(contract_address, &msg, funds.clone(), gas_limit).instrument(span.clone()).await;
+        if self.fall_back_to_inj_fee(&result) {
+            result = self.execute_contract_fast_inner(contract_address, &msg, funds, gas_limit).instrument(span).await;
+        }
+        self.record_tx(&logged_msg, gas_limit, &result);
+        result
+    }
+    
+    /// Execute several contract messages in one transaction without gas simulation, so
+    /// they succeed or fail together. Bridge only: the Rust signer signs single
+    /// mining-contract messages
+    pub async fn execute_contracts_fast(
+        &mut self,
+        messages: Vec<MessageData>,
+        gas_limit: u64,
+    ) -> Result<String> {
+        // Logged under the first message
+        let logged_msg = match messages.first() {
+            Some(message) => message.msg.clone(),
+            None => return Err(anyhow!("No messages to execute")),
+        };
+        let span = tracing::info_span!("execute_contracts_fast", messages = messages.len(), gas_limit);
+        let mut result = self.execute_contracts_fast_inner(messages.clone(), gas_limit).instrument(span.clone()).await;
+        if self.fall_back_to_inj_fee(&result) {
+            result = self.execute_contracts_fast_inner(messages, gas_limit).instrument(span).await;
+        }
+        self.record_tx(&logged_msg, gas_limit, &result);
+        result
+    }
+    
+    async fn execute_contracts_fast_inner(&mut self, messages: Vec<MessageData>, gas_limit: u64) -> Result<String> {
+        let bridge = match (&self.bridge_client, self.use_rust_signer) {
+            (Some(bridge), false) => bridge,
+            _ => return Err(anyhow!("Several messages in one transaction requires the EIP-712 bridge")),
+        };
+        check_fee_cap(gas_limit, self.gas_price, self.max_fee_inj())?;
+        log::info!("Using EIP-712 bridge for a {}-message transaction", messages.len());
+        let (account, mut sequence) = self.account_and_sequence(&self.wallet.address).await?;
+        
+        let mut result = bridge.sign_and_broadcast_messages(
+            self.config.chain_id.clone(),
+            account.account_number,
+            sequence,
+            messages.clone(),
+            gas_limit,
+        ).await;
+        self.log_sequence_error(&result);
+        if let Some(expected) = self.recover_sequence_from(sequence, &result) {
+            sequence = expected;
+            result = bridge.sign_and_broadcast_messages(
+                self.config.chain_id.clone(),
+                account.account_number,
+                sequence,
+                messages,
+                gas_limit,
+            ).await;
+        }
+        self.finish_sequence(sequence, &result);
+        result
+    }
+    
+    /// Send bank tokens from the miner's address without gas simulation. Bridge only:
+    /// the Rust signer only signs mining-contract messages
+    pub async fn send_tokens_fast(&mut self, send: BankSend, gas_limit: u64) -> Result<String> {
+        let logged_msg = serde_json::json!({ "send": &send });
+        let span = tracing::info_span!("send_tokens_fast", to = send.to_address.as_str(), gas_limit);
+        let mut result = self.send_tokens_fast_inner(send.clone(), gas_limit).instrument(span.clone()).await;
+        if self.fall_back_to_inj_fee(&result) {
+            result = self.send_tokens_fast_inner(send, gas_limit).instrument(span).await;
+        }
+        self.record_tx(&logged_msg, gas_limit, &result);
+        result
+    }
+    
+    async fn send_tokens_fast_inner(&mut self, send: BankSend, gas_limit: u64) -> Result<String> {
+        let bridge = match (&self.bridge_client, self.use_rust_signer) {
+            (Some(bridge), false) => bridge,
+            _ => return Err(anyhow!("Bank sends require the EIP-712 bridge")),
+        };
+        check_fee_cap(gas_limit, self.gas_price, self.max_fee_inj())?;
+        log::info!("Using EIP-712 bridge to send tokens to {}", send.to_address);
+        let (account, mut sequence) = self.account_and_sequence(&self.wallet.address).await?;
+        
+        let mut result = bridge.send_tokens(
+            self.config.chain_id.clone(),
+            account.account_number,
+            sequence,
+            send.clone(),
+            gas_limit,
+        ).await;
+        self.log_sequence_error(&result);
+        if let Some(expected) = self.recover_sequence_from(sequence, &result) {
+            sequence = expected;
+            result = bridge.send_tokens(
+                self.config.chain_id.clone(),
+                account.account_number,
+                sequence,
+                send,
+                gas_limit,
+            ).await;
+        }
+        self.finish_sequence(sequence, &result);
+        result
+    }
+    
+    /// Sign a contract message now against the account's current sequence, for
+    /// `execute_prepared_fast` to broadcast later. Rust signer only
+    pub async fn prepare_message(&self, msg: &dyn MessageBuilder) -> Result<PreparedTx> {
+        let rust_signer = match (&self.rust_signer, self.use_rust_signer) {
+            (Some(rust_signer), true) => rust_signer,
+            _ => return Err(anyhow!("Pre-signing transactions requires the Rust signer")),
+        };
+        let account = self.query_account(&self.wallet.address).await?;
+        let sequence = self.sequence_tracker.read()
+            .map_or(account.sequence, |tracker| tracker.peek(account.sequence));
+        rust_signer.prepare_msg(msg, account.account_number, sequence)
+    }
+    
+    /// Broadcast a transaction from `prepare_message` if the account sequence hasn't
+    /// moved since it was signed; otherwise sign the same message fresh
+    pub async fn execute_prepared_fast(
+        &mut self,
+        contract_address: &str,
+        prepared: &PreparedTx,
+        gas_limit: u64,
+    ) -> Result<String> {
+        if let (Some(rust_signer), true) = (&self.rust_signer, self.use_rust_signer) {
+            check_fee_cap(gas_limit, self.gas_price, self.max_fee_inj())?;
+            let (account, sequence) = self.account_and_sequence(&self.wallet.address).await?;
+            if prepared.matches(account.account_number, sequence) {
+                log::info!("Broadcasting pre-signed {} (sequence {})", prepared.msg.name(), sequence);
+                let result = rust_signer.broadcast_prepared(prepared).await;
+                self.log_sequence_error(&result);
+                self.finish_sequence(sequence, &result);
+                self.record_tx(&prepared.msg.to_json()?, gas_limit, &result);
+                // Signed with a fee denom the chain just rejected - sign fresh in INJ
+                if !self.fall_back_to_inj_fee(&result) {
+                    return result;
+                }
+            } else {
+                log::info!("Sequence moved since {} was pre-signed ({} -> {}), signing fresh",
+                    prepared.msg.name(), prepared.sequence, sequence);
+                // Signing fresh picks its own sequence
+                if let Ok(mut tracker) = self.sequence_tracker.write() {
+                    tracker.unused(sequence);
+                }
+            }
+        }
+        self.execute_message_fast(contract_address, &prepared.msg, vec![], gas_limit).await
+    }
+    
+    async fn execute_contract_fast_inner(
+        &mut self,
+        contract_address: &str,
+        msg: &ContractMsg,
+        funds: Vec<Coin>,
+        gas_limit: u64,
     ) -> Result<String> {
         log::warn!("execute_contract_fast: SKIPPING GAS SIMULATION for time-critical transaction");
+        check_fee_cap(gas_limit, self.gas_price, self.max_fee_inj())?;
         
         // Use Rust signer if enabled (preferred for performance and reliability)
         if self.use_rust_signer {
             if let Some(rust_signer) = &self.rust_signer {
                 log::info!("Using Rust-native EIP-712 signer for fast transaction");
-                let account = self.query_account(&self.wallet.address).await?;
-                let sequence = self.get_next_sequence(&self.wallet.address).await?;
-                
-                // Convert funds to chain format
-                let chain_funds: Vec<crate::chain::Coin> = funds.into_iter()
-                    .map(|coin| crate::chain::Coin {
-                        denom: coin.denom,
-                        amount: coin.amount,
-                    })
-                    .collect();
-                
-                // Determine message type and call appropriate method
-                // Handle both "commit" and "commit_solution" message types
                 log::debug!("Rust signer received message: {}", msg);
-                if msg.get("commit").is_some() || msg.get("commit_solution").is_some() {
-                    let msg_key = if msg.get("commit").is_some() { "commit" } else { "commit_solution" };
-                    // The commitment might be an array of bytes, not a string
-                    let commitment = if let Some(comm_arr) = msg[msg_key]["commitment"].as_array() {
-                        // Keep as byte array, don't convert to hex
-                        comm_arr.iter()
-                            .filter_map(|v| v.as_u64().map(|n| n as u8))
-                            .collect::<Vec<u8>>()
-                    } else {
-                        return Err(anyhow!("Missing or invalid commitment in {} message", msg_key));
-                    };
-                    let result = rust_signer.sign_and_broadcast_commit(
-                        commitment,
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("reveal").is_some() || msg.get("reveal_solution").is_some() {
-                    let msg_key = if msg.get("reveal").is_some() { "reveal" } else { "reveal_solution" };
-                    let nonce = msg[msg_key]["nonce"].as_array()
-                        .ok_or_else(|| anyhow!("Missing nonce in {} message", msg_key))?
-                        .iter()
-                        .filter_map(|v| v.as_u64())
-                        .map(|n| n as u8)
-                        .collect::<Vec<u8>>();
-                    let digest = msg[msg_key]["digest"].as_array()
-                        .ok_or_else(|| anyhow!("Missing digest in {} message", msg_key))?
-                        .iter()
-                        .filter_map(|v| v.as_u64())
-                        .map(|n| n as u8)
-                        .collect::<Vec<u8>>();
-                    let salt = msg[msg_key]["salt"].as_array()
-                        .ok_or_else(|| anyhow!("Missing salt in {} message", msg_key))?
-                        .iter()
-                        .filter_map(|v| v.as_u64())
-                        .map(|n| n as u8)
-                        .collect::<Vec<u8>>();
-                    let result = rust_signer.sign_and_broadcast_reveal(
-                        nonce,
-                        digest,
-                        salt,
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("claim_rewards").is_some() || msg.get("claim_reward").is_some() {
-                    // Extract epoch_number from the message
-                    let msg_key = if msg.get("claim_rewards").is_some() { "claim_rewards" } else { "claim_reward" };
-                    let epoch_number = msg[msg_key]["epoch_number"]
-                        .as_u64()
-                        .ok_or_else(|| anyhow!("Missing epoch_number in {} message", msg_key))?;
-                    
-                    let result = rust_signer.sign_and_broadcast_claim(
-                        epoch_number,
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("advance_epoch").is_some() {
-                    let result = rust_signer.sign_and_broadcast_advance_epoch(
-                        account.account_number,
-                        sequence,
-                        Some(chain_funds),
-                    ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else if msg.get("finalize_epoch").is_some() {
-                    let epoch_number = msg["finalize_epoch"]["epoch_number"].as_u64()
-                        .ok_or_else(|| anyhow!("Missing epoch_number in finalize_epoch message"))?;
-                    let result = rust_signer.sign_and_broadcast_finalize_epoch(
-                        epoch_number,
+                let execute_msg = msg.execute_msg()?;
+                let (account, mut sequence) = self.account_and_sequence(&self.wallet.address).await?;
+                
+                let mut result = rust_signer.sign_and_broadcast_msg(
+                    execute_msg,
+                    account.account_number,
+                    sequence,
+                ).await;
+                self.log_sequence_error(&result);
+                if let Some(expected) = self.recover_sequence_from(sequence, &result) {
+                    sequence = expected;
+                    result = rust_signer.sign_and_broadcast_msg(
+                        execute_msg,
                         account.account_number,
                         sequence,
-                        Some(chain_funds),
                     ).await;
-                    
-                    // Handle sequence-related errors
-                    if let Err(ref e) = result {
-                        let error_msg = e.to_string();
-                        if error_msg.contains("account sequence") || 
-                           error_msg.contains("expected") && error_msg.contains("got") {
-                            log::warn!("Sequence mismatch detected: {}", error_msg);
-                            
-                            // Just log the sequence error - we'll fetch fresh on next attempt
-                            if let Some(expected_seq) = self.parse_sequence_error(&error_msg) {
-                                log::info!("Chain expects sequence: {} (will fetch fresh on retry)", expected_seq);
-                            }
-                        }
-                    }
-                    
-                    return result;
-                } else {
-                    return Err(anyhow!("Unsupported message type for Rust signer: {}", msg));
                 }
+                self.finish_sequence(sequence, &result);
+                return result;
             } else {
                 return Err(anyhow!("Rust signer enabled but not initialized"));
             }
         }
         
+        // The bridge and SIGN_MODE_DIRECT sign the message's JSON
+        let msg = msg.to_json()?;
+        
         // Use bridge if available (EIP-712 signing)
         if let Some(bridge) = &self.bridge_client {
             log::info!("Using EIP-712 bridge for fast transaction signing");
-            let account = self.query_account(&self.wallet.address).await?;
-            let sequence = self.get_next_sequence(&self.wallet.address).await?;
+            let (account, mut sequence) = self.account_and_sequence(&self.wallet.address).await?;
             
             let bridge_funds: Vec<crate::chain::bridge_client::Coin> = funds.into_iter()
                 .map(|coin| crate::chain::bridge_client::Coin {
@@ -948,13 +1683,13 @@ impl InjectiveClient {
                 })
                 .collect();
             
-            let result = bridge.sign_and_broadcast(
+            let mut result = bridge.sign_and_broadcast(
                 self.config.chain_id.clone(),
                 account.account_number,
                 sequence,
                 contract_address,
-                msg,
-                bridge_funds,
+                msg.clone(),
+                bridge_funds.clone(),
                 gas_limit,
             ).await;
             
@@ -963,61 +1698,94 @@ impl InjectiveClient {
                 let error_msg = e.to_string();
                 if error_msg.contains("account sequence") || 
                    error_msg.contains("expected") && error_msg.contains("got") {
-                    log::warn!("Sequence mismatch detected: {} (will fetch fresh on retry)", error_msg);
+                    log::warn!("Sequence mismatch detected: {}", error_msg);
                 }
             }
+            if let Some(expected) = self.recover_sequence_from(sequence, &result) {
+                sequence = expected;
+                result = bridge.sign_and_broadcast(
+                    self.config.chain_id.clone(),
+                    account.account_number,
+                    sequence,
+                    contract_address,
+                    msg,
+                    bridge_funds,
+                    gas_limit,
+                ).await;
+            }
+            self.finish_sequence(sequence, &result);
             
             return result;
         }
         
-        // Fall back to old SIGN_MODE_DIRECT (not recommended for Injective)
+        // Fall back to old SIGN_MODE_DIRECT (not recommended for Injective) - only when allowed
+        self.signing_method()?;
         log::warn!("Bridge not configured, using SIGN_MODE_DIRECT (may not earn rewards on Injective)");
         
         let max_retries = 3;
         let mut last_error = None;
+        // Account with the sequence the chain said it expects - used as is on the next attempt
+        let mut recovered: Option<AccountInfo> = None;
         
         for attempt in 0..max_retries {
-            if attempt > 0 {
-                log::info!("Retry attempt {} after sequence error", attempt);
-                // Small delay between retries to let chain state settle
-                tokio::time::sleep(Duration::from_millis(500)).await;
-            }
-            
-            // Always query fresh account info to get latest sequence
-            let account = self.query_account(&self.wallet.address).await?;
+            // Query fresh account info to get latest sequence, unless the chain told us
+            let account = match recovered.take() {
+                Some(account) => account,
+                None => {
+                    if attempt > 0 {
+                        log::info!("Retry attempt {} after sequence error", attempt);
+                        // Small delay between retries to let chain state settle
+                        tokio::time::sleep(Duration::from_millis(500)).await;
+                    }
+                    let (account, sequence) = self.account_and_sequence(&self.wallet.address).await?;
+                    AccountInfo { sequence, ..account }
+                }
+            };
             log::debug!("Account sequence: {}, account_number: {}", 
                 account.sequence, account.account_number);
             
-            // Create transaction with fresh sequence
-            let builder = ProperTxBuilder::new(
-                self.config.chain_id.clone(),
-                account.account_number,
-                account.sequence,
-                &*self.wallet,
-            ).with_gas_limit(gas_limit);
-            
-            let tx_bytes = builder.build_execute_contract_tx(
-                contract_address,
-                serde_json::to_vec(&msg)?,
-                funds.clone(),
-            )?;
-            
-            // Try to broadcast
-            log::info!("Broadcasting transaction with fixed gas limit: {} (attempt {})", gas_limit, attempt + 1);
-            match self.broadcast_tx(tx_bytes).await {
+            let broadcast = async {
+                // Create transaction with fresh sequence
+                let builder = ProperTxBuilder::new(
+                    self.config.chain_id.clone(),
+                    account.account_number,
+                    account.sequence,
+                    &*self.wallet,
+                ).with_gas_limit(gas_limit)
+                .with_fee_granter(self.config.fee_granter.clone())
+                .with_gas_price(self.gas_price, &self.config.fee_denom);
+                
+                let tx_bytes = builder.build_execute_contract_tx(
+                    contract_address,
+                    serde_json::to_vec(&msg)?,
+                    funds.clone(),
+                )?;
+                
+                // Try to broadcast
+                log::info!("Broadcasting transaction with fixed gas limit: {} (attempt {})", gas_limit, attempt + 1);
+                self.broadcast_tx(tx_bytes).await
+            }.await;
+            match broadcast {
                 Ok(response) => {
                     if response.code == 0 {
                         log::info!("Fast transaction successful: {}", response.tx_hash);
+                        self.settle_sequence(account.sequence, None);
                         return Ok(response.tx_hash);
                     } else if response.raw_log.contains("account sequence") || 
                               response.raw_log.contains("signature verification failed") ||
                               response.raw_log.contains("account number") {
-                        // Sequence/account error - retry with fresh account query
+                        // Sequence/account error - retry at the expected sequence, or with a fresh account query
                         log::warn!("Account mismatch detected (attempt {}): {}", attempt + 1, response.raw_log);
+                        recovered = self.recover_sequence(account.sequence, &response.raw_log)
+                            .map(|sequence| AccountInfo { sequence, ..account.clone() });
+                        if recovered.is_none() {
+                            self.settle_sequence(account.sequence, Some(&response.raw_log));
+                        }
                         last_error = Some(anyhow!("Account error: {}", response.raw_log));
                         continue;
                     } else {
                         // Other error - fail immediately (no point retrying)
+                        self.settle_sequence(account.sequence, Some(&response.raw_log));
                         return Err(anyhow!("Transaction failed: {}", response.raw_log));
                     }
                 }
@@ -1027,11 +1795,17 @@ impl InjectiveClient {
                        error_str.contains("signature verification failed") ||
                        error_str.contains("account number") {
                         log::warn!("Account error in broadcast (attempt {}): {}", attempt + 1, e);
-                        // Sequence error - will fetch fresh on retry
+                        // Sequence error - retry at the expected sequence, or fetch fresh
+                        recovered = self.recover_sequence(account.sequence, &error_str)
+                            .map(|sequence| AccountInfo { sequence, ..account.clone() });
+                        if recovered.is_none() {
+                            self.settle_sequence(account.sequence, Some(&error_str));
+                        }
                         last_error = Some(e);
                         continue;
                     } else {
                         // Non-sequence error - fail immediately
+                        self.settle_sequence(account.sequence, Some(&error_str));
                         return Err(e);
                     }
                 }
@@ -1043,9 +1817,9 @@ impl InjectiveClient {
     
     /// Get node info for health check and chain ID - REAL IMPLEMENTATION
     pub async fn get_node_info(&self) -> Result<NodeInfo> {
-        let response = self.with_retry(|| async {
+        let response = self.with_retry("get_node_info", "", || async {
             let channel = self.channel()?;
-            let mut client = TendermintServiceClient::new(channel);
+            let mut client = with_compression!(self, TendermintServiceClient::new(channel));
             let request = tonic::Request::new(GetNodeInfoRequest {});
             client.get_node_info(request).await
                 .map_err(|e| anyhow!("Failed to get node info: {}", e))
@@ -1064,24 +1838,92 @@ impl InjectiveClient {
         })
     }
     
-    /// Retry helper for network operations
-    async fn with_retry<T, F, Fut>(&self, f: F) -> Result<T>
+    /// Retry helper for network operations (traced as a single call)
+    async fn with_retry<T, F, Fut>(&self, method: &'static str, params: &str, f: F) -> Result<T>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T>>,
     {
-        let mut retries = 0;
-        loop {
-            match f().await {
-                Ok(result) => return Ok(result),
-                Err(e) if retries < self.config.max_retries => {
-                    retries += 1;
-                    tokio::time::sleep(Duration::from_millis(100 * retries as u64)).await;
+        self.traced(method, params, async {
+            let mut retries = 0;
+            loop {
+                let result = f().await;
+                if let Err(ref e) = result {
+                    // Noticed here so the retries already go out uncompressed
+                    self.note_compression_rejection(e);
+                }
+                match result {
+                    Ok(result) => return Ok(result),
+                    Err(e) if retries < self.config.max_retries => {
+                        retries += 1;
+                        tracing::Span::current().record("retries", retries);
+                        log::debug!("{} failed (retry {}/{}): {}", method, retries, self.config.max_retries, e);
+                        tokio::time::sleep(Duration::from_millis(100 * retries as u64)).await;
+                    }
+                    Err(e) => return Err(e),
                 }
-                Err(e) => return Err(e),
             }
+        }).await
+    }
+    
+    /// Send later requests uncompressed if `error` says the server rejected compression
+    fn note_compression_rejection(&self, error: &anyhow::Error) {
+        if self.compression().is_some() && is_compression_rejected(&error.to_string()) {
+            log::warn!("{} rejected gRPC compression, falling back to uncompressed: {}", self.config.grpc_endpoint, error);
+            self.compression_rejected.store(true, Ordering::Relaxed);
         }
     }
+    
+    /// Run a gRPC call inside a `grpc_call` span recording endpoint, duration,
+    /// retries and outcome. Nests under the caller's span (e.g. the orchestrator's
+    /// epoch/phase span), and calls over `slow_call_threshold_ms` are logged as warnings.
+    /// `params` summarizes the request for the call trace
+    async fn traced<T, Fut>(&self, method: &'static str, params: &str, call: Fut) -> Result<T>
+    where
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let span = tracing::info_span!(
+            "grpc_call",
+            method,
+            endpoint = %self.config.grpc_endpoint,
+            retries = 0u32,
+            elapsed_ms = tracing::field::Empty,
+            outcome = tracing::field::Empty,
+        );
+        let start = std::time::Instant::now();
+        let result = call.instrument(span.clone()).await;
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+        if let Err(ref e) = result {
+            // Calls without retries only notice once they have failed
+            self.note_compression_rejection(e);
+        }
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        if result.is_ok() {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            self.counters.last_success.store(now, Ordering::Relaxed);
+        } else {
+            self.counters.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        
+        if let Some(ref call_trace) = self.call_trace {
+            let error = result.as_ref().err().map(|e| e.to_string());
+            call_trace.record(ChainCall::new(method, params, elapsed_ms, error.as_deref()));
+        }
+        
+        span.record("elapsed_ms", elapsed_ms);
+        span.record("outcome", outcome);
+        tracing::debug!(parent: &span, method, elapsed_ms, outcome, "gRPC call finished");
+        if elapsed_ms >= self.config.slow_call_threshold_ms {
+            log::warn!(
+                "Slow gRPC call: {} took {}ms ({}) against {}",
+                method, elapsed_ms, outcome, self.config.grpc_endpoint
+            );
+        }
+        result
+    }
 }
 
 // AccountInfo is now defined in account_types module
@@ -1124,6 +1966,227 @@ mod tests {
         assert_eq!(client.config.chain_id, "injective-888");
     }
     
+    #[test]
+    fn test_sequence_tracker_increments_locally() {
+        let mut tracker = SequenceTracker::default();
+        assert_eq!(tracker.resolve(5), 5);
+        tracker.confirm(5);
+        // Node index lags and still reports 5 - we must use 6
+        assert_eq!(tracker.resolve(5), 6);
+        tracker.confirm(6);
+        // Chain caught up past us (e.g. tx sent from elsewhere) - chain wins
+        assert_eq!(tracker.resolve(9), 9);
+    }
+    
+    #[test]
+    fn test_sequence_tracker_tracks_each_broadcast_in_flight() {
+        let mut tracker = SequenceTracker::default();
+        // Two broadcasts at once get their own sequences
+        assert_eq!(tracker.resolve(5), 5);
+        assert_eq!(tracker.resolve(5), 6);
+        // The second lands first - the first is still in flight
+        tracker.confirm(6);
+        assert_eq!(tracker.peek(5), 7);
+        // An unclear failure of the first keeps what the second taught us
+        tracker.release(5);
+        assert_eq!(tracker.resolve(5), 7);
+        // A sequence handed out but never signed with is free again
+        tracker.unused(7);
+        assert_eq!(tracker.peek(5), 7);
+        // The chain rejects 8 expecting 7: everything below 7 is used up
+        assert_eq!(tracker.resolve(5), 7);
+        assert_eq!(tracker.resolve(5), 8);
+        tracker.reconcile(8, 7);
+        assert_eq!(tracker.peek(5), 8);
+    }
+    
+    #[test]
+    fn test_sequence_tracker_reconciles() {
+        let mut tracker = SequenceTracker::default();
+        tracker.resolve(5);
+        tracker.confirm(5);
+        tracker.resolve(5);
+        // Chain says "expected 4, got 6" - trust it
+        tracker.reconcile(6, 4);
+        assert_eq!(tracker.resolve(3), 4);
+        
+        tracker.reset();
+        assert_eq!(tracker.resolve(3), 3);
+    }
+    
+    #[test]
+    fn test_parse_sequence_error() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let client = InjectiveClient::new_testnet(wallet);
+        let msg = "account sequence mismatch, expected 42, got 41: incorrect account sequence";
+        assert_eq!(client.parse_sequence_error(msg), Some(42));
+        assert_eq!(client.parse_sequence_error("out of gas"), None);
+    }
+    
+    #[test]
+    fn test_recover_sequence() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let mut client = InjectiveClient::new_testnet(wallet);
+        let msg = "account sequence mismatch, expected 42, got 41: incorrect account sequence";
+        assert_eq!(client.recover_sequence(41, msg), Some(42));
+        assert_eq!(client.recover_sequence(42, "expected 3, got 4: wrong denom"), None);
+        // The resubmission is in flight at 42, so success moves on to 43
+        client.finish_sequence(42, &Ok("HASH".to_string()));
+        assert_eq!(client.sequence_tracker.read().unwrap().peek(40), 43);
+        
+        client.config.sequence_recovery = false;
+        assert_eq!(client.recover_sequence(41, msg), None);
+    }
+    
+    #[test]
+    fn test_select_signing_method() {
+        assert_eq!(select_signing_method(true, true, false, false).unwrap(), SigningMethod::RustSigner);
+        assert_eq!(select_signing_method(false, false, true, false).unwrap(), SigningMethod::Bridge);
+        // Exactly one signer
+        assert!(select_signing_method(true, true, true, false).is_err());
+        assert!(select_signing_method(true, false, false, true).is_err());
+        // SIGN_MODE_DIRECT only when explicitly allowed
+        assert!(select_signing_method(false, false, false, false).is_err());
+        assert_eq!(select_signing_method(false, false, false, true).unwrap(), SigningMethod::Direct);
+        
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let client = InjectiveClient::new_testnet(wallet);
+        assert!(client.signing_method().is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_fee_cap_rejects_expensive_transactions() {
+        // 300k gas at 500_000_000 per gas = 0.00015 INJ
+        assert!(check_fee_cap(300_000, GAS_PRICE, None).is_ok());
+        assert!(check_fee_cap(300_000, GAS_PRICE, Some(0.0002)).is_ok());
+        assert!(check_fee_cap(300_000, GAS_PRICE, Some(0.0001)).is_err());
+        // A higher live gas price hits the same cap sooner
+        assert!(check_fee_cap(300_000, 2 * GAS_PRICE, Some(0.0002)).is_err());
+        
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let mut client = InjectiveClient::new_testnet(wallet);
+        client.config.max_fee_inj = Some(0.0001);
+        // Rejected before signing or touching the network
+        let err = client
+            .execute_contract("inj1contract", serde_json::json!({"advance_epoch": {}}), vec![], 300_000)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Fee cap exceeded"));
+    }
+    
+    #[tokio::test]
+    async fn test_rust_signer_only_signs_typed_mining_messages() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mut client = InjectiveClient::new_testnet(InjectiveWallet::from_mnemonic_no_passphrase(mnemonic).unwrap());
+        client.enable_rust_signer(mnemonic, "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66").unwrap();
+        
+        // Raw JSON is for other contracts - refused before anything is signed or queried
+        let transfer = serde_json::json!({"transfer": {"recipient": "inj1...", "amount": "1"}});
+        let err = client.execute_contract_fast("inj1token", transfer, vec![], 300_000).await.unwrap_err();
+        assert!(err.to_string().contains("only signs mining contract messages"), "{}", err);
+        let err = client.execute_contract("inj1token", serde_json::json!({"advance_epoch": {}}), vec![], 300_000).await.unwrap_err();
+        assert!(err.to_string().contains("only signs mining contract messages"), "{}", err);
+    }
+    
+    #[test]
+    fn test_fee_denom_rejection_falls_back_to_inj_prices() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let config = ClientConfig {
+            fee_denom: "peggy0xdAC17F958D2ee523a2206206994597C13D831ec7".to_string(),
+            min_gas_price: 0,
+            max_gas_price: u128::MAX,
+            ..ClientConfig::default()
+        };
+        let mut client = InjectiveClient::new(config, wallet);
+        client.set_gas_price(5);
+        
+        assert!(!client.fall_back_to_inj_fee(&Err(anyhow!("account sequence mismatch"))));
+        assert!(client.fall_back_to_inj_fee(&Err(anyhow!("insufficient fee: got 5peggy0x"))));
+        assert_eq!(client.fee_denom(), DEFAULT_FEE_DENOM);
+        assert_eq!(client.gas_price(), GAS_PRICE);
+        assert_eq!((client.config.min_gas_price, client.config.max_gas_price), (DEFAULT_MIN_GAS_PRICE, DEFAULT_MAX_GAS_PRICE));
+        // Already on INJ - nothing left to fall back from
+        assert!(!client.fall_back_to_inj_fee(&Err(anyhow!("insufficient fee"))));
+    }
+    
+    #[test]
+    fn test_compression_rejection_detection() {
+        assert!(is_compression_rejected(
+            "Failed to query contract: status: Unimplemented, message: \"Content is compressed with `gzip` which isn't supported\""
+        ));
+        assert!(is_compression_rejected("status: Internal, message: \"grpc-encoding gzip not supported\""));
+        assert!(!is_compression_rejected("status: Unavailable, message: \"connection reset\""));
+    }
+    
+    #[test]
+    fn test_compression_falls_back_when_rejected() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let config = ClientConfig { grpc_compression: true, ..ClientConfig::default() };
+        let client = InjectiveClient::new(config, wallet);
+        assert_eq!(client.compression(), Some(CompressionEncoding::Gzip));
+        client.compression_rejected.store(true, Ordering::Relaxed);
+        assert_eq!(client.compression(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_compression_rejection_is_noticed_between_retries() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let config = ClientConfig { grpc_compression: true, max_retries: 1, ..ClientConfig::default() };
+        let client = InjectiveClient::new(config, wallet);
+        let attempts = std::sync::Mutex::new(Vec::new());
+        let result = client.with_retry("query_contract_smart", "", || async {
+            let mut attempts = attempts.lock().unwrap();
+            attempts.push(client.compression());
+            if attempts.len() == 1 {
+                Err(anyhow!("status: Unimplemented, message: \"Content is compressed with `gzip` which isn't supported\""))
+            } else {
+                Ok(())
+            }
+        }).await;
+        assert!(result.is_ok());
+        // The retry already went out uncompressed
+        assert_eq!(*attempts.lock().unwrap(), vec![Some(CompressionEncoding::Gzip), None]);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_calls() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let client = InjectiveClient::new(ClientConfig::default(), wallet);
+        let metrics = client.metrics();
+        assert!(!metrics.connected);
+        assert_eq!(metrics.last_success_age_secs, None);
+        assert_eq!((metrics.grpc_errors, metrics.reconnects, metrics.sequence), (0, 0, None));
+
+        let _ = client.traced("failing", "", async { Err::<(), _>(anyhow!("unavailable")) }).await;
+        client.traced("ok", "", async { Ok(()) }).await.unwrap();
+        let metrics = client.metrics();
+        assert_eq!(metrics.grpc_errors, 1);
+        assert_eq!(metrics.last_success_age_secs, Some(0));
+    }
+
+    #[test]
+    fn test_tx_hash() {
+        // SHA-256 of the empty input, uppercase like the chain reports it
+        assert_eq!(tx_hash(&[]), "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855");
+        assert_eq!(tx_hash(b"tx").len(), 64);
+    }
+    
     // Note: Real testnet tests would require actual connection
     // These are just unit tests for the structure
 }
\ No newline at end of file
@@ -0,0 +1,171 @@
+/// Contract event listener - subscribes to Tendermint `Tx` events for the mining
+/// contract over websocket so the orchestrator can react immediately when any
+/// miner advances or finalizes an epoch, instead of waiting for the next poll.
+
+use anyhow::{Result, anyhow};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+/// Default Tendermint websocket endpoints
+pub const TESTNET_EVENTS_ENDPOINT: &str = "wss://testnet.sentry.tm.injective.network:443/websocket";
+pub const MAINNET_EVENTS_ENDPOINT: &str = "wss://sentry.tm.injective.network:443/websocket";
+
+/// Maximum delay between reconnection attempts
+const MAX_RECONNECT_DELAY_SECS: u64 = 60;
+
+/// A mining contract event relevant to the orchestrator
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContractEvent {
+    /// `advance_epoch` executed - a new epoch has started
+    EpochAdvanced { epoch: Option<u64> },
+    /// `finalize_epoch` executed - rewards for the epoch can be claimed
+    EpochFinalized { epoch: Option<u64> },
+    /// Any other contract action (commits/reveals/claims by other miners)
+    Other { action: String },
+}
+
+/// Listener for wasm events emitted by a single contract
+pub struct ContractEventListener {
+    ws_endpoint: String,
+    contract_address: String,
+}
+
+impl ContractEventListener {
+    pub fn new(ws_endpoint: impl Into<String>, contract_address: impl Into<String>) -> Self {
+        Self {
+            ws_endpoint: ws_endpoint.into(),
+            contract_address: contract_address.into(),
+        }
+    }
+
+    /// Spawn the listener in the background; reconnects with backoff on failure
+    /// Events are delivered on the returned channel until the receiver is dropped
+    pub fn spawn(self) -> mpsc::Receiver<ContractEvent> {
+        let (tx, rx) = mpsc::channel(32);
+        tokio::spawn(async move {
+            let mut delay = 1;
+            loop {
+                match self.listen(&tx).await {
+                    Ok(()) => return, // receiver dropped
+                    Err(e) => {
+                        log::warn!("Contract event subscription failed: {}, reconnecting in {}s", e, delay);
+                        tokio::time::sleep(Duration::from_secs(delay)).await;
+                        delay = (delay * 2).min(MAX_RECONNECT_DELAY_SECS);
+                    }
+                }
+                if tx.is_closed() {
+                    return;
+                }
+            }
+        });
+        rx
+    }
+
+    /// Subscribe and forward events until the connection drops or the receiver goes away
+    async fn listen(&self, tx: &mpsc::Sender<ContractEvent>) -> Result<()> {
+        let (mut ws, _) = connect_async(self.ws_endpoint.as_str()).await?;
+        let subscribe = json!({
+            "jsonrpc": "2.0",
+            "method": "subscribe",
+            "id": 1,
+            "params": { "query": subscription_query(&self.contract_address) },
+        });
+        ws.send(Message::Text(subscribe.to_string())).await?;
+        log::info!("Subscribed to contract events for {} via {}", self.contract_address, self.ws_endpoint);
+
+        while let Some(msg) = ws.next().await {
+            let text = match msg? {
+                Message::Text(text) => text,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let value: Value = match serde_json::from_str(&text) {
+                Ok(v) => v,
+                Err(e) => {
+                    log::debug!("Ignoring malformed event message: {}", e);
+                    continue;
+                }
+            };
+            if let Some(error) = value.get("error") {
+                return Err(anyhow!("Subscription error: {}", error));
+            }
+            for event in parse_events(&value) {
+                log::debug!("Contract event: {:?}", event);
+                if tx.send(event).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+
+        Err(anyhow!("Event stream closed"))
+    }
+}
+
+/// Tendermint query selecting transactions that touched the contract
+pub fn subscription_query(contract_address: &str) -> String {
+    format!("tm.event='Tx' AND wasm._contract_address='{}'", contract_address)
+}
+
+/// Extract contract events from a Tendermint subscription message
+pub fn parse_events(message: &Value) -> Vec<ContractEvent> {
+    let events = match message.pointer("/result/events") {
+        Some(events) => events,
+        None => return Vec::new(), // subscription ack or unrelated message
+    };
+
+    let strings = |key: &str| -> Vec<String> {
+        events
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default()
+    };
+
+    let epoch = strings("wasm.epoch")
+        .into_iter()
+        .chain(strings("wasm.epoch_number"))
+        .find_map(|s| s.parse::<u64>().ok());
+
+    strings("wasm.action")
+        .into_iter()
+        .map(|action| match action.as_str() {
+            "advance_epoch" => ContractEvent::EpochAdvanced { epoch },
+            "finalize_epoch" => ContractEvent::EpochFinalized { epoch },
+            _ => ContractEvent::Other { action },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_events() {
+        let ack = json!({"jsonrpc": "2.0", "id": 1, "result": {}});
+        assert!(parse_events(&ack).is_empty());
+
+        let msg = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "query": subscription_query("inj1contract"),
+                "events": {
+                    "wasm._contract_address": ["inj1contract"],
+                    "wasm.action": ["advance_epoch"],
+                    "wasm.epoch": ["42"],
+                }
+            }
+        });
+        assert_eq!(parse_events(&msg), vec![ContractEvent::EpochAdvanced { epoch: Some(42) }]);
+
+        let msg = json!({"result": {"events": {"wasm.action": ["reveal_solution"]}}});
+        assert_eq!(
+            parse_events(&msg),
+            vec![ContractEvent::Other { action: "reveal_solution".to_string() }]
+        );
+    }
+}
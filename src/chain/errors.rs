@@ -0,0 +1,137 @@
+/// Classification of contract/transaction errors
+/// The chain only gives us raw log strings, so known failure modes are matched
+/// here once instead of with ad-hoc `contains` checks at every call site
+
+/// A recognized failure reported by the chain or the mining contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainError {
+    /// Revealed nonce is outside the miner's partition for the epoch
+    /// (stale state or a nonce range computed for the wrong epoch)
+    NonceOutOfRange,
+    /// A commitment for this miner and epoch already exists
+    AlreadyCommitted,
+    /// This miner's solution for the epoch has already been revealed
+    AlreadyRevealed,
+    /// The epoch's reward has already been claimed by this miner
+    AlreadyClaimed,
+    /// Message sent in the wrong epoch phase
+    WrongPhase,
+    /// Account sequence mismatch - the tx should be re-signed and retried
+    AccountSequence,
+    /// Not enough INJ to pay for gas
+    InsufficientFunds,
+    /// Refused locally: the fee would exceed the configured `max_fee_inj`
+    FeeCapExceeded,
+    /// Contract returned an empty/partial response (migration or node resync) - retry later
+    ContractNotReady,
+    /// `advance_epoch` found the epoch already advanced (another miner got there first)
+    AlreadyAdvanced,
+    /// The contract refused the sender (e.g. a permissioned `advance_epoch`)
+    Unauthorized,
+    /// Anything we don't have specific handling for
+    Other,
+}
+
+impl ChainError {
+    /// Classify a raw error message
+    pub fn from_message(message: &str) -> Self {
+        let msg = message.to_lowercase();
+        if msg.contains("nonce out of range") || msg.contains("nonce not in range") {
+            ChainError::NonceOutOfRange
+        } else if msg.contains("already committed") {
+            ChainError::AlreadyCommitted
+        } else if msg.contains("already revealed") {
+            ChainError::AlreadyRevealed
+        } else if msg.contains("already claimed") || msg.contains("already been claimed") {
+            ChainError::AlreadyClaimed
+        } else if msg.contains("wrong phase") {
+            ChainError::WrongPhase
+        } else if msg.contains("account sequence") {
+            ChainError::AccountSequence
+        } else if msg.contains("contract not ready") {
+            ChainError::ContractNotReady
+        } else if msg.contains("already advanced") {
+            ChainError::AlreadyAdvanced
+        } else if (msg.contains("unauthorized") || msg.contains("not authorized"))
+            // The SDK reports bad signatures as "unauthorized" too - that's a signing problem
+            && !msg.contains("signature verification failed")
+        {
+            ChainError::Unauthorized
+        } else if msg.contains("fee cap exceeded") {
+            ChainError::FeeCapExceeded
+        } else if msg.contains("insufficient funds") || msg.contains("insufficient fee") {
+            ChainError::InsufficientFunds
+        } else {
+            ChainError::Other
+        }
+    }
+
+    /// Classify an error returned from a chain call
+    pub fn classify(error: &anyhow::Error) -> Self {
+        // Include the whole context chain - the contract message is usually the innermost cause
+        Self::from_message(&format!("{:#}", error))
+    }
+
+    /// Whether the contract itself refused the message, as opposed to the node, the
+    /// network, our account sequence or a local check - sending it again won't help
+    pub fn is_contract_rejection(error: &anyhow::Error) -> bool {
+        match Self::classify(error) {
+            ChainError::NonceOutOfRange
+            | ChainError::AlreadyCommitted
+            | ChainError::AlreadyRevealed
+            | ChainError::AlreadyClaimed
+            | ChainError::WrongPhase
+            | ChainError::AlreadyAdvanced
+            | ChainError::Unauthorized => true,
+            ChainError::AccountSequence
+            | ChainError::InsufficientFunds
+            | ChainError::FeeCapExceeded
+            | ChainError::ContractNotReady => false,
+            ChainError::Other => format!("{:#}", error).to_lowercase().contains("execute wasm contract failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            ChainError::from_message("failed to execute message; message index: 0: Nonce out of range: execute wasm contract failed"),
+            ChainError::NonceOutOfRange
+        );
+        assert_eq!(ChainError::from_message("Already committed for this epoch"), ChainError::AlreadyCommitted);
+        assert_eq!(ChainError::from_message("Solution already revealed for epoch 7"), ChainError::AlreadyRevealed);
+        assert_eq!(ChainError::from_message("Reward has already been claimed"), ChainError::AlreadyClaimed);
+        assert_eq!(
+            ChainError::from_message("account sequence mismatch, expected 12, got 11"),
+            ChainError::AccountSequence
+        );
+        assert_eq!(ChainError::from_message("connection reset"), ChainError::Other);
+        assert_eq!(
+            ChainError::from_message("execute wasm contract failed: Epoch already advanced"),
+            ChainError::AlreadyAdvanced
+        );
+        assert_eq!(ChainError::from_message("Unauthorized: execute wasm contract failed"), ChainError::Unauthorized);
+        assert_eq!(
+            ChainError::from_message("signature verification failed; please verify account number (1) and chain-id (injective-888): unauthorized"),
+            ChainError::Other
+        );
+
+        let err = anyhow::anyhow!("nonce out of range").context("Reveal transaction failed");
+        assert_eq!(ChainError::classify(&err), ChainError::NonceOutOfRange);
+    }
+
+    #[test]
+    fn test_contract_rejections() {
+        let rejected = |message: &str| ChainError::is_contract_rejection(&anyhow::anyhow!(message.to_string()));
+        assert!(rejected("Wrong phase: execute wasm contract failed"));
+        assert!(rejected("Reveal failed: Failed after 1 retries: invalid digest: execute wasm contract failed"));
+        assert!(!rejected("account sequence mismatch, expected 12, got 11"));
+        assert!(!rejected("Reveal transaction timeout after 30s (cancelled)"));
+        assert!(!rejected("transport error: connection reset"));
+        assert!(!rejected("Contract not ready: empty response"));
+    }
+}
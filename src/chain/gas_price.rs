@@ -0,0 +1,182 @@
+/// Live gas price - Injective's recommended price moves, so a fixed price either
+/// overpays or leaves transactions stuck during fee spikes. Fetched prices are
+/// always clamped so a bad node or oracle can't make the miner pay absurd fees
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Default bounds for fetched gas prices (base units of INJ per gas)
+pub const DEFAULT_MIN_GAS_PRICE: u128 = 160_000_000;
+pub const DEFAULT_MAX_GAS_PRICE: u128 = 2_000_000_000;
+
+/// Denom fees are paid in unless configured otherwise
+pub const DEFAULT_FEE_DENOM: &str = "inj";
+
+/// Where the gas price comes from
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum GasPriceSource {
+    /// The built-in `GAS_PRICE`
+    #[default]
+    Static,
+    /// The node's configured minimum gas price (LCD `cosmos/base/node/v1beta1/config`)
+    Node,
+    /// A gas-price oracle URL answering with a number or `{"gas_price": ...}`
+    Oracle(String),
+}
+
+impl FromStr for GasPriceSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "static" => Ok(GasPriceSource::Static),
+            "node" => Ok(GasPriceSource::Node),
+            lower if lower.starts_with("http://") || lower.starts_with("https://") => {
+                Ok(GasPriceSource::Oracle(s.to_string()))
+            }
+            _ => Err(anyhow!("Unknown gas price source '{}' (expected 'static', 'node' or an oracle URL)", s)),
+        }
+    }
+}
+
+impl fmt::Display for GasPriceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasPriceSource::Static => write!(f, "static"),
+            GasPriceSource::Node => write!(f, "node"),
+            GasPriceSource::Oracle(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// Split a coin like `160000000.0inj` into amount and denom (empty for a bare number)
+fn split_coin(s: &str) -> (&str, &str) {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    s.split_at(split)
+}
+
+/// Parse a price like `160000000.000000000000000000inj` or `500000000`; fractions round up
+fn parse_price(s: &str) -> Option<u128> {
+    let (amount, _denom) = split_coin(s);
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let whole: u128 = whole.parse().ok()?;
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let round_up = fraction.chars().any(|c| c != '0');
+    Some(whole + round_up as u128)
+}
+
+/// Gas price in `denom` from a node `config` response: `minimum_gas_price` may list
+/// several denoms (`"160000000.0inj,1.0peggy0x..."`)
+pub fn parse_node_gas_price(response: &Value, denom: &str) -> Option<u128> {
+    response.get("minimum_gas_price")?
+        .as_str()?
+        .split(',')
+        .find(|coin| split_coin(coin).1 == denom)
+        .and_then(parse_price)
+}
+
+/// Denoms the node accepts fees in (those it lists a minimum gas price for)
+pub fn node_fee_denoms(response: &Value) -> Vec<String> {
+    response.get("minimum_gas_price")
+        .and_then(Value::as_str)
+        .map(|prices| prices.split(',').map(|coin| split_coin(coin).1.to_string()).filter(|d| !d.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Check a fee denom is shaped like a bank denom Injective can charge fees in:
+/// `inj`, a bridged `peggy0x<address>`, an `ibc/<hash>` or a `factory/<creator>/<name>` token
+pub fn validate_fee_denom(denom: &str) -> Result<()> {
+    let is_hex = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_hexdigit());
+    let valid = denom == DEFAULT_FEE_DENOM
+        || denom.strip_prefix("peggy0x").map_or(false, |a| is_hex(a, 40))
+        || denom.strip_prefix("ibc/").map_or(false, |h| is_hex(h, 64))
+        || denom.strip_prefix("factory/").map_or(false, |rest| {
+            matches!(rest.split_once('/'), Some((creator, name)) if creator.starts_with("inj1") && !name.is_empty())
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid fee denom '{}' (expected inj, peggy0x<address>, ibc/<hash> or factory/<creator>/<name>)", denom))
+    }
+}
+
+/// Whether a rejected transaction failed because of the fee denom rather than the
+/// amount - the ante handler reports both as "insufficient fee(s)"
+pub fn is_fee_denom_rejection(message: &str) -> bool {
+    let msg = message.to_lowercase();
+    msg.contains("insufficient fee") || (msg.contains("fee") && msg.contains("denom"))
+}
+
+/// Gas price from an oracle response: a bare number or string, or `{"gas_price": ...}`
+pub fn parse_oracle_gas_price(response: &Value) -> Option<u128> {
+    match response {
+        Value::Number(n) => n.as_u64().map(u128::from).or_else(|| n.as_f64().map(|f| f.ceil() as u128)),
+        Value::String(s) => parse_price(s),
+        Value::Object(map) => map.get("gas_price").and_then(parse_oracle_gas_price),
+        _ => None,
+    }
+}
+
+/// Keep a fetched price within `[min, max]`
+pub fn clamp_gas_price(price: u128, min: u128, max: u128) -> u128 {
+    if price < min {
+        log::warn!("Fetched gas price {} is below the minimum {}, using the minimum", price, min);
+        min
+    } else if price > max {
+        log::warn!("Fetched gas price {} is above the maximum {}, using the maximum", price, max);
+        max
+    } else {
+        price
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_sources_and_prices() {
+        assert_eq!("node".parse::<GasPriceSource>().unwrap(), GasPriceSource::Node);
+        assert_eq!(
+            "https://gas.example.com/inj".parse::<GasPriceSource>().unwrap(),
+            GasPriceSource::Oracle("https://gas.example.com/inj".to_string())
+        );
+        assert!("cheap".parse::<GasPriceSource>().is_err());
+
+        let node = json!({"minimum_gas_price": "1.5peggy0xdac,160000000.000000000000000000inj"});
+        assert_eq!(parse_node_gas_price(&node, "inj"), Some(160_000_000));
+        assert_eq!(parse_node_gas_price(&node, "peggy0xdac"), Some(2));
+        assert_eq!(parse_node_gas_price(&json!({"minimum_gas_price": "0.25inj"}), "inj"), Some(1));
+        assert_eq!(parse_node_gas_price(&json!({"pruning_keep_recent": "0"}), "inj"), None);
+        assert_eq!(node_fee_denoms(&node), vec!["peggy0xdac", "inj"]);
+
+        assert_eq!(parse_oracle_gas_price(&json!(700000000)), Some(700_000_000));
+        assert_eq!(parse_oracle_gas_price(&json!({"gas_price": "650000000inj"})), Some(650_000_000));
+        assert_eq!(parse_oracle_gas_price(&json!({"price": 1})), None);
+    }
+
+    #[test]
+    fn test_fee_denoms() {
+        assert!(validate_fee_denom("inj").is_ok());
+        assert!(validate_fee_denom("peggy0xdAC17F958D2ee523a2206206994597C13D831ec7").is_ok());
+        assert!(validate_fee_denom("factory/inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz/gas").is_ok());
+        assert!(validate_fee_denom("usdt").is_err());
+        assert!(validate_fee_denom("peggy0x123").is_err());
+
+        assert!(is_fee_denom_rejection("insufficient fees; got: 350peggy0xdac required: 56000000000000000inj: insufficient fee"));
+        assert!(!is_fee_denom_rejection("account sequence mismatch"));
+    }
+
+    #[test]
+    fn test_clamp_gas_price() {
+        assert_eq!(clamp_gas_price(0, DEFAULT_MIN_GAS_PRICE, DEFAULT_MAX_GAS_PRICE), DEFAULT_MIN_GAS_PRICE);
+        assert_eq!(clamp_gas_price(500_000_000, DEFAULT_MIN_GAS_PRICE, DEFAULT_MAX_GAS_PRICE), 500_000_000);
+        assert_eq!(clamp_gas_price(u128::MAX, DEFAULT_MIN_GAS_PRICE, DEFAULT_MAX_GAS_PRICE), DEFAULT_MAX_GAS_PRICE);
+    }
+}
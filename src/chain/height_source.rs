@@ -0,0 +1,144 @@
+/// Dedicated block-height source - reveal timing hangs on the block height, so it can
+/// come from a separate endpoint (e.g. a local full node) instead of the sentry the
+/// rest of the traffic goes to. The main endpoint stays the fallback
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// Where block heights are read from when not from the main endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeightSource {
+    /// A Cosmos LCD (REST) endpoint: `cosmos/base/tendermint/v1beta1/blocks/latest`
+    Lcd(String),
+    /// A Tendermint/CometBFT RPC endpoint: `status`
+    Rpc(String),
+}
+
+impl HeightSource {
+    /// URL answering with the latest height
+    pub fn url(&self) -> String {
+        match self {
+            HeightSource::Lcd(base) => format!("{}/cosmos/base/tendermint/v1beta1/blocks/latest", base.trim_end_matches('/')),
+            HeightSource::Rpc(base) => format!("{}/status", base.trim_end_matches('/')),
+        }
+    }
+
+    /// Whether the endpoint is on this machine - the only kind that may bypass a SOCKS5
+    /// proxy, since talking to it leaks nothing
+    pub fn is_loopback(&self) -> bool {
+        let (HeightSource::Lcd(base) | HeightSource::Rpc(base)) = self;
+        let host = match reqwest::Url::parse(base).ok().and_then(|url| url.host_str().map(str::to_string)) {
+            Some(host) => host,
+            None => return false,
+        };
+        host.eq_ignore_ascii_case("localhost")
+            || matches!(host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>(), Ok(ip) if ip.is_loopback())
+    }
+
+    /// The height in a response from `url`
+    pub fn parse(&self, response: &Value) -> Option<u64> {
+        match self {
+            HeightSource::Lcd(_) => parse_lcd_block_height(response),
+            HeightSource::Rpc(_) => parse_rpc_status_height(response),
+        }
+    }
+}
+
+impl FromStr for HeightSource {
+    type Err = anyhow::Error;
+
+    /// `rpc:<url>` for a Tendermint RPC endpoint, `lcd:<url>` or a bare URL for LCD
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (kind, url) = match s.split_once(':') {
+            Some((kind, url)) if kind.eq_ignore_ascii_case("rpc") || kind.eq_ignore_ascii_case("lcd") => (kind.to_lowercase(), url),
+            _ => ("lcd".to_string(), s),
+        };
+        let lower = url.to_lowercase();
+        if !lower.starts_with("http://") && !lower.starts_with("https://") {
+            return Err(anyhow!("Invalid block height endpoint '{}' (expected an http(s) URL, optionally prefixed with 'rpc:' or 'lcd:')", s));
+        }
+        Ok(if kind == "rpc" { HeightSource::Rpc(url.to_string()) } else { HeightSource::Lcd(url.to_string()) })
+    }
+}
+
+impl fmt::Display for HeightSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeightSource::Lcd(url) => write!(f, "lcd:{}", url),
+            HeightSource::Rpc(url) => write!(f, "rpc:{}", url),
+        }
+    }
+}
+
+/// Extract `block.header.height` (a decimal string) from an LCD `blocks/latest` response
+pub fn parse_lcd_block_height(response: &Value) -> Option<u64> {
+    response
+        .get("block")?
+        .get("header")?
+        .get("height")?
+        .as_str()?
+        .parse()
+        .ok()
+}
+
+/// Extract `sync_info.latest_block_height` from a Tendermint RPC `status` response,
+/// with or without the JSON-RPC `result` envelope
+fn parse_rpc_status_height(response: &Value) -> Option<u64> {
+    response
+        .get("result")
+        .unwrap_or(response)
+        .get("sync_info")?
+        .get("latest_block_height")?
+        .as_str()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lcd_block_height() {
+        let response = serde_json::json!({"block": {"header": {"chain_id": "injective-888", "height": "91234567"}}});
+        assert_eq!(parse_lcd_block_height(&response), Some(91234567));
+        assert_eq!(parse_lcd_block_height(&serde_json::json!({"block": {"header": {}}})), None);
+        assert_eq!(parse_lcd_block_height(&serde_json::json!({"code": 5, "message": "not found"})), None);
+    }
+
+    #[test]
+    fn test_parse_rpc_status_height() {
+        let response = serde_json::json!({"jsonrpc": "2.0", "id": -1, "result": {"sync_info": {"latest_block_height": "91234568", "catching_up": false}}});
+        assert_eq!(parse_rpc_status_height(&response), Some(91234568));
+        assert_eq!(parse_rpc_status_height(&response["result"]), Some(91234568));
+        assert_eq!(parse_rpc_status_height(&serde_json::json!({"result": {}})), None);
+    }
+
+    #[test]
+    fn test_height_source_from_str() {
+        let rpc: HeightSource = "rpc:http://127.0.0.1:26657/".parse().unwrap();
+        assert_eq!(rpc, HeightSource::Rpc("http://127.0.0.1:26657/".to_string()));
+        assert_eq!(rpc.url(), "http://127.0.0.1:26657/status");
+
+        let lcd: HeightSource = "http://127.0.0.1:1317".parse().unwrap();
+        assert_eq!(lcd, HeightSource::Lcd("http://127.0.0.1:1317".to_string()));
+        assert_eq!(lcd.url(), "http://127.0.0.1:1317/cosmos/base/tendermint/v1beta1/blocks/latest");
+        assert_eq!("lcd:https://lcd.example.com".parse::<HeightSource>().unwrap().to_string(), "lcd:https://lcd.example.com");
+
+        assert!("localhost:26657".parse::<HeightSource>().is_err());
+        assert!("rpc:".parse::<HeightSource>().is_err());
+    }
+
+    #[test]
+    fn test_height_source_is_loopback() {
+        for local in ["rpc:http://127.0.0.1:26657", "http://localhost:1317", "rpc:http://[::1]:26657/"] {
+            assert!(local.parse::<HeightSource>().unwrap().is_loopback(), "{}", local);
+        }
+        for remote in ["https://lcd.example.com", "rpc:http://10.0.0.5:26657", "http://127.0.0.1.example.com"] {
+            assert!(!remote.parse::<HeightSource>().unwrap().is_loopback(), "{}", remote);
+        }
+    }
+}
@@ -1,5 +1,10 @@
 use super::{ExecuteMsg, MessageBuilder};
+use anyhow::anyhow;
 use blake2::{Blake2b512, Digest};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+use std::str::FromStr;
 
 /// Message for committing a mining solution
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -32,7 +37,7 @@ impl MessageBuilder for CommitSolutionMsg {
 /// Create a commitment hash from solution components
 /// Commitment = Blake2b512(nonce || digest || salt), truncated to 32 bytes
 /// This MUST match the contract's create_solution_commitment function
-fn create_commitment(nonce: [u8; 8], digest: [u8; 16], salt: [u8; 32]) -> [u8; 32] {
+pub fn create_commitment(nonce: [u8; 8], digest: [u8; 16], salt: [u8; 32]) -> [u8; 32] {
     let mut hasher = Blake2b512::new();
     hasher.update(&nonce);
     hasher.update(&digest);
@@ -45,6 +50,112 @@ fn create_commitment(nonce: [u8; 8], digest: [u8; 16], salt: [u8; 32]) -> [u8; 3
     commitment
 }
 
+/// Derive the commitment salt as HMAC-SHA256(secret, epoch || nonce)
+/// With the wallet key as `secret`, a reveal can be rebuilt from the epoch and nonce
+/// alone - nothing else has to survive a lost state file
+pub fn derive_salt(secret: &[u8], epoch: u64, nonce: [u8; 8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(&epoch.to_be_bytes());
+    mac.update(&nonce);
+    let mut salt = [0u8; 32];
+    salt.copy_from_slice(&mac.finalize().into_bytes());
+    salt
+}
+
+/// Where the commitment salt comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaltSource {
+    /// Fresh random salt per solution; the reveal depends on the saved state
+    #[default]
+    Random,
+    /// `derive_salt(wallet key, epoch, nonce)`; the reveal can be recovered without saved state
+    Derived,
+}
+
+impl FromStr for SaltSource {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "random" => Ok(SaltSource::Random),
+            "derived" => Ok(SaltSource::Derived),
+            other => Err(anyhow!("Unknown salt source '{}' (expected 'random' or 'derived')", other)),
+        }
+    }
+}
+
+impl fmt::Display for SaltSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaltSource::Random => write!(f, "random"),
+            SaltSource::Derived => write!(f, "derived"),
+        }
+    }
+}
+
+/// Commitment hashing scheme - selects the formula matching the deployed contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitmentVersion {
+    /// Blake2b512(address || nonce || digest || epoch) - contracts before V3.4
+    Unsalted,
+    /// Blake2b512(nonce || digest || salt) - V3.4 and later
+    #[default]
+    Salted,
+}
+
+impl CommitmentVersion {
+    /// Compute the commitment for a solution under this scheme
+    /// `miner` and `epoch` are only hashed by `Unsalted`, `salt` only by `Salted`
+    pub fn compute(
+        &self,
+        miner: &str,
+        epoch: u64,
+        nonce: [u8; 8],
+        digest: [u8; 16],
+        salt: [u8; 32],
+    ) -> [u8; 32] {
+        match self {
+            CommitmentVersion::Salted => create_commitment(nonce, digest, salt),
+            // Shared with the mobile miner so both hash the epoch in the contract's byte order
+            CommitmentVersion::Unsalted => gmine_mobile::byte_order::unsalted_commitment(miner, epoch, nonce, digest),
+        }
+    }
+    
+    /// Whether a contract (cw2 version string) verifies commitments with this scheme
+    pub fn supports_contract(&self, version: &str) -> bool {
+        let mut parts = version.trim_start_matches('v').split('.');
+        let major = parts.next().and_then(|p| p.parse::<u64>().ok());
+        let minor = parts.next().and_then(|p| p.parse::<u64>().ok()).unwrap_or(0);
+        match self {
+            CommitmentVersion::Unsalted => major == Some(3) && minor < 4,
+            CommitmentVersion::Salted => major == Some(3) && minor >= 4,
+        }
+    }
+}
+
+impl FromStr for CommitmentVersion {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "unsalted" | "v1" => Ok(CommitmentVersion::Unsalted),
+            "salted" | "v2" => Ok(CommitmentVersion::Salted),
+            other => Err(anyhow!("Unknown commitment version '{}' (expected 'salted' or 'unsalted')", other)),
+        }
+    }
+}
+
+impl fmt::Display for CommitmentVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitmentVersion::Unsalted => write!(f, "unsalted"),
+            CommitmentVersion::Salted => write!(f, "salted"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,4 +225,77 @@ mod tests {
         let different_commitment = create_commitment([8, 7, 6, 5, 4, 3, 2, 1], digest, salt);
         assert_ne!(commitment, different_commitment);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_byte_order_vectors() {
+        // Same vectors the mobile miner is tested against: nonce little-endian in both
+        // the commitment and the reveal, epoch big-endian
+        use gmine_mobile::byte_order::vectors;
+        use crate::chain::messages::RevealSolutionMsg;
+        
+        let reveal = RevealSolutionMsg::from_solution(vectors::NONCE, vectors::DIGEST, [0; 32]);
+        assert_eq!(reveal.nonce, vectors::NONCE_BYTES);
+        
+        let salted = CommitmentVersion::Salted.compute(vectors::MINER, vectors::EPOCH, reveal.nonce, vectors::DIGEST, [0; 32]);
+        assert_eq!(hex::encode(salted), vectors::SALTED_COMMITMENT);
+        let unsalted = CommitmentVersion::Unsalted.compute(vectors::MINER, vectors::EPOCH, reveal.nonce, vectors::DIGEST, [0; 32]);
+        assert_eq!(hex::encode(unsalted), vectors::UNSALTED_COMMITMENT);
+    }
+    
+    #[test]
+    #[ignore = "ON_CHAIN_COMMITMENT needs a commit/reveal pair the contract accepted"]
+    fn test_matches_on_chain_commitment() {
+        use gmine_mobile::byte_order::vectors;
+        
+        let accepted = vectors::ON_CHAIN_COMMITMENT.expect("record ON_CHAIN_COMMITMENT from an accepted commit and reveal");
+        let commitment = CommitmentVersion::Salted.compute(accepted.miner, accepted.epoch, accepted.nonce_bytes, accepted.digest, accepted.salt);
+        assert_eq!(hex::encode(commitment), accepted.commitment, "commit {}, reveal {}", accepted.commit_tx, accepted.reveal_tx);
+    }
+    
+    #[test]
+    fn test_commitment_versions() {
+        let (nonce, digest, salt) = ([1u8; 8], [2u8; 16], [3u8; 32]);
+        let miner = "inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz";
+        
+        // Default scheme is the current salted formula
+        assert_eq!(CommitmentVersion::default(), CommitmentVersion::Salted);
+        assert_eq!(
+            CommitmentVersion::Salted.compute(miner, 7, nonce, digest, salt),
+            create_commitment(nonce, digest, salt)
+        );
+        
+        // Unsalted binds the commitment to miner and epoch instead
+        let unsalted = CommitmentVersion::Unsalted.compute(miner, 7, nonce, digest, salt);
+        assert_ne!(unsalted, create_commitment(nonce, digest, salt));
+        assert_ne!(unsalted, CommitmentVersion::Unsalted.compute(miner, 8, nonce, digest, salt));
+        assert_eq!(unsalted, CommitmentVersion::Unsalted.compute(miner, 7, nonce, digest, [0; 32]));
+        
+        assert!(CommitmentVersion::Salted.supports_contract("3.5.0"));
+        assert!(!CommitmentVersion::Salted.supports_contract("3.3.0"));
+        assert!(CommitmentVersion::Unsalted.supports_contract("v3.3"));
+        assert!(!CommitmentVersion::Unsalted.supports_contract("3.4.1"));
+        
+        assert_eq!("Unsalted".parse::<CommitmentVersion>().unwrap(), CommitmentVersion::Unsalted);
+        assert!("sha256".parse::<CommitmentVersion>().is_err());
+    }
+    
+    #[test]
+    fn test_derived_salt_is_deterministic() {
+        let secret = [9u8; 32];
+        let (nonce, digest) = ([1u8; 8], [2u8; 16]);
+        
+        let salt = derive_salt(&secret, 42, nonce);
+        assert_eq!(salt, derive_salt(&secret, 42, nonce));
+        assert_ne!(salt, derive_salt(&secret, 43, nonce));
+        assert_ne!(salt, derive_salt(&secret, 42, [2u8; 8]));
+        assert_ne!(salt, derive_salt(&[8u8; 32], 42, nonce));
+        
+        // A reveal rebuilt from epoch + nonce reproduces the original commitment
+        let committed = create_commitment(nonce, digest, salt);
+        assert_eq!(create_commitment(nonce, digest, derive_salt(&secret, 42, nonce)), committed);
+        
+        assert_eq!("Derived".parse::<SaltSource>().unwrap(), SaltSource::Derived);
+        assert_eq!(SaltSource::default().to_string(), "random");
+        assert!("hmac".parse::<SaltSource>().is_err());
+    }
+}
@@ -6,19 +6,21 @@ mod advance;
 mod stake;
 mod unstake;
 
-pub use commit::CommitSolutionMsg;
+pub use commit::{CommitSolutionMsg, CommitmentVersion, SaltSource, create_commitment, derive_salt};
 pub use reveal::RevealSolutionMsg;
 pub use claim::ClaimRewardMsg;
 pub use finalize::FinalizeEpochMsg;
 pub use advance::AdvanceEpochMsg;
-pub use stake::StakeTokensMsg;
+pub use stake::{StakeTokensMsg, MIN_STAKE_AMOUNT};
 pub use unstake::UnstakeTokensMsg;
 
+use anyhow::anyhow;
 use serde::{Serialize, Deserialize};
+use serde_json::Value;
 use cosmwasm_std::Uint128;
 
 /// Base message structure for CosmWasm ExecuteMsg
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     CommitSolution { commitment: [u8; 32] },
@@ -29,6 +31,53 @@ pub enum ExecuteMsg {
     UnstakeTokens { amount: Uint128 },
 }
 
+impl ExecuteMsg {
+    /// Contract message name, e.g. `commit_solution`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExecuteMsg::CommitSolution { .. } => "commit_solution",
+            ExecuteMsg::RevealSolution { .. } => "reveal_solution",
+            ExecuteMsg::ClaimReward { .. } => "claim_reward",
+            ExecuteMsg::FinalizeEpoch { .. } => "finalize_epoch",
+            ExecuteMsg::AdvanceEpoch {} => "advance_epoch",
+            ExecuteMsg::UnstakeTokens { .. } => "unstake_tokens",
+        }
+    }
+
+    /// The message body without the `{name: ...}` wrapper
+    pub fn payload(&self) -> Value {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(mut map)) => map.remove(self.name()).unwrap_or_else(|| Value::Object(Default::default())),
+            _ => Value::Object(Default::default()),
+        }
+    }
+
+    /// Parse a contract message, accepting the short names older callers use
+    /// (`commit`, `reveal`, `claim_rewards`)
+    pub fn from_json(msg: &Value) -> anyhow::Result<Self> {
+        let (key, body) = match msg.as_object() {
+            Some(map) if map.len() == 1 => map.iter().next().unwrap(),
+            _ => return Err(anyhow!("Expected a single-key contract message, got: {}", msg)),
+        };
+        let name = match key.as_str() {
+            "commit" => "commit_solution",
+            "reveal" => "reveal_solution",
+            "claim_rewards" => "claim_reward",
+            other => other,
+        };
+        let mut wrapped = serde_json::Map::new();
+        wrapped.insert(name.to_string(), body.clone());
+        serde_json::from_value(Value::Object(wrapped))
+            .map_err(|e| anyhow!("Unsupported or malformed {} message: {}", key, e))
+    }
+}
+
+impl MessageBuilder for ExecuteMsg {
+    fn build_msg(&self) -> ExecuteMsg {
+        self.clone()
+    }
+}
+
 /// Contract address for the GMINE mining contract on testnet
 pub const MINING_CONTRACT_ADDRESS: &str = "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66"; // V3.5 with migration capability
 
@@ -47,4 +96,58 @@ pub trait MessageBuilder {
         let msg = self.build_msg();
         Ok(serde_json::to_vec(&msg)?)
     }
+
+    /// The message as the JSON value `execute_contract` takes
+    fn to_json(&self) -> anyhow::Result<Value> {
+        Ok(serde_json::to_value(self.build_msg())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(builder: &dyn MessageBuilder) -> ExecuteMsg {
+        let json = builder.to_json().unwrap();
+        let parsed = ExecuteMsg::from_json(&json).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+        parsed
+    }
+
+    #[test]
+    fn test_each_message_type_round_trips() {
+        let commit = round_trip(&CommitSolutionMsg::from_commitment([7u8; 32]));
+        assert_eq!(commit.name(), "commit_solution");
+        assert_eq!(commit.payload(), json!({"commitment": vec![7u8; 32]}));
+
+        let reveal = round_trip(&RevealSolutionMsg::new([1u8; 8], [2u8; 16], [3u8; 32]));
+        assert_eq!(reveal.name(), "reveal_solution");
+        assert_eq!(reveal.payload()["salt"], json!(vec![3u8; 32]));
+
+        let claim = round_trip(&ClaimRewardMsg::new(42));
+        assert_eq!(claim.name(), "claim_reward");
+        assert_eq!(claim.payload(), json!({"epoch_number": 42}));
+
+        let finalize = round_trip(&FinalizeEpochMsg::new(42));
+        assert_eq!(finalize.name(), "finalize_epoch");
+        assert_eq!(finalize.payload(), json!({"epoch_number": 42}));
+
+        let advance = round_trip(&AdvanceEpochMsg::new());
+        assert_eq!(advance.name(), "advance_epoch");
+        assert_eq!(advance.payload(), json!({}));
+    }
+
+    #[test]
+    fn test_from_json_aliases_and_errors() {
+        let msg = ExecuteMsg::from_json(&json!({"commit": {"commitment": vec![0u8; 32]}})).unwrap();
+        assert_eq!(msg.name(), "commit_solution");
+        let msg = ExecuteMsg::from_json(&json!({"claim_rewards": {"epoch_number": 3}})).unwrap();
+        assert!(matches!(msg, ExecuteMsg::ClaimReward { epoch_number: 3 }));
+
+        // Wrong lengths and unknown messages are rejected rather than signed
+        assert!(ExecuteMsg::from_json(&json!({"commit_solution": {"commitment": [1, 2, 3]}})).is_err());
+        assert!(ExecuteMsg::from_json(&json!({"transfer": {}})).is_err());
+        assert!(ExecuteMsg::from_json(&json!({"advance_epoch": {}, "finalize_epoch": {}})).is_err());
+    }
 }
\ No newline at end of file
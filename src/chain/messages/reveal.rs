@@ -24,7 +24,7 @@ impl RevealSolutionMsg {
     /// Create from a nonce value and digest
     pub fn from_solution(nonce: u64, digest: [u8; 16], salt: [u8; 32]) -> Self {
         Self {
-            nonce: nonce.to_le_bytes(),
+            nonce: gmine_mobile::byte_order::nonce_bytes(nonce),
             digest,
             salt,
         }
@@ -6,7 +6,7 @@ use anyhow::{Result, bail};
 const INJECTIVE_BLOCK_TIME_SECONDS: u64 = 5;
 
 /// Minimum stake amount (1 POWER = 1_000_000 micro)
-const MIN_STAKE_AMOUNT: u128 = 1_000_000;
+pub const MIN_STAKE_AMOUNT: u128 = 1_000_000;
 
 /// Message payload for staking POWER tokens - matches contract's ReceiveMsg
 #[derive(Serialize)]
@@ -8,12 +8,29 @@ pub mod queries;
 pub mod account_types;
 pub mod bridge_client;
 pub mod rust_signer;
+pub mod tx_log;
+pub mod call_trace;
+pub mod contract_events;
+pub mod address;
+pub mod errors;
+pub mod socks;
+pub mod gas_price;
+pub mod height_source;
+pub mod warmup;
 
 pub use wallet::{InjectiveWallet, TransactionSigner};
 // Use the real client implementation
-pub use client_real::{InjectiveClient, ClientConfig};
+pub use client_real::{InjectiveClient, ClientConfig, SigningMethod};
 pub use messages::{CommitSolutionMsg, RevealSolutionMsg, ClaimRewardMsg, StakeTokensMsg, UnstakeTokensMsg};
 pub use tx_builder::ProperTxBuilder;
 pub use queries::{query_epoch_info, EpochInfoResponse, ContractAddresses};
-pub use bridge_client::{BridgeClient, SignRequest, MessageData, Coin};
-pub use rust_signer::RustSigner;
\ No newline at end of file
+pub use bridge_client::{BridgeClient, SignRequest, MessageData, BankSend, Coin, BRIDGE_FEE};
+pub use rust_signer::{PreparedTx, RustSigner};
+pub use tx_log::{TxLog, TxLogEntry};
+pub use call_trace::{CallTrace, ChainCall, CALL_TRACE_CAPACITY};
+pub use contract_events::{ContractEvent, ContractEventListener};
+pub use errors::ChainError;
+pub use socks::Socks5Proxy;
+pub use gas_price::{GasPriceSource, DEFAULT_FEE_DENOM, DEFAULT_MAX_GAS_PRICE, DEFAULT_MIN_GAS_PRICE};
+pub use height_source::HeightSource;
+pub use warmup::{warm_up, WarmUp};
\ No newline at end of file
@@ -69,7 +69,8 @@ pub use cosmos::base::v1beta1::Coin;
 pub use cosmos::tx::v1beta1::{
     Tx, TxRaw, TxBody, AuthInfo, SignDoc, SignerInfo, ModeInfo, Fee, Any, SignMode,
     SimulateRequest, SimulateResponse, GasInfo, BroadcastTxRequest, BroadcastTxResponse,
-    BroadcastMode, TxResponse, service_client::ServiceClient
+    BroadcastMode, TxResponse, GetTxRequest, GetTxResponse, Event, EventAttribute,
+    service_client::ServiceClient
 };
 pub use cosmos::auth::v1beta1::{
     BaseAccount, QueryAccountRequest, QueryAccountResponse,
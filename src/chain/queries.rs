@@ -27,17 +27,64 @@ pub struct MinerInfoQuery {
     pub miner: String,  // Contract expects "miner" field only
 }
 
+/// Query message to get a miner's stored commitment for an epoch
+#[derive(Serialize, Debug)]
+pub struct GetCommitmentMsg {
+    pub commitment: CommitmentQuery,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommitmentQuery {
+    pub miner: String,
+    pub epoch: u64,
+}
+
 /// Empty struct for queries with no parameters
 #[derive(Serialize, Debug)]
 pub struct EmptyStruct {}
 
 /// Phase information with block timing
-#[derive(Deserialize, Debug, Clone)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PhaseInfo {
     Commit { ends_at: u64 },
     Reveal { ends_at: u64 },
     Settlement { ends_at: u64 },
+    /// Phase added by a newer contract version - carries the raw phase name
+    /// so the miner can wait it out instead of failing to parse epoch info
+    Unknown(String),
+}
+
+/// Phases this miner knows how to act on
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum KnownPhase {
+    Commit { ends_at: u64 },
+    Reveal { ends_at: u64 },
+    Settlement { ends_at: u64 },
+}
+
+impl<'de> Deserialize<'de> for PhaseInfo {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if let Ok(known) = serde_json::from_value::<KnownPhase>(value.clone()) {
+            return Ok(match known {
+                KnownPhase::Commit { ends_at } => PhaseInfo::Commit { ends_at },
+                KnownPhase::Reveal { ends_at } => PhaseInfo::Reveal { ends_at },
+                KnownPhase::Settlement { ends_at } => PhaseInfo::Settlement { ends_at },
+            });
+        }
+
+        // Externally tagged: either `"name"` or `{"name": {...}}`
+        let name = match &value {
+            serde_json::Value::String(name) => name.clone(),
+            serde_json::Value::Object(map) => map.keys().next().cloned().unwrap_or_default(),
+            other => other.to_string(),
+        };
+        Ok(PhaseInfo::Unknown(name))
+    }
 }
 
 /// Response from epoch info query
@@ -80,6 +127,34 @@ impl ContractAddresses {
     }
 }
 
+/// Fields without which an epoch response can't be used at all
+const REQUIRED_EPOCH_FIELDS: [&str; 2] = ["epoch_number", "phase"];
+
+/// Parse a `current_epoch` response
+/// An empty or partial response (seen during contract migrations and node resyncs) is
+/// reported as "contract not ready" so callers can back off and retry; anything else
+/// that fails to parse is a genuinely malformed response
+pub fn parse_epoch_info(response: serde_json::Value) -> Result<EpochInfoResponse> {
+    let missing: Vec<&str> = match &response {
+        serde_json::Value::Object(map) => REQUIRED_EPOCH_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| map.get(*field).map_or(true, |v| v.is_null()))
+            .collect(),
+        serde_json::Value::Null => REQUIRED_EPOCH_FIELDS.to_vec(),
+        serde_json::Value::String(s) if s.is_empty() => REQUIRED_EPOCH_FIELDS.to_vec(),
+        other => return Err(anyhow::anyhow!("Malformed epoch response: expected an object, got {}", other)),
+    };
+    if !missing.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Contract not ready: epoch response is missing {} (migration or node resync in progress?)",
+            missing.join(", ")
+        ));
+    }
+
+    serde_json::from_value(response).map_err(|e| anyhow::anyhow!("Malformed epoch response: {}", e))
+}
+
 /// Query current epoch information from the contract
 pub async fn query_epoch_info(
     client: &InjectiveClient,
@@ -112,7 +187,7 @@ pub async fn query_epoch_info(
     log::debug!("Raw epoch response: {}", serde_json::to_string_pretty(&response)?);
     
     // Parse the response
-    let epoch_info: EpochInfoResponse = serde_json::from_value(response)?;
+    let epoch_info = parse_epoch_info(response)?;
     
     log::debug!(
         "Epoch {} - Phase: {:?}, Difficulty: {}",
@@ -124,6 +199,11 @@ pub async fn query_epoch_info(
     Ok(epoch_info)
 }
 
+/// Parse a `miner_stats` response
+pub fn parse_miner_info(response: serde_json::Value) -> Result<MinerInfoResponse> {
+    serde_json::from_value(response).map_err(|e| anyhow::anyhow!("Malformed miner response: {}", e))
+}
+
 /// Query miner information from the contract
 pub async fn query_miner_info(
     client: &InjectiveClient,
@@ -151,7 +231,7 @@ pub async fn query_miner_info(
     log::debug!("Raw miner response: {}", serde_json::to_string_pretty(&response)?);
     
     // Parse the response
-    let miner_info: MinerInfoResponse = serde_json::from_value(response)?;
+    let miner_info = parse_miner_info(response)?;
     
     log::debug!(
         "Miner stats - Successful: {}/{}, Rewards: {}",
@@ -163,6 +243,136 @@ pub async fn query_miner_info(
     Ok(miner_info)
 }
 
+/// Query the commitment the contract stored for `miner` in `epoch` (None if none stored)
+pub async fn query_commitment(
+    client: &InjectiveClient,
+    contract_address: &str,
+    miner_address: &str,
+    epoch: u64,
+) -> Result<Option<[u8; 32]>> {
+    let query_msg = GetCommitmentMsg {
+        commitment: CommitmentQuery {
+            miner: miner_address.to_string(),
+            epoch,
+        },
+    };
+    let response = client.query_contract_smart(
+        contract_address,
+        serde_json::to_vec(&query_msg)?,
+    ).await?;
+    log::debug!("Raw commitment response: {}", response);
+    
+    let value = response.get("commitment").unwrap_or(&response);
+    if value.is_null() {
+        return Ok(None);
+    }
+    parse_commitment(value)
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("Unrecognized commitment format: {}", value))
+}
+
+/// Decode a 32-byte commitment serialized as a byte array, hex or base64 string
+fn parse_commitment(value: &serde_json::Value) -> Option<[u8; 32]> {
+    let bytes: Vec<u8> = match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|v| v.as_u64().and_then(|n| u8::try_from(n).ok()))
+            .collect::<Option<Vec<u8>>>()?,
+        serde_json::Value::String(s) => hex::decode(s).ok().or_else(|| BASE64.decode(s).ok())?,
+        _ => return None,
+    };
+    bytes.try_into().ok()
+}
+
+/// Contract name/version as stored by cw2 under the `contract_info` key
+#[derive(Deserialize, Debug, Clone)]
+pub struct ContractVersion {
+    pub contract: String,
+    pub version: String,
+}
+
+/// Contract major version this miner's commitment format and message schema target
+pub const EXPECTED_CONTRACT_MAJOR: u64 = 3;
+/// Oldest minor version with the current protocol (V3.4 introduced JIT epoch history)
+pub const MIN_CONTRACT_MINOR: u64 = 4;
+
+/// Query the deployed contract's cw2 version
+pub async fn query_contract_version(
+    client: &InjectiveClient,
+    contract_address: &str,
+) -> Result<ContractVersion> {
+    let data = client.query_contract_raw(contract_address, b"contract_info").await?;
+    if data.is_empty() {
+        return Err(anyhow::anyhow!("Contract does not report a cw2 version"));
+    }
+    Ok(serde_json::from_slice(&data)?)
+}
+
+/// Query the PoW parameters the contract verifies solutions with
+/// Older contracts don't implement this query and return an error
+pub async fn query_pow_params(
+    client: &InjectiveClient,
+    contract_address: &str,
+) -> Result<crate::miner::pow::PowParams> {
+    let response = client.query_contract_smart(
+        contract_address,
+        serde_json::to_vec(&json!({"pow_params": {}}))?,
+    ).await?;
+    log::debug!("Raw pow_params response: {}", response);
+    Ok(serde_json::from_value(response)?)
+}
+
+/// Whether a contract version speaks the protocol this miner implements
+pub fn is_compatible_contract_version(version: &str) -> bool {
+    let mut parts = version.trim_start_matches('v').split('.');
+    let major = parts.next().and_then(|p| p.parse::<u64>().ok());
+    let minor = parts.next().and_then(|p| p.parse::<u64>().ok()).unwrap_or(0);
+    major == Some(EXPECTED_CONTRACT_MAJOR) && minor >= MIN_CONTRACT_MINOR
+}
+
+/// Calculate a miner's nonce partition for an epoch
+/// Mirrors the contract's calculate_nonce_range: Blake2b512(address || epoch) picks one of
+/// 1000 partitions, rotated by `epoch * 37` so miners can't grind a favourable slot.
+/// Shared with the mobile miner so both always agree on the range
+pub fn calculate_nonce_range(miner_address: &str, epoch_number: u64) -> (u64, u64) {
+    gmine_mobile::partition::calculate_nonce_range(miner_address, epoch_number)
+}
+
+/// Index (0..1000) of a miner's partition for an epoch
+pub use gmine_mobile::partition::partition_index;
+
+/// Attribute keys the mining contract uses for the reward amount on claim
+const REWARD_ATTRIBUTE_KEYS: [&str; 4] = ["reward", "reward_amount", "claimed_amount", "amount_claimed"];
+
+/// Extract the POWER (micro units) paid to `miner` from a claim transaction's events
+///
+/// Prefers the CW20 `mint`/`transfer` to the miner (what actually landed in the wallet),
+/// falling back to a reward attribute on the mining contract's own wasm event.
+pub fn claimed_reward_from_events(events: &[super::proto::Event], miner: &str) -> Option<u64> {
+    let wasm_events = events.iter().filter(|e| e.r#type == "wasm");
+    let attr = |event: &super::proto::Event, key: &str| -> Option<String> {
+        event.attributes.iter().find(|a| a.key == key).map(|a| a.value.clone())
+    };
+
+    let mut minted: Option<u64> = None;
+    let mut reported: Option<u64> = None;
+    for event in wasm_events {
+        let action = attr(event, "action").unwrap_or_default();
+        let recipient = attr(event, "to").or_else(|| attr(event, "recipient"));
+        if matches!(action.as_str(), "mint" | "transfer") && recipient.as_deref() == Some(miner) {
+            if let Some(amount) = attr(event, "amount").and_then(|a| a.parse::<u64>().ok()) {
+                minted = Some(minted.unwrap_or(0) + amount);
+            }
+        } else if reported.is_none() {
+            reported = REWARD_ATTRIBUTE_KEYS
+                .iter()
+                .find_map(|key| attr(event, key))
+                .and_then(|a| a.trim_end_matches(|c: char| c.is_alphabetic()).parse::<u64>().ok());
+        }
+    }
+    minted.or(reported)
+}
+
 /// Helper function to determine if we're in a valid phase for an action
 pub fn can_commit(phase: &str, block_in_epoch: u64) -> bool {
     phase == "commit" || (phase == "reveal" && block_in_epoch <= 30)
@@ -226,12 +436,135 @@ mod tests {
         assert_eq!(time_until_next_phase("settlement", 48, 50), 4);
     }
     
+    #[test]
+    fn test_contract_version_compatibility() {
+        assert!(is_compatible_contract_version("3.5.0"));
+        assert!(is_compatible_contract_version("v3.4.1"));
+        assert!(!is_compatible_contract_version("3.3.0"));
+        assert!(!is_compatible_contract_version("4.0.0"));
+        assert!(!is_compatible_contract_version("garbage"));
+    }
+    
+    #[test]
+    fn test_nonce_range() {
+        let addr = "inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz";
+        let (start, end) = calculate_nonce_range(addr, 7);
+        assert_eq!(end.wrapping_sub(start), u64::MAX / 1000);
+        assert_eq!(start % (u64::MAX / 1000), 0);
+        // Deterministic per (address, epoch)
+        assert_eq!(calculate_nonce_range(addr, 7), (start, end));
+    }
+    
+    #[test]
+    fn test_phase_info_deserialization() {
+        let phase: PhaseInfo = serde_json::from_value(json!({"reveal": {"ends_at": 120}})).unwrap();
+        assert_eq!(phase, PhaseInfo::Reveal { ends_at: 120 });
+        
+        // Phases from future contract versions don't break parsing
+        let phase: PhaseInfo = serde_json::from_value(json!({"dispute": {"ends_at": 150}})).unwrap();
+        assert_eq!(phase, PhaseInfo::Unknown("dispute".to_string()));
+        let phase: PhaseInfo = serde_json::from_value(json!("paused")).unwrap();
+        assert_eq!(phase, PhaseInfo::Unknown("paused".to_string()));
+    }
+    
+    #[test]
+    fn test_parse_epoch_info_partial_response() {
+        use crate::chain::ChainError;
+        let not_ready = |value: serde_json::Value| {
+            let err = parse_epoch_info(value).unwrap_err();
+            ChainError::classify(&err) == ChainError::ContractNotReady
+        };
+        // Empty and partial responses mean the contract isn't ready yet
+        assert!(not_ready(serde_json::Value::Null));
+        assert!(not_ready(json!({})));
+        assert!(not_ready(json!({"epoch_number": 42, "difficulty": 12})));
+        
+        // Present but wrongly typed fields are genuinely malformed
+        let err = parse_epoch_info(json!({"epoch_number": "forty-two", "phase": "commit"})).unwrap_err();
+        assert_eq!(ChainError::classify(&err), ChainError::Other);
+        assert!(err.to_string().contains("Malformed"));
+        assert!(!not_ready(json!([1, 2, 3])));
+        
+        let info = parse_epoch_info(json!({
+            "epoch_number": 42,
+            "phase": {"commit": {"ends_at": 1000}},
+            "difficulty": 12,
+            "reward_pool": "0",
+            "leading_miner": null,
+            "best_score": null,
+            "start_block": 900,
+            "target_hash": [0, 1, 2],
+        })).unwrap();
+        assert_eq!(info.epoch_number, 42);
+        assert_eq!(info.phase, PhaseInfo::Commit { ends_at: 1000 });
+    }
+    
+    #[test]
+    fn test_claimed_reward_from_events() {
+        use super::super::proto::{Event, EventAttribute};
+        let event = |attrs: &[(&str, &str)]| Event {
+            r#type: "wasm".to_string(),
+            attributes: attrs
+                .iter()
+                .map(|(k, v)| EventAttribute { key: k.to_string(), value: v.to_string(), index: true })
+                .collect(),
+        };
+        let miner = "inj1miner";
+        
+        let events = vec![
+            event(&[("action", "claim_reward"), ("reward", "2500000")]),
+            event(&[("action", "mint"), ("to", miner), ("amount", "2400000")]),
+            event(&[("action", "mint"), ("to", "inj1treasury"), ("amount", "100000")]),
+        ];
+        // Tokens actually minted to the miner win over the contract's reported figure
+        assert_eq!(claimed_reward_from_events(&events, miner), Some(2_400_000));
+        
+        let events = vec![event(&[("action", "claim_reward"), ("reward_amount", "1750000")])];
+        assert_eq!(claimed_reward_from_events(&events, miner), Some(1_750_000));
+        
+        assert_eq!(claimed_reward_from_events(&[], miner), None);
+    }
+    
+    #[test]
+    fn test_parse_commitment() {
+        let bytes = [7u8; 32];
+        assert_eq!(parse_commitment(&json!(bytes.to_vec())), Some(bytes));
+        assert_eq!(parse_commitment(&json!(hex::encode(bytes))), Some(bytes));
+        assert_eq!(parse_commitment(&json!(BASE64.encode(bytes))), Some(bytes));
+        assert_eq!(parse_commitment(&json!([1, 2, 3])), None);
+    }
+    
     #[test]
     fn test_contract_addresses() {
         let addrs = ContractAddresses::testnet();
         assert_eq!(addrs.mining_contract, "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66");
         assert_eq!(addrs.power_token, "inj1esn6fgltm0fvqe2n57cdkvtwwpyyf9due8ps49");
     }
+    
+    #[test]
+    fn test_power_token_from_config() {
+        // A contract address needs a query to tell CW20 apart
+        assert_eq!(PowerToken::from_config("inj1esn6fgltm0fvqe2n57cdkvtwwpyyf9due8ps49"), None);
+        assert_eq!(
+            PowerToken::from_config("factory/inj1esn6fgltm0fvqe2n57cdkvtwwpyyf9due8ps49/power"),
+            Some(PowerToken::Bank { denom: "factory/inj1esn6fgltm0fvqe2n57cdkvtwwpyyf9due8ps49/power".to_string() })
+        );
+        assert_eq!(
+            PowerToken::from_config("peggy0xabc"),
+            Some(PowerToken::Bank { denom: "peggy0xabc".to_string() })
+        );
+    }
+    
+    #[test]
+    fn test_staked_amount() {
+        let info: StakeInfoResponse = serde_json::from_value(json!({
+            "miner": "inj1test", "amount_staked": "2500000000", "lock_until": 0, "multiplier": 1000, "effective_stake": "2500000000"
+        })).unwrap();
+        assert_eq!(info.staked_amount().unwrap(), 2_500_000_000);
+        
+        let bad = StakeInfoResponse { amount_staked: "n/a".to_string(), ..info };
+        assert!(bad.staked_amount().is_err());
+    }
 }
 
 // V3.3 Query Messages
@@ -269,6 +602,14 @@ pub struct StakeInfoResponse {
     // Note: original_lock_duration is not returned by the contract
 }
 
+impl StakeInfoResponse {
+    /// Staked POWER in base units
+    pub fn staked_amount(&self) -> Result<u128> {
+        self.amount_staked.parse()
+            .map_err(|e| anyhow::anyhow!("Invalid amount_staked '{}' in stake info: {}", self.amount_staked, e))
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct EmissionMetricsResponse {
     pub current_epoch_emissions: String,
@@ -406,4 +747,57 @@ pub async fn query_power_balance(
     );
     
     Ok(balance)
-}
\ No newline at end of file
+}
+
+/// How the POWER token is held: a CW20 contract (balances queried from the contract,
+/// staked with CW20 `send`) or a bank denom (e.g. a `factory/...` token)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PowerToken {
+    Cw20 { contract: String },
+    Bank { denom: String },
+}
+
+impl PowerToken {
+    /// What the configured `power_token` is when that's clear without a query:
+    /// anything that isn't a plain `inj1...` address is a bank denom
+    pub fn from_config(power_token: &str) -> Option<Self> {
+        if power_token.starts_with("inj1") && !power_token.contains('/') {
+            None
+        } else {
+            Some(PowerToken::Bank { denom: power_token.to_string() })
+        }
+    }
+}
+
+impl std::fmt::Display for PowerToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerToken::Cw20 { contract } => write!(f, "CW20 {}", contract),
+            PowerToken::Bank { denom } => write!(f, "bank denom {}", denom),
+        }
+    }
+}
+
+/// Work out whether `power_token` is a CW20 contract (it answers `token_info`) or a bank denom
+pub async fn detect_power_token(client: &InjectiveClient, power_token: &str) -> Result<PowerToken> {
+    if let Some(token) = PowerToken::from_config(power_token) {
+        return Ok(token);
+    }
+    
+    let query_data = serde_json::to_vec(&cw20::Cw20QueryMsg::TokenInfo {})?;
+    let response = client.query_contract_smart(power_token, query_data).await
+        .map_err(|e| anyhow::anyhow!("POWER token {} is not a bank denom and didn't answer a CW20 token_info query: {}", power_token, e))?;
+    let info: cw20::TokenInfoResponse = serde_json::from_value(response)?;
+    log::debug!("POWER token {} is CW20 {} ({} decimals)", power_token, info.symbol, info.decimals);
+    
+    Ok(PowerToken::Cw20 { contract: power_token.to_string() })
+}
+
+/// POWER balance (micro units) of an address, however the token is held
+pub async fn query_power_token_balance(client: &InjectiveClient, token: &PowerToken, address: &str) -> Result<u128> {
+    match token {
+        PowerToken::Cw20 { contract } => Ok(query_power_balance(client, contract, address).await?.balance.u128()),
+        PowerToken::Bank { denom } => client.query_bank_balance(address, denom).await,
+    }
+}
+
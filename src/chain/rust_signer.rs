@@ -1,58 +1,250 @@
 /// Native Rust EIP-712 signer to replace Node.js bridge
 use anyhow::{Result, anyhow};
 use gmine_mobile::{
+    execute_msg::ExecuteMsg as MobileExecuteMsg,
     tx_proto::ProtoTransactionBuilder,
     mobile_wallet::MobileWallet,
     types::{Fee as MobileFee, Coin as MobileCoin},
 };
-use serde_json::{json, Value};
-use crate::chain::Coin;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use crate::chain::client_real::tx_hash;
+use crate::chain::messages::{ExecuteMsg, MessageBuilder};
+
+/// Contract messages the signer will sign unless configured otherwise
+pub const DEFAULT_ALLOWED_MESSAGES: &[&str] = &[
+    "commit_solution",
+    "reveal_solution",
+    "claim_reward",
+    "advance_epoch",
+    "finalize_epoch",
+];
+
+/// Gas limit on every transaction the signer builds
+pub const SIGNER_GAS_LIMIT: u64 = 350_000;
+
+/// Map a configured message name (including the short aliases used in
+/// contract messages, e.g. `commit`) to the type the signer builds
+fn canonical_message_type(name: &str) -> Option<&'static str> {
+    match name.trim() {
+        "commit" | "commit_solution" => Some("commit_solution"),
+        "reveal" | "reveal_solution" => Some("reveal_solution"),
+        "claim" | "claim_reward" | "claim_rewards" => Some("claim_reward"),
+        "advance_epoch" => Some("advance_epoch"),
+        "finalize_epoch" => Some("finalize_epoch"),
+        _ => None,
+    }
+}
+
+/// Fixed-size field from the byte vectors the per-type methods take
+fn to_array<const N: usize>(bytes: Vec<u8>, field: &str) -> Result<[u8; N]> {
+    let len = bytes.len();
+    bytes.try_into().map_err(|_| anyhow!("{} must be {} bytes, got {}", field, N, len))
+}
+
+/// A signed transaction held back for later broadcast. Only valid while the
+/// account's sequence is still `sequence`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreparedTx {
+    pub msg: ExecuteMsg,
+    pub account_number: u64,
+    pub sequence: u64,
+    pub tx_bytes: Vec<u8>,
+}
+
+impl PreparedTx {
+    /// Whether this transaction can still be broadcast as signed
+    pub fn matches(&self, account_number: u64, sequence: u64) -> bool {
+        self.account_number == account_number && self.sequence == sequence
+    }
+}
 
 #[derive(Clone)]
 pub struct RustSigner {
     mnemonic: String,
+    derivation_path: String,
     address: String,
     network: String,
     contract_address: String,
+    /// Message types this signer is allowed to sign (safety allowlist)
+    allowed_messages: Vec<String>,
+    /// Hash of the last submitted tx whose outcome is unknown
+    last_tx_hash: Arc<Mutex<Option<String>>>,
+    /// Proxy URL for the broadcast/confirmation HTTP calls
+    proxy: Option<String>,
+    /// LCD endpoint to broadcast to (None = the network's public sentry)
+    rest_url: Option<String>,
+    /// Feegrant granter paying gas for this signer's transactions
+    fee_granter: Option<String>,
+    /// Live gas price; None keeps the fixed 0.0005 INJ fee
+    gas_price: Option<u128>,
+    /// Denom the fee is paid in
+    fee_denom: String,
 }
 
 impl RustSigner {
     /// Create a new RustSigner from mnemonic
     pub fn new(mnemonic: &str, network: &str, contract_address: &str) -> Result<Self> {
+        Self::new_with_path(mnemonic, gmine_mobile::mobile_wallet::INJECTIVE_HD_PATH, network, contract_address)
+    }
+    
+    /// Create a new RustSigner deriving the key from a custom HD path
+    pub fn new_with_path(mnemonic: &str, derivation_path: &str, network: &str, contract_address: &str) -> Result<Self> {
         // Create wallet from mnemonic to get address
-        let wallet = MobileWallet::from_mnemonic_no_passphrase(mnemonic)
+        let wallet = MobileWallet::from_mnemonic_with_path(mnemonic, "", derivation_path)
             .map_err(|e| anyhow!("Failed to create wallet: {}", e))?;
         
         let address = wallet.address.clone();
         
         Ok(Self {
             mnemonic: mnemonic.to_string(),
+            derivation_path: derivation_path.to_string(),
             address,
             network: network.to_string(),
             contract_address: contract_address.to_string(),
+            allowed_messages: DEFAULT_ALLOWED_MESSAGES.iter().map(|m| m.to_string()).collect(),
+            last_tx_hash: Arc::new(Mutex::new(None)),
+            proxy: None,
+            rest_url: None,
+            fee_granter: None,
+            gas_price: None,
+            fee_denom: crate::chain::DEFAULT_FEE_DENOM.to_string(),
         })
     }
     
+    /// Restrict signing to the given message types
+    /// Unknown names are rejected so a typo can't silently disable a message
+    pub fn set_allowed_messages<S: AsRef<str>>(&mut self, messages: &[S]) -> Result<()> {
+        let mut allowed = Vec::new();
+        for name in messages {
+            let canonical = canonical_message_type(name.as_ref()).ok_or_else(|| anyhow!(
+                "Unknown message type '{}' in signer allowlist (known: {})",
+                name.as_ref().trim(), DEFAULT_ALLOWED_MESSAGES.join(", ")
+            ))?;
+            if !allowed.iter().any(|m: &String| m == canonical) {
+                allowed.push(canonical.to_string());
+            }
+        }
+        self.allowed_messages = allowed;
+        Ok(())
+    }
+    
+    /// Message types this signer will sign
+    pub fn allowed_messages(&self) -> &[String] {
+        &self.allowed_messages
+    }
+    
+    /// Fail unless `msg_type` is on the allowlist
+    pub fn check_allowed(&self, msg_type: &str) -> Result<()> {
+        if self.allowed_messages.iter().any(|m| m == msg_type) {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Refusing to sign '{}': not in the signer's allowed messages ({})",
+                msg_type, self.allowed_messages.join(", ")
+            ))
+        }
+    }
+
+    /// Send broadcasts through a proxy (e.g. `socks5h://127.0.0.1:9050`)
+    pub fn set_proxy(&mut self, proxy: Option<String>) {
+        self.proxy = proxy;
+    }
+
+    /// Broadcast to this LCD endpoint instead of the network default
+    pub fn set_rest_url(&mut self, rest_url: Option<String>) {
+        self.rest_url = rest_url;
+    }
+
+    /// Take the hash of the last submitted tx if its outcome is unknown
+    pub fn take_last_tx_hash(&self) -> Option<String> {
+        self.last_tx_hash.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// Have a feegrant granter pay the fee (the wallet then needs no INJ)
+    pub fn set_fee_granter(&mut self, fee_granter: Option<String>) -> Result<()> {
+        if let Some(ref granter) = fee_granter {
+            crate::chain::address::validate(granter)?;
+        }
+        self.fee_granter = fee_granter;
+        Ok(())
+    }
+    
+    /// Price the fee at this gas price instead of the fixed fee
+    pub fn set_gas_price(&mut self, gas_price: Option<u128>) {
+        self.gas_price = gas_price;
+    }
+    
+    /// Pay the fee in this denom; `gas_price` must be in the same denom
+    pub fn set_fee_denom(&mut self, fee_denom: String) {
+        self.fee_denom = fee_denom;
+    }
+    
     /// Get the wallet address
     pub fn address(&self) -> &str {
         &self.address
     }
     
+    /// Sign and broadcast any contract message
+    pub async fn sign_and_broadcast_msg(
+        &self,
+        msg: &dyn MessageBuilder,
+        account_number: u64,
+        sequence: u64,
+    ) -> Result<String> {
+        let prepared = self.prepare_msg(msg, account_number, sequence)?;
+        self.broadcast_prepared(&prepared).await
+    }
+    
+    /// Build and sign a contract message without broadcasting it, so the signing
+    /// work can be done ahead of a short submission window
+    pub fn prepare_msg(&self, msg: &dyn MessageBuilder, account_number: u64, sequence: u64) -> Result<PreparedTx> {
+        let msg = msg.build_msg();
+        self.check_allowed(msg.name())?;
+        // Byte arrays serialize as arrays of numbers, which is what the contract
+        // expects - NOT base64 strings
+        let mobile_msg = MobileExecuteMsg::from_json(&msg.to_json()?).map_err(|e| anyhow!(e))?;
+        log::info!("RustSigner: msg_type={}, msg={}", msg.name(), mobile_msg.to_contract_json());
+        
+        let tx_builder = self.tx_builder()?;
+        
+        // Gas fee for the transaction - the signer never attaches contract funds
+        let mobile_fee = Some(MobileFee {
+            amount: vec![MobileCoin {
+                denom: self.fee_denom.clone(),
+                amount: match self.gas_price {
+                    Some(price) => (SIGNER_GAS_LIMIT as u128 * price).to_string(),
+                    None => "500000000000000".to_string(), // 0.0005 INJ
+                },
+            }],
+            gas: SIGNER_GAS_LIMIT.to_string(),
+            payer: String::new(),
+            granter: self.fee_granter.clone().unwrap_or_default(),
+        });
+        
+        // Build the transaction (returns protobuf bytes)
+        let tx_bytes = tx_builder.build_transaction(
+            &self.address,
+            &self.contract_address,
+            &mobile_msg,
+            account_number,
+            sequence,
+            mobile_fee,
+            "", // memo
+        ).map_err(|e| anyhow!("Failed to build transaction: {}", e))?;
+        
+        Ok(PreparedTx { msg, account_number, sequence, tx_bytes })
+    }
+    
     /// Sign and broadcast a commit transaction
     pub async fn sign_and_broadcast_commit(
         &self,
         commitment: Vec<u8>,
         account_number: u64,
         sequence: u64,
-        fee: Option<Vec<Coin>>,
     ) -> Result<String> {
-        // Contract expects commitment as [u8; 32] which serializes to array of numbers
-        // NOT as a base64 string
-        let msg = json!({
-            "commitment": commitment
-        });
-        
-        self.sign_and_broadcast("commit_solution", msg, account_number, sequence, fee).await
+        let msg = ExecuteMsg::CommitSolution { commitment: to_array(commitment, "commitment")? };
+        self.sign_and_broadcast_msg(&msg, account_number, sequence).await
     }
     
     /// Sign and broadcast a reveal transaction
@@ -63,17 +255,13 @@ impl RustSigner {
         salt: Vec<u8>,
         account_number: u64,
         sequence: u64,
-        fee: Option<Vec<Coin>>,
     ) -> Result<String> {
-        // Contract expects nonce: [u8; 8], digest: [u8; 16], salt: [u8; 32]
-        // which serialize to arrays of numbers, NOT base64 strings
-        let msg = json!({
-            "nonce": nonce,
-            "digest": digest,
-            "salt": salt
-        });
-        
-        self.sign_and_broadcast("reveal_solution", msg, account_number, sequence, fee).await
+        let msg = ExecuteMsg::RevealSolution {
+            nonce: to_array(nonce, "nonce")?,
+            digest: to_array(digest, "digest")?,
+            salt: to_array(salt, "salt")?,
+        };
+        self.sign_and_broadcast_msg(&msg, account_number, sequence).await
     }
     
     /// Sign and broadcast a claim rewards transaction
@@ -82,16 +270,8 @@ impl RustSigner {
         epoch_number: u64,
         account_number: u64,
         sequence: u64,
-        fee: Option<Vec<Coin>>,
     ) -> Result<String> {
-        // ProtoTransactionBuilder expects epoch_number for claim_reward
-        // Add hint to distinguish from finalize_epoch
-        let msg = json!({
-            "epoch_number": epoch_number,
-            "_msg_type": "claim_reward"
-        });
-        
-        self.sign_and_broadcast("claim_reward", msg, account_number, sequence, fee).await
+        self.sign_and_broadcast_msg(&ExecuteMsg::ClaimReward { epoch_number }, account_number, sequence).await
     }
     
     /// Sign and broadcast advance_epoch message
@@ -99,12 +279,8 @@ impl RustSigner {
         &self,
         account_number: u64,
         sequence: u64,
-        fee: Option<Vec<Coin>>,
     ) -> Result<String> {
-        // ProtoTransactionBuilder expects an empty message for advance_epoch
-        let msg = json!({});
-        
-        self.sign_and_broadcast("advance_epoch", msg, account_number, sequence, fee).await
+        self.sign_and_broadcast_msg(&ExecuteMsg::AdvanceEpoch {}, account_number, sequence).await
     }
     
     /// Sign and broadcast finalize_epoch message
@@ -113,77 +289,48 @@ impl RustSigner {
         epoch_number: u64,
         account_number: u64,
         sequence: u64,
-        fee: Option<Vec<Coin>>,
     ) -> Result<String> {
-        // ProtoTransactionBuilder expects epoch_number at top level
-        let msg = json!({
-            "epoch_number": epoch_number
-        });
-        
-        self.sign_and_broadcast("finalize_epoch", msg, account_number, sequence, fee).await
+        self.sign_and_broadcast_msg(&ExecuteMsg::FinalizeEpoch { epoch_number }, account_number, sequence).await
     }
     
-    /// Internal method to sign and broadcast any transaction type
-    async fn sign_and_broadcast(
-        &self,
-        msg_type: &str,
-        msg_data: Value,
-        account_number: u64,
-        sequence: u64,
-        fee: Option<Vec<Coin>>,
-    ) -> Result<String> {
-        log::info!("RustSigner: msg_type={}, msg_data={}", msg_type, msg_data);
+    /// Broadcast a transaction signed by `prepare_msg`
+    pub async fn broadcast_prepared(&self, prepared: &PreparedTx) -> Result<String> {
+        let tx_builder = self.tx_builder()?;
         
-        // For advance_epoch and claim_reward, we need to add a hint for tx_proto
-        let msg_with_hint = if msg_type == "advance_epoch" && msg_data.is_object() && msg_data.as_object().unwrap().is_empty() {
-            json!({"_msg_type": "advance_epoch"})
-        } else {
-            msg_data.clone()
-        };
-        // Recreate wallet and tx_builder for each transaction
-        let wallet = MobileWallet::from_mnemonic_no_passphrase(&self.mnemonic)
+        // Remember the hash so a retry can check whether this attempt landed anyway
+        *self.last_tx_hash.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx_hash(&prepared.tx_bytes));
+        
+        // Submit the transaction
+        let tx_hash = tx_builder.submit_transaction(prepared.tx_bytes.clone())
+            .await
+            .map_err(|e| {
+                let error = e.to_string();
+                if error.contains("failed with code") {
+                    // Rejected by CheckTx - it never entered the mempool
+                    self.last_tx_hash.lock().unwrap_or_else(|e| e.into_inner()).take();
+                }
+                anyhow!("Failed to submit transaction: {}", error)
+            })?;
+        
+        Ok(tx_hash)
+    }
+    
+    /// Recreate the wallet and tx builder for each transaction
+    fn tx_builder(&self) -> Result<ProtoTransactionBuilder> {
+        let wallet = MobileWallet::from_mnemonic_with_path(&self.mnemonic, "", &self.derivation_path)
             .map_err(|e| anyhow!("Failed to create wallet: {}", e))?;
         
         // Get compressed public key for EIP-712 signing
         let compressed_pub_key = wallet.public_key_compressed()
             .map_err(|e| anyhow!("Failed to get compressed public key: {}", e))?;
         
-        let tx_builder = ProtoTransactionBuilder::new(
+        Ok(ProtoTransactionBuilder::new(
             wallet.private_key_bytes(),
             &compressed_pub_key,
             &self.network
-        ).map_err(|e| anyhow!("Failed to create transaction builder: {}", e))?;
-        
-        // Create proper gas fee (not contract funds)
-        // The 'fee' parameter here is actually contract funds, which are usually empty
-        // We need to create a proper gas fee for the transaction
-        let mobile_fee = Some(MobileFee {
-            amount: vec![MobileCoin {
-                denom: "inj".to_string(),
-                amount: "500000000000000".to_string(), // 0.0005 INJ
-            }],
-            gas: "350000".to_string(),
-            payer: String::new(),
-            granter: String::new(),
-        });
-        
-        // Build the transaction (returns protobuf bytes)
-        let tx_bytes = tx_builder.build_transaction(
-            &self.address,
-            &self.contract_address,
-            msg_with_hint,
-            account_number,
-            sequence,
-            mobile_fee,
-            "", // memo
-        ).map_err(|e| anyhow!("Failed to build transaction: {}", e))?;
-        
-        // Submit the transaction
-        let tx_hash = tx_builder.submit_transaction(tx_bytes)
-            .await
-            .map_err(|e| anyhow!("Failed to submit transaction: {}", e))?;
-        
-        Ok(tx_hash)
+        ).map_err(|e| anyhow!("Failed to create transaction builder: {}", e))?
+        .with_proxy(self.proxy.clone())
+        .with_rest_url(self.rest_url.clone()))
     }
 }
 
@@ -197,4 +344,78 @@ mod tests {
         let signer = RustSigner::new(mnemonic, "testnet", "inj1test").unwrap();
         assert!(signer.address().starts_with("inj"));
     }
+    
+    #[test]
+    fn test_allowed_messages() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mut signer = RustSigner::new(mnemonic, "testnet", "inj1test").unwrap();
+        for msg_type in DEFAULT_ALLOWED_MESSAGES {
+            assert!(signer.check_allowed(msg_type).is_ok());
+        }
+        assert!(signer.check_allowed("send").is_err());
+        
+        // Aliases map onto the signer's message types
+        signer.set_allowed_messages(&["commit", "reveal", "reveal_solution"]).unwrap();
+        assert_eq!(signer.allowed_messages(), &["commit_solution", "reveal_solution"]);
+        assert!(signer.check_allowed("commit_solution").is_ok());
+        let err = signer.check_allowed("claim_reward").unwrap_err();
+        assert!(err.to_string().contains("Refusing to sign"));
+        
+        assert!(signer.set_allowed_messages(&["unstake"]).is_err());
+    }
+    
+    #[test]
+    fn test_every_message_type_is_signed_as_the_contract_message() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let signer = RustSigner::new(mnemonic, "testnet", "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66").unwrap();
+        let msgs = [
+            ExecuteMsg::CommitSolution { commitment: [7; 32] },
+            ExecuteMsg::RevealSolution { nonce: [1; 8], digest: [2; 16], salt: [3; 32] },
+            ExecuteMsg::ClaimReward { epoch_number: 7 },
+            ExecuteMsg::FinalizeEpoch { epoch_number: 7 },
+            ExecuteMsg::AdvanceEpoch {},
+        ];
+        for msg in msgs {
+            let prepared = signer.prepare_msg(&msg, 42, 7).unwrap();
+            // The transaction carries the message exactly as the contract parses it
+            let contract_json = msg.to_json().unwrap().to_string();
+            assert!(
+                prepared.tx_bytes.windows(contract_json.len()).any(|w| w == contract_json.as_bytes()),
+                "{} not found in the signed transaction", contract_json
+            );
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_prepared_reveal_is_broadcast_without_signing() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mut signer = RustSigner::new(mnemonic, "testnet", "inj1vd520adql0apl3wsuyhhpptl79yqwxx73e4j66").unwrap();
+        let reveal = ExecuteMsg::RevealSolution { nonce: [1; 8], digest: [2; 16], salt: [3; 32] };
+        
+        // Ahead of the window: sign against the sequence we expect
+        let prepared = signer.prepare_msg(&reveal, 42, 7).unwrap();
+        assert!(prepared.matches(42, 7));
+        assert!(!prepared.matches(42, 8));
+        
+        // From here on the signer refuses reveals, and nothing listens at the endpoint
+        signer.set_allowed_messages(&["commit"]).unwrap();
+        signer.set_rest_url(Some("http://127.0.0.1:1".to_string()));
+        let err = signer.sign_and_broadcast_msg(&reveal, 42, 7).await.unwrap_err();
+        assert!(err.to_string().contains("Refusing to sign"), "{}", err);
+        assert!(signer.take_last_tx_hash().is_none());
+        
+        // The pre-signed reveal goes out as signed - only the broadcast itself fails
+        let err = signer.broadcast_prepared(&prepared).await.unwrap_err();
+        assert!(!err.to_string().contains("Refusing to sign"), "{}", err);
+        assert_eq!(signer.take_last_tx_hash(), Some(tx_hash(&prepared.tx_bytes)));
+    }
+    
+    #[test]
+    fn test_fee_granter_must_be_valid_address() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mut signer = RustSigner::new(mnemonic, "testnet", "inj1test").unwrap();
+        assert!(signer.set_fee_granter(Some("inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz".to_string())).is_ok());
+        assert!(signer.set_fee_granter(Some("inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dy".to_string())).is_err());
+        assert!(signer.set_fee_granter(None).is_ok());
+    }
 }
\ No newline at end of file
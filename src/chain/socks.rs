@@ -0,0 +1,118 @@
+/// SOCKS5 transport - routes chain traffic through a proxy (e.g. Tor) so the
+/// sentry/LCD nodes never see the miner's own IP
+use anyhow::{Result, anyhow};
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::str::FromStr;
+use tokio::net::TcpStream;
+use tokio_socks::tcp::Socks5Stream;
+use tonic::transport::Uri;
+
+/// A SOCKS5 proxy address, accepted as `host:port`, `socks5://host:port` or
+/// `socks5h://host:port`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Socks5Proxy {
+    addr: String,
+}
+
+impl Socks5Proxy {
+    /// `host:port` of the proxy
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Proxy URL for HTTP clients. Always `socks5h` so hostnames are resolved by
+    /// the proxy - local DNS lookups would leak which nodes we talk to
+    pub fn url(&self) -> String {
+        format!("socks5h://{}", self.addr)
+    }
+
+    /// Connector for `Endpoint::connect_with_connector`; tonic layers TLS on top
+    pub fn connector(&self) -> Socks5Connector {
+        Socks5Connector { proxy: self.addr.clone() }
+    }
+}
+
+impl FromStr for Socks5Proxy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let addr = s
+            .strip_prefix("socks5h://")
+            .or_else(|| s.strip_prefix("socks5://"))
+            .unwrap_or(s)
+            .trim_end_matches('/');
+        if addr.contains("://") {
+            return Err(anyhow!("Unsupported proxy '{}' (only SOCKS5 is supported)", s));
+        }
+        let valid = addr
+            .rsplit_once(':')
+            .map(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+            .unwrap_or(false);
+        if !valid {
+            return Err(anyhow!("Invalid SOCKS5 proxy '{}' (expected host:port, e.g. 127.0.0.1:9050)", s));
+        }
+        Ok(Self { addr: addr.to_string() })
+    }
+}
+
+impl fmt::Display for Socks5Proxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.url())
+    }
+}
+
+/// Opens TCP connections to the gRPC endpoint through the SOCKS5 proxy
+#[derive(Debug, Clone)]
+pub struct Socks5Connector {
+    proxy: String,
+}
+
+impl tower::Service<Uri> for Socks5Connector {
+    type Response = TcpStream;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<TcpStream>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut std::task::Context<'_>) -> std::task::Poll<io::Result<()>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let proxy = self.proxy.clone();
+        Box::pin(async move {
+            let host = uri
+                .host()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("No host in {}", uri)))?
+                .to_string();
+            let port = uri.port_u16().unwrap_or(if uri.scheme_str() == Some("http") { 80 } else { 443 });
+            // Pass the hostname through so the proxy does the DNS lookup
+            let stream = Socks5Stream::connect(proxy.as_str(), (host.as_str(), port))
+                .await
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("SOCKS5 proxy {}: {}", proxy, e)))?;
+            Ok(stream.into_inner())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proxy() {
+        let tor: Socks5Proxy = "127.0.0.1:9050".parse().unwrap();
+        assert_eq!(tor.addr(), "127.0.0.1:9050");
+        assert_eq!(tor.url(), "socks5h://127.0.0.1:9050");
+
+        // Local-DNS scheme is upgraded so lookups still go through the proxy
+        let proxy: Socks5Proxy = "socks5://proxy.lan:1080/".parse().unwrap();
+        assert_eq!(proxy.url(), "socks5h://proxy.lan:1080");
+
+        assert!("127.0.0.1".parse::<Socks5Proxy>().is_err());
+        assert!("127.0.0.1:notaport".parse::<Socks5Proxy>().is_err());
+        assert!("http://127.0.0.1:8080".parse::<Socks5Proxy>().is_err());
+    }
+}
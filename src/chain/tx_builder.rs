@@ -4,6 +4,7 @@
 use anyhow::{Result, anyhow};
 use prost::Message;
 use tiny_keccak::Hasher;
+use gmine_mobile::signed_tx_log;
 
 use crate::chain::proto::{
     Any, AuthInfo, Coin, Fee, ModeInfo, MsgExecuteContract, 
@@ -19,6 +20,8 @@ pub struct ProperTxBuilder<'a> {
     sequence: u64,
     gas_limit: u64,
     gas_price: String,
+    /// Address paying the fee through a feegrant allowance (empty = the sender pays)
+    fee_granter: String,
     wallet: &'a InjectiveWallet,
     signer: TransactionSigner,
 }
@@ -37,6 +40,7 @@ impl<'a> ProperTxBuilder<'a> {
             sequence,
             gas_limit: 250000,  // Default gas limit (increased for contract requirements)
             gas_price: "500000000inj".to_string(), // Default gas price
+            fee_granter: String::new(),
             wallet,
             signer: TransactionSigner::new(),
         }
@@ -58,6 +62,18 @@ impl<'a> ProperTxBuilder<'a> {
         self
     }
     
+    /// Builder pattern method to set the gas price (base units of `denom` per gas)
+    pub fn with_gas_price(mut self, gas_price: u128, denom: &str) -> Self {
+        self.gas_price = format!("{}{}", gas_price, denom);
+        self
+    }
+    
+    /// Builder pattern method to have a feegrant granter pay the fee
+    pub fn with_fee_granter(mut self, fee_granter: Option<String>) -> Self {
+        self.fee_granter = fee_granter.unwrap_or_default();
+        self
+    }
+    
     /// Build a complete signed transaction for contract execution
     pub fn build_execute_contract_tx(
         &self,
@@ -96,7 +112,7 @@ impl<'a> ProperTxBuilder<'a> {
             amount: self.parse_gas_price()?,
             gas_limit: self.gas_limit,
             payer: "".to_string(),
-            granter: "".to_string(),
+            granter: self.fee_granter.clone(),
         };
         
         // 5. Get compressed public key and create PubKey protobuf message
@@ -159,6 +175,7 @@ impl<'a> ProperTxBuilder<'a> {
         let mut hasher = Sha256::new();
         hasher.update(&sign_doc_bytes);
         let sign_hash: [u8; 32] = hasher.finalize().into();
+        signed_tx_log::log_sign_hash("sign doc", "MsgExecuteContract", self.account_number, self.sequence, &sign_hash);
         
         // 11. Sign the hash
         let private_key = self.wallet.private_key()?;
@@ -174,6 +191,7 @@ impl<'a> ProperTxBuilder<'a> {
         // 13. Encode TxRaw for broadcast
         let mut tx_bytes = Vec::new();
         tx_raw.encode(&mut tx_bytes)?;
+        signed_tx_log::log_tx_bytes("MsgExecuteContract", &tx_bytes);
         
         Ok(tx_bytes)
     }
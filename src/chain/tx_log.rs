@@ -0,0 +1,148 @@
+/// Append-only transaction log for auditing
+/// Every broadcast transaction is written as one JSON line so operators can
+/// reconcile against the explorer and answer "did my miner actually submit this?"
+
+use anyhow::Result;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A single entry in the transaction log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxLogEntry {
+    /// RFC3339 timestamp of the broadcast
+    pub timestamp: String,
+    /// Message type (top-level key of the execute msg, e.g. "commit_solution")
+    pub tx_type: String,
+    /// Epoch number if the message carries one
+    pub epoch: Option<u64>,
+    /// The execute message as sent to the contract
+    pub msg: Value,
+    /// Gas limit requested
+    pub gas: u64,
+    /// Fee for that gas limit at the gas price, with its denom (e.g. "40000000000000000inj").
+    /// The chain charges for the limit, not for the gas used
+    #[serde(default)]
+    pub fee: Option<String>,
+    /// Transaction hash (None if the broadcast failed before a hash was returned)
+    pub tx_hash: Option<String>,
+    /// Result code (0 on success, None if unknown)
+    pub code: Option<u32>,
+    /// Raw log / error message for failures
+    pub raw_log: Option<String>,
+}
+
+impl TxLogEntry {
+    /// Build an entry from an execute message and its outcome
+    pub fn from_result(msg: &Value, gas: u64, fee: String, result: &Result<String>) -> Self {
+        let (tx_hash, code, raw_log) = match result {
+            Ok(hash) => (Some(hash.clone()), Some(0), None),
+            Err(e) => (None, None, Some(e.to_string())),
+        };
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            tx_type: msg_type(msg),
+            epoch: msg_epoch(msg),
+            msg: msg.clone(),
+            gas,
+            fee: Some(fee),
+            tx_hash,
+            code,
+            raw_log,
+        }
+    }
+}
+
+/// Append-only JSONL writer
+#[derive(Debug, Clone)]
+pub struct TxLog {
+    path: PathBuf,
+}
+
+impl TxLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an entry and flush it to disk so a crash doesn't lose it
+    pub fn append(&self, entry: &TxLogEntry) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = serde_json::to_string(entry)?;
+        writeln!(file, "{}", line)?;
+        file.flush()?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// Read all entries back (for replay/history)
+    pub fn read_all(&self) -> Result<Vec<TxLogEntry>> {
+        let content = std::fs::read_to_string(&self.path)?;
+        let mut entries = Vec::new();
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => log::warn!("Skipping malformed tx log line: {}", e),
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn msg_type(msg: &Value) -> String {
+    msg.as_object()
+        .and_then(|obj| obj.keys().next().cloned())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn msg_epoch(msg: &Value) -> Option<u64> {
+    msg.as_object()
+        .and_then(|obj| obj.values().next())
+        .and_then(|inner| inner.get("epoch_number").or_else(|| inner.get("epoch")))
+        .and_then(|v| v.as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_entry_from_result() {
+        let msg = json!({"claim_reward": {"epoch_number": 42}});
+        let entry = TxLogEntry::from_result(&msg, 400000, "64000000000000000inj".to_string(), &Ok("ABC123".to_string()));
+        assert_eq!(entry.tx_type, "claim_reward");
+        assert_eq!(entry.fee.as_deref(), Some("64000000000000000inj"));
+        assert_eq!(entry.epoch, Some(42));
+        assert_eq!(entry.tx_hash.as_deref(), Some("ABC123"));
+        assert_eq!(entry.code, Some(0));
+
+        let err: Result<String> = Err(anyhow::anyhow!("Transaction failed: out of gas"));
+        let entry = TxLogEntry::from_result(&json!({"advance_epoch": {}}), 250000, "40000000000000000inj".to_string(), &err);
+        assert_eq!(entry.tx_type, "advance_epoch");
+        assert!(entry.epoch.is_none());
+        assert!(entry.raw_log.unwrap().contains("out of gas"));
+    }
+
+    #[test]
+    fn test_append_and_read() {
+        let path = std::env::temp_dir().join(format!("gmine_tx_log_{}.jsonl", uuid::Uuid::new_v4()));
+        let log = TxLog::new(&path);
+        let msg = json!({"commit_solution": {"commitment": [1, 2, 3]}});
+        log.append(&TxLogEntry::from_result(&msg, 250000, "40000000000000000inj".to_string(), &Ok("H1".to_string()))).unwrap();
+        log.append(&TxLogEntry::from_result(&msg, 250000, "40000000000000000inj".to_string(), &Ok("H2".to_string()))).unwrap();
+
+        let entries = log.read_all().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].tx_hash.as_deref(), Some("H2"));
+        let _ = std::fs::remove_file(path);
+    }
+}
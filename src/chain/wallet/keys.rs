@@ -1,12 +1,12 @@
 use anyhow::{Result, bail};
 use bip39::Mnemonic;
-use bip32::{XPrv, ChildNumber};
+use bip32::{XPrv, DerivationPath};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 use tiny_keccak::{Hasher, Keccak};
 use bech32::{self, Hrp};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-const INJECTIVE_HD_PATH: &str = "m/44'/60'/0'/0/0"; // Ethereum-style HD path for Injective
+pub const INJECTIVE_HD_PATH: &str = "m/44'/60'/0'/0/0"; // Ethereum-style HD path for Injective
 const INJECTIVE_PREFIX: &str = "inj";
 
 /// Secure wallet for Injective blockchain
@@ -24,6 +24,12 @@ pub struct InjectiveWallet {
 impl InjectiveWallet {
     /// Create a wallet from a BIP39 mnemonic phrase with optional passphrase
     pub fn from_mnemonic(mnemonic_str: &str, passphrase: &str) -> Result<Self> {
+        Self::from_mnemonic_with_path(mnemonic_str, passphrase, INJECTIVE_HD_PATH)
+    }
+    
+    /// Create a wallet from a BIP39 mnemonic using a custom BIP32 derivation path
+    /// (e.g. "m/44'/60'/0'/0/1" for the second address of a MetaMask-style wallet)
+    pub fn from_mnemonic_with_path(mnemonic_str: &str, passphrase: &str, path: &str) -> Result<Self> {
         // Parse and validate mnemonic
         let mnemonic = Mnemonic::parse(mnemonic_str)?;
         
@@ -31,7 +37,7 @@ impl InjectiveWallet {
         let seed = mnemonic.to_seed(passphrase);
         
         // Derive private key using proper BIP32 HD derivation
-        let private_key = derive_private_key_bip32(&seed, INJECTIVE_HD_PATH)?;
+        let private_key = derive_private_key_bip32(&seed, path)?;
         
         // Get public key from private key
         let secp = Secp256k1::new();
@@ -58,6 +64,20 @@ impl InjectiveWallet {
         })
     }
     
+    /// Create a throwaway wallet from a random key
+    /// Used by read-only clients that never sign; the address is never funded
+    pub fn ephemeral() -> Result<Self> {
+        let secp = Secp256k1::new();
+        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+        let address = generate_injective_address(&public_key)?;
+        
+        Ok(Self {
+            address,
+            private_key_bytes: secret_key.secret_bytes(),
+            public_key_bytes: public_key.serialize_uncompressed(),
+        })
+    }
+    
     /// Create a wallet from a BIP39 mnemonic with no passphrase
     pub fn from_mnemonic_no_passphrase(mnemonic_str: &str) -> Result<Self> {
         Self::from_mnemonic(mnemonic_str, "")
@@ -70,6 +90,11 @@ impl InjectiveWallet {
             .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))
     }
     
+    /// Commitment salt derived from this wallet's key (see `SaltSource::Derived`)
+    pub fn derive_salt(&self, epoch: u64, nonce: [u8; 8]) -> [u8; 32] {
+        crate::chain::messages::derive_salt(&self.private_key_bytes, epoch, nonce)
+    }
+    
     /// Get the public key as a PublicKey
     pub fn public_key(&self) -> Result<PublicKey> {
         let secp = Secp256k1::new();
@@ -79,46 +104,56 @@ impl InjectiveWallet {
     
     /// Get the public key as uncompressed bytes (65 bytes with 0x04 prefix)
     pub fn public_key_bytes(&self) -> [u8; 65] {
+        self.public_key_uncompressed()
+    }
+    
+    /// Get the public key as uncompressed bytes (65 bytes: 0x04 + X + Y)
+    /// This is the form the address is derived from
+    pub fn public_key_uncompressed(&self) -> [u8; 65] {
         self.public_key_bytes
     }
     
-    /// Get the public key as compressed bytes (33 bytes)
+    /// Get the public key as compressed bytes (33 bytes: 0x02/0x03 + X)
+    /// This is the form carried in the signer info of a transaction
     pub fn public_key_compressed(&self) -> Result<[u8; 33]> {
-        let public_key = self.public_key()?;
+        let public_key = PublicKey::from_slice(&self.public_key_bytes)
+            .map_err(|e| anyhow::anyhow!("Invalid public key: {}", e))?;
         Ok(public_key.serialize())
     }
+    
+    /// The 20-byte Ethereum-style address behind the bech32 `inj1...` address
+    pub fn ethereum_address(&self) -> [u8; 20] {
+        ethereum_address_from_uncompressed(&self.public_key_bytes)
+    }
 }
 
 /// Derive a private key using proper BIP32 HD derivation
-fn derive_private_key_bip32(seed: &[u8], _path_str: &str) -> Result<[u8; 32]> {
-    // Create extended private key from seed
-    let xprv = XPrv::new(seed)
-        .map_err(|e| anyhow::anyhow!("Failed to create XPrv from seed: {}", e))?;
-    
-    // Derive using Ethereum HD path: m/44'/60'/0'/0/0
+fn derive_private_key_bip32(seed: &[u8], path_str: &str) -> Result<[u8; 32]> {
+    // Parse the HD path, e.g. Ethereum-style m/44'/60'/0'/0/0
     // 44' = BIP44 purpose (hardened)
     // 60' = Ethereum coin type (hardened) 
-    // 0' = Account 0 (hardened)
+    // 0' = Account (hardened)
     // 0 = External chain
-    // 0 = Address index 0
-    let derived = xprv
-        .derive_child(ChildNumber::new(44, true)?)  // 44'
-        .and_then(|k| k.derive_child(ChildNumber::new(60, true)?))  // 60'
-        .and_then(|k| k.derive_child(ChildNumber::new(0, true)?))   // 0'
-        .and_then(|k| k.derive_child(ChildNumber::new(0, false)?))  // 0
-        .and_then(|k| k.derive_child(ChildNumber::new(0, false)?))  // 0
+    // 0 = Address index
+    let path: DerivationPath = path_str.parse()
+        .map_err(|e| anyhow::anyhow!("Invalid derivation path '{}': {}", path_str, e))?;
+    
+    let derived = XPrv::derive_from_path(seed, &path)
         .map_err(|e| anyhow::anyhow!("Failed to derive key: {}", e))?;
     
     // Return the private key bytes
     Ok(derived.to_bytes())
 }
 
-/// Generate an Injective address from a public key
-/// Uses Ethereum-style address derivation with bech32 encoding
-fn generate_injective_address(public_key: &PublicKey) -> Result<String> {
-    // Get uncompressed public key bytes (65 bytes: 0x04 + 32 bytes X + 32 bytes Y)
-    let pubkey_bytes = public_key.serialize_uncompressed();
-    
+/// Default Injective HD path with the given address index (m/44'/60'/0'/0/{index})
+pub fn hd_path_for_index(index: u32) -> String {
+    format!("m/44'/60'/0'/0/{}", index)
+}
+
+/// Ethereum-style address from an uncompressed public key (0x04 + 32 bytes X + 32 bytes Y):
+/// the last 20 bytes of Keccak256 over the X,Y coordinates. Injective uses this
+/// (eth_secp256k1), not the Cosmos SHA256/RIPEMD160 of the compressed key
+fn ethereum_address_from_uncompressed(pubkey_bytes: &[u8; 65]) -> [u8; 20] {
     // Skip the 0x04 prefix byte, take only the X,Y coordinates (64 bytes)
     let coords = &pubkey_bytes[1..];
     
@@ -129,20 +164,30 @@ fn generate_injective_address(public_key: &PublicKey) -> Result<String> {
     hasher.finalize(&mut hash);
     
     // Take the last 20 bytes of the hash (Ethereum address format)
-    let addr_bytes = &hash[12..32];
+    let mut addr_bytes = [0u8; 20];
+    addr_bytes.copy_from_slice(&hash[12..32]);
+    addr_bytes
+}
+
+/// Generate an Injective address from a public key
+/// Uses Ethereum-style address derivation with bech32 encoding
+fn generate_injective_address(public_key: &PublicKey) -> Result<String> {
+    // Always derive from the uncompressed form, whatever form the key was parsed from
+    let addr_bytes = ethereum_address_from_uncompressed(&public_key.serialize_uncompressed());
     
     // Encode as bech32 with 'inj' prefix for Injective
     let hrp = Hrp::parse(INJECTIVE_PREFIX)?;
-    let encoded = bech32::encode::<bech32::Bech32>(hrp, addr_bytes)?;
+    let encoded = bech32::encode::<bech32::Bech32>(hrp, &addr_bytes)?;
     
     Ok(encoded)
 }
 
+const TEST_VECTOR_ETH_ADDRESS: &str = "9858effd232b4033e47d90003d41ec34ecaeda94";
+
 /// Validate address against known test vectors
 pub fn validate_with_test_vector() -> Result<()> {
-    // Test vector from Injective documentation
-    // Mnemonic: "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
-    // Expected address will vary based on proper implementation
+    // Test vector: the well-known "abandon ... about" mnemonic on the default path
+    // derives 0x9858EfFD232B4033E47d90003D41EC34EcaEda94 on Ethereum
     
     let test_mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
     let wallet = InjectiveWallet::from_mnemonic_no_passphrase(test_mnemonic)?;
@@ -150,8 +195,6 @@ pub fn validate_with_test_vector() -> Result<()> {
     // Log the generated address for manual verification
     println!("Test vector address: {}", wallet.address);
     
-    // In production, compare against known good address
-    // For now, just ensure it has the right format
     if !wallet.address.starts_with("inj1") {
         bail!("Invalid address prefix");
     }
@@ -160,6 +203,11 @@ pub fn validate_with_test_vector() -> Result<()> {
         bail!("Invalid address length: expected 42, got {}", wallet.address.len());
     }
     
+    let eth_address = hex::encode(wallet.ethereum_address());
+    if eth_address != TEST_VECTOR_ETH_ADDRESS {
+        bail!("Address mismatch: expected 0x{}, got 0x{}", TEST_VECTOR_ETH_ADDRESS, eth_address);
+    }
+    
     Ok(())
 }
 
@@ -214,6 +262,53 @@ mod tests {
         } // Automatic zeroization happens here
     }
     
+    #[test]
+    fn test_derivation_paths() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        
+        // Default path matches the well-known 0x9858EfFD232B4033E47d90003D41EC34EcaEda94
+        let default = InjectiveWallet::from_mnemonic_no_passphrase(mnemonic).unwrap();
+        let explicit = InjectiveWallet::from_mnemonic_with_path(mnemonic, "", INJECTIVE_HD_PATH).unwrap();
+        assert_eq!(default.address, "inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz");
+        assert_eq!(explicit.address, default.address);
+        
+        // Address index 1 (0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0)
+        let index1 = InjectiveWallet::from_mnemonic_with_path(mnemonic, "", &hd_path_for_index(1)).unwrap();
+        assert_eq!(index1.address, "inj1d7ky6xxfzg6rh7r05uzfxexafepy4wwqp4z8h8");
+        
+        // Account 1 (hardened)
+        let account1 = InjectiveWallet::from_mnemonic_with_path(mnemonic, "", "m/44'/60'/1'/0/0").unwrap();
+        assert_eq!(account1.address, "inj10zpe7cz56lk38yvt4cz88w33k89f6un9hh75cs");
+        
+        // Garbage paths are rejected
+        assert!(InjectiveWallet::from_mnemonic_with_path(mnemonic, "", "not/a/path").is_err());
+    }
+    
+    #[test]
+    fn test_public_key_forms() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let uncompressed = wallet.public_key_uncompressed();
+        let compressed = wallet.public_key_compressed().unwrap();
+        
+        // Same X coordinate, prefix carries the parity of Y
+        assert_eq!(uncompressed[0], 0x04);
+        assert_eq!(compressed[1..], uncompressed[1..33]);
+        assert_eq!(compressed[0], if uncompressed[64] & 1 == 0 { 0x02 } else { 0x03 });
+        
+        // Both forms parse to the same key, and the address is the same from either
+        let from_compressed = PublicKey::from_slice(&compressed).unwrap();
+        assert_eq!(from_compressed.serialize_uncompressed(), uncompressed);
+        assert_eq!(from_compressed, wallet.public_key().unwrap());
+        assert_eq!(generate_injective_address(&from_compressed).unwrap(), wallet.address);
+        
+        // Keccak256 of the uncompressed key gives the known Ethereum address
+        assert_eq!(hex::encode(wallet.ethereum_address()), TEST_VECTOR_ETH_ADDRESS);
+        let hrp = Hrp::parse(INJECTIVE_PREFIX).unwrap();
+        assert_eq!(bech32::encode::<bech32::Bech32>(hrp, &wallet.ethereum_address()).unwrap(), wallet.address);
+    }
+    
     #[test]
     fn test_validation() {
         // Test the validation function
@@ -1,7 +1,7 @@
 mod keys;
 mod signer;
 
-pub use keys::InjectiveWallet;
+pub use keys::{InjectiveWallet, INJECTIVE_HD_PATH, hd_path_for_index};
 pub use signer::TransactionSigner;
 
 #[cfg(test)]
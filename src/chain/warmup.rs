@@ -0,0 +1,161 @@
+/// Contract warm-up - before any mining, the epoch, miner and config queries are run
+/// once and their responses checked for the shape this miner expects, so a contract
+/// mid-migration or on an unexpected version is refused with a clear reason instead
+/// of showing up later as failed commits and reveals
+use anyhow::{Result, anyhow};
+use serde_json::{json, Value};
+
+use super::InjectiveClient;
+use super::queries::{
+    parse_miner_info, query_epoch_info, EpochInfoResponse, GetMinerInfoMsg, MinerInfoQuery, MinerInfoResponse, PhaseInfo,
+};
+
+/// Lowest difficulty a live epoch can have - 0 would accept any hash
+pub const MIN_SANE_DIFFICULTY: u8 = 1;
+
+/// Highest difficulty that is still mineable; digests are 128 bits but nothing past
+/// this many leading zeros will ever be found
+pub const MAX_SANE_DIFFICULTY: u8 = 64;
+
+/// What the warm-up queries returned, once validated
+#[derive(Debug, Clone)]
+pub struct WarmUp {
+    pub epoch: EpochInfoResponse,
+    /// None when the address has no mining history yet
+    pub miner: Option<MinerInfoResponse>,
+    /// None when the contract doesn't implement the `config` query
+    pub config: Option<Value>,
+}
+
+/// Check that an epoch response is something a miner can work with
+pub fn check_epoch_info(epoch: &EpochInfoResponse) -> Result<()> {
+    if let PhaseInfo::Unknown(name) = &epoch.phase {
+        return Err(anyhow!("epoch {} is in unknown phase '{}' (contract upgraded or migrating?)", epoch.epoch_number, name));
+    }
+    if !(MIN_SANE_DIFFICULTY..=MAX_SANE_DIFFICULTY).contains(&epoch.difficulty) {
+        return Err(anyhow!(
+            "epoch {} difficulty {} is outside {}..={}",
+            epoch.epoch_number, epoch.difficulty, MIN_SANE_DIFFICULTY, MAX_SANE_DIFFICULTY
+        ));
+    }
+    if epoch.target_hash.len() != 32 {
+        return Err(anyhow!("epoch {} target_hash is {} bytes, expected 32", epoch.epoch_number, epoch.target_hash.len()));
+    }
+    Ok(())
+}
+
+/// Check that miner stats are internally consistent
+pub fn check_miner_info(miner: &MinerInfoResponse) -> Result<()> {
+    for (field, value) in [("current_stake", &miner.current_stake), ("total_rewards_earned", &miner.total_rewards_earned)] {
+        if value.parse::<u128>().is_err() {
+            return Err(anyhow!("miner {} '{}' is not an amount", field, value));
+        }
+    }
+    if miner.successful_mines > miner.total_attempts {
+        return Err(anyhow!(
+            "miner reports {} successful mines out of {} attempts",
+            miner.successful_mines, miner.total_attempts
+        ));
+    }
+    Ok(())
+}
+
+/// Check that a `config` response is a populated object
+pub fn check_contract_config(config: &Value) -> Result<()> {
+    match config {
+        Value::Object(map) if !map.is_empty() => Ok(()),
+        other => Err(anyhow!("contract config is not a populated object: {}", other)),
+    }
+}
+
+/// Run the warm-up queries against `contract_address` and validate the responses.
+/// `miner_address` is optional so read-only commands can check without a wallet
+pub async fn warm_up(client: &InjectiveClient, contract_address: &str, miner_address: Option<&str>) -> Result<WarmUp> {
+    let fail = |what: &str, e: anyhow::Error| anyhow!("Contract {} failed the warm-up {} check: {}", contract_address, what, e);
+
+    let epoch = query_epoch_info(client, contract_address).await.map_err(|e| fail("epoch", e))?;
+    check_epoch_info(&epoch).map_err(|e| fail("epoch", e))?;
+
+    // A query error is an address without history; a response that doesn't parse is not
+    let miner = match miner_address {
+        Some(address) => {
+            let query = GetMinerInfoMsg { miner_stats: MinerInfoQuery { miner: address.to_string() } };
+            match client.query_contract_smart(contract_address, serde_json::to_vec(&query)?).await {
+                Ok(response) => {
+                    let miner = parse_miner_info(response).map_err(|e| fail("miner", e))?;
+                    check_miner_info(&miner).map_err(|e| fail("miner", e))?;
+                    Some(miner)
+                }
+                Err(e) => {
+                    log::debug!("No miner stats for {} ({})", address, e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    // Older contracts don't implement the config query
+    let config = match client.query_contract_smart(contract_address, serde_json::to_vec(&json!({"config": {}}))?).await {
+        Ok(config) => {
+            check_contract_config(&config).map_err(|e| fail("config", e))?;
+            Some(config)
+        }
+        Err(e) => {
+            log::debug!("Contract does not report its config ({})", e);
+            None
+        }
+    };
+
+    Ok(WarmUp { epoch, miner, config })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch(phase: PhaseInfo, difficulty: u8, target_len: usize) -> EpochInfoResponse {
+        EpochInfoResponse {
+            epoch_number: 42,
+            phase,
+            difficulty,
+            reward_pool: "1000000".to_string(),
+            leading_miner: None,
+            best_score: None,
+            start_block: 100,
+            target_hash: vec![7; target_len],
+        }
+    }
+
+    #[test]
+    fn test_check_epoch_info() {
+        assert!(check_epoch_info(&epoch(PhaseInfo::Commit { ends_at: 150 }, 8, 32)).is_ok());
+
+        let unknown = check_epoch_info(&epoch(PhaseInfo::Unknown("migrating".to_string()), 8, 32)).unwrap_err();
+        assert!(unknown.to_string().contains("unknown phase 'migrating'"));
+        assert!(check_epoch_info(&epoch(PhaseInfo::Commit { ends_at: 150 }, 0, 32)).is_err());
+        assert!(check_epoch_info(&epoch(PhaseInfo::Commit { ends_at: 150 }, 200, 32)).is_err());
+        let short = check_epoch_info(&epoch(PhaseInfo::Reveal { ends_at: 160 }, 8, 31)).unwrap_err();
+        assert!(short.to_string().contains("31 bytes"));
+    }
+
+    #[test]
+    fn test_check_miner_info_and_config() {
+        let miner = parse_miner_info(json!({
+            "current_stake": "5000000",
+            "last_attempt_block": 90,
+            "penalty_level": 0,
+            "successful_mines": 3,
+            "total_attempts": 10,
+            "total_rewards_earned": "2500000",
+        })).unwrap();
+        assert!(check_miner_info(&miner).is_ok());
+        assert!(check_miner_info(&MinerInfoResponse { successful_mines: 11, ..miner.clone() }).is_err());
+        assert!(check_miner_info(&MinerInfoResponse { current_stake: "-1".to_string(), ..miner }).is_err());
+        assert!(parse_miner_info(json!({"miner": "inj1..."})).is_err());
+
+        assert!(check_contract_config(&json!({"admin": "inj1...", "epoch_duration": 50})).is_ok());
+        assert!(check_contract_config(&json!({})).is_err());
+        assert!(check_contract_config(&Value::Null).is_err());
+    }
+}
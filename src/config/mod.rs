@@ -1,7 +1,14 @@
-use anyhow::Result;
+use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
 use std::path::Path;
 
+/// Prefix of environment variables that override config values
+pub const ENV_PREFIX: &str = "GMINE_";
+/// Separates the section from the field in an override's name:
+/// `GMINE_CHAIN__GRPC_ENDPOINT` sets `grpc_endpoint` in `[chain]`
+const ENV_SEPARATOR: &str = "__";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub chain: ChainConfig,
@@ -71,9 +78,15 @@ impl Default for Config {
 }
 
 impl Config {
+    /// Defaults, overridden by the file at `path`, overridden by `GMINE_*` variables
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-        let config: Config = toml::from_str(&content)?;
+        let (config, _) = load_layered(&Config::default(), Some(path.as_ref()))?;
+        Ok(config)
+    }
+    
+    /// Defaults overridden by `GMINE_*` variables, for running without a config file
+    pub fn from_env() -> Result<Self> {
+        let (config, _) = load_layered(&Config::default(), None)?;
         Ok(config)
     }
 
@@ -84,4 +97,152 @@ impl Config {
     }
 }
 
+/// Build a config from layers, later ones winning: `defaults`, the TOML file at `path`
+/// (if given), then `GMINE_<SECTION>__<FIELD>` environment variables. Returns the
+/// config and the names of the variables that were applied (values may be secrets)
+pub fn load_layered<T: Serialize + DeserializeOwned>(defaults: &T, path: Option<&Path>) -> Result<(T, Vec<String>)> {
+    let mut config = toml::Value::try_from(defaults).context("Failed to serialize default config")?;
+    if let Some(path) = path {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let file: toml::Value = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+        merge(&mut config, file);
+    }
+    let applied = apply_env_overrides(&mut config, std::env::vars())?;
+    let config = config.try_into().context("Invalid config after applying environment overrides")?;
+    Ok((config, applied))
+}
+
+/// Copy `overlay` into `base`, merging tables key by key
+fn merge(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Apply every `GMINE_<SECTION>__<FIELD>` variable in `vars` to `config`. Names are
+/// case-insensitive; variables without the `__` separator (e.g. `GMINE_DEVNET_GRPC`)
+/// aren't config overrides and are skipped
+pub fn apply_env_overrides(
+    config: &mut toml::Value,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<Vec<String>> {
+    let mut applied = Vec::new();
+    for (name, raw) in vars {
+        let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        if !key.contains(ENV_SEPARATOR) {
+            continue;
+        }
+        let path: Vec<String> = key.split(ENV_SEPARATOR).map(str::to_lowercase).collect();
+        if path.iter().any(String::is_empty) {
+            return Err(anyhow!("Invalid config override {}: empty section or field name", name));
+        }
+        
+        let (field, sections) = path.split_last().expect("split yields at least one part");
+        let mut table = config.as_table_mut().ok_or_else(|| anyhow!("Config is not a table"))?;
+        for section in sections {
+            table = table
+                .entry(section.clone())
+                .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+                .as_table_mut()
+                .ok_or_else(|| anyhow!("Invalid config override {}: {} is not a section", name, section))?;
+        }
+        let value = env_value(&raw, table.get(field)).with_context(|| format!("Invalid config override {}", name))?;
+        table.insert(field.clone(), value);
+        applied.push(name);
+    }
+    applied.sort();
+    Ok(applied)
+}
+
+/// Parse an override as the type of the value it replaces. A field that isn't set
+/// yet takes a TOML literal (`4`, `true`, `["a", "b"]`), falling back to a string
+fn env_value(raw: &str, current: Option<&toml::Value>) -> Result<toml::Value> {
+    let value = match current {
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        Some(toml::Value::Integer(_)) => toml::Value::Integer(raw.trim().parse().with_context(|| format!("expected an integer, got '{}'", raw))?),
+        Some(toml::Value::Float(_)) => toml::Value::Float(raw.trim().parse().with_context(|| format!("expected a number, got '{}'", raw))?),
+        Some(toml::Value::Boolean(_)) => match raw.trim().to_lowercase().as_str() {
+            "true" | "1" | "yes" => toml::Value::Boolean(true),
+            "false" | "0" | "no" => toml::Value::Boolean(false),
+            _ => return Err(anyhow!("expected true or false, got '{}'", raw)),
+        },
+        Some(toml::Value::Table(_)) => return Err(anyhow!("it names a section, not a field")),
+        // Arrays take a TOML array, or a comma-separated list of strings
+        Some(toml::Value::Array(_)) => parse_literal(raw).filter(toml::Value::is_array).unwrap_or_else(|| {
+            toml::Value::Array(
+                raw.split(',').map(str::trim).filter(|item| !item.is_empty())
+                    .map(|item| toml::Value::String(item.to_string()))
+                    .collect(),
+            )
+        }),
+        _ => parse_literal(raw).unwrap_or_else(|| toml::Value::String(raw.to_string())),
+    };
+    Ok(value)
+}
+
+fn parse_literal(raw: &str) -> Option<toml::Value> {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw)).ok()?.remove("value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_env_overrides_win_over_file_and_defaults() {
+        let mut config = toml::Value::try_from(Config::default()).unwrap();
+        merge(&mut config, toml::from_str("[miner]\nthreads = 2\nbatch_size = 50").unwrap());
+        let applied = apply_env_overrides(&mut config, vars(&[
+            ("GMINE_CHAIN__GRPC_ENDPOINT", "http://localhost:9900"),
+            ("GMINE_MINER__THREADS", "8"),
+            ("GMINE_MINER__AUTO_STAKE_ENABLED", "true"),
+            ("GMINE_MINER__STAKE_DURATION_DAYS", "90"),
+            ("GMINE_DEVNET_GRPC", "ignored"),
+            ("MNEMONIC", "ignored"),
+        ])).unwrap();
+        assert_eq!(applied.len(), 4);
+
+        let config: Config = config.try_into().unwrap();
+        assert_eq!(config.chain.grpc_endpoint, "http://localhost:9900");
+        assert_eq!(config.miner.threads, 8);
+        assert_eq!(config.miner.batch_size, 50);
+        assert!(config.miner.auto_stake_enabled);
+        assert_eq!(config.miner.stake_duration_days, Some(90));
+        assert_eq!(config.chain.chain_id, "injective-888");
+    }
+
+    #[test]
+    fn test_env_values_take_the_field_type() {
+        let current = toml::Value::String(String::new());
+        assert_eq!(env_value("123", Some(&current)).unwrap(), toml::Value::String("123".to_string()));
+        assert!(env_value("lots", Some(&toml::Value::Integer(1))).is_err());
+        assert_eq!(
+            env_value("inj1a, inj1b", Some(&toml::Value::Array(Vec::new()))).unwrap(),
+            toml::Value::Array(vec![toml::Value::String("inj1a".to_string()), toml::Value::String("inj1b".to_string())])
+        );
+        assert_eq!(env_value("rig-01", None).unwrap(), toml::Value::String("rig-01".to_string()));
+
+        let mut config = toml::Value::try_from(Config::default()).unwrap();
+        assert!(apply_env_overrides(&mut config, vars(&[("GMINE_CHAIN", "x"), ("GMINE_CHAIN__", "x")])).is_err());
+    }
+}
+
 // Note: extern crate not needed in Rust 2021 edition
\ No newline at end of file
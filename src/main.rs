@@ -5,8 +5,10 @@ mod orchestrator;
 mod telemetry;
 mod bridge_manager;
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use clap::{Parser, Subcommand};
+use std::path::Path;
+use std::str::FromStr;
 use std::time::Duration;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -17,6 +19,44 @@ const MAX_DIFFICULTY: u8 = 32;
 const MAX_THREADS: usize = 256;
 const MAX_DURATION: u64 = 86400; // 24 hours
 
+/// `--difficulty`: a fixed value, or `auto` for the live contract's current difficulty
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DifficultyArg {
+    Fixed(u8),
+    Auto,
+}
+
+impl FromStr for DifficultyArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(DifficultyArg::Auto);
+        }
+        s.parse().map(DifficultyArg::Fixed)
+            .map_err(|_| format!("expected a difficulty or 'auto', got '{}'", s))
+    }
+}
+
+/// `--epoch`: a fixed test epoch, or `current` for the live epoch and its target hash
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EpochArg {
+    Fixed(u64),
+    Current,
+}
+
+impl FromStr for EpochArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("current") {
+            return Ok(EpochArg::Current);
+        }
+        s.parse().map(EpochArg::Fixed)
+            .map_err(|_| format!("expected an epoch number or 'current', got '{}'", s))
+    }
+}
+
 #[derive(Parser)]
 #[command(name = "gmine-miner")]
 #[command(about = "GMINE Mining Client for Injective", version)]
@@ -33,13 +73,21 @@ enum Commands {
         #[arg(short, long, default_value = "1")]
         threads: usize,
         
-        /// Difficulty target
+        /// Difficulty target, or "auto" to use the live contract's current difficulty
         #[arg(short, long, default_value = "8")]
-        difficulty: u8,
+        difficulty: DifficultyArg,
+        
+        /// Epoch to mine, or "current" to mine the live epoch's target hash
+        #[arg(short, long, default_value = "1")]
+        epoch: EpochArg,
         
         /// Mining duration in seconds
         #[arg(long, default_value = "60")]
         duration: u64,
+        
+        /// Configuration file with the chain endpoint and contract (used by auto/current)
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
     },
     
     /// Run the full miner
@@ -54,6 +102,10 @@ enum Commands {
         /// Output path for config file
         #[arg(short, long, default_value = "config.toml")]
         output: String,
+        
+        /// Default the thread count to physical cores instead of hyperthreads
+        #[arg(long)]
+        use_physical_cores_only: bool,
     },
 }
 
@@ -70,14 +122,19 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Test { threads, difficulty, duration } => {
-            run_test(threads, difficulty, duration).await?;
+        Commands::Test { threads, difficulty, epoch, duration, config } => {
+            run_test(threads, difficulty, epoch, duration, &config).await?;
         }
         Commands::Mine { config: _ } => {
             info!("Full mining mode not yet implemented. Use 'test' command for now.");
         }
-        Commands::Init { output } => {
-            let config = config::Config::default();
+        Commands::Init { output, use_physical_cores_only } => {
+            let mut config = config::Config::default();
+            let cores = miner::CoreCounts::detect();
+            info!("Detected {} logical / {} physical CPU cores", cores.logical, cores.physical);
+            if use_physical_cores_only {
+                config.miner.threads = cores.default_workers(true);
+            }
             config.save(&output)?;
             info!("Configuration file created at: {}", output);
         }
@@ -86,10 +143,31 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run_test(threads: usize, difficulty: u8, duration: u64) -> Result<()> {
+/// Fetch the live epoch from the mining contract named in the config file (or the default)
+async fn fetch_live_epoch(config_path: &str) -> Result<chain::EpochInfoResponse> {
+    let config = if Path::new(config_path).exists() {
+        config::Config::load(config_path)?
+    } else {
+        config::Config::from_env()?
+    };
+    let client_config = chain::ClientConfig {
+        grpc_endpoint: config.chain.grpc_endpoint.clone(),
+        chain_id: config.chain.chain_id.clone(),
+        ..chain::ClientConfig::default()
+    };
+    
+    info!("Querying live epoch from {} via {}", config.chain.mining_contract, config.chain.grpc_endpoint);
+    let mut client = chain::InjectiveClient::new_read_only(client_config)?;
+    client.connect().await?;
+    chain::query_epoch_info(&client, &config.chain.mining_contract).await
+}
+
+async fn run_test(threads: usize, difficulty: DifficultyArg, epoch: EpochArg, duration: u64, config_path: &str) -> Result<()> {
     // Validate inputs
-    if difficulty < MIN_DIFFICULTY || difficulty > MAX_DIFFICULTY {
-        bail!("Difficulty must be between {} and {}", MIN_DIFFICULTY, MAX_DIFFICULTY);
+    if let DifficultyArg::Fixed(difficulty) = difficulty {
+        if difficulty < MIN_DIFFICULTY || difficulty > MAX_DIFFICULTY {
+            bail!("Difficulty must be between {} and {}", MIN_DIFFICULTY, MAX_DIFFICULTY);
+        }
     }
     if threads == 0 || threads > MAX_THREADS {
         bail!("Thread count must be between 1 and {}", MAX_THREADS);
@@ -98,6 +176,20 @@ async fn run_test(threads: usize, difficulty: u8, duration: u64) -> Result<()> {
         bail!("Duration must be between 1 and {} seconds", MAX_DURATION);
     }
     
+    // Only touch the network when asked to mirror live conditions
+    let live = if difficulty == DifficultyArg::Auto || epoch == EpochArg::Current {
+        let info = fetch_live_epoch(config_path).await?;
+        info!("Live epoch {}: difficulty {}", info.epoch_number, info.difficulty);
+        Some(info)
+    } else {
+        None
+    };
+    let difficulty = match (difficulty, &live) {
+        (DifficultyArg::Fixed(difficulty), _) => difficulty,
+        (DifficultyArg::Auto, Some(info)) => info.difficulty,
+        (DifficultyArg::Auto, None) => unreachable!("live epoch is fetched for auto difficulty"),
+    };
+    
     info!("Starting mining test");
     info!("Threads: {}", threads);
     info!("Difficulty: {}", difficulty);
@@ -108,9 +200,20 @@ async fn run_test(threads: usize, difficulty: u8, duration: u64) -> Result<()> {
     // Start mining with a large nonce range
     let nonce_start = 0u64;
     let nonce_end = u64::MAX / 1000; // Use 1/1000th of total range
-    let test_epoch = 1; // Test epoch number
     
-    engine.start_mining(test_epoch, difficulty, (nonce_start, nonce_end)).await?;
+    match (epoch, &live) {
+        (EpochArg::Current, Some(info)) => {
+            let target_hash: [u8; 32] = info.target_hash.as_slice().try_into()
+                .map_err(|_| anyhow!("Invalid target_hash length {}", info.target_hash.len()))?;
+            info!("Epoch: {} (live, target {})", info.epoch_number, hex::encode(target_hash));
+            engine.start_mining_with_target(info.epoch_number, target_hash, difficulty, (nonce_start, nonce_end)).await?;
+        }
+        (EpochArg::Fixed(test_epoch), _) => {
+            info!("Epoch: {}", test_epoch);
+            engine.start_mining(test_epoch, difficulty, (nonce_start, nonce_end)).await?;
+        }
+        (EpochArg::Current, None) => unreachable!("live epoch is fetched for the current epoch"),
+    }
     
     // Poll for solution
     let start_time = std::time::Instant::now();
@@ -142,3 +245,19 @@ async fn run_test(threads: usize, difficulty: u8, duration: u64) -> Result<()> {
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_auto_args() {
+        assert_eq!("auto".parse::<DifficultyArg>().unwrap(), DifficultyArg::Auto);
+        assert_eq!("12".parse::<DifficultyArg>().unwrap(), DifficultyArg::Fixed(12));
+        assert!("hard".parse::<DifficultyArg>().is_err());
+        
+        assert_eq!("Current".parse::<EpochArg>().unwrap(), EpochArg::Current);
+        assert_eq!("7".parse::<EpochArg>().unwrap(), EpochArg::Fixed(7));
+        assert!("latest".parse::<EpochArg>().is_err());
+    }
+}
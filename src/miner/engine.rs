@@ -6,11 +6,14 @@ use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::{error, info};
 
-use super::{solution::Solution, worker::MiningWorker};
+use super::{solution::Solution, solver_pool::SolverMemoryPool, worker::{MiningWorker, SharedRange}};
 
 pub struct MiningEngine {
     threads: usize,
+    /// Hashes since the current run started - reset on every start, used for the rate
     hash_counter: Arc<AtomicU64>,
+    /// Lifetime hashes per worker slot, never reset
+    worker_hashes: Vec<Arc<AtomicU64>>,
     should_stop: Arc<AtomicBool>,
     workers: Vec<JoinHandle<()>>,
     solution_tx: mpsc::Sender<Solution>,
@@ -18,6 +21,18 @@ pub struct MiningEngine {
     start_time: Arc<Mutex<Option<Instant>>>,
     last_hash_count: Arc<AtomicU64>,
     last_hash_time: Arc<Mutex<Option<Instant>>>,
+    /// Extra range workers pick up once their own slice is exhausted
+    overflow_range: Option<(u64, u64)>,
+    /// Period over which workers are started one at a time (zero = all at once)
+    ramp_up: Duration,
+    /// Solver buffers handed to workers and reused across epochs
+    solver_pool: Arc<SolverMemoryPool>,
+    /// Each worker's cursor through its own slice, with the slice end
+    progress: Vec<(Arc<AtomicU64>, u64)>,
+    /// Checkpointed slices to continue instead of splitting the range afresh
+    resume_ranges: Option<Vec<(u64, u64)>>,
+    /// Fraction of the range the worker slices are rotated by (0.0 = start at the bottom)
+    nonce_offset: f64,
 }
 
 impl MiningEngine {
@@ -27,6 +42,7 @@ impl MiningEngine {
         Self {
             threads,
             hash_counter: Arc::new(AtomicU64::new(0)),
+            worker_hashes: Vec::new(),
             should_stop: Arc::new(AtomicBool::new(false)),
             workers: Vec::new(),
             solution_tx,
@@ -34,9 +50,67 @@ impl MiningEngine {
             start_time: Arc::new(Mutex::new(None)),
             last_hash_count: Arc::new(AtomicU64::new(0)),
             last_hash_time: Arc::new(Mutex::new(None)),
+            overflow_range: None,
+            ramp_up: Duration::ZERO,
+            solver_pool: Arc::new(SolverMemoryPool::new()),
+            progress: Vec::new(),
+            resume_ranges: None,
+            nonce_offset: 0.0,
         }
     }
 
+    /// Change the worker count; applies to the next call to `start_mining`
+    pub fn set_threads(&mut self, threads: usize) {
+        self.threads = threads.max(1);
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Stagger worker start-up over `ramp_up` to avoid a sudden power/thermal spike
+    pub fn set_ramp_up(&mut self, ramp_up: Duration) {
+        self.ramp_up = ramp_up;
+    }
+
+    /// Set (or clear) the extended range searched after the primary range is exhausted
+    /// Applies to the next call to `start_mining`
+    pub fn set_overflow_range(&mut self, range: Option<(u64, u64)>) {
+        self.overflow_range = range;
+    }
+
+    /// Continue these (checkpointed) slices, one worker each, instead of splitting
+    /// the range on the next call to `start_mining`
+    pub fn set_resume_ranges(&mut self, ranges: Option<Vec<(u64, u64)>>) {
+        self.resume_ranges = ranges;
+    }
+
+    /// Rotate where workers start in the range by `offset` (a fraction in 0.0..1.0), so
+    /// machines sharing a partition don't all hash the same nonces first
+    /// Applies to the next call to `start_mining`
+    pub fn set_nonce_offset(&mut self, offset: f64) {
+        self.nonce_offset = if offset.is_finite() { offset.rem_euclid(1.0) } else { 0.0 };
+    }
+
+    /// Hashes computed since the engine was created, across all runs
+    pub fn total_hashes(&self) -> u64 {
+        self.worker_hashes.iter().map(|total| total.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Lifetime hashes of each worker slot (slot i is the i-th worker of every run)
+    pub fn worker_total_hashes(&self) -> Vec<u64> {
+        self.worker_hashes.iter().map(|total| total.load(Ordering::Relaxed)).collect()
+    }
+
+    /// Unsearched remainder of each worker's slice, for checkpointing
+    pub fn search_progress(&self) -> Vec<(u64, u64)> {
+        self.progress
+            .iter()
+            .map(|(cursor, end)| (cursor.load(Ordering::Relaxed), *end))
+            .filter(|(next, end)| next < end)
+            .collect()
+    }
+
     pub async fn start_mining(
         &mut self,
         challenge: [u8; 32],
@@ -64,8 +138,26 @@ impl MiningEngine {
             info!("Drained {} stale solutions from channel", drained);
         }
 
-        let nonce_range = nonce_end - nonce_start;
-        let nonce_per_worker = nonce_range / self.threads as u64;
+        info!(
+            "Solver memory: {} pooled buffers available ({} allocated in total)",
+            self.solver_pool.available(),
+            self.solver_pool.allocated()
+        );
+
+        let slices = match self.resume_ranges.take() {
+            Some(ranges) if !ranges.is_empty() => {
+                info!("Resuming nonce search from checkpoint across {} worker slices", ranges.len());
+                ranges.into_iter().map(|range| (range, None)).collect()
+            }
+            _ => {
+                let rotation = rotation(nonce_start, nonce_end, self.nonce_offset);
+                if rotation > 0 {
+                    info!("Nonce offset {:.4}: workers start {} nonces into the range", self.nonce_offset, rotation);
+                }
+                split_range_rotated(nonce_start, nonce_end, self.threads, rotation)
+            }
+        };
+        let worker_count = slices.len();
 
         self.should_stop.store(false, Ordering::Relaxed);
         self.hash_counter.store(0, Ordering::Relaxed);
@@ -80,28 +172,62 @@ impl MiningEngine {
             }
         }
 
-        for i in 0..self.threads {
-            let worker_start = nonce_start + (i as u64 * nonce_per_worker);
-            let worker_end = if i == self.threads - 1 {
-                nonce_end
-            } else {
-                worker_start + nonce_per_worker
-            };
+        let overflow = self
+            .overflow_range
+            .map(|(start, end)| Arc::new(SharedRange::new(start, end)));
+        if let Some((start, end)) = self.overflow_range {
+            info!("Extended nonce search enabled: {} to {}", start, end);
+        }
 
-            let worker = MiningWorker::new(
+        // Worker i waits i * step before hashing so load climbs gradually
+        let ramp_step = self.ramp_up / worker_count.max(1) as u32;
+        if !ramp_step.is_zero() {
+            info!("Ramping up {} workers over {:?}", worker_count, self.ramp_up);
+        }
+
+        self.progress.clear();
+        while self.worker_hashes.len() < worker_count {
+            self.worker_hashes.push(Arc::new(AtomicU64::new(0)));
+        }
+        for (i, ((worker_start, worker_end), wrap)) in slices.into_iter().enumerate() {
+            let cursor = Arc::new(AtomicU64::new(worker_start));
+            self.progress.push((Arc::clone(&cursor), worker_end));
+
+            let mut worker = MiningWorker::new(
                 i,
                 worker_start,
                 worker_end,
                 Arc::clone(&self.hash_counter),
                 Arc::clone(&self.should_stop),
-            );
+            )
+            .with_overflow(overflow.clone())
+            .with_total_hashes(Arc::clone(&self.worker_hashes[i]))
+            .with_progress(cursor);
+            // The wrapped part is checkpointed as a slice of its own
+            if let Some((wrap_start, wrap_end)) = wrap {
+                let wrap_cursor = Arc::new(AtomicU64::new(wrap_start));
+                self.progress.push((Arc::clone(&wrap_cursor), wrap_end));
+                worker = worker.with_wrap(wrap_start, wrap_end, wrap_cursor);
+            }
 
             let solution_tx = self.solution_tx.clone();
             let challenge = challenge; // No need to clone, arrays are Copy
             let should_stop = Arc::clone(&self.should_stop);
+            let delay = ramp_step * i as u32;
+            let threads = worker_count;
+            let solver_pool = Arc::clone(&self.solver_pool);
 
             let handle = tokio::task::spawn_blocking(move || {
-                if let Some(solution) = worker.mine(&challenge, difficulty) {
+                if !delay.is_zero() {
+                    if !ramp_delay(delay, &should_stop) {
+                        return;
+                    }
+                    info!("Ramp-up: worker {}/{} started", i + 1, threads);
+                }
+                let mut memory = solver_pool.checkout();
+                let solution = worker.mine(&mut memory, &challenge, difficulty);
+                solver_pool.checkin(memory);
+                if let Some(solution) = solution {
                     if let Err(e) = solution_tx.blocking_send(solution) {
                         error!("Failed to send solution: {}", e);
                     }
@@ -203,6 +329,7 @@ impl MiningEngine {
 
     async fn start_hashrate_monitor(&self) {
         let hash_counter = Arc::clone(&self.hash_counter);
+        let worker_hashes = self.worker_hashes.clone();
         let should_stop = Arc::clone(&self.should_stop);
 
         tokio::spawn(async move {
@@ -218,10 +345,13 @@ impl MiningEngine {
                 
                 if elapsed > 0.0 {
                     let hashrate = (current_count - last_count) as f64 / elapsed;
+                    let total: u64 = worker_hashes.iter().map(|total| total.load(Ordering::Relaxed)).sum();
                     info!(
-                        "Hashrate: {:.2} H/s ({:.2} MH/s)",
+                        "Hashrate: {:.2} H/s ({:.2} MH/s), {} hashes this run, {} total",
                         hashrate,
-                        hashrate / 1_000_000.0
+                        hashrate / 1_000_000.0,
+                        current_count,
+                        total
                     );
                 }
                 
@@ -232,6 +362,58 @@ impl MiningEngine {
     }
 }
 
+/// Split `start..end` into `threads` contiguous slices, the last taking the remainder
+fn split_range(start: u64, end: u64, threads: usize) -> Vec<(u64, u64)> {
+    let threads = threads.max(1);
+    let per_worker = (end - start) / threads as u64;
+    (0..threads)
+        .map(|i| {
+            let worker_start = start + i as u64 * per_worker;
+            let worker_end = if i == threads - 1 { end } else { worker_start + per_worker };
+            (worker_start, worker_end)
+        })
+        .collect()
+}
+
+/// Nonces to rotate `start..end` by for a fractional `offset`
+fn rotation(start: u64, end: u64, offset: f64) -> u64 {
+    let len = end.saturating_sub(start);
+    ((len as f64 * offset) as u64).min(len.saturating_sub(1))
+}
+
+/// `split_range` with every slice moved `rotation` nonces up the range. A slice
+/// running past `end` continues from `start`; that part is returned separately
+fn split_range_rotated(start: u64, end: u64, threads: usize, rotation: u64) -> Vec<((u64, u64), Option<(u64, u64)>)> {
+    let len = end - start;
+    let rotation = rotation.checked_rem(len).unwrap_or(0);
+    // Offset `len - rotation` into the split is where the rotated range wraps to `start`
+    let wrap_at = len - rotation;
+    split_range(0, len, threads)
+        .into_iter()
+        .map(|(from, to)| {
+            if to <= wrap_at {
+                ((start + rotation + from, start + rotation + to), None)
+            } else if from >= wrap_at {
+                ((start + from - wrap_at, start + to - wrap_at), None)
+            } else {
+                ((start + rotation + from, end), Some((start, start + to - wrap_at)))
+            }
+        })
+        .collect()
+}
+
+/// Sleep for `delay` in small steps, returning false if mining was stopped meanwhile
+fn ramp_delay(delay: Duration, should_stop: &AtomicBool) -> bool {
+    let deadline = Instant::now() + delay;
+    while Instant::now() < deadline {
+        if should_stop.load(Ordering::Relaxed) {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(50).min(deadline.saturating_duration_since(Instant::now())));
+    }
+    !should_stop.load(Ordering::Relaxed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,4 +440,76 @@ mod tests {
 
         engine.shutdown().await;
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(split_range(100, 400, 3), vec![(100, 200), (200, 300), (300, 400)]);
+        assert_eq!(split_range(0, 10, 3), vec![(0, 3), (3, 6), (6, 10)]);
+    }
+
+    #[test]
+    fn test_split_range_rotated() {
+        assert_eq!(
+            split_range_rotated(100, 400, 3, 0),
+            vec![((100, 200), None), ((200, 300), None), ((300, 400), None)]
+        );
+        // Rotated by 150: the middle slice runs off the end and wraps to the start
+        assert_eq!(
+            split_range_rotated(100, 400, 3, 150),
+            vec![((250, 350), None), ((350, 400), Some((100, 150))), ((150, 250), None)]
+        );
+        assert_eq!(rotation(100, 400, 0.5), 150);
+        assert_eq!(rotation(0, u64::MAX, 1.0), u64::MAX - 1);
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_checkpoint() {
+        let mut engine = MiningEngine::new(4);
+        // Impossible difficulty so nothing is found; stop right away
+        engine.set_resume_ranges(Some(vec![(500, 600), (900, 1000)]));
+        engine.start_mining([0u8; 32], 255, 0, 1000).await.unwrap();
+        engine.shutdown().await;
+
+        // Workers ran on the checkpointed slices, not the full range
+        let progress = engine.search_progress();
+        assert!(progress.len() <= 2);
+        assert!(progress.iter().all(|&(next, end)| {
+            ((500..600).contains(&next) && end == 600) || ((900..1000).contains(&next) && end == 1000)
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_total_hashes_survive_restarts() {
+        let mut engine = MiningEngine::new(2);
+        engine.start_mining([0u8; 32], 255, 0, 40).await.unwrap();
+        while engine.workers.iter().any(|worker| !worker.is_finished()) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        engine.shutdown().await;
+        assert_eq!(engine.worker_total_hashes(), vec![20, 20]);
+
+        // The run counter starts over, the lifetime totals don't
+        engine.set_threads(3);
+        engine.start_mining([1u8; 32], 255, 0, 30).await.unwrap();
+        engine.shutdown().await;
+        engine.start_mining([2u8; 32], 255, 0, 30).await.unwrap();
+        while engine.workers.iter().any(|worker| !worker.is_finished()) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        engine.shutdown().await;
+        assert_eq!(engine.hash_counter.load(Ordering::Relaxed), 30);
+        assert!(engine.total_hashes() >= 70);
+        assert_eq!(engine.worker_total_hashes().len(), 3);
+    }
+
+    #[test]
+    fn test_ramp_delay_aborts_on_stop() {
+        let stop = AtomicBool::new(true);
+        let start = Instant::now();
+        assert!(!ramp_delay(Duration::from_secs(10), &stop));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        let stop = AtomicBool::new(false);
+        assert!(ramp_delay(Duration::from_millis(20), &stop));
+    }
+}
@@ -2,6 +2,8 @@
 /// Provides the interface expected by Gemini Pro's orchestrator design
 
 use anyhow::Result;
+use gmine_mobile::byte_order;
+use gmine_mobile::proof::SolutionProof;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use blake2::{Blake2b512, Digest};
@@ -9,14 +11,58 @@ use rand::Rng;
 
 use super::engine::MiningEngine;
 use super::solution::Solution;
+use crate::chain::messages::create_commitment;
 use crate::orchestrator::CommitmentData;
 
+/// Commitment data for a worker's solution, or None when it can't be built in full:
+/// no epoch or challenge to tie it to (mining was stopped meanwhile), or a digest that
+/// isn't the drillx hash of the nonce. The commitment is computed from the exact nonce,
+/// digest and salt stored next to it, so the reveal always matches the commit
+fn commitment_data(
+    epoch: Option<u64>,
+    challenge: Option<[u8; 32]>,
+    solution: &Solution,
+    salt: [u8; 32],
+) -> Option<CommitmentData> {
+    let (epoch, challenge) = match (epoch, challenge) {
+        (Some(epoch), Some(challenge)) => (epoch, challenge),
+        _ => {
+            log::warn!("Dropping solution (nonce {}) found after mining stopped", solution.nonce);
+            return None;
+        }
+    };
+    let nonce = byte_order::nonce_bytes(solution.nonce);
+    let mut memory = equix::SolverMemory::new();
+    match drillx::hash_with_memory(&mut memory, &challenge, &nonce) {
+        Ok(hash) if hash.d == solution.digest && hash.difficulty() >= solution.difficulty as u32 => {}
+        _ => {
+            log::warn!(
+                "Dropping solution (nonce {}) for epoch {}: digest {} does not verify against the challenge",
+                solution.nonce, epoch, hex::encode(solution.digest)
+            );
+            return None;
+        }
+    }
+    
+    Some(CommitmentData {
+        epoch,
+        nonce,
+        digest: solution.digest,
+        salt,
+        commitment: create_commitment(nonce, solution.digest, salt),
+        prepared_reveal: None,
+        found_at_ms: None,
+    })
+}
+
 /// Adapter that wraps MiningEngine to work with the orchestrator
 pub struct MiningAdapter {
     /// The underlying mining engine
     engine: Arc<RwLock<MiningEngine>>,
     /// Current epoch being mined
     current_epoch: Arc<RwLock<Option<u64>>>,
+    /// Challenge the engine is hashing against
+    current_challenge: Arc<RwLock<Option<[u8; 32]>>>,
     /// Last found solution
     last_solution: Arc<RwLock<Option<Solution>>>,
 }
@@ -27,6 +73,7 @@ impl MiningAdapter {
         Self {
             engine: Arc::new(RwLock::new(MiningEngine::new(worker_count))),
             current_epoch: Arc::new(RwLock::new(None)),
+            current_challenge: Arc::new(RwLock::new(None)),
             last_solution: Arc::new(RwLock::new(None)),
         }
     }
@@ -43,6 +90,7 @@ impl MiningAdapter {
         
         // Generate challenge from epoch (placeholder - should query from contract)
         let challenge = self.generate_challenge(epoch);
+        *self.current_challenge.write().await = Some(challenge);
         
         // Start the engine
         let mut engine = self.engine.write().await;
@@ -76,6 +124,7 @@ impl MiningAdapter {
         
         // Use the actual target hash from the contract
         let challenge = target_hash;
+        *self.current_challenge.write().await = Some(challenge);
         
         // Start the engine
         let mut engine = self.engine.write().await;
@@ -96,6 +145,37 @@ impl MiningAdapter {
         Ok(())
     }
     
+    /// Set the extended range searched once the primary range is exhausted
+    /// Takes effect on the next start_mining call
+    pub async fn set_overflow_range(&self, range: Option<(u64, u64)>) {
+        self.engine.write().await.set_overflow_range(range);
+    }
+    
+    /// Continue checkpointed slices on the next start_mining call
+    pub async fn set_resume_ranges(&self, ranges: Option<Vec<(u64, u64)>>) {
+        self.engine.write().await.set_resume_ranges(ranges);
+    }
+    
+    /// Unsearched remainder of each worker's slice
+    pub async fn search_progress(&self) -> Vec<(u64, u64)> {
+        self.engine.read().await.search_progress()
+    }
+    
+    /// Change the worker count used by the next start_mining call
+    pub async fn set_threads(&self, threads: usize) {
+        self.engine.write().await.set_threads(threads);
+    }
+    
+    /// Stagger worker start-up over the given period
+    pub async fn set_ramp_up(&self, ramp_up: std::time::Duration) {
+        self.engine.write().await.set_ramp_up(ramp_up);
+    }
+    
+    /// Rotate where workers start in the nonce range (fraction of the range)
+    pub async fn set_nonce_offset(&self, offset: f64) {
+        self.engine.write().await.set_nonce_offset(offset);
+    }
+    
     /// Stop mining
     pub async fn stop_mining(&self) -> Result<()> {
         let engine = self.engine.read().await;
@@ -115,30 +195,14 @@ impl MiningAdapter {
             std::time::Duration::from_millis(10)
         ).await;
         
-        if let Some(sol) = solution {
-            // Store the solution
-            *self.last_solution.write().await = Some(sol.clone());
-            
-            // Generate commitment data
-            let salt = self.generate_salt();
-            let commitment = self.create_commitment(
-                &sol.nonce.to_le_bytes(),
-                &sol.digest,
-                &salt,
-            );
-            
-            let epoch = self.current_epoch.read().await.unwrap_or(0);
-            
-            return Some(CommitmentData {
-                epoch,
-                nonce: sol.nonce.to_le_bytes(),
-                digest: sol.digest,
-                salt,
-                commitment,
-            });
-        }
+        let sol = solution?;
+        let epoch = *self.current_epoch.read().await;
+        let challenge = *self.current_challenge.read().await;
+        let data = commitment_data(epoch, challenge, &sol, self.generate_salt())?;
         
-        None
+        // Store the solution
+        *self.last_solution.write().await = Some(sol);
+        Some(data)
     }
     
     /// Get current hashrate
@@ -146,6 +210,24 @@ impl MiningAdapter {
         self.engine.read().await.get_hashrate()
     }
     
+    /// Hashes computed since the engine was created
+    pub async fn total_hashes(&self) -> u64 {
+        self.engine.read().await.total_hashes()
+    }
+    
+    /// Lifetime hashes of each worker slot
+    pub async fn worker_total_hashes(&self) -> Vec<u64> {
+        self.engine.read().await.worker_total_hashes()
+    }
+    
+    /// Offline-verifiable proof of the last found solution for the current epoch
+    pub async fn last_solution_proof(&self, miner: &str) -> Option<SolutionProof> {
+        let epoch = (*self.current_epoch.read().await)?;
+        let challenge = (*self.current_challenge.read().await)?;
+        let solution = self.last_solution.read().await.clone()?;
+        Some(SolutionProof::new(epoch, miner, &challenge, solution.nonce, &solution.digest, solution.difficulty as u32))
+    }
+    
     /// Generate challenge from epoch (placeholder implementation)
     fn generate_challenge(&self, epoch: u64) -> [u8; 32] {
         // In production, this would query the actual challenge from the contract
@@ -166,23 +248,6 @@ impl MiningAdapter {
         rng.fill(&mut salt);
         salt
     }
-    
-    /// Create commitment hash using Blake2b512 (matches contract)
-    fn create_commitment(
-        &self,
-        nonce: &[u8; 8],
-        digest: &[u8; 16],
-        salt: &[u8; 32],
-    ) -> [u8; 32] {
-        let mut hasher = Blake2b512::new();
-        hasher.update(nonce);
-        hasher.update(digest);
-        hasher.update(salt);
-        let result = hasher.finalize();
-        let mut commitment = [0u8; 32];
-        commitment.copy_from_slice(&result[0..32]);
-        commitment
-    }
 }
 
 /// Wrapper that implements the interface expected by the orchestrator
@@ -220,6 +285,30 @@ impl MiningEngineWrapper {
         self.adapter.check_solution().await
     }
     
+    pub async fn set_overflow_range(&mut self, range: Option<(u64, u64)>) {
+        self.adapter.set_overflow_range(range).await
+    }
+    
+    pub async fn set_ramp_up(&mut self, ramp_up: std::time::Duration) {
+        self.adapter.set_ramp_up(ramp_up).await
+    }
+    
+    pub async fn set_nonce_offset(&mut self, offset: f64) {
+        self.adapter.set_nonce_offset(offset).await
+    }
+    
+    pub async fn set_resume_ranges(&mut self, ranges: Option<Vec<(u64, u64)>>) {
+        self.adapter.set_resume_ranges(ranges).await
+    }
+    
+    pub async fn search_progress(&self) -> Vec<(u64, u64)> {
+        self.adapter.search_progress().await
+    }
+    
+    pub async fn set_threads(&mut self, threads: usize) {
+        self.adapter.set_threads(threads).await
+    }
+    
     pub async fn stop_mining(&mut self) -> Result<()> {
         self.adapter.stop_mining().await
     }
@@ -227,6 +316,18 @@ impl MiningEngineWrapper {
     pub async fn get_hashrate(&self) -> f64 {
         self.adapter.get_hashrate().await
     }
+    
+    pub async fn total_hashes(&self) -> u64 {
+        self.adapter.total_hashes().await
+    }
+    
+    pub async fn worker_total_hashes(&self) -> Vec<u64> {
+        self.adapter.worker_total_hashes().await
+    }
+    
+    pub async fn last_solution_proof(&self, miner: &str) -> Option<SolutionProof> {
+        self.adapter.last_solution_proof(miner).await
+    }
 }
 
 #[cfg(test)]
@@ -250,4 +351,35 @@ mod tests {
         // Stop mining
         wrapper.stop_mining().await.unwrap();
     }
+    
+    #[test]
+    fn test_commitment_data_is_self_consistent() {
+        use crate::chain::messages::CommitmentVersion;
+        
+        let challenge = [9u8; 32];
+        let mut memory = equix::SolverMemory::new();
+        let solution = (0..64u64)
+            .find_map(|nonce| {
+                drillx::hash_with_memory(&mut memory, &challenge, &byte_order::nonce_bytes(nonce))
+                    .ok()
+                    .map(|hash| Solution::new(nonce, hash.d, hash.difficulty() as u8))
+            })
+            .unwrap();
+        
+        let data = commitment_data(Some(7), Some(challenge), &solution, [3; 32]).unwrap();
+        assert_eq!(data.epoch, 7);
+        assert_eq!(data.nonce, byte_order::nonce_bytes(solution.nonce));
+        assert_eq!(data.digest, solution.digest);
+        assert_eq!(data.salt, [3; 32]);
+        assert_eq!(data.commitment, create_commitment(data.nonce, data.digest, data.salt));
+        assert!(data.is_consistent(CommitmentVersion::Salted, "inj1miner"));
+        
+        // Nothing partially filled: no epoch, no challenge or a digest that doesn't verify
+        assert!(commitment_data(None, Some(challenge), &solution, [3; 32]).is_none());
+        assert!(commitment_data(Some(7), None, &solution, [3; 32]).is_none());
+        assert!(commitment_data(Some(7), Some([8; 32]), &solution, [3; 32]).is_none());
+        let mut tampered = solution.clone();
+        tampered.digest[0] ^= 1;
+        assert!(commitment_data(Some(7), Some(challenge), &tampered, [3; 32]).is_none());
+    }
 }
\ No newline at end of file
@@ -2,6 +2,10 @@ pub mod engine;
 pub mod solution;
 pub mod worker;
 pub mod mining_adapter;
+pub mod thread_scaling;
+pub mod pow;
+pub mod solver_pool;
 
 pub use engine::MiningEngine as RawMiningEngine;
-pub use mining_adapter::MiningEngineWrapper as MiningEngine;
\ No newline at end of file
+pub use mining_adapter::MiningEngineWrapper as MiningEngine;
+pub use thread_scaling::{CoreCounts, HostConditions, ThreadScaler};
\ No newline at end of file
@@ -0,0 +1,192 @@
+/// Proof-of-work parameters - drillx/equix settings the contract verifies against
+/// These are fixed by the vendored `drillx` version, so they're pinned here and
+/// checked against the deployed contract before mining starts
+use anyhow::{Result, anyhow};
+use gmine_mobile::byte_order;
+use serde::Deserialize;
+
+/// Hash algorithm used for solutions
+pub const POW_ALGORITHM: &str = "drillx";
+/// drillx parameter set of the vendored implementation (gmine-vendor v1.0.0)
+pub const POW_PARAMS_VERSION: u32 = 1;
+/// Vendored drillx release the parameters come from
+pub const POW_VENDOR_VERSION: &str = "gmine-vendor v1.0.0";
+/// Contract major version whose verification uses `POW_PARAMS_VERSION`
+/// Used when the contract doesn't report its PoW parameters directly
+pub const POW_CONTRACT_MAJOR: u64 = 3;
+
+/// PoW parameters as reported by the contract
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct PowParams {
+    pub algorithm: String,
+    pub version: u32,
+}
+
+impl PowParams {
+    /// Parameters this miner produces solutions for
+    pub fn miner() -> Self {
+        Self {
+            algorithm: POW_ALGORITHM.to_string(),
+            version: POW_PARAMS_VERSION,
+        }
+    }
+}
+
+/// Fail unless the contract verifies with the same parameters we mine with
+pub fn check_pow_params(contract: &PowParams) -> Result<()> {
+    let miner = PowParams::miner();
+    if !contract.algorithm.eq_ignore_ascii_case(&miner.algorithm) || contract.version != miner.version {
+        return Err(anyhow!(
+            "Contract verifies {} v{} but this miner implements {} v{} ({}) - \
+             solutions would be rejected, update the miner",
+            contract.algorithm, contract.version, miner.algorithm, miner.version, POW_VENDOR_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// Fallback for contracts that don't report PoW parameters: the parameters are
+/// pinned to the contract's major version, so a different major means they may have changed
+pub fn check_pow_contract_version(version: &str) -> Result<()> {
+    let major = version.trim_start_matches('v').split('.').next().and_then(|p| p.parse::<u64>().ok());
+    if major != Some(POW_CONTRACT_MAJOR) {
+        return Err(anyhow!(
+            "Contract v{} may use different PoW parameters than this miner ({} v{}, pinned to contract v{}.x) - \
+             refusing to mine invalid solutions, update the miner",
+            version, POW_ALGORITHM, POW_PARAMS_VERSION, POW_CONTRACT_MAJOR
+        ));
+    }
+    Ok(())
+}
+
+/// Challenge hashed by the drillx self-test
+const SELF_TEST_CHALLENGE: [u8; 32] = *b"gmine drillx self-test challenge";
+/// First nonce the self-test tries; equix finds no solution for some nonces, so the
+/// following ones are tried until one hashes
+const SELF_TEST_FIRST_NONCE: u64 = 0;
+/// Nonces tried before the self-test gives up on finding one that hashes
+const SELF_TEST_MAX_NONCES: u64 = 64;
+
+/// A drillx input and the output `POW_VENDOR_VERSION` produces for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrillxVector {
+    pub challenge: [u8; 32],
+    pub nonce: u64,
+    pub digest: [u8; 16],
+    pub difficulty: u32,
+}
+
+impl std::fmt::Display for DrillxVector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "challenge {}, nonce {}, digest {}, difficulty {}",
+            hex::encode(self.challenge), self.nonce, hex::encode(self.digest), self.difficulty
+        )
+    }
+}
+
+/// A solution the mining contract accepted on-chain: the epoch's challenge, the nonce,
+/// digest and difficulty from its reveal. NOT PINNED YET - no accepted reveal has been
+/// recorded here. Copy the four values from one (with its tx hash in this comment),
+/// then make this a plain `DrillxVector` so a mismatch is always fatal. Until then only
+/// determinism is checked
+pub const KNOWN_DRILLX_VECTOR: Option<DrillxVector> = None;
+
+/// Hash one challenge and nonce with the linked drillx
+pub fn hash_drillx_vector(challenge: &[u8; 32], nonce: u64) -> Result<DrillxVector> {
+    let mut memory = equix::SolverMemory::new();
+    let hash = drillx::hash_with_memory(&mut memory, challenge, &byte_order::nonce_bytes(nonce))
+        .map_err(|_| anyhow!("drillx produced no hash for nonce {}", nonce))?;
+    Ok(DrillxVector { challenge: *challenge, nonce, digest: hash.d, difficulty: hash.difficulty() })
+}
+
+/// Hash the self-test challenge with the linked drillx
+pub fn compute_drillx_vector() -> Result<DrillxVector> {
+    let mut memory = equix::SolverMemory::new();
+    (SELF_TEST_FIRST_NONCE..SELF_TEST_FIRST_NONCE + SELF_TEST_MAX_NONCES)
+        .find_map(|nonce| {
+            drillx::hash_with_memory(&mut memory, &SELF_TEST_CHALLENGE, &byte_order::nonce_bytes(nonce))
+                .ok()
+                .map(|hash| DrillxVector { challenge: SELF_TEST_CHALLENGE, nonce, digest: hash.d, difficulty: hash.difficulty() })
+        })
+        .ok_or_else(|| anyhow!(
+            "drillx self-test: none of nonces {}..{} produced a hash",
+            SELF_TEST_FIRST_NONCE, SELF_TEST_FIRST_NONCE + SELF_TEST_MAX_NONCES
+        ))
+}
+
+/// Fail unless the drillx `expected` output is reproduced by `actual`
+pub fn check_drillx_vector(actual: &DrillxVector, expected: &DrillxVector) -> Result<()> {
+    if actual != expected {
+        return Err(anyhow!(
+            "drillx algorithm mismatch - incompatible with contract: the self-test produced {} \
+             but {} ({} v{}) produces {}. Solutions from this build would be rejected, refusing to mine",
+            actual, POW_VENDOR_VERSION, POW_ALGORITHM, POW_PARAMS_VERSION, expected
+        ));
+    }
+    Ok(())
+}
+
+/// Startup self-test: a drillx upgrade that changes the hash output would otherwise
+/// only show as every solution being rejected. Hashes a fixed challenge twice (fresh
+/// solver memory each time), then rehashes the accepted solution in `KNOWN_DRILLX_VECTOR`
+pub fn drillx_self_test() -> Result<DrillxVector> {
+    let actual = compute_drillx_vector()?;
+    let again = compute_drillx_vector()?;
+    if actual != again {
+        return Err(anyhow!(
+            "drillx algorithm mismatch - incompatible with contract: the same input hashed to {} and {}",
+            actual, again
+        ));
+    }
+    match KNOWN_DRILLX_VECTOR {
+        Some(expected) => check_drillx_vector(&hash_drillx_vector(&expected.challenge, expected.nonce)?, &expected)?,
+        None => log::warn!("drillx self-test: no known-good vector pinned, only checked determinism ({})", actual),
+    }
+    Ok(actual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_pow_params() {
+        assert!(check_pow_params(&PowParams::miner()).is_ok());
+        assert!(check_pow_params(&PowParams { algorithm: "DrillX".to_string(), version: 1 }).is_ok());
+
+        let err = check_pow_params(&PowParams { algorithm: "drillx".to_string(), version: 2 }).unwrap_err();
+        assert!(err.to_string().contains("update the miner"));
+        assert!(check_pow_params(&PowParams { algorithm: "sha256".to_string(), version: 1 }).is_err());
+    }
+
+    #[test]
+    fn test_check_pow_contract_version() {
+        assert!(check_pow_contract_version("3.4.0").is_ok());
+        assert!(check_pow_contract_version("v3.9").is_ok());
+        assert!(check_pow_contract_version("4.0.0").is_err());
+        assert!(check_pow_contract_version("garbage").is_err());
+    }
+
+    #[test]
+    fn test_drillx_self_test() {
+        let vector = drillx_self_test().unwrap();
+        assert!(check_drillx_vector(&vector, &vector).is_ok());
+        // A pinned vector is rehashed from its own challenge and nonce
+        assert_eq!(hash_drillx_vector(&vector.challenge, vector.nonce).unwrap(), vector);
+
+        let mut changed = vector;
+        changed.digest[0] ^= 1;
+        let err = check_drillx_vector(&changed, &vector).unwrap_err();
+        assert!(err.to_string().contains("drillx algorithm mismatch"));
+    }
+
+    #[test]
+    #[ignore = "KNOWN_DRILLX_VECTOR needs a solution the contract accepted on-chain"]
+    fn test_drillx_matches_pinned_vector() {
+        let expected = KNOWN_DRILLX_VECTOR.expect("pin KNOWN_DRILLX_VECTOR from an accepted reveal");
+        let actual = hash_drillx_vector(&expected.challenge, expected.nonce).unwrap();
+        check_drillx_vector(&actual, &expected).unwrap();
+    }
+}
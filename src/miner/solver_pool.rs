@@ -0,0 +1,97 @@
+/// Pool of equix solver buffers kept alive across epochs
+/// Each worker needs tens of MB of solver memory; reusing it avoids allocator
+/// churn and page faults every time workers are restarted for a new epoch
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Stack of each worker's blocking thread (tokio's default)
+const WORKER_STACK_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Memory one mining worker needs: its solver buffer plus its thread's stack
+pub fn worker_memory_bytes() -> u64 {
+    std::mem::size_of::<equix::SolverMemory>() as u64 + WORKER_STACK_BYTES
+}
+
+/// How many of `requested` workers fit in `budget_bytes` at `per_worker_bytes` each
+/// Always at least one - a budget too small for any worker still mines on one
+pub fn workers_within_budget(requested: usize, budget_bytes: u64, per_worker_bytes: u64) -> usize {
+    let fit = budget_bytes / per_worker_bytes.max(1);
+    requested.min(usize::try_from(fit).unwrap_or(usize::MAX)).max(1)
+}
+
+pub struct SolverMemoryPool {
+    buffers: Mutex<Vec<Box<equix::SolverMemory>>>,
+    /// Total buffers ever allocated by this pool
+    allocated: AtomicUsize,
+}
+
+impl SolverMemoryPool {
+    pub fn new() -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+            allocated: AtomicUsize::new(0),
+        }
+    }
+
+    /// Take a buffer from the pool, allocating only if none is free
+    pub fn checkout(&self) -> Box<equix::SolverMemory> {
+        let reused = self.buffers.lock().unwrap_or_else(|e| e.into_inner()).pop();
+        reused.unwrap_or_else(|| {
+            self.allocated.fetch_add(1, Ordering::Relaxed);
+            Box::new(equix::SolverMemory::new())
+        })
+    }
+
+    /// Return a buffer for the next epoch's workers
+    pub fn checkin(&self, memory: Box<equix::SolverMemory>) {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner()).push(memory);
+    }
+
+    /// Buffers currently idle in the pool
+    pub fn available(&self) -> usize {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Total buffers allocated over the pool's lifetime
+    pub fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for SolverMemoryPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workers_within_budget() {
+        let mb = 1024 * 1024;
+        assert_eq!(workers_within_budget(8, 1024 * mb, 100 * mb), 8);
+        assert_eq!(workers_within_budget(8, 350 * mb, 100 * mb), 3);
+        assert_eq!(workers_within_budget(8, 10 * mb, 100 * mb), 1);
+        assert!(worker_memory_bytes() > WORKER_STACK_BYTES);
+    }
+
+    #[test]
+    fn test_buffers_are_reused() {
+        let pool = SolverMemoryPool::new();
+        let a = pool.checkout();
+        let b = pool.checkout();
+        assert_eq!(pool.allocated(), 2);
+
+        pool.checkin(a);
+        pool.checkin(b);
+        assert_eq!(pool.available(), 2);
+
+        // A second "epoch" with the same worker count allocates nothing new
+        let _a = pool.checkout();
+        let _b = pool.checkout();
+        assert_eq!(pool.allocated(), 2);
+        assert_eq!(pool.available(), 0);
+    }
+}
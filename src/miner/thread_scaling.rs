@@ -0,0 +1,179 @@
+/// Adaptive worker count - picks a thread count per epoch from difficulty and
+/// host conditions (CPU temperature, battery) within configured bounds
+use sysinfo::Components;
+
+/// At or below this difficulty the minimum thread count finds solutions in time
+const DIFFICULTY_LOW: u8 = 10;
+/// At or above this difficulty we want every thread we're allowed
+const DIFFICULTY_HIGH: u8 = 20;
+/// CPU temperatures (°C) at which we start backing off
+const TEMP_WARM_C: f32 = 75.0;
+const TEMP_HOT_C: f32 = 85.0;
+
+/// Snapshot of host state relevant to thread scaling
+#[derive(Debug, Clone, Default)]
+pub struct HostConditions {
+    /// Hottest reported CPU/component temperature in °C (None if unavailable)
+    pub cpu_temp_c: Option<f32>,
+    /// Whether the machine is running on battery power
+    pub on_battery: bool,
+}
+
+impl HostConditions {
+    /// Read current temperature and power source from the OS (best effort)
+    pub fn detect() -> Self {
+        let components = Components::new_with_refreshed_list();
+        let cpu_temp_c = components
+            .iter()
+            .map(|c| c.temperature())
+            .filter(|t| t.is_finite() && *t > 0.0)
+            .fold(None, |max: Option<f32>, t| Some(max.map_or(t, |m| m.max(t))));
+
+        Self {
+            cpu_temp_c,
+            on_battery: on_battery(),
+        }
+    }
+}
+
+/// Linux: on battery if a battery is present and no mains supply is online
+#[cfg(target_os = "linux")]
+fn on_battery() -> bool {
+    let entries = match std::fs::read_dir("/sys/class/power_supply") {
+        Ok(entries) => entries,
+        Err(_) => return false,
+    };
+    let mut has_battery = false;
+    let mut mains_online = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+        match kind.trim() {
+            "Battery" => has_battery = true,
+            "Mains" | "USB" => {
+                if std::fs::read_to_string(path.join("online")).map_or(false, |s| s.trim() == "1") {
+                    mains_online = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    has_battery && !mains_online
+}
+
+#[cfg(not(target_os = "linux"))]
+fn on_battery() -> bool {
+    false
+}
+
+/// Logical (hyperthread) and physical CPU core counts
+/// equix is memory-bandwidth bound, so a second hyperthread per core often lowers
+/// hashrate instead of raising it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoreCounts {
+    pub logical: usize,
+    pub physical: usize,
+}
+
+impl CoreCounts {
+    pub fn detect() -> Self {
+        let logical = num_cpus::get().max(1);
+        Self {
+            logical,
+            physical: num_cpus::get_physical().clamp(1, logical),
+        }
+    }
+
+    /// Whether the CPU runs more than one hardware thread per core
+    pub fn has_smt(&self) -> bool {
+        self.logical > self.physical
+    }
+
+    /// Default worker count: every core but one, counting only physical cores
+    /// when `physical_only` is set
+    pub fn default_workers(&self, physical_only: bool) -> usize {
+        let cores = if physical_only { self.physical } else { self.logical };
+        cores.saturating_sub(1).max(1)
+    }
+}
+
+/// Chooses a worker count within [min_threads, max_threads]
+#[derive(Debug, Clone)]
+pub struct ThreadScaler {
+    min_threads: usize,
+    max_threads: usize,
+}
+
+impl ThreadScaler {
+    pub fn new(min_threads: usize, max_threads: usize) -> Self {
+        let min_threads = min_threads.max(1);
+        Self {
+            min_threads,
+            max_threads: max_threads.max(min_threads),
+        }
+    }
+
+    /// Thread count for an epoch at `difficulty` under `conditions`
+    pub fn threads_for(&self, difficulty: u8, conditions: &HostConditions) -> usize {
+        // Scale linearly with difficulty between the low and high marks
+        let span = (self.max_threads - self.min_threads) as f64;
+        let fraction = if difficulty <= DIFFICULTY_LOW {
+            0.0
+        } else if difficulty >= DIFFICULTY_HIGH {
+            1.0
+        } else {
+            (difficulty - DIFFICULTY_LOW) as f64 / (DIFFICULTY_HIGH - DIFFICULTY_LOW) as f64
+        };
+        let mut threads = self.min_threads as f64 + span * fraction;
+
+        // Back off when hot or unplugged
+        match conditions.cpu_temp_c {
+            Some(t) if t >= TEMP_HOT_C => threads *= 0.5,
+            Some(t) if t >= TEMP_WARM_C => threads *= 0.75,
+            _ => {}
+        }
+        if conditions.on_battery {
+            threads *= 0.5;
+        }
+
+        (threads.round() as usize).clamp(self.min_threads, self.max_threads)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scales_with_difficulty() {
+        let scaler = ThreadScaler::new(2, 12);
+        let cool = HostConditions::default();
+        assert_eq!(scaler.threads_for(8, &cool), 2);
+        assert_eq!(scaler.threads_for(15, &cool), 7);
+        assert_eq!(scaler.threads_for(24, &cool), 12);
+    }
+
+    #[test]
+    fn test_default_workers_by_core_type() {
+        let smt = CoreCounts { logical: 16, physical: 8 };
+        assert!(smt.has_smt());
+        assert_eq!(smt.default_workers(false), 15);
+        assert_eq!(smt.default_workers(true), 7);
+
+        let single = CoreCounts { logical: 1, physical: 1 };
+        assert!(!single.has_smt());
+        assert_eq!(single.default_workers(true), 1);
+    }
+
+    #[test]
+    fn test_backs_off_when_hot_or_on_battery() {
+        let scaler = ThreadScaler::new(2, 12);
+        let hot = HostConditions { cpu_temp_c: Some(90.0), on_battery: false };
+        assert_eq!(scaler.threads_for(24, &hot), 6);
+
+        let unplugged_hot = HostConditions { cpu_temp_c: Some(90.0), on_battery: true };
+        // Never below the configured minimum
+        assert_eq!(scaler.threads_for(24, &unplugged_hot), 3);
+        assert_eq!(scaler.threads_for(8, &unplugged_hot), 2);
+    }
+}
@@ -1,4 +1,5 @@
 use drillx;
+use gmine_mobile::byte_order;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
@@ -8,13 +9,55 @@ use super::solution::Solution;
 
 // Constants for performance tuning
 const HASH_COUNTER_BATCH_SIZE: u64 = 10;
+/// Nonces claimed at a time from a shared overflow range
+const OVERFLOW_CHUNK_SIZE: u64 = 100_000;
+
+/// A nonce range shared between workers, handed out in chunks
+/// Used for extended search once a worker exhausts its own slice
+pub struct SharedRange {
+    next: AtomicU64,
+    end: u64,
+}
+
+impl SharedRange {
+    pub fn new(start: u64, end: u64) -> Self {
+        Self {
+            next: AtomicU64::new(start),
+            end,
+        }
+    }
+
+    /// Claim the next chunk, or None once the range is exhausted
+    pub fn claim(&self, chunk: u64) -> Option<(u64, u64)> {
+        // CAS loop rather than fetch_add so the cursor can't wrap past u64::MAX
+        let mut start = self.next.load(Ordering::Relaxed);
+        loop {
+            if start >= self.end {
+                return None;
+            }
+            let end = start.saturating_add(chunk).min(self.end);
+            match self.next.compare_exchange_weak(start, end, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => return Some((start, end)),
+                Err(current) => start = current,
+            }
+        }
+    }
+}
 
 pub struct MiningWorker {
     pub id: usize,
     pub nonce_start: u64,
     pub nonce_end: u64,
+    /// Hashes for the current run, reset by the engine on each start (rate measurement)
     pub hash_counter: Arc<AtomicU64>,
+    /// This worker slot's hashes over the engine's lifetime, never reset
+    pub total_hashes: Option<Arc<AtomicU64>>,
     pub should_stop: Arc<AtomicBool>,
+    pub overflow: Option<Arc<SharedRange>>,
+    /// Next unsearched nonce of this worker's own slice, for checkpointing
+    pub progress: Option<Arc<AtomicU64>>,
+    /// Second part of a slice that wrapped past the end of the range, with its own progress
+    pub wrap: Option<(u64, u64, Arc<AtomicU64>)>,
 }
 
 impl MiningWorker {
@@ -30,28 +73,110 @@ impl MiningWorker {
             nonce_start,
             nonce_end,
             hash_counter,
+            total_hashes: None,
             should_stop,
+            overflow: None,
+            progress: None,
+            wrap: None,
         }
     }
 
-    pub fn mine(&self, challenge: &[u8; 32], difficulty: u8) -> Option<Solution> {
-        let start_time = Instant::now();
-        let mut nonce = self.nonce_start;
+    /// Continue into a shared overflow range after this worker's slice is exhausted
+    pub fn with_overflow(mut self, overflow: Option<Arc<SharedRange>>) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Also count hashes into a lifetime total that outlives this run
+    pub fn with_total_hashes(mut self, total_hashes: Arc<AtomicU64>) -> Self {
+        self.total_hashes = Some(total_hashes);
+        self
+    }
+
+    /// Publish progress through the worker's own slice to `progress`
+    pub fn with_progress(mut self, progress: Arc<AtomicU64>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Search `start..end` once the worker's own slice is done (a rotated slice's wrapped part)
+    pub fn with_wrap(mut self, start: u64, end: u64, progress: Arc<AtomicU64>) -> Self {
+        self.wrap = Some((start, end, progress));
+        self
+    }
+
+    /// Search for a solution, hashing with the caller's (reusable) solver memory
+    pub fn mine(&self, memory: &mut equix::SolverMemory, challenge: &[u8; 32], difficulty: u8) -> Option<Solution> {
         let mut hash_attempts = 0u64;
+        let solution = self.search(memory, challenge, difficulty, &mut hash_attempts);
+        // Hashes since the last full batch would otherwise go uncounted
+        self.count_hashes(hash_attempts % HASH_COUNTER_BATCH_SIZE);
+        solution
+    }
+
+    fn count_hashes(&self, hashes: u64) {
+        self.hash_counter.fetch_add(hashes, Ordering::Relaxed);
+        if let Some(total) = &self.total_hashes {
+            total.fetch_add(hashes, Ordering::Relaxed);
+        }
+    }
+
+    fn search(&self, memory: &mut equix::SolverMemory, challenge: &[u8; 32], difficulty: u8, hash_attempts: &mut u64) -> Option<Solution> {
+        let start_time = Instant::now();
 
         info!(
             "Worker {} starting mining. Range: {} to {}, Difficulty: {}",
             self.id, self.nonce_start, self.nonce_end, difficulty
         );
         
+        let progress = self.progress.as_deref();
+        if let Some(sol) = self.mine_range(memory, self.nonce_start, self.nonce_end, challenge, difficulty, start_time, hash_attempts, progress) {
+            return Some(sol);
+        }
+        if let Some((start, end, ref progress)) = self.wrap {
+            if let Some(sol) = self.mine_range(memory, start, end, challenge, difficulty, start_time, hash_attempts, Some(progress)) {
+                return Some(sol);
+            }
+        }
+        
+        // Own slice exhausted - help with the overflow range if one was configured
+        if let Some(overflow) = &self.overflow {
+            info!("Worker {} exhausted its range, continuing in extended range", self.id);
+            while !self.should_stop.load(Ordering::Relaxed) {
+                let (start, end) = overflow.claim(OVERFLOW_CHUNK_SIZE)?;
+                if let Some(sol) = self.mine_range(memory, start, end, challenge, difficulty, start_time, hash_attempts, None) {
+                    return Some(sol);
+                }
+            }
+        }
+
+        None
+    }
 
-        while nonce < self.nonce_end && !self.should_stop.load(Ordering::Relaxed) {
-            let nonce_bytes = nonce.to_le_bytes();
-            hash_attempts += 1;
+    #[allow(clippy::too_many_arguments)]
+    fn mine_range(
+        &self,
+        memory: &mut equix::SolverMemory,
+        nonce_start: u64,
+        nonce_end: u64,
+        challenge: &[u8; 32],
+        difficulty: u8,
+        start_time: Instant,
+        hash_attempts: &mut u64,
+        progress: Option<&AtomicU64>,
+    ) -> Option<Solution> {
+        let mut nonce = nonce_start;
+
+        while nonce < nonce_end && !self.should_stop.load(Ordering::Relaxed) {
+            let nonce_bytes = byte_order::nonce_bytes(nonce);
+            *hash_attempts += 1;
             
             // Update counter periodically (batch for performance)
-            if hash_attempts % HASH_COUNTER_BATCH_SIZE == 0 {
-                self.hash_counter.fetch_add(HASH_COUNTER_BATCH_SIZE, Ordering::Relaxed);
+            if *hash_attempts % HASH_COUNTER_BATCH_SIZE == 0 {
+                self.count_hashes(HASH_COUNTER_BATCH_SIZE);
+                if let Some(progress) = progress {
+                    progress.store(nonce, Ordering::Relaxed);
+                }
                 debug!(
                     "Worker {} processed {} attempts, current nonce: {}",
                     self.id, hash_attempts, nonce
@@ -59,7 +184,7 @@ impl MiningWorker {
             }
             
             // Try to generate a hash
-            match drillx::hash(challenge, &nonce_bytes) {
+            match drillx::hash_with_memory(memory, challenge, &nonce_bytes) {
                 Ok(hash) => {
                     let hash_difficulty = hash.difficulty() as u8;
                     if hash_difficulty >= difficulty {
@@ -73,7 +198,7 @@ impl MiningWorker {
                         );
 
                         let mut sol = Solution::new(nonce, hash.d, hash_difficulty);
-                        sol.hash_attempts = hash_attempts;
+                        sol.hash_attempts = *hash_attempts;
                         sol.time_taken_ms = elapsed.as_millis() as u64;
 
                         return Some(sol);
@@ -88,8 +213,45 @@ impl MiningWorker {
             nonce += 1;
         }
 
+        if let Some(progress) = progress {
+            progress.store(nonce, Ordering::Relaxed);
+        }
         None
     }
 
     // Removed unused mine_random method
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_counters() {
+        let run = Arc::new(AtomicU64::new(0));
+        let total = Arc::new(AtomicU64::new(100));
+        let worker = MiningWorker::new(0, 0, 25, Arc::clone(&run), Arc::new(AtomicBool::new(false)))
+            .with_total_hashes(Arc::clone(&total));
+        let mut memory = Box::new(equix::SolverMemory::new());
+        // Impossible difficulty: the whole slice is hashed, including the partial batch
+        assert!(worker.mine(&mut memory, &[0u8; 32], 255).is_none());
+        assert_eq!(run.load(Ordering::Relaxed), 25);
+        // The lifetime total keeps counting on from earlier runs
+        assert_eq!(total.load(Ordering::Relaxed), 125);
+    }
+
+    #[test]
+    fn test_shared_range_chunks() {
+        let range = SharedRange::new(100, 350);
+        assert_eq!(range.claim(100), Some((100, 200)));
+        assert_eq!(range.claim(100), Some((200, 300)));
+        assert_eq!(range.claim(100), Some((300, 350)));
+        assert_eq!(range.claim(100), None);
+    }
+
+    #[test]
+    fn test_shared_range_near_max() {
+        let range = SharedRange::new(u64::MAX - 10, u64::MAX);
+        assert_eq!(range.claim(100), Some((u64::MAX - 10, u64::MAX)));
+    }
 }
\ No newline at end of file
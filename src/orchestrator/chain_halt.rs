@@ -0,0 +1,97 @@
+/// Chain halt detection - a block height that stops advancing for longer than a
+/// threshold means the chain is halted, and mining or submitting against it is wasted
+use std::time::{Duration, Instant};
+
+/// How often the block height is sampled while the chain is moving
+pub const CHAIN_HALT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often the main loop re-checks a halted chain
+pub const CHAIN_HALT_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// A change in whether the chain is halted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HaltChange {
+    /// The height has been stuck at `height` for `stalled_for`
+    Halted { height: u64, stalled_for: Duration },
+    /// Blocks are being produced again after `halted_for` without
+    Resumed { height: u64, halted_for: Duration },
+}
+
+/// Tracks how long the block height has stayed the same
+#[derive(Debug)]
+pub struct ChainHaltDetector {
+    threshold: Duration,
+    /// Last height seen and when it was first seen
+    last: Option<(u64, Instant)>,
+    halted: bool,
+}
+
+impl ChainHaltDetector {
+    pub fn new(threshold: Duration) -> Self {
+        Self { threshold, last: None, halted: false }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Record the height seen at `now`; returns a change when the chain halts or resumes
+    pub fn observe(&mut self, height: u64, now: Instant) -> Option<HaltChange> {
+        match self.last {
+            Some((last_height, since)) if last_height == height => {
+                let stalled_for = now.saturating_duration_since(since);
+                if !self.halted && stalled_for >= self.threshold {
+                    self.halted = true;
+                    return Some(HaltChange::Halted { height, stalled_for });
+                }
+                None
+            }
+            Some((_, since)) => {
+                self.last = Some((height, now));
+                if std::mem::take(&mut self.halted) {
+                    return Some(HaltChange::Resumed { height, halted_for: now.saturating_duration_since(since) });
+                }
+                None
+            }
+            None => {
+                self.last = Some((height, now));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_halt_and_resume() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut detector = ChainHaltDetector::new(Duration::from_secs(120));
+
+        assert_eq!(detector.observe(100, at(0)), None);
+        assert_eq!(detector.observe(100, at(60)), None);
+        assert_eq!(detector.observe(100, at(119)), None);
+        assert!(!detector.is_halted());
+
+        assert_eq!(detector.observe(100, at(120)), Some(HaltChange::Halted { height: 100, stalled_for: Duration::from_secs(120) }));
+        assert!(detector.is_halted());
+        // Reported once
+        assert_eq!(detector.observe(100, at(300)), None);
+
+        assert_eq!(detector.observe(101, at(310)), Some(HaltChange::Resumed { height: 101, halted_for: Duration::from_secs(310) }));
+        assert!(!detector.is_halted());
+    }
+
+    #[test]
+    fn test_advancing_chain_never_halts() {
+        let start = Instant::now();
+        let mut detector = ChainHaltDetector::new(Duration::from_secs(120));
+        for i in 0..100 {
+            assert_eq!(detector.observe(1_000 + i, start + Duration::from_secs(i * 10)), None);
+        }
+        assert!(!detector.is_halted());
+    }
+}
@@ -0,0 +1,103 @@
+/// Bounded claim retries - an epoch that never becomes claimable (settlement stuck,
+/// nobody advancing it) is retried with a growing delay, then given up on so the
+/// miner goes back to mining instead of polling it forever
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Delay after the first not-ready attempt; doubles with each one after
+const CLAIM_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+
+/// Upper bound for the delay between attempts on one epoch
+const CLAIM_RETRY_MAX_DELAY: Duration = Duration::from_secs(120);
+
+/// What to do after an attempt found the epoch not ready to claim
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimRetry {
+    /// Try again after this long
+    RetryIn(Duration),
+    /// The attempt budget is used up - stop trying to claim the epoch
+    GiveUp { attempts: u32 },
+}
+
+/// Not-ready attempts per epoch being claimed
+#[derive(Debug, Default)]
+pub struct ClaimRetries {
+    /// Epoch -> (attempts so far, earliest time for the next one)
+    epochs: HashMap<u64, (u32, Instant)>,
+}
+
+/// Delay before the attempt following `attempts` not-ready ones
+fn retry_delay(attempts: u32) -> Duration {
+    CLAIM_RETRY_BASE_DELAY
+        .saturating_mul(1u32 << attempts.saturating_sub(1).min(16))
+        .min(CLAIM_RETRY_MAX_DELAY)
+}
+
+impl ClaimRetries {
+    /// Time left before `epoch` may be tried again (None = now)
+    pub fn wait_remaining(&self, epoch: u64, now: Instant) -> Option<Duration> {
+        self.epochs.get(&epoch)
+            .map(|&(_, next)| next.saturating_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Record that `epoch` wasn't claimable yet. `max_attempts` of 0 means unlimited
+    pub fn record_not_ready(&mut self, epoch: u64, max_attempts: u32, now: Instant) -> ClaimRetry {
+        let attempts = self.epochs.get(&epoch).map_or(0, |&(attempts, _)| attempts) + 1;
+        if max_attempts > 0 && attempts >= max_attempts {
+            self.epochs.remove(&epoch);
+            return ClaimRetry::GiveUp { attempts };
+        }
+        let delay = retry_delay(attempts);
+        self.epochs.insert(epoch, (attempts, now + delay));
+        ClaimRetry::RetryIn(delay)
+    }
+
+    /// Forget `epoch` once its claim is done (or given up)
+    pub fn clear(&mut self, epoch: u64) {
+        self.epochs.remove(&epoch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay(1), Duration::from_secs(5));
+        assert_eq!(retry_delay(2), Duration::from_secs(10));
+        assert_eq!(retry_delay(4), Duration::from_secs(40));
+        assert_eq!(retry_delay(6), CLAIM_RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(u32::MAX), CLAIM_RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let now = Instant::now();
+        let mut retries = ClaimRetries::default();
+        assert_eq!(retries.wait_remaining(7, now), None);
+
+        assert_eq!(retries.record_not_ready(7, 3, now), ClaimRetry::RetryIn(Duration::from_secs(5)));
+        assert_eq!(retries.wait_remaining(7, now), Some(Duration::from_secs(5)));
+        assert_eq!(retries.wait_remaining(7, now + Duration::from_secs(5)), None);
+        // Epochs are tracked separately
+        assert_eq!(retries.wait_remaining(8, now), None);
+
+        assert_eq!(retries.record_not_ready(7, 3, now), ClaimRetry::RetryIn(Duration::from_secs(10)));
+        assert_eq!(retries.record_not_ready(7, 3, now), ClaimRetry::GiveUp { attempts: 3 });
+        // Forgotten once given up
+        assert_eq!(retries.wait_remaining(7, now), None);
+    }
+
+    #[test]
+    fn test_unlimited_attempts() {
+        let now = Instant::now();
+        let mut retries = ClaimRetries::default();
+        for _ in 0..100 {
+            assert!(matches!(retries.record_not_ready(7, 0, now), ClaimRetry::RetryIn(_)));
+        }
+        retries.clear(7);
+        assert_eq!(retries.wait_remaining(7, now), None);
+    }
+}
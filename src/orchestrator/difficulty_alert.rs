@@ -0,0 +1,123 @@
+/// Difficulty change alerts - the difficulty read when each epoch's mining starts is
+/// compared with the last one seen, and a change is logged and optionally POSTed to
+/// a webhook, so operators hear about profitability shifts without watching logs
+use anyhow::{Result, anyhow};
+use reqwest::{Client, ClientBuilder};
+use serde::Serialize;
+use std::time::Duration;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A difficulty change between two observations
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DifficultyChange {
+    /// Epoch the previous difficulty was seen in
+    pub previous_epoch: u64,
+    pub epoch: u64,
+    pub old_difficulty: u8,
+    pub new_difficulty: u8,
+}
+
+impl DifficultyChange {
+    /// Difficulty bits gained (negative when it dropped)
+    pub fn delta(&self) -> i16 {
+        self.new_difficulty as i16 - self.old_difficulty as i16
+    }
+}
+
+/// Remembers the last difficulty seen
+#[derive(Debug, Default)]
+pub struct DifficultyWatch {
+    last: Option<(u64, u8)>,
+}
+
+impl DifficultyWatch {
+    /// Record the difficulty for `epoch`; returns the change if it differs from the
+    /// last one seen. The first observation is never a change
+    pub fn observe(&mut self, epoch: u64, difficulty: u8) -> Option<DifficultyChange> {
+        let previous = self.last.replace((epoch, difficulty));
+        match previous {
+            Some((previous_epoch, old_difficulty)) if old_difficulty != difficulty => Some(DifficultyChange {
+                previous_epoch,
+                epoch,
+                old_difficulty,
+                new_difficulty: difficulty,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Body POSTed to the webhook
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    event: &'static str,
+    miner: &'a str,
+    #[serde(flatten)]
+    change: &'a DifficultyChange,
+}
+
+/// HTTP client for the difficulty webhook
+#[derive(Clone)]
+pub struct DifficultyWebhook {
+    client: Client,
+    url: String,
+}
+
+impl DifficultyWebhook {
+    pub fn new(url: &str) -> Result<Self> {
+        let client = ClientBuilder::new()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()?;
+        Ok(Self { client, url: url.to_string() })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// POST the change as JSON; fails unless the webhook answers with a 2xx status
+    pub async fn notify(&self, change: &DifficultyChange, miner: &str) -> Result<()> {
+        let payload = WebhookPayload { event: "difficulty_changed", miner, change };
+        let response = self.client.post(&self.url).json(&payload).send().await
+            .map_err(|e| anyhow!("Difficulty webhook {} unreachable: {}", self.url, e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("Difficulty webhook {} answered {}", self.url, status));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe_reports_changes_only() {
+        let mut watch = DifficultyWatch::default();
+        assert_eq!(watch.observe(10, 12), None);
+        assert_eq!(watch.observe(11, 12), None);
+
+        let change = watch.observe(12, 14).unwrap();
+        assert_eq!(change, DifficultyChange { previous_epoch: 11, epoch: 12, old_difficulty: 12, new_difficulty: 14 });
+        assert_eq!(change.delta(), 2);
+
+        // A mid-epoch retarget counts too
+        assert_eq!(watch.observe(12, 13).unwrap().delta(), -1);
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let change = DifficultyChange { previous_epoch: 4, epoch: 5, old_difficulty: 8, new_difficulty: 10 };
+        let body = serde_json::to_value(WebhookPayload { event: "difficulty_changed", miner: "rig-01", change: &change }).unwrap();
+        assert_eq!(body, serde_json::json!({
+            "event": "difficulty_changed",
+            "miner": "rig-01",
+            "previous_epoch": 4,
+            "epoch": 5,
+            "old_difficulty": 8,
+            "new_difficulty": 10,
+        }));
+    }
+}
@@ -0,0 +1,84 @@
+/// Short-lived cache for `query_epoch_info` - one orchestrator tick reads the epoch
+/// from several places (loop head, phase arms, retry helpers), and within a second
+/// they all get the same answer from the chain anyway
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::chain::EpochInfoResponse;
+
+/// How long a fetched epoch reading is reused
+pub const EPOCH_INFO_TTL: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub struct EpochInfoCache {
+    ttl: Duration,
+    /// Latest reading per contract address, with when it was fetched
+    entries: Mutex<HashMap<String, (Instant, EpochInfoResponse)>>,
+}
+
+impl EpochInfoCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cached reading for `contract` if it is younger than the TTL
+    pub fn get(&self, contract: &str) -> Option<EpochInfoResponse> {
+        let entries = self.entries.lock().ok()?;
+        entries.get(contract)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, info)| info.clone())
+    }
+
+    pub fn insert(&self, contract: &str, info: EpochInfoResponse) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(contract.to_string(), (Instant::now(), info));
+        }
+    }
+
+    /// Drop every reading, e.g. once the phase is known to have changed
+    pub fn invalidate(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn epoch_info(epoch_number: u64) -> EpochInfoResponse {
+        serde_json::from_value(serde_json::json!({
+            "epoch_number": epoch_number,
+            "start_block": 100,
+            "target_hash": [0u8; 32],
+            "difficulty": 8,
+            "reward_pool": "0",
+            "leading_miner": null,
+            "best_score": 0,
+            "phase": { "commit": { "ends_at": 130 } },
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_hits_until_ttl_expires() {
+        let cache = EpochInfoCache::new(Duration::from_millis(50));
+        assert!(cache.get("inj1mining").is_none());
+
+        cache.insert("inj1mining", epoch_info(7));
+        assert_eq!(cache.get("inj1mining").map(|info| info.epoch_number), Some(7));
+        // Keyed on the contract
+        assert!(cache.get("inj1other").is_none());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(cache.get("inj1mining").is_none());
+
+        cache.insert("inj1mining", epoch_info(8));
+        cache.invalidate();
+        assert!(cache.get("inj1mining").is_none());
+    }
+}
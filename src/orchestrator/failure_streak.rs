@@ -0,0 +1,116 @@
+/// Circuit breaker for epochs whose commit/reveal pipeline fails
+/// One failed epoch is usually the network; the same failure every epoch (e.g. a
+/// signing bug) just burns gas, so after enough in a row the miner pauses or exits
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// What to do once `max_failed_epochs` epochs in a row have failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailureAction {
+    /// Stop mining for a while, then try again
+    #[default]
+    Pause,
+    /// Exit with a nonzero code so a supervisor (or a human) steps in
+    Exit,
+}
+
+impl FromStr for FailureAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "pause" => Ok(FailureAction::Pause),
+            "exit" => Ok(FailureAction::Exit),
+            other => Err(anyhow!("Unknown failure action '{}' (expected 'pause' or 'exit')", other)),
+        }
+    }
+}
+
+impl fmt::Display for FailureAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureAction::Pause => write!(f, "pause"),
+            FailureAction::Exit => write!(f, "exit"),
+        }
+    }
+}
+
+/// Returned from the run loop when the breaker trips with `FailureAction::Exit`
+#[derive(Debug, thiserror::Error)]
+#[error("{epochs} consecutive epochs failed to commit or reveal - stopping so the problem can be investigated")]
+pub struct RepeatedFailureError {
+    pub epochs: u32,
+}
+
+/// Consecutive epochs whose commit or reveal failed; persisted with the mining state
+/// so a restart doesn't reset it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FailureStreak {
+    /// Fully failed epochs in a row
+    pub count: u32,
+    /// Epoch with a failed commit/reveal that may still succeed on retry
+    pub pending: Option<u64>,
+}
+
+impl FailureStreak {
+    /// A commit or reveal transaction for `epoch` failed
+    pub fn record_failure(&mut self, epoch: u64) {
+        self.pending = Some(epoch);
+    }
+
+    /// A reveal landed - the pipeline works
+    pub fn record_success(&mut self) {
+        self.count = 0;
+        self.pending = None;
+    }
+
+    /// Count the pending epoch as failed once the chain has moved past it without
+    /// a successful reveal. Returns the new streak length when it grew
+    pub fn settle(&mut self, chain_epoch: u64) -> Option<u32> {
+        match self.pending {
+            Some(epoch) if chain_epoch > epoch => {
+                self.pending = None;
+                self.count += 1;
+                Some(self.count)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_counts_only_epochs_that_never_recovered() {
+        let mut streak = FailureStreak::default();
+        assert_eq!(streak.settle(5), None);
+
+        // Commit failed, retry in the same epoch revealed fine
+        streak.record_failure(5);
+        assert_eq!(streak.settle(5), None);
+        streak.record_success();
+        assert_eq!(streak.settle(6), None);
+
+        // Two epochs fail outright
+        streak.record_failure(6);
+        streak.record_failure(6);
+        assert_eq!(streak.settle(7), Some(1));
+        assert_eq!(streak.settle(8), None);
+        streak.record_failure(8);
+        assert_eq!(streak.settle(9), Some(2));
+
+        streak.record_success();
+        assert_eq!(streak.count, 0);
+
+        assert_eq!("EXIT".parse::<FailureAction>().unwrap(), FailureAction::Exit);
+        assert!("restart".parse::<FailureAction>().is_err());
+    }
+}
@@ -0,0 +1,249 @@
+/// Liveness/readiness probes for container deployments
+/// `/healthz` answers as long as the process is running, `/readyz` only when the
+/// miner is connected, has queried the chain recently and has gas to spend.
+/// `/status` adds network-level metrics from the chain client.
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::chain::client_real::ClientMetrics;
+
+/// Shared health state updated by the orchestrator and read by the probe server
+pub struct HealthState {
+    connected: AtomicBool,
+    /// Unix timestamp of the last successful epoch query (0 = never)
+    last_chain_query: AtomicU64,
+    /// Last observed gas balance and the minimum for its denom, in base units
+    /// (None until first checked)
+    gas_balance: RwLock<Option<(u128, u128)>>,
+    /// Latest chain client metrics (None until first refreshed)
+    network: RwLock<Option<ClientMetrics>>,
+    max_query_age_secs: u64,
+}
+
+/// Readiness report returned by `/readyz`
+#[derive(Debug, Clone, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub connected: bool,
+    /// Seconds since the last successful chain query (None if never)
+    pub last_query_age_secs: Option<u64>,
+    pub gas_balance: Option<String>,
+    /// Reasons the miner is not ready (empty when ready)
+    pub reasons: Vec<String>,
+}
+
+/// Report returned by `/status`
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusReport {
+    #[serde(flatten)]
+    pub readiness: Readiness,
+    pub network: Option<ClientMetrics>,
+}
+
+impl HealthState {
+    pub fn new(max_query_age_secs: u64) -> Self {
+        Self {
+            connected: AtomicBool::new(false),
+            last_chain_query: AtomicU64::new(0),
+            gas_balance: RwLock::new(None),
+            network: RwLock::new(None),
+            max_query_age_secs,
+        }
+    }
+
+    pub fn set_connected(&self, connected: bool) {
+        self.connected.store(connected, Ordering::Relaxed);
+    }
+
+    /// Record a successful chain query
+    pub fn record_chain_query(&self) {
+        self.last_chain_query.store(now_secs(), Ordering::Relaxed);
+    }
+
+    /// Record the gas balance and the minimum readiness needs, both in the fee denom
+    /// they were read in
+    pub fn set_gas_balance(&self, balance: u128, min_balance: u128) {
+        if let Ok(mut guard) = self.gas_balance.write() {
+            *guard = Some((balance, min_balance));
+        }
+    }
+
+    /// Last observed gas balance in base units
+    pub fn gas_balance(&self) -> Option<u128> {
+        self.gas_balance.read().ok().and_then(|g| g.map(|(balance, _)| balance))
+    }
+
+    pub fn set_network_metrics(&self, metrics: ClientMetrics) {
+        if let Ok(mut guard) = self.network.write() {
+            *guard = Some(metrics);
+        }
+    }
+
+    /// Readiness plus the latest network metrics
+    pub fn status(&self) -> StatusReport {
+        StatusReport {
+            readiness: self.readiness(),
+            network: self.network.read().ok().and_then(|n| n.clone()),
+        }
+    }
+
+    /// Evaluate readiness against the configured thresholds
+    pub fn readiness(&self) -> Readiness {
+        self.readiness_at(now_secs())
+    }
+
+    fn readiness_at(&self, now: u64) -> Readiness {
+        let connected = self.connected.load(Ordering::Relaxed);
+        let last_query = self.last_chain_query.load(Ordering::Relaxed);
+        let last_query_age_secs = (last_query > 0).then(|| now.saturating_sub(last_query));
+        let gas_balance = self.gas_balance.read().ok().and_then(|g| *g);
+        let (gas_balance, min_gas_balance) = match gas_balance {
+            Some((balance, min_balance)) => (Some(balance), min_balance),
+            None => (None, 0),
+        };
+
+        let mut reasons = Vec::new();
+        if !connected {
+            reasons.push("not connected to chain".to_string());
+        }
+        match last_query_age_secs {
+            None => reasons.push("no successful chain query yet".to_string()),
+            Some(age) if age > self.max_query_age_secs => {
+                reasons.push(format!("last chain query {}s ago (max {}s)", age, self.max_query_age_secs));
+            }
+            _ => {}
+        }
+        match gas_balance {
+            None => reasons.push("gas balance not checked yet".to_string()),
+            Some(balance) if balance < min_gas_balance => {
+                reasons.push(format!("gas balance {} below minimum {}", balance, min_gas_balance));
+            }
+            _ => {}
+        }
+
+        Readiness {
+            ready: reasons.is_empty(),
+            connected,
+            last_query_age_secs,
+            gas_balance: gas_balance.map(|b| b.to_string()),
+            reasons,
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Serve `/healthz`, `/readyz` and `/status` on the given address until the task is dropped
+pub async fn serve_health(addr: &str, state: Arc<HealthState>) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("Health probes listening on http://{} (/healthz, /readyz, /status)", addr);
+
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                log::warn!("Health probe accept failed: {}", e);
+                continue;
+            }
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_probe(stream, &state).await {
+                log::debug!("Health probe request failed: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_probe(mut stream: TcpStream, state: &HealthState) -> anyhow::Result<()> {
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let path = request
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("/");
+
+    let (status, body) = match path {
+        "/healthz" => ("200 OK", "ok".to_string()),
+        "/readyz" => {
+            let readiness = state.readiness();
+            let status = if readiness.ready { "200 OK" } else { "503 Service Unavailable" };
+            (status, serde_json::to_string(&readiness)?)
+        }
+        "/status" => ("200 OK", serde_json::to_string(&state.status())?),
+        _ => ("404 Not Found", "not found".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readiness() {
+        let state = HealthState::new(60);
+        let report = state.readiness();
+        assert!(!report.ready);
+        assert_eq!(report.reasons.len(), 3);
+
+        state.set_connected(true);
+        state.record_chain_query();
+        state.set_gas_balance(5000, 1000);
+        assert!(state.readiness().ready);
+
+        // Stale chain query
+        let later = now_secs() + 120;
+        let report = state.readiness_at(later);
+        assert!(!report.ready);
+        assert!(report.reasons[0].contains("last chain query"));
+
+        // Underfunded wallet
+        state.set_gas_balance(10, 1000);
+        assert!(!state.readiness().ready);
+
+        // The same balance is enough against another denom's minimum
+        state.set_gas_balance(10, 5);
+        assert!(state.readiness().ready);
+    }
+
+    #[test]
+    fn test_status_includes_network_metrics() {
+        let state = HealthState::new(60);
+        let status = serde_json::to_value(state.status()).unwrap();
+        assert_eq!(status["ready"], false);
+        assert!(status["network"].is_null());
+
+        state.set_network_metrics(ClientMetrics {
+            endpoint: "https://grpc.example:443".to_string(),
+            grpc_errors: 3,
+            reconnects: 1,
+            sequence: Some(17),
+            ..ClientMetrics::default()
+        });
+        let status = serde_json::to_value(state.status()).unwrap();
+        assert_eq!(status["network"]["grpc_errors"], 3);
+        assert_eq!(status["network"]["sequence"], 17);
+        assert_eq!(status["network"]["endpoint"], "https://grpc.example:443");
+    }
+}
@@ -0,0 +1,184 @@
+/// Liveness heartbeat for external watchdogs - a small file rewritten from the main
+/// loop (a cron healthcheck alerts when it goes stale) and, under systemd, `sd_notify`
+/// watchdog pings so `WatchdogSec=` restarts a hung miner. No port needs opening
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The heartbeat file is rewritten at most this often
+pub const HEARTBEAT_FILE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub struct Heartbeat {
+    path: Option<PathBuf>,
+    last_write: Option<Instant>,
+    notify: Option<SdNotify>,
+}
+
+impl Heartbeat {
+    /// Heartbeat to `path` (None = no file); systemd notification is picked up from the environment
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let notify = SdNotify::from_env();
+        if let Some(ref notify) = notify {
+            match notify.watchdog_interval {
+                Some(interval) => log::info!("systemd watchdog enabled, pinging every {:?}", interval / 2),
+                None => log::info!("Running under systemd, reporting readiness"),
+            }
+        }
+        Self { path, last_write: None, notify }
+    }
+
+    /// Startup is done - tells systemd a `Type=notify` service is up
+    pub fn ready(&mut self) {
+        if let Some(ref mut notify) = self.notify {
+            notify.send("READY=1");
+        }
+    }
+
+    /// Record that the main loop is alive in `phase` of `epoch`
+    pub fn beat(&mut self, epoch: u64, phase: &str) {
+        if let Some(ref mut notify) = self.notify {
+            notify.watchdog();
+        }
+
+        let Some(ref path) = self.path else {
+            return;
+        };
+        if self.last_write.map_or(false, |t| t.elapsed() < HEARTBEAT_FILE_INTERVAL) {
+            return;
+        }
+        self.last_write = Some(Instant::now());
+        if let Err(e) = write_atomic(path, &render(unix_secs(), epoch, phase)) {
+            log::warn!("Failed to write heartbeat file {:?}: {}", path, e);
+        }
+    }
+}
+
+/// One JSON line with `timestamp` (unix seconds), `phase` and `epoch`
+fn render(timestamp: u64, epoch: u64, phase: &str) -> String {
+    let mut line = serde_json::json!({ "timestamp": timestamp, "phase": phase, "epoch": epoch }).to_string();
+    line.push('\n');
+    line
+}
+
+/// Replace `path` atomically so a watchdog never reads a partial file
+fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let temp_path = format!("{}.tmp", path.display());
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+fn unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// systemd's notification socket (`NOTIFY_SOCKET`) and watchdog timeout (`WATCHDOG_USEC`)
+#[derive(Debug)]
+struct SdNotify {
+    socket: String,
+    /// Set when systemd expects watchdog pings from this process
+    watchdog_interval: Option<Duration>,
+    last_watchdog: Option<Instant>,
+}
+
+impl SdNotify {
+    fn from_env() -> Option<Self> {
+        let socket = std::env::var("NOTIFY_SOCKET").ok().filter(|s| !s.is_empty())?;
+        let watchdog_interval = watchdog_interval(
+            std::env::var("WATCHDOG_USEC").ok().as_deref(),
+            std::env::var("WATCHDOG_PID").ok().as_deref(),
+            std::process::id(),
+        );
+        Some(Self { socket, watchdog_interval, last_watchdog: None })
+    }
+
+    /// Ping the watchdog at half its timeout, as systemd recommends
+    fn watchdog(&mut self) {
+        let Some(interval) = self.watchdog_interval else {
+            return;
+        };
+        if self.last_watchdog.map_or(false, |t| t.elapsed() < interval / 2) {
+            return;
+        }
+        self.last_watchdog = Some(Instant::now());
+        self.send("WATCHDOG=1");
+    }
+
+    fn send(&self, message: &str) {
+        if let Err(e) = send_notification(&self.socket, message) {
+            log::warn!("Failed to notify systemd ({}): {}", message, e);
+        }
+    }
+}
+
+/// Watchdog timeout from `WATCHDOG_USEC`, unless `WATCHDOG_PID` names another process
+fn watchdog_interval(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if let Some(pid) = pid {
+        if pid.parse::<u32>().ok() != Some(own_pid) {
+            return None;
+        }
+    }
+    usec?.parse::<u64>().ok().filter(|&usec| usec > 0).map(Duration::from_micros)
+}
+
+/// A socket starting with `@` is in the Linux abstract namespace
+#[cfg(unix)]
+fn send_notification(socket: &str, message: &str) -> std::io::Result<()> {
+    use std::os::unix::net::UnixDatagram;
+
+    let datagram = UnixDatagram::unbound()?;
+    #[cfg(target_os = "linux")]
+    if let Some(name) = socket.strip_prefix('@') {
+        use std::os::linux::net::SocketAddrExt;
+        let addr = std::os::unix::net::SocketAddr::from_abstract_name(name.as_bytes())?;
+        datagram.send_to_addr(message.as_bytes(), &addr)?;
+        return Ok(());
+    }
+    datagram.send_to(message.as_bytes(), socket)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_notification(_socket: &str, _message: &str) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let line = render(1_700_000_000, 42, "finding_solution");
+        assert!(line.ends_with('\n') && line.lines().count() == 1);
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed, serde_json::json!({ "timestamp": 1_700_000_000u64, "phase": "finding_solution", "epoch": 42 }));
+    }
+
+    #[test]
+    fn test_watchdog_interval() {
+        assert_eq!(watchdog_interval(Some("30000000"), None, 7), Some(Duration::from_secs(30)));
+        assert_eq!(watchdog_interval(Some("30000000"), Some("7"), 7), Some(Duration::from_secs(30)));
+        // Meant for another process (e.g. a wrapper script's child)
+        assert_eq!(watchdog_interval(Some("30000000"), Some("8"), 7), None);
+        assert_eq!(watchdog_interval(None, None, 7), None);
+        assert_eq!(watchdog_interval(Some("0"), None, 7), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sends_notification() {
+        let path = std::env::temp_dir().join(format!("gmine_notify_{}.sock", uuid::Uuid::new_v4().simple()));
+        let listener = std::os::unix::net::UnixDatagram::bind(&path).unwrap();
+
+        send_notification(path.to_str().unwrap(), "WATCHDOG=1").unwrap();
+        let mut buf = [0u8; 64];
+        let len = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"WATCHDOG=1");
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,187 @@
+/// Per-wallet instance lock - two miners on one mnemonic race on account sequence
+/// numbers and both fail constantly, so the second one refuses to start instead.
+/// The holder refreshes a heartbeat, so a lock left behind by a crash goes stale
+/// and can be reclaimed
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How often the holder refreshes its heartbeat
+pub const INSTANCE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A lock whose heartbeat is older than this belongs to a dead instance
+pub const INSTANCE_LOCK_STALE_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockInfo {
+    pid: u32,
+    /// Tells apart instances with the same pid (e.g. on different hosts sharing the state dir)
+    instance: Uuid,
+    /// Unix seconds
+    heartbeat: u64,
+}
+
+impl LockInfo {
+    fn age_secs(&self) -> u64 {
+        unix_secs().saturating_sub(self.heartbeat)
+    }
+
+    fn is_stale(&self) -> bool {
+        self.age_secs() >= INSTANCE_LOCK_STALE_AFTER.as_secs() || pid_running(self.pid) == Some(false)
+    }
+}
+
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+    info: LockInfo,
+}
+
+impl InstanceLock {
+    /// Lock file for `address` in `dir` (the state file's directory)
+    /// A warm standby holds its own lock, so it runs alongside the primary but not another standby
+    pub fn path_for(dir: &Path, address: &str, standby: bool) -> PathBuf {
+        let role = if standby { ".standby" } else { "" };
+        dir.join(format!("gmine_{}{}.lock", address, role))
+    }
+
+    /// Take the lock for `address`, reclaiming a stale one
+    /// Fails if another live instance holds it
+    pub fn acquire(dir: &Path, address: &str, standby: bool) -> Result<Self> {
+        let path = Self::path_for(dir, address, standby);
+        let role = if standby { "standby miner" } else { "miner" };
+        let info = LockInfo { pid: std::process::id(), instance: Uuid::new_v4(), heartbeat: unix_secs() };
+
+        // Second attempt is after removing a stale lock
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(serde_json::to_string(&info)?.as_bytes())?;
+                    return Ok(Self { path, info });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(anyhow!("Failed to create instance lock {:?}: {}", path, e)),
+            }
+
+            match read(&path) {
+                Some(holder) if !holder.is_stale() => {
+                    return Err(anyhow!(
+                        "Another {} (pid {}) is already running with wallet {} (lock {:?}, last heartbeat {}s ago). \
+                         Two miners on one wallet race on sequence numbers and both keep failing - stop the other \
+                         one or use a different --account-index",
+                        role, holder.pid, address, path, holder.age_secs()
+                    ));
+                }
+                Some(holder) => log::warn!(
+                    "Reclaiming stale instance lock {:?} (pid {}, last heartbeat {}s ago)",
+                    path, holder.pid, holder.age_secs()
+                ),
+                // Unreadable - a crash between create and write
+                None => log::warn!("Reclaiming unreadable instance lock {:?}", path),
+            }
+            if let Err(e) = fs::remove_file(&path) {
+                if e.kind() != ErrorKind::NotFound {
+                    return Err(anyhow!("Failed to remove stale instance lock {:?}: {}", path, e));
+                }
+            }
+        }
+        Err(anyhow!("Another miner took the instance lock {:?} while it was being reclaimed", path))
+    }
+
+    /// Refresh the heartbeat; errors if another instance has taken the lock over
+    /// (only possible if this process stalled for longer than the stale timeout)
+    pub fn heartbeat(&mut self) -> Result<()> {
+        match read(&self.path) {
+            Some(holder) if holder.instance == self.info.instance => {}
+            Some(holder) => {
+                return Err(anyhow!("Instance lock {:?} was taken over by pid {}", self.path, holder.pid));
+            }
+            None => log::warn!("Instance lock {:?} disappeared, recreating it", self.path),
+        }
+        self.info.heartbeat = unix_secs();
+        let temp_path = format!("{}.tmp", self.path.display());
+        fs::write(&temp_path, serde_json::to_string(&self.info)?)?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Keep the heartbeat fresh in the background; the lock is released when the
+    /// task is dropped (aborted or the runtime shuts down)
+    pub fn spawn_heartbeat(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(INSTANCE_HEARTBEAT_INTERVAL).await;
+                if let Err(e) = self.heartbeat() {
+                    log::error!("{}", e);
+                }
+            }
+        })
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // Never delete a lock another instance has taken over
+        if read(&self.path).map_or(false, |holder| holder.instance == self.info.instance) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+fn read(path: &Path) -> Option<LockInfo> {
+    fs::read_to_string(path).ok().and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+fn unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Whether `pid` is alive, where that can be checked cheaply (None = unknown)
+/// A pid from another host sharing the state dir may be misjudged, so this only
+/// ever shortens the wait for a lock whose holder is clearly gone
+#[cfg(target_os = "linux")]
+fn pid_running(pid: u32) -> Option<bool> {
+    Some(Path::new("/proc").join(pid.to_string()).exists())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pid_running(_pid: u32) -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refuses_live_lock_and_reclaims_stale_one() {
+        let dir = std::env::temp_dir();
+        let address = format!("inj1test{}", Uuid::new_v4().simple());
+        let path = InstanceLock::path_for(&dir, &address, false);
+
+        let mut first = InstanceLock::acquire(&dir, &address, false).unwrap();
+        let err = InstanceLock::acquire(&dir, &address, false).unwrap_err();
+        assert!(err.to_string().contains("already running"));
+        // A standby runs alongside the primary
+        drop(InstanceLock::acquire(&dir, &address, true).unwrap());
+
+        // Simulate a crashed holder: heartbeat long past the stale timeout
+        first.info.heartbeat = 0;
+        fs::write(&path, serde_json::to_string(&first.info).unwrap()).unwrap();
+        let second = InstanceLock::acquire(&dir, &address, false).unwrap();
+        assert!(first.heartbeat().is_err());
+
+        // The old holder must not delete the new holder's lock
+        drop(first);
+        assert!(path.exists());
+        drop(second);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,129 @@
+/// Miner metrics in OpenMetrics text format, written to a file for the node_exporter
+/// textfile collector (or anything else that scrapes files) - no port needs opening
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// How often the metrics file is rewritten
+pub const METRICS_FILE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Point-in-time values for every exported metric
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    pub epoch: u64,
+    pub phase: &'static str,
+    pub difficulty: u8,
+    /// H/s
+    pub hashrate: f64,
+    /// Hashes computed since start
+    pub total_hashes: u64,
+    pub solutions_found: u64,
+    pub solutions_submitted: u64,
+    pub commit_latency_p50_ms: Option<u64>,
+    pub commit_latency_p95_ms: Option<u64>,
+    pub connected: bool,
+    pub last_chain_query_age_secs: Option<u64>,
+    /// Base units (1e-18 INJ)
+    pub gas_balance: Option<u128>,
+    pub grpc_errors: u64,
+    pub reconnects: u64,
+}
+
+enum Kind {
+    Gauge,
+    Counter,
+}
+
+struct Writer {
+    out: String,
+    labels: String,
+}
+
+impl Writer {
+    fn metric(&mut self, name: &str, kind: Kind, help: &str, value: impl std::fmt::Display) {
+        let kind = match kind {
+            Kind::Gauge => "gauge",
+            Kind::Counter => "counter",
+        };
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(self.out, "{}{{{}}} {}", name, self.labels, value);
+    }
+}
+
+/// Escape a label value (backslash, double quote and newline)
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Render the snapshot; every sample carries a `miner` label
+/// Counters keep the `_total` suffix in the family name so the Prometheus text
+/// parser used by node_exporter reads them as counters too
+pub fn render(snapshot: &MetricsSnapshot, miner: &str) -> String {
+    let mut w = Writer { out: String::new(), labels: format!("miner=\"{}\"", escape_label(miner)) };
+
+    w.metric("gmine_epoch", Kind::Gauge, "Epoch being mined", snapshot.epoch);
+    let _ = writeln!(w.out, "# HELP gmine_phase Current orchestrator phase (1 for the active one)");
+    let _ = writeln!(w.out, "# TYPE gmine_phase gauge");
+    let _ = writeln!(w.out, "gmine_phase{{{},phase=\"{}\"}} 1", w.labels, snapshot.phase);
+    w.metric("gmine_difficulty", Kind::Gauge, "Difficulty of the current epoch", snapshot.difficulty);
+    w.metric("gmine_hashrate_hashes_per_second", Kind::Gauge, "Current hashrate", snapshot.hashrate);
+    w.metric("gmine_hashes_total", Kind::Counter, "Hashes computed since start", snapshot.total_hashes);
+    w.metric("gmine_solutions_found_total", Kind::Counter, "Solutions found since start", snapshot.solutions_found);
+    w.metric("gmine_solutions_submitted_total", Kind::Counter, "Solutions revealed since start", snapshot.solutions_submitted);
+    if let Some(p50) = snapshot.commit_latency_p50_ms {
+        w.metric("gmine_commit_latency_p50_seconds", Kind::Gauge, "Median time from finding a solution to its commit landing", p50 as f64 / 1000.0);
+    }
+    if let Some(p95) = snapshot.commit_latency_p95_ms {
+        w.metric("gmine_commit_latency_p95_seconds", Kind::Gauge, "95th percentile time from finding a solution to its commit landing", p95 as f64 / 1000.0);
+    }
+    w.metric("gmine_chain_connected", Kind::Gauge, "Whether the chain client is connected", snapshot.connected as u8);
+    if let Some(age) = snapshot.last_chain_query_age_secs {
+        w.metric("gmine_last_chain_query_age_seconds", Kind::Gauge, "Seconds since the last successful chain query", age);
+    }
+    if let Some(balance) = snapshot.gas_balance {
+        w.metric("gmine_gas_balance_inj", Kind::Gauge, "Wallet INJ balance available for gas", balance as f64 / 1e18);
+    }
+    w.metric("gmine_grpc_errors_total", Kind::Counter, "Failed gRPC calls since start", snapshot.grpc_errors);
+    w.metric("gmine_reconnects_total", Kind::Counter, "Chain reconnects since start", snapshot.reconnects);
+
+    w.out.push_str("# EOF\n");
+    w.out
+}
+
+/// Replace `path` atomically so a scraper never reads a partial file
+/// The temp name doesn't end in `.prom`, so the textfile collector ignores it
+pub fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    let temp_path = format!("{}.tmp", path.display());
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let snapshot = MetricsSnapshot {
+            epoch: 42,
+            phase: "finding_solution",
+            hashrate: 1250.5,
+            total_hashes: 9_000_000,
+            solutions_found: 3,
+            gas_balance: Some(1_500_000_000_000_000_000),
+            ..MetricsSnapshot::default()
+        };
+        let text = render(&snapshot, "rig \"7\"");
+
+        assert!(text.contains("gmine_epoch{miner=\"rig \\\"7\\\"\"} 42\n"));
+        assert!(text.contains("gmine_phase{miner=\"rig \\\"7\\\"\",phase=\"finding_solution\"} 1\n"));
+        assert!(text.contains("# TYPE gmine_solutions_found_total counter\n"));
+        assert!(text.contains("gmine_hashes_total{miner=\"rig \\\"7\\\"\"} 9000000\n"));
+        assert!(text.contains("gmine_gas_balance_inj{miner=\"rig \\\"7\\\"\"} 1.5\n"));
+        // No latency samples until a commit has landed
+        assert!(!text.contains("gmine_commit_latency"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
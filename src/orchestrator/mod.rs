@@ -15,21 +15,66 @@ use std::time::Duration;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use tokio::time::sleep;
+use tracing::Instrument;
 use uuid::Uuid;
+use rand::Rng;
 
-use crate::chain::{InjectiveClient, query_epoch_info};
-use crate::chain::queries::{PhaseInfo, query_stake_info, query_emission_metrics};
+use crate::chain::{InjectiveClient, ChainError, ContractEvent, EpochInfoResponse, PreparedTx, query_epoch_info, DEFAULT_FEE_DENOM};
+use crate::chain::queries::{PhaseInfo, calculate_nonce_range, claimed_reward_from_events, query_commitment, query_power_balance, query_stake_info, query_emission_metrics};
+use crate::chain::messages::{CommitmentVersion, RevealSolutionMsg, SaltSource, MIN_STAKE_AMOUNT};
+use crate::chain::client_real::fee_for_gas_at;
 use crate::chain::wallet::InjectiveWallet;
 // Messages are now handled by transaction_manager
-use crate::miner::MiningEngine;
+use crate::miner::{MiningEngine, HostConditions, ThreadScaler};
 // Import EnhancedTelemetryReporter for comprehensive metrics
-use crate::telemetry::EnhancedTelemetryReporter;
+use crate::telemetry::{EnhancedTelemetryReporter, TelemetrySender, TelemetryUpdate, TELEMETRY_QUEUE_CAPACITY, load_or_create_miner_id};
+use gmine_mobile::byte_order::{nonce_bytes, nonce_from_bytes};
+use gmine_mobile::proof::SolutionProof;
 
 // Transaction manager is in the same orchestrator module
 mod transaction_manager;
 mod epoch_monitor;
 mod stats;
-pub use self::stats::{MiningStatistics, StatsCollector};
+mod profitability;
+mod health;
+mod schedule;
+mod phase_debounce;
+mod submission;
+mod failure_streak;
+mod epoch_cache;
+mod state_file;
+mod metrics;
+mod instance_lock;
+mod heartbeat;
+mod difficulty_alert;
+mod multi_contract;
+mod chain_halt;
+mod notify_hook;
+mod claim_retry;
+pub use self::stats::{AdvanceOutcome, LatencySummary, MiningStatistics, StatsCollector};
+pub use self::profitability::{AttemptGas, ProfitabilityEstimate, ProfitabilityTracker};
+pub use self::health::{HealthState, Readiness, serve_health};
+pub use self::schedule::{MiningSchedule, ScheduleWindow};
+pub use self::submission::{PoolClient, SubmissionTarget};
+pub use self::failure_streak::{FailureAction, FailureStreak, RepeatedFailureError};
+pub use self::instance_lock::InstanceLock;
+pub use self::multi_contract::{AggregateStats, AGGREGATE_STATS_INTERVAL, contract_scoped_path};
+pub use self::transaction_manager::BroadcastLimiter;
+use self::phase_debounce::PhaseDebouncer;
+use self::epoch_cache::{EpochInfoCache, EPOCH_INFO_TTL};
+use self::state_file::StateFile;
+use self::metrics::{MetricsSnapshot, METRICS_FILE_INTERVAL};
+use self::heartbeat::Heartbeat;
+use self::difficulty_alert::{DifficultyWatch, DifficultyWebhook};
+use self::notify_hook::{NotifyEvent, NotifyHook};
+use self::claim_retry::{ClaimRetries, ClaimRetry};
+use self::chain_halt::{ChainHaltDetector, HaltChange, CHAIN_HALT_CHECK_INTERVAL, CHAIN_HALT_POLL_INTERVAL};
+
+/// How often the gas balance is re-checked for readiness reporting
+const GAS_BALANCE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often a live gas price (node or oracle source) is re-fetched
+const GAS_PRICE_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
 
 /// Mining phase within an epoch lifecycle
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -48,6 +93,30 @@ pub enum MiningPhase {
     Claiming(u64),
 }
 
+impl MiningPhase {
+    /// Short phase name for logs and trace spans
+    pub fn name(&self) -> &'static str {
+        match self {
+            MiningPhase::Idle => "idle",
+            MiningPhase::FindingSolution => "finding_solution",
+            MiningPhase::Committing(_) => "committing",
+            MiningPhase::WaitingForRevealWindow(_) => "waiting_for_reveal",
+            MiningPhase::Revealing(_) => "revealing",
+            MiningPhase::Claiming(_) => "claiming",
+        }
+    }
+    
+    /// The solution being committed or revealed, if any
+    pub fn commitment_data(&self) -> Option<&CommitmentData> {
+        match self {
+            MiningPhase::Committing(data)
+            | MiningPhase::WaitingForRevealWindow(data)
+            | MiningPhase::Revealing(data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
 /// Data needed for reveal phase
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CommitmentData {
@@ -56,6 +125,45 @@ pub struct CommitmentData {
     pub digest: [u8; 16],
     pub salt: [u8; 32],
     pub commitment: [u8; 32],
+    /// Reveal signed ahead of time with the predicted sequence (`--prepare-reveal`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prepared_reveal: Option<PreparedTx>,
+    /// When the solution was found (unix ms), for find-to-commit latency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub found_at_ms: Option<u64>,
+}
+
+impl CommitmentData {
+    /// Whether the saved nonce/digest/salt still hash to the saved commitment
+    pub fn is_consistent(&self, version: CommitmentVersion, miner: &str) -> bool {
+        version.compute(miner, self.epoch, self.nonce, self.digest, self.salt) == self.commitment
+    }
+    
+    /// Commitment for a solution using the wallet-derived salt
+    /// Reproducible from the epoch, nonce and digest alone
+    pub fn derived(wallet: &InjectiveWallet, version: CommitmentVersion, epoch: u64, nonce: [u8; 8], digest: [u8; 16]) -> Self {
+        let salt = wallet.derive_salt(epoch, nonce);
+        let commitment = version.compute(&wallet.address, epoch, nonce, digest, salt);
+        Self { epoch, nonce, digest, salt, commitment, prepared_reveal: None, found_at_ms: None }
+    }
+}
+
+/// Nonce search progress saved while finding a solution, so a restart within the
+/// same epoch continues where the workers left off
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SearchCheckpoint {
+    pub epoch: u64,
+    /// Unsearched remainder (start, end) of each worker's slice
+    pub ranges: Vec<(u64, u64)>,
+}
+
+impl SearchCheckpoint {
+    /// Whether this checkpoint can resume a search of `nonce_range` in `epoch`
+    pub fn applies_to(&self, epoch: u64, nonce_range: (u64, u64)) -> bool {
+        self.epoch == epoch
+            && !self.ranges.is_empty()
+            && self.ranges.iter().all(|&(start, end)| start < end && start >= nonce_range.0 && end <= nonce_range.1)
+    }
 }
 
 /// Complete mining state including epoch and phase
@@ -66,6 +174,131 @@ pub struct MiningState {
     pub last_saved: u64, // Timestamp for state saves
     #[serde(default)]
     pub committed_epochs: Vec<u64>, // Track epochs we've already committed to
+    #[serde(default)]
+    pub search_checkpoint: Option<SearchCheckpoint>,
+    #[serde(default)]
+    pub failure_streak: FailureStreak,
+    /// Revealed epochs whose claim was deferred so mining could move on (oldest first)
+    #[serde(default)]
+    pub pending_claims: Vec<u64>,
+    /// Recent epochs whose claim was given up after `max_claim_attempts`
+    #[serde(default)]
+    pub abandoned_claims: Vec<u64>,
+}
+
+/// Outcome of one attempt to finalize and claim an epoch
+#[derive(Debug, PartialEq)]
+enum ClaimProgress {
+    /// Claimed, failed or no longer claimable - stop tracking the epoch
+    Done,
+    /// Settlement (or the epoch advance) isn't finished yet - try again later
+    NotReady,
+}
+
+/// How far the deferred claim being worked on has got. Each loop pass only checks on
+/// it, so the state machine never waits for its transactions
+#[derive(Debug, Clone, PartialEq)]
+enum DeferredClaim {
+    /// Finalization was queued; the claim goes out once it has had time to land
+    Finalizing { claim_at: std::time::Instant },
+    /// Claim transaction `tx_id` was queued at `queued_at`
+    Claiming { tx_id: u64, queued_at: std::time::Instant },
+}
+
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// How often to re-check the schedule (and keep the chain connection warm) while paused
+const SCHEDULE_IDLE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often the main loop polls while the failure breaker has mining paused
+const FAILURE_PAUSE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Whether `attempts` rejected reveals use up a `max_attempts` budget (0 = unlimited)
+fn reveal_attempts_exhausted(attempts: u32, max_attempts: u32) -> bool {
+    max_attempts > 0 && attempts >= max_attempts
+}
+
+/// Whether a reveal resumed from state for `commit_epoch` can go out right now: the
+/// chain is still in that epoch and its reveal phase is open
+fn reveal_due_on_resume(commit_epoch: u64, info: &EpochInfoResponse) -> bool {
+    info.epoch_number == commit_epoch && matches!(info.phase, PhaseInfo::Reveal { .. })
+}
+
+/// The contract's target_hash as the 32-byte challenge; any other length is a
+/// malformed epoch response and is rejected rather than padded or truncated
+fn target_hash_array(epoch: u64, target_hash: &[u8]) -> Result<[u8; 32]> {
+    <[u8; 32]>::try_from(target_hash).map_err(|_| anyhow!(
+        "Malformed target_hash for epoch {}: expected 32 bytes, got {}",
+        epoch,
+        target_hash.len()
+    ))
+}
+
+/// How often the nonce search progress is checkpointed to the state file
+const SEARCH_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Attempts (2s apart) to fetch a claim tx's events after it succeeds
+const CLAIM_TX_LOOKUP_ATTEMPTS: u32 = 5;
+
+/// Time given to a finalize_epoch transaction to land before claiming
+const FINALIZE_SETTLE_TIME: Duration = Duration::from_secs(10);
+
+/// Upper bound for the backoff while the contract reports it isn't ready
+const CONTRACT_NOT_READY_MAX_BACKOFF_MS: u64 = 60_000;
+
+/// Number of recent committed epochs remembered in state
+const COMMITTED_EPOCHS_HISTORY: usize = 20;
+
+impl MiningState {
+    /// Whether a commitment for this epoch was already accepted
+    /// The contract allows one commitment per miner per epoch
+    pub fn has_committed(&self, epoch: u64) -> bool {
+        self.committed_epochs.contains(&epoch)
+    }
+    
+    /// Record an accepted commitment; returns false if the epoch was already recorded
+    pub fn record_commit(&mut self, epoch: u64) -> bool {
+        if self.has_committed(epoch) {
+            return false;
+        }
+        self.committed_epochs.push(epoch);
+        // Keep only recent epochs to avoid unbounded growth
+        if self.committed_epochs.len() > COMMITTED_EPOCHS_HISTORY {
+            self.committed_epochs.remove(0);
+        }
+        true
+    }
+    
+    /// Queue a claim to run in the background; false when `limit` claims are already pending
+    pub fn defer_claim(&mut self, epoch: u64, limit: usize) -> bool {
+        if self.pending_claims.contains(&epoch) {
+            return true;
+        }
+        if self.pending_claims.len() >= limit {
+            return false;
+        }
+        self.pending_claims.push(epoch);
+        true
+    }
+    
+    /// Stop trying to claim `epoch`: drop it from the pending claims, remember it as
+    /// abandoned and, if it was being claimed in the foreground, go back to idle
+    pub fn abandon_claim(&mut self, epoch: u64) {
+        self.pending_claims.retain(|&e| e != epoch);
+        if !self.abandoned_claims.contains(&epoch) {
+            self.abandoned_claims.push(epoch);
+            if self.abandoned_claims.len() > COMMITTED_EPOCHS_HISTORY {
+                self.abandoned_claims.remove(0);
+            }
+        }
+        if self.phase == MiningPhase::Claiming(epoch) {
+            self.phase = MiningPhase::Idle;
+        }
+    }
 }
 
 impl Default for MiningState {
@@ -75,6 +308,10 @@ impl Default for MiningState {
             phase: MiningPhase::Idle,
             last_saved: 0,
             committed_epochs: Vec::new(),
+            search_checkpoint: None,
+            failure_streak: FailureStreak::default(),
+            pending_claims: Vec::new(),
+            abandoned_claims: Vec::new(),
         }
     }
 }
@@ -94,12 +331,156 @@ pub struct OrchestratorConfig {
     pub retry_delay_ms: u64,
     /// Mining contract address
     pub contract_address: String,
+    /// More mining contracts to mine at the same time (e.g. both sides of a contract
+    /// migration). Expanded into one config per contract by `per_contract`
+    pub additional_contracts: Vec<String>,
+    /// CW20 POWER token contract, for staking
+    pub power_token: String,
+    /// Stake the wallet's POWER balance along with each claim, locked for this many
+    /// days (0, 30, 90, 180, 365 or 730). None = never stake automatically
+    pub auto_stake_days: Option<u64>,
     /// Number of worker threads for mining
     pub worker_count: usize,
     /// Safety buffer for phase-ending transactions (in blocks)
     /// Commits and reveals won't be submitted if less than this many blocks remain
     /// Default: 8 blocks (~8 seconds) to account for transaction processing time
     pub submission_buffer_blocks: u64,
+    /// Whether to claim rewards after a successful reveal
+    /// When false, the orchestrator goes straight back to idle after revealing
+    /// (useful for pooled/delegated setups where claims are handled elsewhere)
+    pub claim_enabled: bool,
+    /// Keep hashing into the adjacent partition once our own range is exhausted
+    /// NOTE: the contract only accepts reveals whose nonce falls inside the miner's
+    /// Blake2b-derived partition (1/1000th of the nonce space, rotated each epoch),
+    /// so solutions found outside it are rejected with "nonce out of range".
+    /// Only useful against a contract that relaxes that check. Default: false
+    pub extended_nonce_search: bool,
+    /// Readiness fails if the last successful epoch query is older than this (seconds)
+    pub health_max_query_age_secs: u64,
+    /// Readiness fails if the wallet's INJ balance drops below this (base units)
+    /// Default: 0.01 INJ
+    pub min_gas_balance: u128,
+    /// Like `min_gas_balance`, while fees are paid in a denom other than INJ (base units
+    /// of that denom). `min_gas_balance` applies again once fees fall back to INJ. Default: 0
+    pub fee_denom_min_balance: u128,
+    /// Start mining workers one at a time over this many seconds instead of all at once
+    /// Smooths the power/thermal spike on laptops and other constrained hardware. Default: 0
+    pub worker_ramp_up_secs: u64,
+    /// Rotate where the workers start within the nonce partition, as a fraction of it
+    /// (0.0-1.0). Machines mining one wallet give each a different value. Default: 0.0
+    pub nonce_offset: f64,
+    /// Adjust the worker count each epoch from difficulty and CPU temperature/battery
+    /// When false, `worker_count` threads are always used
+    pub adaptive_threads: bool,
+    /// Lower bound for adaptive thread scaling
+    pub min_threads: usize,
+    /// Upper bound for adaptive thread scaling
+    pub max_threads: usize,
+    /// Delay the first mining start by a random 0..=N seconds
+    /// Spreads out commits when a fleet of miners is (re)started at once. Default: 0
+    pub startup_jitter_seconds: u64,
+    /// When resuming with an unrevealed commitment, check the saved nonce/digest/salt
+    /// against the commitment stored on-chain and skip the reveal if they don't match
+    pub verify_commitment_on_resume: bool,
+    /// When resuming with an unrevealed commitment while its reveal window is open,
+    /// reveal straight away instead of after the startup checks and first poll
+    pub reveal_on_resume: bool,
+    /// Only start mining inside these daily windows (local time). Outside them the
+    /// orchestrator stays idle but keeps polling the chain; in-flight reveals and
+    /// claims still complete. None = mine around the clock
+    pub schedule: Option<MiningSchedule>,
+    /// Human-readable name for this miner (e.g. "rig-basement-01"), included in
+    /// telemetry and the tracing span of every loop iteration. None = use the instance id
+    pub miner_label: Option<String>,
+    /// Fixed miner instance id for telemetry. None = load it from (or create) the
+    /// `.id` file next to `state_file`, so the id survives restarts and upgrades
+    pub miner_id: Option<Uuid>,
+    /// Commitment hashing scheme; must match what the deployed contract verifies
+    pub commitment_version: CommitmentVersion,
+    /// Consecutive polls that must report the same phase before committing or revealing
+    /// Guards against transient phase readings around boundaries. Default: 1 (2 recommended)
+    pub phase_confirmations: u32,
+    /// Where found solutions are sent. Default: on-chain only. With a pool target but
+    /// no `OnChain`, the pool does the commit/reveal and the miner moves on to the next epoch
+    pub submission_targets: Vec<SubmissionTarget>,
+    /// When the epoch advances before a solution is committed, restart mining on the
+    /// new epoch immediately (true) or go idle and wait for the next poll (false)
+    pub restart_on_epoch_advance: bool,
+    /// Commitment salt source. `Derived` makes reveals recoverable from the last
+    /// solution proof when the state file is lost. Default: random
+    pub salt_source: SaltSource,
+    /// Trip the failure breaker after this many consecutive epochs whose commit or
+    /// reveal failed. 0 = never
+    pub max_failed_epochs: u32,
+    /// Give up on an epoch's reveal after the contract rejected it this many times,
+    /// instead of retrying until the window closes. Transient failures don't count.
+    /// 0 = until the window closes. Default: 5
+    pub max_reveal_attempts: u32,
+    /// Give up on claiming an epoch after this many attempts found it not claimable
+    /// yet (e.g. its settlement never finishes), backing off between them, so the
+    /// miner goes back to mining. 0 = keep trying. Default: 10
+    pub max_claim_attempts: u32,
+    /// What the failure breaker does when it trips
+    pub failure_action: FailureAction,
+    /// How long `FailureAction::Pause` stops mining before trying again
+    pub failure_pause_secs: u64,
+    /// Sign the reveal during the commit phase (Rust signer only) so revealing is a
+    /// single broadcast. Re-signed at reveal time if the account sequence moved
+    pub prepare_reveal: bool,
+    /// Epochs tracked at once. Above 1, claims are deferred and run while the next
+    /// epoch is mined instead of blocking it. Default: 1 (claim before mining on)
+    pub max_tracked_epochs: usize,
+    /// Rotated backups of the state file to keep (`state.1` newest); a state file that
+    /// fails to parse on startup is replaced by the newest good backup. 0 = none
+    pub state_backups: usize,
+    /// Rewrite this file with current metrics in OpenMetrics text format every
+    /// few seconds (e.g. for the node_exporter textfile collector). None = off
+    pub metrics_file: Option<PathBuf>,
+    /// Rewrite this file with a timestamp, the phase and the epoch on every pass of the
+    /// main loop, for watchdogs that detect a hung miner by its age. None = off
+    pub heartbeat_file: Option<PathBuf>,
+    /// POST a JSON event with the old and new difficulty here when it changes. None = log only
+    pub difficulty_webhook: Option<String>,
+    /// Never send `advance_epoch` - wait for another miner (or a keeper) to start the
+    /// next epoch instead of paying gas for it
+    pub no_advance: bool,
+    /// Mine live epochs but never commit, reveal, claim or advance - load testing
+    /// without gas or a funded wallet. Found solutions are only counted
+    pub no_submit: bool,
+    /// Warm standby for a primary miner on the same wallet: mine every epoch, but only
+    /// commit if the primary's commitment isn't on-chain by the takeover point
+    pub standby: bool,
+    /// Blocks before the end of the commit phase (on top of `submission_buffer_blocks`)
+    /// at which a standby stops waiting for the primary and commits itself
+    pub standby_takeover_blocks: u64,
+    /// Seconds to wait after a claim before mining again; the first epoch started
+    /// afterwards is checked on-chain for a commitment we already made
+    pub post_claim_cooldown_secs: u64,
+    /// Re-fetch the epoch's target hash and difficulty this often (seconds) while mining,
+    /// restarting the workers if a retarget changed them. None = fetch once per epoch
+    pub target_refresh_secs: Option<u64>,
+    /// Treat the chain as halted once the block height hasn't moved for this many
+    /// seconds: mining and submissions pause until it advances again. 0 = never
+    pub chain_halt_secs: u64,
+    /// Run this command on key events (solution found, reward claimed, errors) with the
+    /// event name, epoch and a description appended as arguments. None = no notifications
+    pub notify_command: Option<String>,
+    /// How long to wait for a commit transaction to be confirmed (seconds)
+    pub commit_confirmation_timeout_secs: u64,
+    /// How long to wait for a reveal transaction to be confirmed (seconds). Short, so a
+    /// stuck reveal fails while there is still time in the window to retry it
+    pub reveal_confirmation_timeout_secs: u64,
+    /// How long to wait for a claim transaction to be confirmed (seconds)
+    pub claim_confirmation_timeout_secs: u64,
+    /// How often a queued transaction's status is checked while waiting (milliseconds)
+    pub confirmation_poll_ms: u64,
+    /// Send queued transactions most urgent first (reveals, then commits, then claims)
+    /// rather than in queue order. Matters once claims overlap the next epoch's
+    /// commit and reveal (`max_tracked_epochs` above 1)
+    pub prioritize_transactions: bool,
+    /// Broadcasts in flight at once per signer, across every contract sharing the
+    /// wallet. Sequence numbers are strictly ordered, so above 1 invites sequence races
+    pub max_in_flight_per_signer: usize,
 }
 
 impl Default for OrchestratorConfig {
@@ -111,16 +492,128 @@ impl Default for OrchestratorConfig {
             max_retries: 3,
             retry_delay_ms: 1000,
             contract_address: String::new(),
+            additional_contracts: Vec::new(),
+            power_token: String::new(),
+            auto_stake_days: None,
             worker_count: 4,
             submission_buffer_blocks: 8,  // Conservative default
+            claim_enabled: true,
+            extended_nonce_search: false,
+            health_max_query_age_secs: 60,
+            min_gas_balance: 10_000_000_000_000_000,
+            fee_denom_min_balance: 0,
+            worker_ramp_up_secs: 0,
+            nonce_offset: 0.0,
+            adaptive_threads: false,
+            min_threads: 1,
+            max_threads: 4,
+            startup_jitter_seconds: 0,
+            verify_commitment_on_resume: true,
+            reveal_on_resume: true,
+            schedule: None,
+            miner_label: None,
+            miner_id: None,
+            commitment_version: CommitmentVersion::default(),
+            phase_confirmations: 1,
+            submission_targets: vec![SubmissionTarget::OnChain],
+            restart_on_epoch_advance: true,
+            salt_source: SaltSource::default(),
+            max_failed_epochs: 5,
+            max_reveal_attempts: 5,
+            max_claim_attempts: 10,
+            failure_action: FailureAction::default(),
+            failure_pause_secs: 1800,
+            prepare_reveal: false,
+            max_tracked_epochs: 1,
+            state_backups: 3,
+            metrics_file: None,
+            heartbeat_file: None,
+            difficulty_webhook: None,
+            no_advance: false,
+            no_submit: false,
+            standby: false,
+            standby_takeover_blocks: 20,
+            post_claim_cooldown_secs: 5,
+            target_refresh_secs: None,
+            chain_halt_secs: 120,
+            notify_command: None,
+            commit_confirmation_timeout_secs: 120,
+            reveal_confirmation_timeout_secs: 30,
+            claim_confirmation_timeout_secs: 300,
+            confirmation_poll_ms: 500,
+            prioritize_transactions: true,
+            max_in_flight_per_signer: 1,
+        }
+    }
+}
+
+/// Wait for a queued transaction to be confirmed, checking its status every `poll`
+/// Returns the tx hash, or None if the message had already been applied on-chain.
+/// On timeout the transaction is cancelled, so retrying the submission doesn't queue
+/// a second copy behind the first
+async fn wait_for_confirmation(
+    tx_manager: &transaction_manager::TransactionManager,
+    tx_id: u64,
+    what: &str,
+    timeout: Duration,
+    poll: Duration,
+) -> Result<Option<String>> {
+    let start = std::time::Instant::now();
+    loop {
+        if start.elapsed() > timeout {
+            return Err(confirmation_timeout(tx_manager, tx_id, what, timeout).await);
         }
+        
+        match confirmation_outcome(what, tx_manager.get_status(tx_id).await) {
+            Some(outcome) => return outcome,
+            // Still pending or processing
+            None => sleep(poll).await,
+        }
+    }
+}
+
+/// What a transaction's status means for whoever waits on it (see `wait_for_confirmation`);
+/// None while it is still pending or processing
+fn confirmation_outcome(
+    what: &str,
+    status: Option<transaction_manager::TransactionStatus>,
+) -> Option<Result<Option<String>>> {
+    match status {
+        Some(transaction_manager::TransactionStatus::Success { tx_hash }) => {
+            log::info!("{} successful: {}", what, tx_hash);
+            Some(Ok(Some(tx_hash)))
+        }
+        Some(transaction_manager::TransactionStatus::Failed { error }) => {
+            Some(Err(anyhow!("{} failed: {}", what, error)))
+        }
+        Some(transaction_manager::TransactionStatus::AlreadyApplied { reason }) => {
+            log::info!("{} already on chain: {}", what, reason);
+            Some(Ok(None))
+        }
+        Some(transaction_manager::TransactionStatus::Skipped { reason }) => {
+            Some(Err(anyhow!("{} not sent: {}", what, reason)))
+        }
+        _ => None,
     }
 }
 
+/// Cancel a transaction whose confirmation wait ran out and describe what happened
+async fn confirmation_timeout(
+    tx_manager: &transaction_manager::TransactionManager,
+    tx_id: u64,
+    what: &str,
+    timeout: Duration,
+) -> anyhow::Error {
+    let outcome = if tx_manager.cancel(tx_id).await { "cancelled" } else { "already sent, it may still land" };
+    anyhow!("{} transaction timeout after {}s ({})", what, timeout.as_secs(), outcome)
+}
+
 /// Main orchestrator coordinating all mining operations
 pub struct MiningOrchestrator {
     /// Current mining state
     state: MiningState,
+    /// Where the state is persisted, with its backups
+    state_file: StateFile,
     /// Configuration
     config: OrchestratorConfig,
     /// Chain client for blockchain interaction (shared with transaction manager)
@@ -135,8 +628,64 @@ pub struct MiningOrchestrator {
     stats_collector: Arc<Mutex<StatsCollector>>,
     /// Telemetry reporter for production monitoring
     telemetry_reporter: Option<Arc<EnhancedTelemetryReporter>>,
+    /// Sends telemetry in the background so the state machine never waits on it
+    telemetry_sender: Option<TelemetrySender>,
     /// Last telemetry timestamp (instance-specific, not static)
     last_telemetry_time: std::sync::atomic::AtomicU64,
+    /// Reward/cost estimation from difficulty, hashrate and claim history
+    profitability: ProfitabilityTracker,
+    /// Liveness/readiness state exposed to the health probe server
+    health: Arc<HealthState>,
+    /// When the gas balance was last checked for readiness
+    last_gas_check: Option<std::time::Instant>,
+    /// Last live gas price fetch
+    last_gas_price_refresh: Option<std::time::Instant>,
+    /// Last metrics file write
+    last_metrics_write: Option<std::time::Instant>,
+    /// Heartbeat file and systemd watchdog notifications
+    heartbeat: Heartbeat,
+    /// Epoch the primary committed for while we were on standby - nothing more to do in it
+    standby_deferred_epoch: Option<u64>,
+    /// Set when a claim finishes; mining waits until then
+    claim_cooldown_until: Option<std::time::Instant>,
+    /// Set when the failure breaker pauses mining; the loop keeps polling until then
+    failure_pause_until: Option<std::time::Instant>,
+    /// Contract events (epoch advanced/finalized) used to cut polling waits short
+    event_rx: Option<tokio::sync::mpsc::Receiver<ContractEvent>>,
+    /// Whether the startup jitter delay still has to be applied
+    startup_jitter_pending: bool,
+    /// Label (or instance id) identifying this miner in logs and telemetry
+    miner_name: String,
+    /// Whether we were inside a schedule window on the last check (None = not checked yet)
+    schedule_active: Option<bool>,
+    /// Consecutive phase readings seen before acting on commit/reveal
+    phase_debounce: PhaseDebouncer,
+    /// Recent epoch info, shared by the reads within one loop iteration
+    epoch_cache: EpochInfoCache,
+    /// Pools found solutions are submitted to
+    pools: Vec<PoolClient>,
+    /// When the nonce search progress was last checkpointed
+    last_search_checkpoint: std::time::Instant,
+    /// Target hash and difficulty the workers are mining against
+    mining_target: Option<([u8; 32], u8)>,
+    /// When the target was last fetched
+    last_target_refresh: std::time::Instant,
+    /// Reveals the contract rejected for the epoch being revealed
+    reveal_attempts: u32,
+    /// Not-ready claim attempts and their backoff, per epoch
+    claim_retries: ClaimRetries,
+    /// Deferred claim in progress and its stage (restarts from the top after a restart)
+    deferred_claim: Option<(u64, DeferredClaim)>,
+    /// Last difficulty mined at, to notice changes
+    difficulty_watch: DifficultyWatch,
+    /// Where difficulty changes are reported
+    difficulty_webhook: Option<DifficultyWebhook>,
+    /// Block height progress, to notice a halted chain
+    halt_detector: ChainHaltDetector,
+    /// When the block height was last sampled for halt detection
+    last_halt_check: Option<std::time::Instant>,
+    /// Command run on key events
+    notify_hook: Option<NotifyHook>,
 }
 
 impl MiningOrchestrator {
@@ -146,13 +695,28 @@ impl MiningOrchestrator {
         client: InjectiveClient,
         wallet: InjectiveWallet,
     ) -> Result<Self> {
-        // Load saved state or use default
-        let mut state = Self::load_state(&config.state_file).unwrap_or_default();
+        let limiter = Arc::new(BroadcastLimiter::new(config.max_in_flight_per_signer));
+        Self::new_shared(config, Arc::new(RwLock::new(client)), wallet, limiter).await
+    }
+    
+    /// Create an orchestrator on a client and broadcast limiter shared with others
+    /// mining other contracts from the same wallet, so their transactions take turns
+    /// on the account sequence
+    pub async fn new_shared(
+        config: OrchestratorConfig,
+        client_arc: Arc<RwLock<InjectiveClient>>,
+        wallet: InjectiveWallet,
+        limiter: Arc<BroadcastLimiter>,
+    ) -> Result<Self> {
+        // Load saved state (or its newest good backup) or use default
+        let state_file = StateFile::new(config.state_file.clone(), config.state_backups);
+        let mut state = state_file.load::<MiningState>().unwrap_or_default();
         
         // Validate loaded state against current epoch to prevent stale state issues
         // If state is more than 5 epochs behind, discard it and start fresh
         // This prevents the "nonce out of range" infinite loop bug
-        match query_epoch_info(&client, &config.contract_address).await {
+        let current_epoch_info = query_epoch_info(&*client_arc.read().await, &config.contract_address).await;
+        match current_epoch_info {
             Ok(current_epoch_info) => {
                 let current_epoch = current_epoch_info.epoch_number;
                 
@@ -186,16 +750,29 @@ impl MiningOrchestrator {
         }
         
         // Create mining engine
-        let engine = MiningEngine::new(config.worker_count);
+        let mut engine = MiningEngine::new(config.worker_count);
+        if config.worker_ramp_up_secs > 0 {
+            engine.set_ramp_up(Duration::from_secs(config.worker_ramp_up_secs)).await;
+        }
+        if config.nonce_offset > 0.0 {
+            engine.set_nonce_offset(config.nonce_offset).await;
+        }
+        
+        // Instance id persists across restarts; the optional label is what humans see
+        let miner_id = config.miner_id
+            .unwrap_or_else(|| load_or_create_miner_id(&config.state_file.with_extension("id")));
+        let instance_id = format!("miner-{}", miner_id);
+        let miner_name = config.miner_label.clone().unwrap_or_else(|| instance_id.clone());
+        log::info!("Miner: {} ({})", miner_name, instance_id);
         
         // Create enhanced telemetry reporter with comprehensive metrics
         let telemetry_reporter = match EnhancedTelemetryReporter::new(
             wallet.address.clone(),
-            format!("miner-{}", Uuid::new_v4()),
+            instance_id,
         ) {
             Ok(reporter) => {
                 log::info!("Enhanced telemetry reporter initialized for {}", wallet.address);
-                Some(Arc::new(reporter))
+                Some(Arc::new(reporter.with_label(config.miner_label.clone())))
             },
             Err(e) => {
                 log::warn!("Failed to initialize telemetry: {}", e);
@@ -203,9 +780,6 @@ impl MiningOrchestrator {
             }
         };
         
-        // Wrap client in Arc<RwLock> for sharing with transaction manager
-        let client_arc = Arc::new(RwLock::new(client));
-        
         // Create transaction manager configuration
         let tx_config = transaction_manager::TransactionManagerConfig {
             max_retries: 3,
@@ -214,23 +788,92 @@ impl MiningOrchestrator {
             gas_price_multiplier: 1.1,
             max_queue_size: 100,
             contract_address: config.contract_address.clone(),
+            power_token_address: config.power_token.clone(),
+            max_in_flight_per_signer: config.max_in_flight_per_signer,
+            pending_tx_wait_ms: 6000,
+            prioritize: config.prioritize_transactions,
         };
         
         // Create and start transaction manager
-        let tx_manager = transaction_manager::TransactionManager::new(tx_config, client_arc.clone());
+        let stats_collector = Arc::new(Mutex::new(StatsCollector::new()));
+        let tx_manager = transaction_manager::TransactionManager::new(tx_config, client_arc.clone())
+            .with_stats(stats_collector.clone())
+            .with_limiter(limiter);
         tx_manager.start().await?;
         log::info!("Transaction manager initialized and started");
         
+        let health = Arc::new(HealthState::new(config.health_max_query_age_secs));
+        
+        let pools = config.submission_targets.iter()
+            .filter_map(|target| match target {
+                SubmissionTarget::Pool { url, auth } => Some(PoolClient::new(url, auth.clone())),
+                SubmissionTarget::OnChain => None,
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for pool in &pools {
+            log::info!("Submitting solutions to pool {}", pool.url());
+        }
+        let difficulty_webhook = config.difficulty_webhook.as_deref().map(DifficultyWebhook::new).transpose()?;
+        if let Some(ref webhook) = difficulty_webhook {
+            log::info!("Reporting difficulty changes to {}", webhook.url());
+        }
+        let notify_hook = config.notify_command.as_deref().map(NotifyHook::new).transpose()?;
+        if let Some(ref hook) = notify_hook {
+            log::info!("Notifying on solutions, claims and errors with: {}", hook.command());
+        }
+        if config.no_submit {
+            log::info!("No-submit mode: mining live epochs without submitting anything");
+        } else if config.standby {
+            log::info!(
+                "Warm standby: committing only if the primary hasn't committed {} blocks before the commit phase ends",
+                config.submission_buffer_blocks + config.standby_takeover_blocks
+            );
+        } else if !config.submission_targets.contains(&SubmissionTarget::OnChain) {
+            log::info!("Pool-only mode: solutions are not committed on-chain by this miner");
+        }
+        
+        let phase_debounce = PhaseDebouncer::new(config.phase_confirmations);
+        let heartbeat = Heartbeat::new(config.heartbeat_file.clone());
+        
         Ok(Self {
             state,
+            state_file,
             config,
             client: client_arc,
             engine,
             wallet,
             tx_manager: Some(tx_manager),
-            stats_collector: Arc::new(Mutex::new(StatsCollector::new())),
+            stats_collector,
+            telemetry_sender: telemetry_reporter.clone().map(|reporter| TelemetrySender::spawn(reporter, TELEMETRY_QUEUE_CAPACITY)),
             telemetry_reporter,
             last_telemetry_time: std::sync::atomic::AtomicU64::new(0),
+            profitability: ProfitabilityTracker::new(),
+            health,
+            last_gas_check: None,
+            last_gas_price_refresh: None,
+            last_metrics_write: None,
+            heartbeat,
+            standby_deferred_epoch: None,
+            claim_cooldown_until: None,
+            failure_pause_until: None,
+            event_rx: None,
+            startup_jitter_pending: true,
+            miner_name,
+            schedule_active: None,
+            phase_debounce,
+            epoch_cache: EpochInfoCache::new(EPOCH_INFO_TTL),
+            last_search_checkpoint: std::time::Instant::now(),
+            mining_target: None,
+            last_target_refresh: std::time::Instant::now(),
+            reveal_attempts: 0,
+            claim_retries: ClaimRetries::default(),
+            deferred_claim: None,
+            difficulty_watch: DifficultyWatch::default(),
+            difficulty_webhook,
+            halt_detector: ChainHaltDetector::new(Duration::from_secs(config.chain_halt_secs)),
+            last_halt_check: None,
+            notify_hook,
+            pools,
         })
     }
     
@@ -239,14 +882,17 @@ impl MiningOrchestrator {
         log::info!("Starting mining orchestrator");
         log::info!("Loaded state: epoch={}, phase={:?}", self.state.epoch, self.state.phase);
         
-        // Test telemetry connection
+        // Test telemetry connection in the background - a slow backend mustn't delay startup
         if let Some(ref reporter) = self.telemetry_reporter {
             log::info!("Testing telemetry connection to https://gmine.gelotto.io/api/telemetry...");
-            if reporter.test_connection().await? {
-                log::info!("✓ Telemetry backend connected - dashboard should receive data");
-            } else {
-                log::error!("✗ Telemetry backend NOT reachable - dashboard will show zeros!");
-            }
+            let reporter = reporter.clone();
+            tokio::spawn(async move {
+                match reporter.test_connection().await {
+                    Ok(true) => log::info!("✓ Telemetry backend connected - dashboard should receive data"),
+                    Ok(false) => log::error!("✗ Telemetry backend NOT reachable - dashboard will show zeros!"),
+                    Err(e) => log::error!("✗ Telemetry connection test failed: {}", e),
+                }
+            });
         }
         
         // V3.3: Check and display stake status
@@ -259,8 +905,8 @@ impl MiningOrchestrator {
                     log::info!("✓ V3.3 Stake Status: {} POWER staked, {}x multiplier", amount, multiplier);
                     
                     if amount > 0.0 && stake_info.lock_until > 0 {
-                        let current_block = self.get_block_height_with_retry().await.unwrap_or(0);
-                        if stake_info.lock_until > current_block {
+                        let current_block = self.get_block_height_with_retry().await;
+                        if let Some(current_block) = current_block.ok().filter(|&block| stake_info.lock_until > block) {
                             let blocks_remaining = stake_info.lock_until - current_block;
                             let days_remaining = blocks_remaining * 5 / (24 * 60 * 60);
                             log::info!("  Lock expires in {} days ({} blocks)", days_remaining, blocks_remaining);
@@ -299,601 +945,1066 @@ impl MiningOrchestrator {
             }
         }
         
+        // Nothing is submitted in no-submit mode, so a resumed commit/reveal/claim is dropped
+        if self.config.no_submit && !matches!(self.state.phase, MiningPhase::Idle | MiningPhase::FindingSolution) {
+            log::warn!("No-submit mode: not resuming {} for epoch {}", self.state.phase.name(), self.state.epoch);
+            self.transition_to_idle().await?;
+        }
+        
+        // If we're resuming with an unrevealed commitment, make sure it will actually reveal
+        if let MiningPhase::WaitingForRevealWindow(ref data) | MiningPhase::Revealing(ref data) = self.state.phase {
+            if self.config.verify_commitment_on_resume {
+                let data = data.clone();
+                if !self.verify_resumed_commitment(&data).await {
+                    log::error!("Skipping reveal for epoch {} - a reveal from this state is guaranteed to fail", data.epoch);
+                    self.transition_to_idle().await?;
+                }
+            }
+        }
+        
+        // Restarted mid-reveal? Reveal now rather than after the first poll
+        if self.config.reveal_on_resume {
+            self.reveal_on_resume().await?;
+        }
+        
+        // Saved state lost the reveal? With derived salts it can be rebuilt from the last proof
+        if self.config.salt_source == SaltSource::Derived
+            && matches!(self.state.phase, MiningPhase::Idle | MiningPhase::FindingSolution)
+        {
+            self.recover_derived_reveal().await?;
+        }
+        
         // If we're resuming in FindingSolution phase, restart the mining engine
         if matches!(self.state.phase, MiningPhase::FindingSolution) {
             log::info!("Resuming mining for epoch {}", self.state.epoch);
-            let difficulty = self.get_difficulty_with_retry().await?;
-            let nonce_range = self.get_nonce_range_with_retry().await?;
-            
-            // Update statistics
-            self.stats_collector.lock().await.start_mining(
-                self.state.epoch, 
-                difficulty, 
-                nonce_range.0, 
-                nonce_range.1
-            ).await;
-            
-            // Restart the mining engine
-            self.engine.start_mining(self.state.epoch, difficulty, nonce_range).await?;
+            self.start_engine(self.state.epoch).await?;
             log::info!("Mining engine restarted for epoch {}", self.state.epoch);
         }
         
+        self.heartbeat.ready();
+        
         // Main orchestration loop
         loop {
+            self.heartbeat.beat(self.state.epoch, self.state.phase.name());
+            self.refresh_health().await;
+            
+            if self.check_chain_halt().await? {
+                sleep(CHAIN_HALT_POLL_INTERVAL).await;
+                continue;
+            }
+            
             // Get current chain epoch with retry
             let chain_epoch = self.get_current_epoch_with_retry().await?;
             
-            // Process based on current state - clone to avoid borrow checker issues
-            let current_phase = self.state.phase.clone();
-            match current_phase {
-                MiningPhase::Idle => {
-                    // Check if we should start mining for current or new epoch
-                    if chain_epoch >= self.state.epoch {
-                        // Also check if we're in a mineable phase (Commit phase)
-                        let client = self.client.read().await;
-                        match query_epoch_info(&*client, &self.config.contract_address).await {
-                            Ok(epoch_info) => {
-                                drop(client);
-                                match epoch_info.phase {
-                                    PhaseInfo::Commit { ends_at } => {
-                                        // Check if we've already committed to this epoch
-                                        if self.state.committed_epochs.contains(&chain_epoch) {
-                                            log::info!("Already committed to epoch {}, waiting for next epoch", chain_epoch);
-                                            sleep(Duration::from_secs(self.config.epoch_poll_interval)).await;
-                                        } else {
-                                            log::info!("Starting mining for epoch {} (Commit phase, ends at block {})", 
-                                                      chain_epoch, ends_at);
-                                            self.transition_to_finding_solution(chain_epoch).await?;
-                                        }
+            self.check_failure_streak(chain_epoch).await?;
+            
+            if let Some(remaining) = self.failure_pause_remaining()? {
+                // Paused on purpose, not hung - keep the heartbeat going
+                self.heartbeat.beat(self.state.epoch, "paused");
+                sleep(remaining.min(FAILURE_PAUSE_POLL_INTERVAL)).await;
+                continue;
+            }
+            
+            if !self.check_schedule().await? {
+                sleep(SCHEDULE_IDLE_POLL_INTERVAL).await;
+                continue;
+            }
+            
+            // Run this iteration inside an epoch/phase span so client call traces correlate
+            let span = tracing::info_span!(
+                "mining",
+                miner = %self.miner_name,
+                epoch = chain_epoch,
+                phase = self.state.phase.name()
+            );
+            self.step(chain_epoch).instrument(span).await?;
+            
+            // Deferred claims run while the workers hash or nothing else is due
+            if !self.config.no_submit && matches!(self.state.phase, MiningPhase::Idle | MiningPhase::FindingSolution) {
+                if let Err(e) = self.process_pending_claim(chain_epoch).await {
+                    log::warn!("Deferred claim: {:#}", e);
+                }
+            }
+            
+            // Small delay to prevent tight loops
+            sleep(Duration::from_millis(100)).await;
+        }
+    }
+    
+    /// One pass of the state machine for the current phase
+    async fn step(&mut self, chain_epoch: u64) -> Result<()> {
+        // Process based on current state - clone to avoid borrow checker issues
+        let current_phase = self.state.phase.clone();
+        match current_phase {
+            MiningPhase::Idle => {
+                // Check if we should start mining for current or new epoch
+                if chain_epoch >= self.state.epoch {
+                    // Also check if we're in a mineable phase (Commit phase)
+                    match self.epoch_info().await {
+                        Ok(epoch_info) => {
+                            match epoch_info.phase {
+                                PhaseInfo::Commit { ends_at } => {
+                                    // Check if we've already committed to this epoch
+                                    if self.state.has_committed(chain_epoch) {
+                                        log::info!("Already committed to epoch {}, waiting for next epoch", chain_epoch);
+                                        self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                    } else if self.standby_deferred_epoch == Some(chain_epoch) {
+                                        log::debug!("Primary committed for epoch {}, standing by until the next epoch", chain_epoch);
+                                        self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                    } else if let Some(remaining) = self.claim_cooldown_remaining() {
+                                        log::debug!("Post-claim cooldown: {}s before mining epoch {}", remaining.as_secs(), chain_epoch);
+                                        sleep(remaining.min(Duration::from_secs(self.config.epoch_poll_interval))).await;
+                                    } else if self.claim_cooldown_until.take().is_some() && self.committed_on_chain(chain_epoch).await {
+                                        // Our commit landed but the state file never heard of it
+                                        log::info!("Already committed to epoch {} on-chain, waiting for next epoch", chain_epoch);
+                                        self.state.record_commit(chain_epoch);
+                                        self.save_state()?;
+                                    } else {
+                                        log::info!("Starting mining for epoch {} (Commit phase, ends at block {})", 
+                                                  chain_epoch, ends_at);
+                                        self.transition_to_finding_solution(chain_epoch).await?;
                                     }
-                                    PhaseInfo::Settlement { ends_at } => {
-                                        // Check if settlement has ended and needs advancement
-                                        let current_block = self.get_block_height_with_retry().await.unwrap_or(ends_at + 1);
-                                        if current_block > ends_at {
-                                            log::info!("Settlement ended for epoch {}, advancing to next epoch", chain_epoch);
-                                            if let Some(ref tx_manager) = self.tx_manager {
-                                                match tx_manager.queue_advance_epoch().await {
-                                                    Ok(tx_id) => {
-                                                        log::info!("Queued advance_epoch transaction {}", tx_id);
-                                                        sleep(Duration::from_secs(5)).await;
-                                                    }
-                                                    Err(e) => {
-                                                        log::error!("Failed to advance epoch - TRANSACTION ERROR: {}", e);
-                                                    }
+                                }
+                                PhaseInfo::Settlement { ends_at } => {
+                                    // Check if settlement has ended and needs advancement
+                                    let current_block = match self.get_block_height_with_retry().await {
+                                        Ok(height) => height,
+                                        Err(e) => {
+                                            log::warn!("Could not read the block height to check epoch {}'s settlement: {}", chain_epoch, e);
+                                            self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                            return Ok(());
+                                        }
+                                    };
+                                    if !self.self_advance_enabled().await {
+                                        // Advancing is a transaction - leave it to other miners
+                                        self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                    } else if current_block > ends_at {
+                                        log::info!("Settlement ended for epoch {}, advancing to next epoch", chain_epoch);
+                                        if let Some(ref tx_manager) = self.tx_manager {
+                                            match tx_manager.queue_advance_epoch().await {
+                                                Ok(tx_id) => {
+                                                    log::info!("Queued advance_epoch transaction {}", tx_id);
+                                                    sleep(Duration::from_secs(5)).await;
+                                                }
+                                                Err(e) => {
+                                                    log::error!("Failed to advance epoch - TRANSACTION ERROR: {}", e);
                                                 }
                                             }
-                                        } else {
-                                            log::debug!("Waiting for settlement to end (current: {}, ends: {})", current_block, ends_at);
-                                            sleep(Duration::from_secs(self.config.epoch_poll_interval)).await;
                                         }
-                                    }
-                                    _ => {
-                                        log::debug!("Waiting for Commit phase to start mining");
-                                        sleep(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                    } else {
+                                        log::debug!("Waiting for settlement to end (current: {}, ends: {})", current_block, ends_at);
+                                        self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
                                     }
                                 }
-                            }
-                            Err(e) => {
-                                log::error!("Failed to query epoch info: {}", e);
-                                sleep(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                PhaseInfo::Unknown(name) => {
+                                    log::warn!("Epoch {} is in unrecognized phase '{}' - contract may have been upgraded, waiting", 
+                                              chain_epoch, name);
+                                    self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                }
+                                _ => {
+                                    log::debug!("Waiting for Commit phase to start mining");
+                                    self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                                }
                             }
                         }
-                    } else {
-                        // Wait before checking again
-                        sleep(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                        Err(e) => {
+                            log::error!("Failed to query epoch info: {}", e);
+                            sleep(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                        }
                     }
+                } else {
+                    // Wait before checking again
+                    self.wait_for_chain_event(Duration::from_secs(self.config.epoch_poll_interval)).await;
                 }
-                
-                MiningPhase::FindingSolution => {
-                    // Check if solution finding is complete
-                    if let Some(solution) = self.engine.check_solution().await {
-                        log::info!("Found solution for epoch {}", self.state.epoch);
+            }
+            
+            MiningPhase::FindingSolution if chain_epoch > self.state.epoch => {
+                self.abandon_stale_epoch(self.state.epoch, chain_epoch).await?;
+            }
+            
+            MiningPhase::FindingSolution => {
+                // Check if solution finding is complete
+                if let Some(mut solution) = self.engine.check_solution().await {
+                    log::info!("Found solution for epoch {}", self.state.epoch);
+                    if self.config.salt_source == SaltSource::Derived {
+                        solution = CommitmentData::derived(
+                            &self.wallet, self.config.commitment_version, solution.epoch, solution.nonce, solution.digest,
+                        );
+                    } else {
+                        // The engine always produces a salted commitment - rehash for the configured scheme
+                        solution.commitment = self.config.commitment_version.compute(
+                            &self.wallet.address, solution.epoch, solution.nonce, solution.digest, solution.salt,
+                        );
+                    }
+                    solution.found_at_ms = Some(unix_millis());
+                    let proof = self.engine.last_solution_proof(&self.wallet.address).await;
+                    let difficulty = {
+                        let mut stats_collector = self.stats_collector.lock().await;
+                        let difficulty = match proof {
+                            Some(ref proof) => proof.difficulty.min(u8::MAX as u32) as u8,
+                            None => stats_collector.get_stats().await.current_difficulty,
+                        };
+                        stats_collector.solution_found(difficulty).await;
+                        difficulty
+                    };
+                    self.notify(NotifyEvent::SolutionFound { epoch: self.state.epoch, difficulty });
+                    if let Some(ref proof) = proof {
+                        self.save_solution_proof(proof);
+                    }
+                    
+                    // Report telemetry for solution found
+                    if let Some(ref reporter) = self.telemetry_reporter {
+                        let hashrate = self.engine.get_hashrate().await;
+                        let hashrate_mhs = hashrate / 1_000_000.0; // Convert H/s to MH/s
+                        let nonce_range = self.get_nonce_range_with_retry().await.ok();
+                        let stats = reporter.get_stats().await;
+                        self.send_telemetry(TelemetryUpdate {
+                            epoch: self.state.epoch,
+                            phase: "FindingSolution".to_string(),
+                            hashrate_mhs: Some(hashrate_mhs),
+                            solutions_found: Some(stats.epochs_won as u32 + 1), // Total solutions (including this one)
+                            reveals_submitted: Some(stats.reveals_successful as u32), // Total successful reveals
+                            nonce_range,
+                            ..TelemetryUpdate::default()
+                        });
+                    }
+                    
+                    if self.config.no_submit {
+                        self.observe_solution(solution.epoch).await?;
+                    } else if self.state.has_committed(solution.epoch) {
+                        // A second commit for the same epoch is a guaranteed rejection - don't pay gas for it
+                        log::info!("Already committed for epoch {}, discarding additional solution", solution.epoch);
+                        self.engine.stop_mining().await?;
+                        self.transition_to_idle().await?;
+                    } else {
+                        if let Some(ref proof) = proof {
+                            self.submit_to_pools(proof).await;
+                        }
+                        if self.config.submission_targets.contains(&SubmissionTarget::OnChain) {
+                            self.transition_to_committing(solution).await?;
+                        } else {
+                            // The pool commits and reveals - this epoch is done for us
+                            log::info!("Solution for epoch {} handed to the pool, waiting for the next epoch", solution.epoch);
+                            self.state.record_commit(solution.epoch);
+                            self.engine.stop_mining().await?;
+                            self.transition_to_idle().await?;
+                        }
+                    }
+                } else {
+                    // Continue mining - send periodic telemetry every 30 seconds
+                    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+                    let last = self.last_telemetry_time.load(std::sync::atomic::Ordering::Relaxed);
+                    
+                    if now - last > 30 {
+                        self.last_telemetry_time.store(now, std::sync::atomic::Ordering::Relaxed);
+                        
+                        // Periodic profitability estimate
+                        let hashrate = self.engine.get_hashrate().await;
+                        self.profitability.record_hashrate(hashrate);
+                        self.log_profitability().await;
                         
-                        // Report telemetry for solution found
                         if let Some(ref reporter) = self.telemetry_reporter {
                             let hashrate = self.engine.get_hashrate().await;
                             let hashrate_mhs = hashrate / 1_000_000.0; // Convert H/s to MH/s
                             let nonce_range = self.get_nonce_range_with_retry().await.ok();
                             let stats = reporter.get_stats().await;
-                            match reporter.send_telemetry(
-                                self.state.epoch,
-                                "FindingSolution",
-                                Some(hashrate_mhs),
-                                Some(stats.epochs_won as u32 + 1), // Total solutions (including this one)
-                                Some(stats.reveals_successful as u32), // Total successful reveals
-                                None, // network_info
-                                None, // power_balance
-                                None, // gas_balance
-                                None, // last_error
+                            self.send_telemetry(TelemetryUpdate {
+                                epoch: self.state.epoch,
+                                phase: "FindingSolution".to_string(),
+                                hashrate_mhs: Some(hashrate_mhs),
+                                solutions_found: Some(stats.epochs_won as u32), // Total solutions found
+                                reveals_submitted: Some(stats.reveals_successful as u32), // Total successful reveals
                                 nonce_range,
-                            ).await {
-                                Ok(_) => log::info!("✓ Telemetry sent: solution found for epoch {}", self.state.epoch),
-                                Err(e) => log::error!("✗ Failed to send telemetry: {}", e),
-                            }
+                                ..TelemetryUpdate::default()
+                            });
                         }
-                        
-                        self.transition_to_committing(solution).await?;
-                    } else {
-                        // Continue mining - send periodic telemetry every 30 seconds
-                        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
-                        let last = self.last_telemetry_time.load(std::sync::atomic::Ordering::Relaxed);
-                        
-                        if now - last > 30 {
-                            self.last_telemetry_time.store(now, std::sync::atomic::Ordering::Relaxed);
-                            
-                            if let Some(ref reporter) = self.telemetry_reporter {
-                                let hashrate = self.engine.get_hashrate().await;
-                                let hashrate_mhs = hashrate / 1_000_000.0; // Convert H/s to MH/s
-                                let nonce_range = self.get_nonce_range_with_retry().await.ok();
-                                let stats = reporter.get_stats().await;
-                                match reporter.send_telemetry(
-                                    self.state.epoch,
-                                    "FindingSolution",
-                                    Some(hashrate_mhs),
-                                    Some(stats.epochs_won as u32), // Total solutions found
-                                    Some(stats.reveals_successful as u32), // Total successful reveals
-                                    None, // network_info
-                                    None, // power_balance
-                                    None, // gas_balance
-                                    None, // last_error
-                                    nonce_range,
-                                ).await {
-                                    Ok(_) => log::debug!("✓ Periodic telemetry sent"),
-                                    Err(e) => log::error!("✗ Failed to send periodic telemetry: {}", e),
-                                }
-                            }
-                        }
-                        
-                        sleep(Duration::from_secs(1)).await;
                     }
+                    
+                    if self.last_search_checkpoint.elapsed() >= SEARCH_CHECKPOINT_INTERVAL {
+                        self.checkpoint_search().await;
+                    }
+                    
+                    self.refresh_target().await?;
+                    
+                    sleep(Duration::from_secs(1)).await;
                 }
-                
-                MiningPhase::Committing(data) => {
-                    // Check if we're in the right phase to commit
-                    let client = self.client.read().await;
-                    match query_epoch_info(&*client, &self.config.contract_address).await {
-                        Ok(epoch_info) => {
-                            drop(client); // Release lock before submitting
-                            
-                            // Check phase
-                            match epoch_info.phase {
-                                PhaseInfo::Commit { ends_at } => {
-                                    // Check if we have enough time to commit
-                                    let current_block = self.get_block_height_with_retry().await.unwrap_or(0);
-                                    let blocks_remaining = if ends_at > current_block {
-                                        ends_at - current_block
-                                    } else {
-                                        0
-                                    };
-                                    
-                                    if blocks_remaining >= self.config.submission_buffer_blocks {
-                                        // Good to commit - enough time for transaction processing
-                                        log::info!("In Commit phase for epoch {} with {} blocks remaining (need at least {})", 
-                                                  data.epoch, blocks_remaining, self.config.submission_buffer_blocks);
-                                        match self.submit_commitment(&data).await {
-                                        Ok(_) => {
-                                            log::info!("Successfully committed for epoch {}", data.epoch);
-                                            // Track that we've committed to this epoch
-                                            if !self.state.committed_epochs.contains(&data.epoch) {
-                                                self.state.committed_epochs.push(data.epoch);
-                                                // Keep only recent epochs to avoid unbounded growth
-                                                if self.state.committed_epochs.len() > 20 {
-                                                    self.state.committed_epochs.remove(0);
+            }
+            
+            MiningPhase::Committing(data) if self.state.has_committed(data.epoch) => {
+                // E.g. resumed from a stale state file after a commit for this epoch went through
+                log::warn!("Epoch {} already has a commitment, skipping duplicate commit", data.epoch);
+                self.transition_to_waiting_for_reveal(data).await?;
+            }
+            
+            MiningPhase::Committing(data) if chain_epoch > data.epoch => {
+                // A commit for a past epoch is always rejected - don't pay for it
+                self.abandon_stale_epoch(data.epoch, chain_epoch).await?;
+            }
+            
+            MiningPhase::Committing(data) => {
+                // Check if we're in the right phase to commit
+                match self.epoch_info().await {
+                    Ok(epoch_info) => {
+                        let phase_confirmed = self.confirm_phase(&epoch_info);
+                        
+                        // Check phase
+                        match epoch_info.phase {
+                            PhaseInfo::Commit { .. } if !phase_confirmed => {
+                                sleep(Duration::from_secs(1)).await;
+                            }
+                            PhaseInfo::Commit { ends_at } => {
+                                // Check if we have enough time to commit
+                                let current_block = match self.get_block_height_with_retry().await {
+                                    Ok(height) => height,
+                                    Err(e) => {
+                                        // Without a height there's no telling whether the commit would land in time
+                                        log::warn!("Could not read the block height to commit for epoch {}, retrying: {}", data.epoch, e);
+                                        sleep(Duration::from_secs(1)).await;
+                                        return Ok(());
+                                    }
+                                };
+                                let blocks_remaining = if ends_at > current_block {
+                                    ends_at - current_block
+                                } else {
+                                    0
+                                };
+                                
+                                if self.config.standby && !self.standby_should_commit(data.epoch, blocks_remaining).await? {
+                                    return Ok(());
+                                }
+                                
+                                if blocks_remaining >= self.config.submission_buffer_blocks {
+                                    // Good to commit - enough time for transaction processing
+                                    log::info!("In Commit phase for epoch {} with {} blocks remaining (need at least {})", 
+                                              data.epoch, blocks_remaining, self.config.submission_buffer_blocks);
+                                    match self.submit_commitment(&data).await {
+                                    Ok(_) => {
+                                        log::info!("Successfully committed for epoch {}", data.epoch);
+                                        // Track that we've committed to this epoch
+                                        self.state.record_commit(data.epoch);
+                                        if let Some(found_at) = data.found_at_ms {
+                                            self.record_commit_latency(found_at).await;
+                                        }
+                                        // Track successful commit in telemetry
+                                        if let Some(ref reporter) = self.telemetry_reporter {
+                                            reporter.record_commit_attempt(true, None).await;
+                                        }
+                                        self.transition_to_waiting_for_reveal(data.clone()).await?;
+                                    }
+                                    Err(e) => {
+                                        let error_str = e.to_string();
+                                        log::error!("Failed to commit: {}", error_str);
+                                        
+                                        let classified = ChainError::classify(&e);
+                                        if classified == ChainError::AlreadyCommitted && self.config.standby {
+                                            // The primary's late commit landed first - ours never did
+                                            log::info!("Standby: primary committed for epoch {} after all, standing by", data.epoch);
+                                            self.standby_deferred_epoch = Some(data.epoch);
+                                            self.transition_to_idle().await?;
+                                        } else if classified == ChainError::AlreadyCommitted {
+                                            // Self-healing: If we get "already committed" error, update our local state
+                                            log::warn!("Detected 'already committed' error - fixing local state discrepancy");
+                                            
+                                            // Add epoch to committed list if not already there
+                                            if self.state.record_commit(data.epoch) {
+                                                // Save corrected state immediately
+                                                if let Err(save_err) = self.save_state() {
+                                                    log::error!("Failed to save corrected state: {}", save_err);
+                                                } else {
+                                                    log::info!("Successfully saved corrected state with epoch {} marked as committed", data.epoch);
                                                 }
                                             }
-                                            // Track successful commit in telemetry
+                                            
+                                            // Transition to waiting for reveal since we're already committed
+                                            self.transition_to_waiting_for_reveal(data.clone()).await?;
+                                        } else if classified == ChainError::NonceOutOfRange {
                                             if let Some(ref reporter) = self.telemetry_reporter {
-                                                reporter.record_commit_attempt(true, None).await;
+                                                reporter.record_commit_attempt(false, None).await;
                                             }
-                                            self.transition_to_waiting_for_reveal(data.clone()).await?;
-                                        }
-                                        Err(e) => {
-                                            let error_str = e.to_string();
-                                            log::error!("Failed to commit: {}", error_str);
-                                            
-                                            // Self-healing: If we get "already committed" error, update our local state
-                                            if error_str.contains("Already committed") || error_str.contains("already committed") {
-                                                log::warn!("Detected 'already committed' error - fixing local state discrepancy");
-                                                
-                                                // Add epoch to committed list if not already there
-                                                if !self.state.committed_epochs.contains(&data.epoch) {
-                                                    self.state.committed_epochs.push(data.epoch);
-                                                    // Keep only recent epochs to avoid unbounded growth
-                                                    if self.state.committed_epochs.len() > 20 {
-                                                        self.state.committed_epochs.remove(0);
-                                                    }
-                                                    // Save corrected state immediately
-                                                    if let Err(save_err) = self.save_state() {
-                                                        log::error!("Failed to save corrected state: {}", save_err);
-                                                    } else {
-                                                        log::info!("Successfully saved corrected state with epoch {} marked as committed", data.epoch);
-                                                    }
-                                                }
-                                                
-                                                // Transition to waiting for reveal since we're already committed
-                                                self.transition_to_waiting_for_reveal(data.clone()).await?;
-                                            } else {
-                                                // Track failed commit in telemetry for other errors
-                                                if let Some(ref reporter) = self.telemetry_reporter {
-                                                    reporter.record_commit_attempt(false, None).await;
-                                                }
-                                                // Retry or transition back to idle if epoch passed
-                                                if chain_epoch > self.state.epoch {
-                                                    log::warn!("Epoch passed, returning to idle");
-                                                    self.transition_to_idle().await?;
-                                                }
+                                            self.recover_from_nonce_out_of_range(&data).await?;
+                                        } else {
+                                            // Track failed commit in telemetry for other errors
+                                            if let Some(ref reporter) = self.telemetry_reporter {
+                                                reporter.record_commit_attempt(false, None).await;
+                                            }
+                                            self.state.failure_streak.record_failure(data.epoch);
+                                            // Retry or transition back to idle if epoch passed
+                                            if chain_epoch > self.state.epoch {
+                                                log::warn!("Epoch passed, returning to idle");
+                                                self.transition_to_idle().await?;
                                             }
                                         }
                                     }
-                                    } else {
-                                        log::warn!("Not enough time to commit - only {} blocks remaining (need at least {})", 
-                                                  blocks_remaining, self.config.submission_buffer_blocks);
-                                        log::warn!("Skipping commit for epoch {} to avoid late transaction", data.epoch);
-                                        self.transition_to_idle().await?;
-                                    }
                                 }
-                                PhaseInfo::Settlement { ends_at } => {
-                                    // Check if settlement has ended and we need to advance
-                                    let current_block = self.get_block_height_with_retry().await
-                                        .unwrap_or(epoch_info.start_block + 100); // Fallback estimate
-                                    if current_block >= ends_at {
-                                        // First check if epoch already auto-advanced
-                                        match self.get_current_epoch_with_retry().await {
-                                            Ok(current_epoch_number) => {
-                                                if current_epoch_number > epoch_info.epoch_number {
-                                                    // Epoch already advanced naturally
-                                                    log::info!("Epoch auto-advanced from {} to {}", 
-                                                        epoch_info.epoch_number, current_epoch_number);
-                                                    self.state.epoch = current_epoch_number;
-                                                    // Transition to idle to wait for new epoch info
-                                                    self.transition_to_idle().await?;
-                                                    continue; // Skip to next iteration
-                                                } else if current_block > ends_at + 50 {
-                                                    // Epoch is stuck past grace period, needs manual advancement
-                                                    log::warn!("Epoch {} stuck in settlement (block {} > end {}+50), attempting manual advance", 
-                                                        epoch_info.epoch_number, current_block, ends_at);
-                                                    if let Some(ref tx_manager) = self.tx_manager {
-                                                        match tx_manager.queue_advance_epoch().await {
-                                                            Ok(tx_id) => {
-                                                                log::info!("Queued advance_epoch transaction {}", tx_id);
-                                                                // Wait for advancement to complete
-                                                                sleep(Duration::from_secs(5)).await;
-                                                            }
-                                                            Err(e) => {
-                                                                log::error!("Failed to queue advance_epoch: {:?}", e);
-                                                            }
+                                } else {
+                                    log::warn!("Not enough time to commit - only {} blocks remaining (need at least {})", 
+                                              blocks_remaining, self.config.submission_buffer_blocks);
+                                    log::warn!("Skipping commit for epoch {} to avoid late transaction", data.epoch);
+                                    self.transition_to_idle().await?;
+                                }
+                            }
+                            PhaseInfo::Settlement { ends_at } => {
+                                // Check if settlement has ended and we need to advance
+                                let current_block = match self.get_block_height_with_retry().await {
+                                    Ok(height) => height,
+                                    Err(e) => {
+                                        log::warn!("Could not read the block height to check epoch {}'s settlement: {}", epoch_info.epoch_number, e);
+                                        sleep(Duration::from_secs(1)).await;
+                                        return Ok(());
+                                    }
+                                };
+                                if current_block >= ends_at {
+                                    // First check if epoch already auto-advanced
+                                    match self.get_current_epoch_with_retry().await {
+                                        Ok(current_epoch_number) => {
+                                            if current_epoch_number > epoch_info.epoch_number {
+                                                // Epoch already advanced naturally
+                                                log::info!("Epoch auto-advanced from {} to {}", 
+                                                    epoch_info.epoch_number, current_epoch_number);
+                                                self.state.epoch = current_epoch_number;
+                                                // Transition to idle to wait for new epoch info
+                                                self.transition_to_idle().await?;
+                                                return Ok(()); // Skip to next iteration
+                                            } else if current_block > ends_at + 50 && self.self_advance_enabled().await {
+                                                // Epoch is stuck past grace period, needs manual advancement
+                                                log::warn!("Epoch {} stuck in settlement (block {} > end {}+50), attempting manual advance", 
+                                                    epoch_info.epoch_number, current_block, ends_at);
+                                                if let Some(ref tx_manager) = self.tx_manager {
+                                                    match tx_manager.queue_advance_epoch().await {
+                                                        Ok(tx_id) => {
+                                                            log::info!("Queued advance_epoch transaction {}", tx_id);
+                                                            // Wait for advancement to complete
+                                                            sleep(Duration::from_secs(5)).await;
+                                                        }
+                                                        Err(e) => {
+                                                            log::error!("Failed to queue advance_epoch: {:?}", e);
                                                         }
                                                     }
                                                 }
                                             }
-                                            Err(e) => {
-                                                log::error!("Failed to get current epoch info: {:?}", e);
-                                            }
                                         }
-                                    } else {
-                                        // Still in settlement, not at end block yet
-                                        log::debug!("Settlement phase ongoing, {} blocks until end", ends_at - current_block);
+                                        Err(e) => {
+                                            log::error!("Failed to get current epoch info: {:?}", e);
+                                        }
                                     }
-                                    // Stay in Committing phase to retry
+                                } else {
+                                    // Still in settlement, not at end block yet
+                                    log::debug!("Settlement phase ongoing, {} blocks until end", ends_at - current_block);
                                 }
-                                PhaseInfo::Reveal { .. } => {
-                                    // Too late to commit for this epoch
-                                    log::warn!("Already in Reveal phase, missed commit window");
-                                    if chain_epoch > self.state.epoch {
-                                        self.transition_to_idle().await?;
-                                    } else {
-                                        // Wait for next epoch
-                                        sleep(Duration::from_secs(5)).await;
-                                    }
+                                // Stay in Committing phase to retry
+                            }
+                            PhaseInfo::Reveal { .. } => {
+                                // Too late to commit for this epoch
+                                log::warn!("Already in Reveal phase, missed commit window");
+                                if chain_epoch > self.state.epoch {
+                                    self.transition_to_idle().await?;
+                                } else {
+                                    // Wait for next epoch
+                                    sleep(Duration::from_secs(5)).await;
                                 }
                             }
+                            PhaseInfo::Unknown(name) => {
+                                // Stay in Committing and retry once the phase is one we understand
+                                log::warn!("Unrecognized contract phase '{}' while committing for epoch {}, retrying", 
+                                          name, data.epoch);
+                                sleep(Duration::from_secs(self.config.epoch_poll_interval)).await;
+                            }
                         }
-                        Err(e) => {
-                            log::error!("Failed to query epoch info: {}", e);
-                            sleep(Duration::from_secs(5)).await;
-                        }
+                    }
+                    Err(e) => {
+                        log::error!("Failed to query epoch info: {}", e);
+                        sleep(Duration::from_secs(5)).await;
                     }
                 }
-                
-                MiningPhase::WaitingForRevealWindow(data) => {
-                    // Wait for reveal phase in the SAME epoch we committed (not next epoch!)
-                    // Commits and reveals happen in the same epoch, just different phases
-                    if chain_epoch == data.epoch {
-                        // Still in the same epoch, check if we're in reveal phase
-                        let client = self.client.read().await;
-                        match query_epoch_info(&*client, &self.config.contract_address).await {
-                            Ok(epoch_info) => {
-                                drop(client);
-                                match epoch_info.phase {
-                                    PhaseInfo::Reveal { .. } => {
-                                        log::info!("Reveal phase active for epoch {}, revealing commitment from epoch {}", 
-                                                  chain_epoch, data.epoch);
-                                        // Extract commitment data and transition to revealing
-                                        self.transition_to_revealing(data).await?
-                                    }
-                                    PhaseInfo::Commit { .. } => {
-                                        // Still in commit phase, check more frequently
-                                        log::debug!("Waiting for reveal phase (currently in commit phase of epoch {})", chain_epoch);
-                                        sleep(Duration::from_secs(2)).await;  // Check more frequently for phase changes
-                                    }
-                                    PhaseInfo::Settlement { .. } => {
-                                        // In settlement, wait for next epoch
-                                        log::debug!("In settlement phase, waiting for next epoch");
-                                        sleep(Duration::from_secs(5)).await;
-                                    }
+            }
+            
+            MiningPhase::WaitingForRevealWindow(data) => {
+                // Wait for reveal phase in the SAME epoch we committed (not next epoch!)
+                // Commits and reveals happen in the same epoch, just different phases
+                if chain_epoch == data.epoch {
+                    // Still in the same epoch, check if we're in reveal phase
+                    match self.epoch_info().await {
+                        Ok(epoch_info) => {
+                            let phase_confirmed = self.confirm_phase(&epoch_info);
+                            match epoch_info.phase {
+                                PhaseInfo::Reveal { .. } if !phase_confirmed => {
+                                    sleep(Duration::from_secs(self.config.reveal_wait_interval)).await;
+                                }
+                                PhaseInfo::Reveal { .. } => {
+                                    log::info!("Reveal phase active for epoch {}, revealing commitment from epoch {}", 
+                                              chain_epoch, data.epoch);
+                                    // Extract commitment data and transition to revealing
+                                    self.transition_to_revealing(data).await?
+                                }
+                                PhaseInfo::Commit { .. } => {
+                                    // Still in commit phase, check more frequently
+                                    log::debug!("Waiting for reveal phase (currently in commit phase of epoch {})", chain_epoch);
+                                    if self.config.prepare_reveal && data.prepared_reveal.is_none() {
+                                        self.prepare_reveal(data).await?;
+                                    }
+                                    sleep(Duration::from_secs(2)).await;  // Check more frequently for phase changes
+                                }
+                                PhaseInfo::Settlement { .. } => {
+                                    // In settlement, wait for next epoch
+                                    log::debug!("In settlement phase, waiting for next epoch");
+                                    self.wait_for_chain_event(Duration::from_secs(5)).await;
+                                }
+                                PhaseInfo::Unknown(name) => {
+                                    log::warn!("Unrecognized contract phase '{}' while waiting to reveal epoch {}", 
+                                              name, data.epoch);
+                                    self.wait_for_chain_event(Duration::from_secs(5)).await;
                                 }
-                            }
-                            Err(e) => {
-                                log::error!("Failed to query epoch info: {}", e);
-                                sleep(Duration::from_secs(5)).await;
                             }
                         }
-                    } else if chain_epoch > data.epoch {
-                        // We missed the reveal window - the chain has moved past our committed epoch
-                        log::warn!("Missed reveal window for epoch {} (current epoch: {}). Starting fresh with current epoch.", 
-                                  data.epoch, chain_epoch);
-                        
-                        // Check if we've already committed to the new epoch
-                        if self.state.committed_epochs.contains(&chain_epoch) {
-                            log::info!("Already committed to epoch {}, transitioning to idle", chain_epoch);
-                            self.transition_to_idle().await?;
-                        } else {
-                            // Transition to finding solution for the current epoch
-                            self.state.epoch = chain_epoch;
-                            self.transition_to_finding_solution(chain_epoch).await?;
+                        Err(e) => {
+                            log::error!("Failed to query epoch info: {}", e);
+                            sleep(Duration::from_secs(5)).await;
                         }
+                    }
+                } else if chain_epoch > data.epoch {
+                    // We missed the reveal window - the chain has moved past our committed epoch
+                    log::warn!("Missed reveal window for epoch {} (current epoch: {}). Starting fresh with current epoch.", 
+                              data.epoch, chain_epoch);
+                    
+                    // Check if we've already committed to the new epoch
+                    if self.state.has_committed(chain_epoch) {
+                        log::info!("Already committed to epoch {}, transitioning to idle", chain_epoch);
+                        self.transition_to_idle().await?;
                     } else {
-                        // chain_epoch < data.epoch shouldn't happen but wait if it does
-                        log::debug!("Waiting for epoch {} (current: {})", data.epoch, chain_epoch);
-                        sleep(Duration::from_secs(self.config.reveal_wait_interval)).await;
+                        // Transition to finding solution for the current epoch
+                        self.state.epoch = chain_epoch;
+                        self.transition_to_finding_solution(chain_epoch).await?;
                     }
+                } else {
+                    // chain_epoch < data.epoch shouldn't happen but wait if it does
+                    log::debug!("Waiting for epoch {} (current: {})", data.epoch, chain_epoch);
+                    sleep(Duration::from_secs(self.config.reveal_wait_interval)).await;
                 }
-                
-                MiningPhase::Revealing(data) => {
-                    // Check if we're in the reveal phase before submitting
-                    let client = self.client.read().await;
-                    match query_epoch_info(&*client, &self.config.contract_address).await {
-                        Ok(epoch_info) => {
-                            drop(client); // Release lock before submitting
-                            
-                            match epoch_info.phase {
-                                PhaseInfo::Reveal { ends_at } => {
-                                    // Good to reveal - log timing info
-                                    let current_block = self.get_block_height_with_retry().await.unwrap_or(0);
-                                    let blocks_remaining = if ends_at > current_block {
-                                        ends_at - current_block
-                                    } else {
-                                        0
-                                    };
-                                    log::info!("In Reveal phase for epoch {} with {} blocks remaining (current: {}, ends: {})", 
-                                              epoch_info.epoch_number, blocks_remaining, current_block, ends_at);
-                                    
-                                    // Only attempt reveal if we have enough time for transaction processing
-                                    // Account for network latency and block inclusion time
-                                    if blocks_remaining >= self.config.submission_buffer_blocks {
-                                        match self.submit_reveal(&data).await {
-                        Ok(_) => {
-                            log::info!("Successfully revealed for epoch {}", data.epoch);
-                            
-                            // Report successful reveal
-                            if let Some(ref reporter) = self.telemetry_reporter {
-                                let hashrate = self.engine.get_hashrate().await;
-                                let hashrate_mhs = hashrate / 1_000_000.0; // Convert H/s to MH/s
-                                let nonce_range = self.get_nonce_range_with_retry().await.ok();
-                                reporter.record_reveal_attempt(true, None).await;
-                                let stats = reporter.get_stats().await;
-                                match reporter.send_telemetry(
-                                    self.state.epoch,
-                                    "Revealing",
-                                    Some(hashrate_mhs),
-                                    Some(stats.epochs_won as u32), // Total solutions found
-                                    Some(stats.reveals_successful as u32), // Total successful reveals (including this one)
-                                    None, // network_info
-                                    None, // power_balance
-                                    None, // gas_balance
-                                    None, // last_error
-                                    nonce_range,
-                                ).await {
-                                    Ok(_) => log::info!("✓ Telemetry sent: reveal submitted for epoch {}", self.state.epoch),
-                                    Err(e) => log::error!("✗ Failed to send reveal telemetry: {}", e),
-                                }
+            }
+            
+            MiningPhase::Revealing(data) => {
+                // Check if we're in the reveal phase before submitting
+                // Phase and block height are independent reads - fetch them concurrently
+                // since every round trip here eats into the reveal window
+                let (epoch_result, block_result) = tokio::join!(
+                    self.epoch_info(),
+                    self.get_block_height_with_retry(),
+                );
+                match epoch_result {
+                    Ok(epoch_info) => {
+                        let phase_confirmed = self.confirm_phase(&epoch_info);
+                        match epoch_info.phase {
+                            PhaseInfo::Reveal { .. } if !phase_confirmed => {
+                                sleep(Duration::from_secs(1)).await;
                             }
-                            
+                            PhaseInfo::Reveal { ends_at } => {
+                                // Good to reveal - log timing info
+                                let current_block = match block_result {
+                                    Ok(height) => height,
+                                    Err(e) => {
+                                        // Without a height there's no telling whether the reveal would land in time
+                                        log::warn!("Could not read the block height to reveal for epoch {}, retrying: {}", data.epoch, e);
+                                        sleep(Duration::from_secs(1)).await;
+                                        return Ok(());
+                                    }
+                                };
+                                let blocks_remaining = if ends_at > current_block {
+                                    ends_at - current_block
+                                } else {
+                                    0
+                                };
+                                log::info!("In Reveal phase for epoch {} with {} blocks remaining (current: {}, ends: {})", 
+                                          epoch_info.epoch_number, blocks_remaining, current_block, ends_at);
+                                
+                                // Only attempt reveal if we have enough time for transaction processing
+                                // Account for network latency and block inclusion time
+                                if blocks_remaining >= self.config.submission_buffer_blocks {
+                                    match self.submit_reveal(&data).await {
+                    Ok(_) => {
+                        log::info!("Successfully revealed for epoch {}", data.epoch);
+                        self.state.failure_streak.record_success();
+                        
+                        // Report successful reveal
+                        if let Some(ref reporter) = self.telemetry_reporter {
+                            let hashrate = self.engine.get_hashrate().await;
+                            let hashrate_mhs = hashrate / 1_000_000.0; // Convert H/s to MH/s
+                            let nonce_range = self.get_nonce_range_with_retry().await.ok();
+                            reporter.record_reveal_attempt(true, None).await;
+                            let stats = reporter.get_stats().await;
+                            self.send_telemetry(TelemetryUpdate {
+                                epoch: self.state.epoch,
+                                phase: "Revealing".to_string(),
+                                hashrate_mhs: Some(hashrate_mhs),
+                                solutions_found: Some(stats.epochs_won as u32), // Total solutions found
+                                reveals_submitted: Some(stats.reveals_successful as u32), // Total successful reveals (including this one)
+                                nonce_range,
+                                ..TelemetryUpdate::default()
+                            });
+                        }
+                        
+                        // Record the reveal in local stats regardless of whether we claim
+                        self.stats_collector.lock().await.solution_submitted().await;
+                        
+                        if !self.config.claim_enabled {
+                            log::info!("Claims disabled - skipping claim for epoch {}", epoch_info.epoch_number);
+                            self.transition_to_idle().await?;
+                        } else {
                             // Claim for the CURRENT epoch (reveal epoch), not commitment epoch
                             // Reveals are stored with the current epoch number in the contract
                             self.transition_to_claiming(epoch_info.epoch_number).await?;
                         }
-                                        Err(e) => {
-                                            log::error!("Failed to reveal: {}", e);
-                                            // Track failed reveal in telemetry
-                                            if let Some(ref reporter) = self.telemetry_reporter {
-                                                reporter.record_reveal_attempt(false, None).await;
-                                            }
-                                            // Check if reveal window passed by querying chain state
-                                            if self.is_past_reveal_window().await? {
+                    }
+                                    Err(e) => {
+                                        log::error!("Failed to reveal: {}", e);
+                                        self.state.failure_streak.record_failure(data.epoch);
+                                        // Track failed reveal in telemetry
+                                        if let Some(ref reporter) = self.telemetry_reporter {
+                                            reporter.record_reveal_attempt(false, None).await;
+                                        }
+                                        // Retrying can never succeed - the nonce is outside our partition
+                                        if ChainError::classify(&e) == ChainError::NonceOutOfRange {
+                                            self.recover_from_nonce_out_of_range(&data).await?;
+                                            return Ok(());
+                                        }
+                                        // Only the contract refusing the reveal uses up the budget - a
+                                        // sequence race or an RPC hiccup says nothing about the next try
+                                        if ChainError::is_contract_rejection(&e) {
+                                            self.reveal_attempts += 1;
+                                        } else {
+                                            log::info!("Reveal for epoch {} failed on a transient error, not counted as an attempt", data.epoch);
+                                        }
+                                        // Check if reveal window passed by querying chain state
+                                        if self.is_past_reveal_window().await? {
+                                            if !self.config.claim_enabled {
+                                                log::warn!("Reveal window passed, claims disabled - going idle");
+                                                self.transition_to_idle().await?;
+                                            } else {
                                                 log::warn!("Reveal window passed, moving to claim");
                                                 // Claim for the CURRENT epoch (reveal epoch), not commitment epoch
                                                 // Reveals are stored with the current epoch number in the contract
                                                 self.transition_to_claiming(epoch_info.epoch_number).await?;
                                             }
+                                        } else if reveal_attempts_exhausted(self.reveal_attempts, self.config.max_reveal_attempts) {
+                                            self.abandon_reveal(&data, &e).await?;
                                         }
                                     }
-                                    } else {
-                                        log::warn!("Not enough time to reveal - only {} blocks remaining (need at least {})", 
-                                                  blocks_remaining, self.config.submission_buffer_blocks);
-                                        self.transition_to_idle().await?;
-                                    }
-                                }
-                                PhaseInfo::Settlement { .. } => {
-                                    // Still in settlement, wait for reveal phase
-                                    log::debug!("Waiting for reveal phase (currently in settlement)");
-                                    sleep(Duration::from_secs(2)).await;
                                 }
-                                PhaseInfo::Commit { .. } => {
-                                    // Somehow we're in commit phase - might have missed reveal window
-                                    log::warn!("In commit phase, might have missed reveal window");
+                                } else {
+                                    log::warn!("Not enough time to reveal - only {} blocks remaining (need at least {})", 
+                                              blocks_remaining, self.config.submission_buffer_blocks);
                                     self.transition_to_idle().await?;
                                 }
                             }
-                        }
-                        Err(e) => {
-                            log::error!("Failed to query epoch info during reveal: {}", e);
-                            sleep(Duration::from_secs(5)).await;
-                        }
-                    }
-                }
-                
-                MiningPhase::Claiming(claim_epoch) => {
-                    // First check if we're trying to claim from an old epoch
-                    let client = self.client.read().await;
-                    match query_epoch_info(&*client, &self.config.contract_address).await {
-                        Ok(current_epoch_info) => {
-                            drop(client); // Release lock
-                            
-                            // If current epoch is much newer than claim epoch, skip claiming and start fresh
-                            if current_epoch_info.epoch_number > claim_epoch + 1 {
-                                log::warn!("Trying to claim from old epoch {}. Current epoch is {}. Skipping to current epoch.", 
-                                          claim_epoch, current_epoch_info.epoch_number);
+                            PhaseInfo::Settlement { .. } => {
+                                // Still in settlement, wait for reveal phase
+                                log::debug!("Waiting for reveal phase (currently in settlement)");
+                                sleep(Duration::from_secs(2)).await;
+                            }
+                            PhaseInfo::Commit { .. } => {
+                                // Somehow we're in commit phase - might have missed reveal window
+                                log::warn!("In commit phase, might have missed reveal window");
                                 self.transition_to_idle().await?;
-                                continue; // Keep the orchestrator running
                             }
-                        }
-                        Err(e) => {
-                            log::error!("Failed to query current epoch info during claiming: {}", e);
-                            drop(client);
-                            sleep(Duration::from_secs(5)).await;
-                            continue; // Keep the orchestrator running
+                            PhaseInfo::Unknown(name) => {
+                                // Keep the commitment and retry - the reveal window may still open
+                                log::warn!("Unrecognized contract phase '{}' while revealing epoch {}, retrying", 
+                                          name, data.epoch);
+                                sleep(Duration::from_secs(5)).await;
+                            }
                         }
                     }
-                    
-                    // CRITICAL: First check if the epoch's settlement phase has ended
-                    // Epochs can only be finalized AFTER settlement phase completes
-                    log::info!("Checking if epoch {} settlement is complete before finalizing", claim_epoch);
-                    
-                    let settlement_complete = self.wait_for_settlement_completion(claim_epoch).await?;
-                    if !settlement_complete {
-                        log::warn!("Settlement for epoch {} not yet complete, will retry later", claim_epoch);
+                    Err(e) => {
+                        log::error!("Failed to query epoch info during reveal: {}", e);
                         sleep(Duration::from_secs(5)).await;
-                        continue; // Keep the orchestrator running
                     }
-                    
-                    // For old epochs, we don't need to advance_epoch - that's only for the current epoch
-                    // Old epochs are already in history, we just need to finalize and claim
-                    if let Some(ref tx_manager) = self.tx_manager {
-                        // Get current epoch to check if we're claiming from an old epoch
-                        let client = self.client.read().await;
-                        let current_epoch_info = query_epoch_info(&*client, &self.config.contract_address).await?;
-                        drop(client);
-                        
-                        // CRITICAL FIX: If claiming the current epoch, we MUST advance first
-                        // The V3.4 contract requires epochs to be in EPOCH_HISTORY before finalization
-                        if claim_epoch == current_epoch_info.epoch_number {
-                            log::info!("Claiming current epoch {}, must advance to next epoch first", claim_epoch);
-                            
-                            // Check if we're past settlement phase
-                            match current_epoch_info.phase {
-                                PhaseInfo::Settlement { ends_at } => {
-                                    let current_block = self.get_block_height_with_retry().await?;
-                                    if current_block > ends_at {
-                                        log::info!("Settlement phase ended for epoch {}, advancing to next epoch", claim_epoch);
-                                        match tx_manager.queue_advance_epoch().await {
-                                            Ok(tx_id) => {
-                                                log::info!("Queued advance_epoch transaction {} to move epoch {} to history", tx_id, claim_epoch);
-                                                // Wait for advance to complete
-                                                log::info!("Waiting 10 seconds for epoch advancement...");
-                                                sleep(Duration::from_secs(10)).await;
-                                                
-                                                // After advancing, the claim_epoch is now a past epoch
-                                                log::info!("Epoch {} should now be in history, proceeding with finalization", claim_epoch);
-                                            }
-                                            Err(e) => {
-                                                log::error!("Failed to advance epoch: {}. Cannot finalize current epoch without advancing first!", e);
-                                                // Cannot proceed with finalization without advance
-                                                self.transition_to_idle().await?;
-                                                continue;
-                                            }
-                                        }
-                                    } else {
-                                        log::warn!("Still in settlement phase for epoch {}, cannot claim yet", claim_epoch);
-                                        sleep(Duration::from_secs(5)).await;
-                                        continue;
-                                    }
+                }
+            }
+            
+            MiningPhase::Claiming(claim_epoch) => {
+                if let Some(wait) = self.claim_retries.wait_remaining(claim_epoch, std::time::Instant::now()) {
+                    // Back to the main loop every poll, so heartbeats and health checks carry on
+                    self.wait_for_chain_event(wait.min(Duration::from_secs(self.config.epoch_poll_interval))).await;
+                    return Ok(());
+                }
+                match self.try_claim(claim_epoch).await? {
+                    ClaimProgress::Done => {
+                        self.claim_retries.clear(claim_epoch);
+                        self.start_claim_cooldown();
+                        self.transition_to_idle().await?;
+                    }
+                    ClaimProgress::NotReady => self.claim_not_ready(claim_epoch).await?,
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Advance (if needed), finalize and claim `claim_epoch`
+    async fn try_claim(&mut self, claim_epoch: u64) -> Result<ClaimProgress> {
+        // First check if we're trying to claim from an old epoch
+        match self.epoch_info().await {
+            Ok(current_epoch_info) => {
+                // If current epoch is much newer than claim epoch, skip claiming and start fresh
+                if current_epoch_info.epoch_number > claim_epoch + 1 {
+                    log::warn!("Trying to claim from old epoch {}. Current epoch is {}. Skipping to current epoch.", 
+                              claim_epoch, current_epoch_info.epoch_number);
+                    return Ok(ClaimProgress::Done);
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to query current epoch info during claiming: {}", e);
+                return Ok(ClaimProgress::NotReady);
+            }
+        }
+        
+        // CRITICAL: First check if the epoch's settlement phase has ended
+        // Epochs can only be finalized AFTER settlement phase completes
+        log::info!("Checking if epoch {} settlement is complete before finalizing", claim_epoch);
+        
+        let settlement_complete = match self.wait_for_settlement_completion(claim_epoch).await {
+            Ok(complete) => complete,
+            Err(e) => {
+                log::warn!("Could not check settlement for epoch {}: {}", claim_epoch, e);
+                return Ok(ClaimProgress::NotReady);
+            }
+        };
+        if !settlement_complete {
+            log::warn!("Settlement for epoch {} not yet complete, will retry later", claim_epoch);
+            return Ok(ClaimProgress::NotReady);
+        }
+        
+        // For old epochs, we don't need to advance_epoch - that's only for the current epoch
+        // Old epochs are already in history, we just need to finalize and claim
+        if let Some(ref tx_manager) = self.tx_manager {
+            // Get current epoch to check if we're claiming from an old epoch
+            let current_epoch_info = match self.epoch_info().await {
+                Ok(info) => info,
+                Err(e) => {
+                    log::warn!("Could not query epoch info to claim epoch {}: {}", claim_epoch, e);
+                    return Ok(ClaimProgress::NotReady);
+                }
+            };
+            
+            // CRITICAL FIX: If claiming the current epoch, we MUST advance first
+            // The V3.4 contract requires epochs to be in EPOCH_HISTORY before finalization
+            if claim_epoch == current_epoch_info.epoch_number {
+                log::info!("Claiming current epoch {}, must advance to next epoch first", claim_epoch);
+                
+                // Check if we're past settlement phase
+                match current_epoch_info.phase {
+                    PhaseInfo::Settlement { ends_at } => {
+                        let current_block = match self.get_block_height_with_retry().await {
+                            Ok(height) => height,
+                            Err(e) => {
+                                log::warn!("Could not read the block height to claim epoch {}: {}", claim_epoch, e);
+                                return Ok(ClaimProgress::NotReady);
+                            }
+                        };
+                        if current_block > ends_at {
+                            if !self.self_advance_enabled().await {
+                                log::info!("Settlement phase ended for epoch {}, waiting for another miner to advance it", claim_epoch);
+                                return Ok(ClaimProgress::NotReady);
+                            }
+                            log::info!("Settlement phase ended for epoch {}, advancing to next epoch", claim_epoch);
+                            match tx_manager.queue_advance_epoch().await {
+                                Ok(tx_id) => {
+                                    log::info!("Queued advance_epoch transaction {} to move epoch {} to history", tx_id, claim_epoch);
+                                    // Wait for advance to complete
+                                    log::info!("Waiting up to 10 seconds for epoch advancement...");
+                                    self.wait_for_chain_event(Duration::from_secs(10)).await;
+                                    
+                                    // After advancing, the claim_epoch is now a past epoch
+                                    log::info!("Epoch {} should now be in history, proceeding with finalization", claim_epoch);
                                 }
-                                _ => {
-                                    log::warn!("Current epoch {} is in {:?} phase, expected Settlement. Skipping claim.", 
-                                             claim_epoch, current_epoch_info.phase);
-                                    self.transition_to_idle().await?;
-                                    continue;
+                                Err(e) => {
+                                    log::error!("Failed to advance epoch: {}. Cannot finalize current epoch without advancing first!", e);
+                                    // Cannot proceed with finalization without advance
+                                    return Ok(ClaimProgress::Done);
                                 }
                             }
                         } else {
-                            log::info!("Claiming from past epoch {}, current is {} - already in history", 
-                                     claim_epoch, current_epoch_info.epoch_number);
-                        }
-                        
-                        // Now try to finalize the epoch (which should be in history)
-                        log::info!("Attempting to finalize epoch {} before claiming", claim_epoch);
-                        match tx_manager.queue_finalize_epoch(claim_epoch).await {
-                            Ok(tx_id) => {
-                                log::info!("Queued finalize_epoch transaction {} for epoch {}", tx_id, claim_epoch);
-                                // Wait for finalization to complete and be confirmed on chain
-                                log::info!("Waiting 10 seconds for finalization to be confirmed on chain...");
-                                sleep(Duration::from_secs(10)).await;
-                            }
-                            Err(e) => {
-                                log::debug!("Finalize epoch failed (may already be finalized): {}", e);
-                                // Continue to claim anyway - epoch might already be finalized
-                            }
+                            log::warn!("Still in settlement phase for epoch {}, cannot claim yet", claim_epoch);
+                            return Ok(ClaimProgress::NotReady);
                         }
                     }
-                    
-                    // Now submit claim transaction for the specific epoch we revealed
-                    match self.submit_claim(claim_epoch).await {
-                        Ok(_) => {
-                            log::info!("Successfully claimed rewards for epoch {}", claim_epoch);
-                            // Record successful claim
-                            if let Some(ref reporter) = self.telemetry_reporter {
-                                reporter.record_claim_attempt(true, Some(1_000_000), None).await; // 1 POWER = 1M micro
-                            }
-                            self.transition_to_idle().await?;
-                        }
-                        Err(e) => {
-                            log::error!("Failed to claim for epoch {}: {}", claim_epoch, e);
-                            // Record failed claim
-                            if let Some(ref reporter) = self.telemetry_reporter {
-                                reporter.record_claim_attempt(false, None, None).await;
-                            }
-                            // Move to idle regardless - can retry claims later
-                            self.transition_to_idle().await?;
-                        }
+                    _ => {
+                        log::warn!("Current epoch {} is in {:?} phase, expected Settlement. Skipping claim.", 
+                                 claim_epoch, current_epoch_info.phase);
+                        return Ok(ClaimProgress::Done);
                     }
                 }
+            } else {
+                log::info!("Claiming from past epoch {}, current is {} - already in history", 
+                         claim_epoch, current_epoch_info.epoch_number);
             }
             
-            // Small delay to prevent tight loops
-            sleep(Duration::from_millis(100)).await;
+            // Now try to finalize the epoch (which should be in history)
+            log::info!("Attempting to finalize epoch {} before claiming", claim_epoch);
+            match tx_manager.queue_finalize_epoch(claim_epoch).await {
+                Ok(tx_id) => {
+                    log::info!("Queued finalize_epoch transaction {} for epoch {}", tx_id, claim_epoch);
+                    // Wait for finalization to complete and be confirmed on chain
+                    log::info!("Waiting {} seconds for finalization to be confirmed on chain...", FINALIZE_SETTLE_TIME.as_secs());
+                    sleep(FINALIZE_SETTLE_TIME).await;
+                }
+                Err(e) => {
+                    log::debug!("Finalize epoch failed (may already be finalized): {}", e);
+                    // Continue to claim anyway - epoch might already be finalized
+                }
+            }
+        }
+        
+        // Now submit claim transaction for the specific epoch we revealed
+        let stake = self.auto_stake_amount().await;
+        let result = self.submit_claim(claim_epoch, stake).await;
+        self.record_claim_result(claim_epoch, result, CLAIM_TX_LOOKUP_ATTEMPTS).await;
+        Ok(ClaimProgress::Done)
+    }
+    
+    /// Log, notify and record the outcome of the claim for `claim_epoch`, looking its
+    /// reward up in the tx events (up to `lookup_attempts` tries)
+    async fn record_claim_result(&mut self, claim_epoch: u64, result: Result<Option<String>>, lookup_attempts: u32) {
+        match result {
+            Ok(tx_hash) => {
+                log::info!("Successfully claimed rewards for epoch {}", claim_epoch);
+                let (reward, fee_wei) = match tx_hash {
+                    Some(ref hash) => self.claimed_reward(hash, lookup_attempts).await,
+                    None => (None, None),
+                };
+                match reward {
+                    Some(micro) => log::info!("Claimed {} POWER for epoch {}", micro as f64 / 1_000_000.0, claim_epoch),
+                    None => log::warn!("Could not determine claimed amount for epoch {} from tx events", claim_epoch),
+                }
+                self.notify(NotifyEvent::RewardClaimed { epoch: claim_epoch, reward });
+                // Record successful claim
+                if let Some(ref reporter) = self.telemetry_reporter {
+                    reporter.record_claim_attempt(true, reward, fee_wei).await;
+                }
+                // Unknown amounts are left out rather than skewing the reward average
+                if let Some(micro) = reward {
+                    self.profitability.record_reward(micro);
+                }
+            }
+            Err(e) => {
+                log::error!("Failed to claim for epoch {}: {}", claim_epoch, e);
+                self.notify(NotifyEvent::Error { epoch: claim_epoch, message: format!("Claim failed: {}", e) });
+                // Record failed claim
+                if let Some(ref reporter) = self.telemetry_reporter {
+                    reporter.record_claim_attempt(false, None, None).await;
+                }
+                // Only the contract turning the claim down means no reward; a timeout, a
+                // node error or a duplicate claim says nothing about the amount
+                let no_reward = ChainError::is_contract_rejection(&e)
+                    && !matches!(ChainError::classify(&e), ChainError::AlreadyClaimed | ChainError::WrongPhase);
+                if no_reward {
+                    self.profitability.record_reward(0);
+                }
+            }
+        }
+    }
+    
+    /// Resumed with an unrevealed (and verified) commitment while the reveal window is
+    /// open: reveal now instead of waiting for the first poll to notice the phase
+    async fn reveal_on_resume(&mut self) -> Result<()> {
+        let data = match &self.state.phase {
+            MiningPhase::WaitingForRevealWindow(data) | MiningPhase::Revealing(data) => data.clone(),
+            _ => return Ok(()),
+        };
+        let info = match self.epoch_info().await {
+            Ok(info) => info,
+            Err(e) => {
+                log::warn!("Could not check the reveal window for resumed epoch {}: {}", data.epoch, e);
+                return Ok(());
+            }
+        };
+        if !reveal_due_on_resume(data.epoch, &info) {
+            return Ok(());
+        }
+        // This reading counts towards the phase confirmations the reveal waits for
+        self.confirm_phase(&info);
+        log::info!("Resumed inside the reveal window for epoch {} - revealing immediately", data.epoch);
+        self.transition_to_revealing(data).await?;
+        self.step(info.epoch_number).await
+    }
+    
+    /// Count a solution found in no-submit mode and wait for the next epoch
+    async fn observe_solution(&mut self, epoch: u64) -> Result<()> {
+        let stats = self.stats_collector.lock().await.get_stats().await;
+        let hashrate = self.engine.get_hashrate().await;
+        log::info!(
+            "No-submit: solution for epoch {} not submitted ({} found this session, {:.2} MH/s, difficulty {}, {:.0} hashes per solution)",
+            epoch, stats.solutions_found, hashrate / 1_000_000.0, stats.current_difficulty,
+            stats.hashes_per_solution().unwrap_or_default()
+        );
+        // Marked like a commit so the epoch isn't mined again
+        self.state.record_commit(epoch);
+        self.engine.stop_mining().await?;
+        self.transition_to_idle().await
+    }
+    
+    /// Move the oldest deferred claim on by one step once the chain has moved its epoch
+    /// to history: finalize, then claim, then record the result. Never waits on a
+    /// transaction - a step that isn't due yet returns straight away
+    async fn process_pending_claim(&mut self, chain_epoch: u64) -> Result<()> {
+        let epoch = match self.state.pending_claims.first() {
+            Some(&epoch) if epoch < chain_epoch => epoch,
+            _ => return Ok(()),
+        };
+        let now = std::time::Instant::now();
+        let stage = match self.deferred_claim.take() {
+            Some((claim_epoch, stage)) if claim_epoch == epoch => Some(stage),
+            _ => None,
+        };
+        match stage {
+            None => {
+                if self.claim_retries.wait_remaining(epoch, now).is_some() {
+                    return Ok(());
+                }
+                log::info!("Claiming deferred epoch {} while mining epoch {}", epoch, chain_epoch);
+                self.start_deferred_claim(epoch).await
+            }
+            Some(DeferredClaim::Finalizing { claim_at }) if now < claim_at => {
+                self.deferred_claim = Some((epoch, DeferredClaim::Finalizing { claim_at }));
+                Ok(())
+            }
+            Some(DeferredClaim::Finalizing { .. }) => self.queue_deferred_claim(epoch).await,
+            Some(DeferredClaim::Claiming { tx_id, queued_at }) => {
+                let tx_manager = match self.tx_manager {
+                    Some(ref tx_manager) => tx_manager,
+                    None => return self.finish_deferred_claim(epoch, Ok(None)).await,
+                };
+                let timeout = Duration::from_secs(self.config.claim_confirmation_timeout_secs);
+                let result = if queued_at.elapsed() > timeout {
+                    // A slow node, not a verdict - try again after the backoff
+                    let e = confirmation_timeout(tx_manager, tx_id, "Claim", timeout).await;
+                    log::warn!("Deferred claim for epoch {}: {}", epoch, e);
+                    return self.claim_not_ready(epoch).await;
+                } else {
+                    match confirmation_outcome("Claim", tx_manager.get_status(tx_id).await) {
+                        Some(result) => result,
+                        None => {
+                            self.deferred_claim = Some((epoch, DeferredClaim::Claiming { tx_id, queued_at }));
+                            return Ok(());
+                        }
+                    }
+                };
+                self.finish_deferred_claim(epoch, result).await
+            }
+        }
+    }
+    
+    /// First step of a deferred claim: once the epoch is settled, queue its finalization
+    /// (or the claim itself, if there is nothing to finalize). Unlike `try_claim` there is
+    /// no age cutoff: deferred claims settle one at a time, so with `max_tracked_epochs`
+    /// above 2 the oldest is routinely more than one epoch behind
+    async fn start_deferred_claim(&mut self, epoch: u64) -> Result<()> {
+        match self.wait_for_settlement_completion(epoch).await {
+            Ok(true) => {}
+            Ok(false) => return self.claim_not_ready(epoch).await,
+            Err(e) => {
+                log::warn!("Could not check settlement for deferred epoch {}: {}", epoch, e);
+                return self.claim_not_ready(epoch).await;
+            }
+        }
+        let queued = match self.tx_manager {
+            Some(ref tx_manager) => tx_manager.queue_finalize_epoch(epoch).await,
+            None => return self.queue_deferred_claim(epoch).await,
+        };
+        match queued {
+            Ok(tx_id) => {
+                log::info!("Queued finalize_epoch transaction {} for deferred epoch {}", tx_id, epoch);
+                let claim_at = std::time::Instant::now() + FINALIZE_SETTLE_TIME;
+                self.deferred_claim = Some((epoch, DeferredClaim::Finalizing { claim_at }));
+                Ok(())
+            }
+            Err(e) => {
+                log::debug!("Finalize epoch failed (may already be finalized): {}", e);
+                self.queue_deferred_claim(epoch).await
+            }
+        }
+    }
+    
+    /// Queue the claim for a deferred epoch; its confirmation is checked on later passes
+    async fn queue_deferred_claim(&mut self, epoch: u64) -> Result<()> {
+        let stake = self.auto_stake_amount().await;
+        let queued = match self.tx_manager {
+            Some(ref tx_manager) => self.queue_claim_tx(tx_manager, epoch, stake).await,
+            None => {
+                log::warn!("Transaction manager not initialized, using placeholder");
+                return self.finish_deferred_claim(epoch, Ok(None)).await;
+            }
+        };
+        match queued {
+            Ok(tx_id) => {
+                let queued_at = std::time::Instant::now();
+                self.deferred_claim = Some((epoch, DeferredClaim::Claiming { tx_id, queued_at }));
+                Ok(())
+            }
+            Err(e) => {
+                // Queue full and the like - back off and retry like an unready claim
+                log::warn!("Could not queue the claim for deferred epoch {}: {}", epoch, e);
+                self.claim_not_ready(epoch).await
+            }
+        }
+    }
+    
+    /// Record how a deferred claim ended and stop tracking it
+    async fn finish_deferred_claim(&mut self, epoch: u64, result: Result<Option<String>>) -> Result<()> {
+        // A single lookup: waiting for the tx to be indexed would hold up the state machine
+        self.record_claim_result(epoch, result, 1).await;
+        self.drop_deferred_claim(epoch)
+    }
+    
+    /// Stop tracking a deferred claim
+    fn drop_deferred_claim(&mut self, epoch: u64) -> Result<()> {
+        self.claim_retries.clear(epoch);
+        self.state.pending_claims.retain(|&e| e != epoch);
+        self.save_state()
+    }
+    
+    /// `epoch` couldn't be claimed yet: back off before the next attempt, or give the
+    /// claim up once `max_claim_attempts` is used up so mining isn't held up by it
+    async fn claim_not_ready(&mut self, epoch: u64) -> Result<()> {
+        let retry = self.claim_retries.record_not_ready(epoch, self.config.max_claim_attempts, std::time::Instant::now());
+        let attempts = match retry {
+            ClaimRetry::RetryIn(delay) => {
+                log::info!("Epoch {} not claimable yet, retrying in {}s", epoch, delay.as_secs());
+                return Ok(());
+            }
+            ClaimRetry::GiveUp { attempts } => attempts,
+        };
+        
+        let reason = format!("Claim for epoch {} abandoned after {} attempts: epoch never became claimable", epoch, attempts);
+        log::error!("{} - resuming mining", reason);
+        self.notify(NotifyEvent::Error { epoch, message: reason });
+        if let Some(ref reporter) = self.telemetry_reporter {
+            reporter.record_claim_attempt(false, None, None).await;
         }
+        self.state.abandon_claim(epoch);
+        self.save_state()
     }
     
     // State transition methods
     
     async fn transition_to_finding_solution(&mut self, epoch: u64) -> Result<()> {
+        self.apply_startup_jitter().await;
+        
         self.state.epoch = epoch;
         self.state.phase = MiningPhase::FindingSolution;
         self.save_state()?;
         
+        self.start_engine(epoch).await
+    }
+    
+    /// Start the workers on `epoch`'s challenge, continuing from a search checkpoint
+    /// for the same epoch if there is one
+    async fn start_engine(&mut self, epoch: u64) -> Result<()> {
         // Get epoch info including target_hash from contract
-        let client = self.client.read().await;
-        let epoch_info = query_epoch_info(&*client, &self.config.contract_address).await?;
-        drop(client);
+        let epoch_info = self.epoch_info().await?;
         
         // Extract target_hash from epoch info and convert to array
-        let target_hash_vec = epoch_info.target_hash;
-        if target_hash_vec.len() != 32 {
-            return Err(anyhow::anyhow!("Invalid target_hash length: {}", target_hash_vec.len()));
-        }
-        let mut target_hash = [0u8; 32];
-        target_hash.copy_from_slice(&target_hash_vec);
+        let target_hash = target_hash_array(epoch, &epoch_info.target_hash)?;
         log::info!("Got target_hash from contract for epoch {}: {:?}", epoch, target_hash);
         
         // Start mining workers
@@ -902,14 +2013,114 @@ impl MiningOrchestrator {
         
         // Update statistics
         self.stats_collector.lock().await.start_mining(epoch, difficulty, nonce_range.0, nonce_range.1).await;
+        self.profitability.record_epoch(epoch, difficulty);
+        self.report_difficulty_change(epoch, difficulty);
+        
+        // Only resume a checkpoint taken for this epoch and partition
+        let resume_ranges = self.state.search_checkpoint.take()
+            .filter(|checkpoint| checkpoint.applies_to(epoch, nonce_range))
+            .map(|checkpoint| checkpoint.ranges);
+        if let Some(ref ranges) = resume_ranges {
+            let remaining: u64 = ranges.iter().map(|(start, end)| end - start).sum();
+            log::info!(
+                "Continuing epoch {} search from checkpoint: {} of {} nonces left",
+                epoch, remaining, nonce_range.1.saturating_sub(nonce_range.0)
+            );
+        }
         
         // Pass the actual target_hash to the mining engine
+        self.apply_thread_scaling(difficulty).await;
+        self.engine.set_overflow_range(self.overflow_range(nonce_range)).await;
+        self.engine.set_resume_ranges(resume_ranges).await;
         self.engine.start_mining_with_target(epoch, target_hash, difficulty, nonce_range).await?;
+        self.last_search_checkpoint = std::time::Instant::now();
+        self.mining_target = Some((target_hash, difficulty));
+        self.last_target_refresh = std::time::Instant::now();
+        
+        Ok(())
+    }
+    
+    /// Re-fetch the target while mining and restart the workers if a mid-epoch retarget
+    /// changed the target hash or difficulty. No-op unless `target_refresh_secs` is set
+    async fn refresh_target(&mut self) -> Result<()> {
+        let interval = match self.config.target_refresh_secs {
+            Some(secs) => Duration::from_secs(secs),
+            None => return Ok(()),
+        };
+        if self.last_target_refresh.elapsed() < interval {
+            return Ok(());
+        }
+        self.last_target_refresh = std::time::Instant::now();
+        
+        let (target_hash, difficulty) = match self.mining_target {
+            Some(target) => target,
+            None => return Ok(()),
+        };
+        let info = match self.epoch_info().await {
+            Ok(info) => info,
+            Err(e) => {
+                log::debug!("Target refresh for epoch {} failed: {}", self.state.epoch, e);
+                return Ok(());
+            }
+        };
+        // A new epoch is handled by abandon_stale_epoch
+        if info.epoch_number != self.state.epoch {
+            return Ok(());
+        }
+        if info.target_hash.as_slice() == target_hash.as_slice() && info.difficulty == difficulty {
+            return Ok(());
+        }
         
+        log::warn!(
+            "Mid-epoch retarget detected for epoch {}: difficulty {} -> {}, target hash {} - restarting workers",
+            self.state.epoch, difficulty, info.difficulty,
+            if info.target_hash.as_slice() == target_hash.as_slice() { "unchanged" } else { "changed" }
+        );
+        self.engine.stop_mining().await?;
+        // Progress against the old target says nothing about the new one
+        self.state.search_checkpoint = None;
+        self.start_engine(self.state.epoch).await?;
+        log::info!("Applied retarget for epoch {}: now mining at difficulty {}", self.state.epoch, info.difficulty);
         Ok(())
     }
     
+    /// The epoch advanced before we committed: drop the stale solution (or unfinished
+    /// search) and, if configured, start mining the new epoch right away
+    async fn abandon_stale_epoch(&mut self, stale_epoch: u64, chain_epoch: u64) -> Result<()> {
+        log::info!("Epoch advanced from {} to {} before committing, discarding stale work", stale_epoch, chain_epoch);
+        self.engine.stop_mining().await?;
+        self.state.search_checkpoint = None;
+        
+        if !self.config.restart_on_epoch_advance || self.state.has_committed(chain_epoch) {
+            return self.transition_to_idle().await;
+        }
+        
+        let epoch_info = self.epoch_info().await;
+        match epoch_info {
+            Ok(info) if info.epoch_number == chain_epoch && matches!(info.phase, PhaseInfo::Commit { .. }) => {
+                log::info!("Restarting mining for epoch {}", chain_epoch);
+                self.transition_to_finding_solution(chain_epoch).await
+            }
+            // Not mineable yet (or the query failed) - Idle starts mining once it is
+            _ => self.transition_to_idle().await,
+        }
+    }
+    
+    /// Save the workers' progress so a restart within this epoch doesn't re-hash searched nonces
+    async fn checkpoint_search(&mut self) {
+        self.last_search_checkpoint = std::time::Instant::now();
+        let ranges = self.engine.search_progress().await;
+        if ranges.is_empty() {
+            return;
+        }
+        self.state.search_checkpoint = Some(SearchCheckpoint { epoch: self.state.epoch, ranges });
+        if let Err(e) = self.save_state() {
+            log::warn!("Failed to checkpoint nonce search: {}", e);
+        }
+    }
+    
     async fn transition_to_committing(&mut self, solution: CommitmentData) -> Result<()> {
+        self.state.search_checkpoint = None;
         self.state.phase = MiningPhase::Committing(solution);
         self.save_state()?;
         Ok(())
@@ -923,12 +2134,23 @@ impl MiningOrchestrator {
     }
     
     async fn transition_to_revealing(&mut self, data: CommitmentData) -> Result<()> {
+        self.reveal_attempts = 0;
         self.state.phase = MiningPhase::Revealing(data);
         self.save_state()?;
         Ok(())
     }
     
     async fn transition_to_claiming(&mut self, epoch: u64) -> Result<()> {
+        // Pipelining: leave the claim for later and move straight on to the next epoch
+        if self.config.max_tracked_epochs > 1 {
+            if self.state.defer_claim(epoch, self.config.max_tracked_epochs - 1) {
+                log::info!("Deferring claim for epoch {} ({} pending), moving on to the next epoch", 
+                          epoch, self.state.pending_claims.len());
+                return self.transition_to_idle().await;
+            }
+            log::warn!("Already tracking {} epochs, claiming epoch {} before mining on", 
+                      self.config.max_tracked_epochs, epoch);
+        }
         self.state.phase = MiningPhase::Claiming(epoch);
         self.save_state()?;
         Ok(())
@@ -947,39 +2169,99 @@ impl MiningOrchestrator {
             .duration_since(std::time::UNIX_EPOCH)?
             .as_secs();
         
-        let serialized = serde_json::to_string_pretty(&self.state)?;
-        
-        // Atomic write: write to temp file first, then rename
-        let temp_path = format!("{}.tmp", self.config.state_file.display());
-        fs::write(&temp_path, &serialized)?;
-        
-        // Rename is atomic on most filesystems
-        fs::rename(&temp_path, &self.config.state_file)?;
+        self.state_file.save(&self.state)?;
         
         log::debug!("State saved atomically to {:?}", self.config.state_file);
         Ok(())
     }
     
-    fn load_state(path: &PathBuf) -> Result<MiningState> {
-        let serialized = fs::read_to_string(path)?;
-        let state = serde_json::from_str(&serialized)?;
-        log::info!("Loaded saved state from {:?}", path);
-        Ok(state)
+    /// Path of the last solution's proof (for `gmine export-solution`), next to the state file
+    pub fn solution_proof_file(state_file: &PathBuf) -> PathBuf {
+        PathBuf::from(format!("{}.solution.json", state_file.display()))
     }
     
-    // Chain interaction methods with retry logic (as recommended by Gemini Pro)
+    /// Persist an offline-verifiable proof of the solution just found (best effort)
+    fn save_solution_proof(&self, proof: &SolutionProof) {
+        let path = Self::solution_proof_file(&self.config.state_file);
+        if let Err(e) = fs::write(&path, proof.to_json()) {
+            log::warn!("Failed to write solution proof to {:?}: {}", path, e);
+        }
+    }
     
-    async fn connect_with_retry(&mut self) -> Result<()> {
-        let mut retries = 0;
-        let mut delay = self.config.retry_delay_ms;
-        
-        loop {
-            let mut client = self.client.write().await;
-            match client.connect().await {
-                Ok(_) => {
-                    log::info!("Connected to chain");
-                    return Ok(());
-                }
+    /// POST a found solution to every configured pool (failures are logged, not fatal)
+    async fn submit_to_pools(&self, proof: &SolutionProof) {
+        for pool in &self.pools {
+            match pool.submit(proof, &self.miner_name).await {
+                Ok(()) => log::info!("Submitted epoch {} solution to pool {}", proof.epoch, pool.url()),
+                Err(e) => log::error!("Failed to submit solution to pool: {}", e),
+            }
+        }
+    }
+    
+    /// Path of the profitability snapshot written next to the state file
+    pub fn profitability_file(state_file: &PathBuf) -> PathBuf {
+        PathBuf::from(format!("{}.profitability.json", state_file.display()))
+    }
+    
+    /// Track how long a solution took from being found to its commit landing
+    async fn record_commit_latency(&self, found_at_ms: u64) {
+        let latency = Duration::from_millis(unix_millis().saturating_sub(found_at_ms));
+        let mut stats_collector = self.stats_collector.lock().await;
+        stats_collector.record_commit_latency(latency).await;
+        if let Some(summary) = stats_collector.get_stats().await.commit_latency {
+            log::info!(
+                "⏱ Find-to-commit: {:.1}s (avg {:.1}s, p95 {:.1}s over {} commits)",
+                latency.as_secs_f64(), summary.avg_ms as f64 / 1000.0, summary.p95_ms as f64 / 1000.0, summary.count
+            );
+        }
+    }
+    
+    /// Fees of one commit + reveal at the client's current gas price and fee denom
+    async fn attempt_gas(&self) -> AttemptGas {
+        let client = self.client.read().await;
+        AttemptGas {
+            gas_limit: transaction_manager::COMMIT_GAS_LIMIT + transaction_manager::REVEAL_GAS_LIMIT,
+            gas_price: client.gas_price(),
+            fee_denom: client.fee_denom().to_string(),
+        }
+    }
+    
+    /// Log the current profitability estimate and persist it for `gmine status`
+    async fn log_profitability(&self) {
+        let estimate = self.profitability.estimate(&self.attempt_gas().await);
+        log::info!("💰 Profitability: {}", estimate.summary());
+        if let Some(per_hour) = estimate.reward_per_hour_power {
+            log::info!("   Estimated reward: {:.4} POWER/hour", per_hour);
+        }
+        if let Some(break_even) = estimate.break_even_per_power {
+            log::info!("   Break-even: attempts pay off if 1 POWER is worth more than {:.6} {}", break_even, estimate.fee_unit);
+        }
+        
+        let path = Self::profitability_file(&self.config.state_file);
+        match serde_json::to_string_pretty(&estimate) {
+            Ok(serialized) => {
+                if let Err(e) = fs::write(&path, serialized) {
+                    log::debug!("Could not write profitability snapshot: {}", e);
+                }
+            }
+            Err(e) => log::debug!("Could not serialize profitability snapshot: {}", e),
+        }
+    }
+    
+    // Chain interaction methods with retry logic (as recommended by Gemini Pro)
+    
+    async fn connect_with_retry(&mut self) -> Result<()> {
+        let mut retries = 0;
+        let mut delay = self.config.retry_delay_ms;
+        
+        loop {
+            let mut client = self.client.write().await;
+            match client.connect().await {
+                Ok(_) => {
+                    log::info!("Connected to chain");
+                    self.health.set_connected(true);
+                    return Ok(());
+                }
                 Err(e) if retries < self.config.max_retries => {
                     retries += 1;
                     log::warn!("Connection failed (attempt {}/{}): {}", retries, self.config.max_retries, e);
@@ -994,13 +2276,21 @@ impl MiningOrchestrator {
     async fn get_current_epoch_with_retry(&self) -> Result<u64> {
         let mut retries = 0;
         let mut delay = self.config.retry_delay_ms;
+        let mut not_ready_delay = self.config.retry_delay_ms;
         
         loop {
-            let client = self.client.read().await;
-            match query_epoch_info(&*client, &self.config.contract_address).await {
+            let result = self.epoch_info().await;
+            match result {
                 Ok(info) => {
+                    self.health.record_chain_query();
                     return Ok(info.epoch_number);
                 }
+                // Migration/resync in progress - wait it out rather than failing the loop
+                Err(e) if ChainError::classify(&e) == ChainError::ContractNotReady => {
+                    log::info!("{} - retrying in {}ms", e, not_ready_delay);
+                    sleep(Duration::from_millis(not_ready_delay)).await;
+                    not_ready_delay = (not_ready_delay * 2).min(CONTRACT_NOT_READY_MAX_BACKOFF_MS);
+                }
                 Err(e) if retries < self.config.max_retries => {
                     retries += 1;
                     log::warn!("Failed to query epoch (attempt {}/{}): {}", 
@@ -1036,21 +2326,42 @@ impl MiningOrchestrator {
                     delay *= 2; // Exponential backoff
                 }
                 Err(e) => {
+                    // No guessed fallback - callers decide how to proceed without a height
                     log::error!("Failed to get block height after {} retries: {}", self.config.max_retries, e);
-                    // Fallback to a reasonable estimate
-                    return Ok(87634000);
+                    return Err(e);
                 }
             }
         }
     }
     
+    /// Current epoch info, reusing a reading younger than `EPOCH_INFO_TTL`
+    async fn epoch_info(&self) -> Result<EpochInfoResponse> {
+        if let Some(info) = self.epoch_cache.get(&self.config.contract_address) {
+            return Ok(info);
+        }
+        let client = self.client.read().await;
+        let info = query_epoch_info(&*client, &self.config.contract_address).await?;
+        drop(client);
+        self.epoch_cache.insert(&self.config.contract_address, info.clone());
+        Ok(info)
+    }
+    
+    /// Feed a phase reading to the debouncer; an unconfirmed reading means the phase
+    /// is changing, so the next poll must go to the chain rather than the cache
+    fn confirm_phase(&mut self, info: &EpochInfoResponse) -> bool {
+        let confirmed = self.phase_debounce.observe(info.epoch_number, &info.phase);
+        if !confirmed {
+            self.epoch_cache.invalidate();
+        }
+        confirmed
+    }
+    
     async fn get_difficulty_with_retry(&self) -> Result<u8> {
         let mut retries = 0;
         let mut delay = self.config.retry_delay_ms;
         
         loop {
-            let client = self.client.read().await;
-            match query_epoch_info(&*client, &self.config.contract_address).await {
+            match self.epoch_info().await {
                 Ok(info) => {
                     return Ok(info.difficulty);
                 }
@@ -1066,17 +2377,36 @@ impl MiningOrchestrator {
         }
     }
     
+    /// Log a difficulty change since the last epoch mined and send it to the webhook
+    /// The webhook call runs in the background so mining doesn't wait on it
+    fn report_difficulty_change(&mut self, epoch: u64, difficulty: u8) {
+        let Some(change) = self.difficulty_watch.observe(epoch, difficulty) else {
+            return;
+        };
+        log::warn!(
+            "Difficulty changed from {} (epoch {}) to {} (epoch {}), {:+} bits",
+            change.old_difficulty, change.previous_epoch, change.new_difficulty, change.epoch, change.delta()
+        );
+        if let Some(ref webhook) = self.difficulty_webhook {
+            let webhook = webhook.clone();
+            let miner = self.miner_name.clone();
+            tokio::spawn(async move {
+                if let Err(e) = webhook.notify(&change, &miner).await {
+                    log::warn!("{}", e);
+                }
+            });
+        }
+    }
+    
     /// Wait for an epoch's settlement phase to complete
     /// Returns true if settlement is complete, false if still ongoing
     async fn wait_for_settlement_completion(&self, target_epoch: u64) -> Result<bool> {
-        use crate::chain::queries::{query_epoch_info, PhaseInfo};
-        
-        let current_block = self.get_block_height_with_retry().await?;
-        
-        // Query current epoch info to see what epoch we're in
-        let mut client = self.client.write().await;
-        let epoch_info = query_epoch_info(&mut *client, &self.config.contract_address).await?;
-        drop(client);
+        // Block height and current epoch info are independent - query them concurrently
+        let (current_block, epoch_info) = tokio::join!(
+            self.get_block_height_with_retry(),
+            self.epoch_info(),
+        );
+        let (current_block, epoch_info) = (current_block?, epoch_info?);
         
         log::info!("Current epoch: {}, target epoch: {}, current block: {}", 
                   epoch_info.epoch_number, target_epoch, current_block);
@@ -1103,7 +2433,7 @@ impl MiningOrchestrator {
                         return Ok(false);
                     }
                 }
-                PhaseInfo::Commit { .. } | PhaseInfo::Reveal { .. } => {
+                PhaseInfo::Commit { .. } | PhaseInfo::Reveal { .. } | PhaseInfo::Unknown(_) => {
                     log::info!("Epoch {} is in {:?} phase, settlement not reached yet", target_epoch, epoch_info.phase);
                     return Ok(false);
                 }
@@ -1116,31 +2446,76 @@ impl MiningOrchestrator {
         Ok(false)
     }
 
+    /// Hold off mining for the configured cooldown after a claim
+    fn start_claim_cooldown(&mut self) {
+        self.claim_cooldown_until = Some(
+            std::time::Instant::now() + Duration::from_secs(self.config.post_claim_cooldown_secs)
+        );
+    }
+    
+    /// Time left in the post-claim cooldown (None once it has passed)
+    fn claim_cooldown_remaining(&self) -> Option<Duration> {
+        self.claim_cooldown_until
+            .map(|until| until.saturating_duration_since(std::time::Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+    
+    /// Whether the contract holds a commitment from us for `epoch` (false if unknown)
+    async fn committed_on_chain(&self, epoch: u64) -> bool {
+        let client = self.client.read().await;
+        match query_commitment(&*client, &self.config.contract_address, &self.wallet.address, epoch).await {
+            Ok(commitment) => commitment.is_some(),
+            Err(e) => {
+                log::debug!("Could not check for an existing commitment for epoch {}: {}", epoch, e);
+                false
+            }
+        }
+    }
+    
+    /// Warm standby: whether to commit our solution ourselves. The primary's on-chain
+    /// commitment is its heartbeat - once it's there the epoch is handled and our
+    /// solution is dropped; if it's still missing at the takeover point, we fail over
+    async fn standby_should_commit(&mut self, epoch: u64, blocks_remaining: u64) -> Result<bool> {
+        let client = self.client.read().await;
+        let primary = query_commitment(&*client, &self.config.contract_address, &self.wallet.address, epoch).await;
+        drop(client);
+        
+        let takeover_at = self.config.submission_buffer_blocks + self.config.standby_takeover_blocks;
+        match primary {
+            Ok(Some(_)) => {
+                log::info!("Standby: primary committed for epoch {}, discarding our solution", epoch);
+                self.standby_deferred_epoch = Some(epoch);
+                self.transition_to_idle().await?;
+                Ok(false)
+            }
+            // A failed query is no sign the primary is down - wait for the takeover point
+            Ok(None) | Err(_) if blocks_remaining > takeover_at => {
+                log::debug!(
+                    "Standby: waiting for the primary's commitment for epoch {} ({} blocks left, takeover at {})",
+                    epoch, blocks_remaining, takeover_at
+                );
+                sleep(Duration::from_secs(2)).await;
+                Ok(false)
+            }
+            Ok(None) => {
+                log::warn!(
+                    "Standby: primary has not committed for epoch {} with {} blocks left - taking over",
+                    epoch, blocks_remaining
+                );
+                Ok(true)
+            }
+            Err(e) => {
+                log::warn!("Standby: could not check for the primary's commitment ({}) - taking over", e);
+                Ok(true)
+            }
+        }
+    }
+    
     async fn get_nonce_range_with_retry(&self) -> Result<(u64, u64)> {
         // Calculate nonce range using Blake2b512 to match the contract's calculate_nonce_range function
-        use blake2::{Blake2b512, Digest};
-        
         let miner_address = &self.wallet.address;
         let epoch_number = self.state.epoch;
-        
-        // Hash miner address to get deterministic partition
-        let mut hasher = Blake2b512::new();
-        hasher.update(miner_address.as_bytes());
-        hasher.update(&epoch_number.to_be_bytes());
-        
-        let hash = hasher.finalize();
-        let partition_seed = u64::from_be_bytes([
-            hash[0], hash[1], hash[2], hash[3], hash[4], hash[5], hash[6], hash[7],
-        ]);
-        
-        // Each miner gets 1/1000th of total nonce space per epoch
-        let nonce_space = u64::MAX / 1000;
-        let partition_offset = (partition_seed % 1000) * nonce_space;
-        
-        // Rotate partitions each epoch to prevent grinding
-        let epoch_rotation = (epoch_number * 37) % 1000; // Prime rotation
-        let rotated_offset = partition_offset.wrapping_add(epoch_rotation * nonce_space);
-        let max_nonce = rotated_offset.wrapping_add(nonce_space);
+        let (rotated_offset, max_nonce) = calculate_nonce_range(miner_address, epoch_number);
         
         log::info!("Calculated Blake2b512 nonce range for epoch {} and miner {}: {} to {}", 
             epoch_number, miner_address, rotated_offset, max_nonce);
@@ -1148,138 +2523,520 @@ impl MiningOrchestrator {
         Ok((rotated_offset, max_nonce))
     }
     
+    /// Pick this epoch's worker count when adaptive threading is enabled
+    async fn apply_thread_scaling(&mut self, difficulty: u8) {
+        if !self.config.adaptive_threads {
+            return;
+        }
+        let scaler = ThreadScaler::new(self.config.min_threads, self.config.max_threads);
+        let conditions = HostConditions::detect();
+        let threads = scaler.threads_for(difficulty, &conditions);
+        log::info!(
+            "Adaptive threads: {} workers for difficulty {} (cpu temp: {:?}, on battery: {})",
+            threads, difficulty, conditions.cpu_temp_c, conditions.on_battery
+        );
+        self.engine.set_threads(threads).await;
+    }
+    
+    /// Adjacent partition to search after our own range is exhausted (if enabled)
+    fn overflow_range(&self, nonce_range: (u64, u64)) -> Option<(u64, u64)> {
+        if !self.config.extended_nonce_search {
+            return None;
+        }
+        let (start, end) = nonce_range;
+        // Partition at the top of the nonce space has nothing above it
+        if end <= start || end == u64::MAX {
+            return None;
+        }
+        Some((end, end.saturating_add(u64::MAX / 1000)))
+    }
+    
+    /// How often a queued transaction's status is checked while waiting for it
+    fn confirmation_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.config.confirmation_poll_ms.max(1))
+    }
+    
     async fn submit_commitment(&mut self, data: &CommitmentData) -> Result<()> {
         if let Some(ref tx_manager) = self.tx_manager {
             let tx_id = tx_manager.queue_commit(data.epoch, data.commitment).await?;
             log::info!("Queued commitment transaction {} for epoch {}", tx_id, data.epoch);
             
-            // Wait for transaction to complete (with timeout)
-            let timeout = Duration::from_secs(120); // Increased timeout for blockchain confirmation
-            let start = std::time::Instant::now();
-            
-            loop {
-                if start.elapsed() > timeout {
-                    return Err(anyhow!("Commitment transaction timeout"));
-                }
-                
-                if let Some(status) = tx_manager.get_status(tx_id).await {
-                    match status {
-                        transaction_manager::TransactionStatus::Success { tx_hash } => {
-                            log::info!("Commitment successful: {}", tx_hash);
-                            return Ok(());
-                        }
-                        transaction_manager::TransactionStatus::Failed { error } => {
-                            return Err(anyhow!("Commitment failed: {}", error));
-                        }
-                        _ => {
-                            // Still pending or processing
-                            sleep(Duration::from_millis(500)).await;
-                        }
-                    }
-                } else {
-                    sleep(Duration::from_millis(500)).await;
-                }
-            }
+            let timeout = Duration::from_secs(self.config.commit_confirmation_timeout_secs);
+            wait_for_confirmation(tx_manager, tx_id, "Commitment", timeout, self.confirmation_poll_interval()).await?;
+            Ok(())
         } else {
             log::warn!("Transaction manager not initialized, using placeholder");
             Ok(())
         }
     }
     
+    /// Sign the reveal while the commit phase is still open so submitting it later
+    /// is a single broadcast. Best effort: on failure the reveal is signed as usual
+    async fn prepare_reveal(&mut self, mut data: CommitmentData) -> Result<()> {
+        let reveal = RevealSolutionMsg::new(data.nonce, data.digest, data.salt);
+        let prepared = self.client.read().await.prepare_message(&reveal).await;
+        match prepared {
+            Ok(prepared) => {
+                log::info!("Pre-signed reveal for epoch {} with sequence {}", data.epoch, prepared.sequence);
+                data.prepared_reveal = Some(prepared);
+                self.transition_to_waiting_for_reveal(data).await
+            }
+            Err(e) => {
+                log::warn!("Could not pre-sign reveal for epoch {}: {}", data.epoch, e);
+                Ok(())
+            }
+        }
+    }
+    
     async fn submit_reveal(&mut self, data: &CommitmentData) -> Result<()> {
         if let Some(ref tx_manager) = self.tx_manager {
-            let tx_id = tx_manager.queue_reveal(data.epoch, data.nonce, data.digest, data.salt).await?;
+            let tx_id = tx_manager.queue_prepared_reveal(
+                data.epoch, data.nonce, data.digest, data.salt, data.prepared_reveal.clone(),
+            ).await?;
             log::info!("Queued reveal transaction {} for epoch {}", tx_id, data.epoch);
             
-            // Wait for transaction to complete (with timeout)
-            let timeout = Duration::from_secs(120);
-            let start = std::time::Instant::now();
-            
-            loop {
-                if start.elapsed() > timeout {
-                    return Err(anyhow!("Reveal transaction timeout"));
-                }
-                
-                if let Some(status) = tx_manager.get_status(tx_id).await {
-                    match status {
-                        transaction_manager::TransactionStatus::Success { tx_hash } => {
-                            log::info!("Reveal successful: {}", tx_hash);
-                            return Ok(());
-                        }
-                        transaction_manager::TransactionStatus::Failed { error } => {
-                            return Err(anyhow!("Reveal failed: {}", error));
-                        }
-                        _ => {
-                            // Still pending or processing
-                            sleep(Duration::from_millis(500)).await;
-                        }
-                    }
-                } else {
-                    sleep(Duration::from_millis(500)).await;
-                }
-            }
+            let timeout = Duration::from_secs(self.config.reveal_confirmation_timeout_secs);
+            wait_for_confirmation(tx_manager, tx_id, "Reveal", timeout, self.confirmation_poll_interval()).await?;
+            Ok(())
         } else {
             log::warn!("Transaction manager not initialized, using placeholder");
             Ok(())
         }
     }
     
-    async fn submit_claim(&mut self, epoch: u64) -> Result<()> {
+    /// POWER (micro units) to stake along with the next claim: the wallet's whole
+    /// balance, i.e. earlier rewards not staked yet. None if auto-staking is off or
+    /// there is less than the contract's minimum stake
+    async fn auto_stake_amount(&self) -> Option<u128> {
+        self.config.auto_stake_days?;
+        let client = self.client.read().await;
+        let balance = query_power_balance(&*client, &self.config.power_token, &self.wallet.address).await;
+        drop(client);
+        match balance {
+            Ok(balance) if balance.balance.u128() >= MIN_STAKE_AMOUNT => Some(balance.balance.u128()),
+            Ok(balance) => {
+                log::debug!("Auto-stake: {} micro POWER is below the minimum stake, not staking", balance.balance);
+                None
+            }
+            Err(e) => {
+                log::warn!("Auto-stake: could not query POWER balance, not staking with this claim: {}", e);
+                None
+            }
+        }
+    }
+    
+    /// Submit a claim and wait for it; returns the tx hash (None without a tx manager)
+    /// A `stake` amount is staked in the same transaction as the claim
+    async fn submit_claim(&mut self, epoch: u64, stake: Option<u128>) -> Result<Option<String>> {
         if let Some(ref tx_manager) = self.tx_manager {
-            let tx_id = tx_manager.queue_claim(epoch).await?;
-            log::info!("Queued claim transaction {} for epoch {}", tx_id, epoch);
-            
-            // Wait for transaction to complete (with timeout)
-            let timeout = Duration::from_secs(120);
-            let start = std::time::Instant::now();
-            
-            loop {
-                if start.elapsed() > timeout {
-                    return Err(anyhow!("Claim transaction timeout"));
+            let tx_id = self.queue_claim_tx(tx_manager, epoch, stake).await?;
+            let timeout = Duration::from_secs(self.config.claim_confirmation_timeout_secs);
+            wait_for_confirmation(tx_manager, tx_id, "Claim", timeout, self.confirmation_poll_interval()).await
+        } else {
+            log::warn!("Transaction manager not initialized, using placeholder");
+            Ok(None)
+        }
+    }
+    
+    /// Queue the claim for `epoch`, staking `stake` POWER in the same transaction when auto-staking
+    async fn queue_claim_tx(
+        &self,
+        tx_manager: &transaction_manager::TransactionManager,
+        epoch: u64,
+        stake: Option<u128>,
+    ) -> Result<u64> {
+        let tx_id = match (stake, self.config.auto_stake_days) {
+            (Some(amount), Some(days)) => match tx_manager.queue_claim_and_stake(epoch, amount, days).await {
+                Ok(tx_id) => {
+                    log::info!(
+                        "Auto-stake: staking {:.6} POWER for {} days in the same transaction as the claim",
+                        amount as f64 / 1_000_000.0, days
+                    );
+                    tx_id
                 }
-                
-                if let Some(status) = tx_manager.get_status(tx_id).await {
-                    match status {
-                        transaction_manager::TransactionStatus::Success { tx_hash } => {
-                            log::info!("Claim successful: {}", tx_hash);
-                            return Ok(());
-                        }
-                        transaction_manager::TransactionStatus::Failed { error } => {
-                            return Err(anyhow!("Claim failed: {}", error));
-                        }
-                        _ => {
-                            // Still pending or processing
-                            sleep(Duration::from_millis(500)).await;
-                        }
+                Err(e) => {
+                    log::warn!("Auto-stake: could not queue stake ({}), claiming only", e);
+                    tx_manager.queue_claim(epoch).await?
+                }
+            },
+            _ => tx_manager.queue_claim(epoch).await?,
+        };
+        log::info!("Queued claim transaction {} for epoch {}", tx_id, epoch);
+        Ok(tx_id)
+    }
+    
+    /// Check persisted commitment data against itself and against the contract
+    /// Returns false only when the reveal is known to be unrevealable
+    async fn verify_resumed_commitment(&self, data: &CommitmentData) -> bool {
+        if !data.is_consistent(self.config.commitment_version, &self.wallet.address) {
+            log::error!(
+                "Saved state for epoch {} is corrupted: nonce/digest/salt do not hash to the saved commitment {}",
+                data.epoch, hex::encode(data.commitment)
+            );
+            return false;
+        }
+        
+        let client = self.client.read().await;
+        let on_chain = query_commitment(&*client, &self.config.contract_address, &self.wallet.address, data.epoch).await;
+        drop(client);
+        match on_chain {
+            Ok(Some(commitment)) if commitment == data.commitment => {
+                log::info!("Verified saved commitment for epoch {} matches on-chain commitment", data.epoch);
+                true
+            }
+            Ok(Some(commitment)) => {
+                log::error!(
+                    "Saved commitment for epoch {} does not match on-chain commitment (saved {}, chain {})",
+                    data.epoch, hex::encode(data.commitment), hex::encode(commitment)
+                );
+                false
+            }
+            Ok(None) => {
+                log::error!("No commitment on chain for epoch {} - the saved commit never landed", data.epoch);
+                false
+            }
+            Err(e) => {
+                // Older contracts may not expose the query - fall back to the local check
+                log::warn!("Could not verify commitment on chain ({}), trusting saved state", e);
+                true
+            }
+        }
+    }
+    
+    /// Count epochs the chain has moved past with a failed commit/reveal, and pause
+    /// or bail out once too many failed in a row
+    async fn check_failure_streak(&mut self, chain_epoch: u64) -> Result<()> {
+        let failed_epoch = self.state.failure_streak.pending;
+        let Some(failed) = self.state.failure_streak.settle(chain_epoch) else {
+            return Ok(());
+        };
+        self.save_state()?;
+        log::warn!("Epoch {} failed to commit/reveal ({} in a row)", failed_epoch.unwrap_or_default(), failed);
+        if self.config.max_failed_epochs == 0 || failed < self.config.max_failed_epochs {
+            return Ok(());
+        }
+        
+        log::error!("================================================================");
+        log::error!("{} consecutive epochs failed to commit or reveal.", failed);
+        log::error!("This is usually not the network - check signing, gas and contract");
+        log::error!("version in the log above before spending more gas.");
+        log::error!("================================================================");
+        self.notify(NotifyEvent::Error {
+            epoch: failed_epoch.unwrap_or_default(),
+            message: format!("{} consecutive epochs failed to commit or reveal", failed),
+        });
+        if let Some(call_trace) = self.client.read().await.call_trace() {
+            match call_trace.dump(&format!("{} consecutive epochs failed to commit or reveal", failed)) {
+                Ok(()) => log::error!("Recent chain calls written to {}", call_trace.report_path().display()),
+                Err(e) => log::warn!("Failed to write crash report: {}", e),
+            }
+        }
+        self.engine.stop_mining().await?;
+        self.transition_to_idle().await?;
+        match self.config.failure_action {
+            FailureAction::Exit => Err(RepeatedFailureError { epochs: failed }.into()),
+            FailureAction::Pause => {
+                log::error!("Pausing mining for {}s before trying again", self.config.failure_pause_secs);
+                self.failure_pause_until = Some(std::time::Instant::now() + Duration::from_secs(self.config.failure_pause_secs));
+                Ok(())
+            }
+        }
+    }
+    
+    /// Time left in a failure breaker pause (None when not paused). Once it has passed
+    /// the streak starts over
+    fn failure_pause_remaining(&mut self) -> Result<Option<Duration>> {
+        let until = match self.failure_pause_until {
+            Some(until) => until,
+            None => return Ok(None),
+        };
+        let remaining = until.saturating_duration_since(std::time::Instant::now());
+        if !remaining.is_zero() {
+            return Ok(Some(remaining));
+        }
+        log::info!("Failure pause over, resuming mining");
+        self.failure_pause_until = None;
+        self.state.failure_streak.reset();
+        self.save_state()?;
+        Ok(None)
+    }
+    
+    /// Rebuild the reveal for the last found solution from its saved proof and the
+    /// derived salt, and resume waiting for the reveal window if the chain holds
+    /// exactly that commitment
+    async fn recover_derived_reveal(&mut self) -> Result<()> {
+        let path = Self::solution_proof_file(&self.config.state_file);
+        let Some(proof) = fs::read_to_string(&path).ok().and_then(|json| SolutionProof::from_json(&json).ok()) else {
+            return Ok(());
+        };
+        if proof.miner != self.wallet.address || proof.epoch < self.state.epoch || self.state.has_committed(proof.epoch) {
+            return Ok(());
+        }
+        // Commits and reveals happen in the same epoch - older solutions can't be revealed anymore
+        if self.get_current_epoch_with_retry().await? != proof.epoch {
+            return Ok(());
+        }
+        let Some(digest) = hex::decode(&proof.digest).ok().and_then(|d| <[u8; 16]>::try_from(d).ok()) else {
+            log::warn!("Solution proof {} has an invalid digest, cannot recover reveal", path.display());
+            return Ok(());
+        };
+        
+        let data = CommitmentData::derived(
+            &self.wallet, self.config.commitment_version, proof.epoch, nonce_bytes(proof.nonce), digest,
+        );
+        let client = self.client.read().await;
+        let on_chain = query_commitment(&*client, &self.config.contract_address, &self.wallet.address, proof.epoch).await;
+        drop(client);
+        match on_chain {
+            Ok(Some(commitment)) if commitment == data.commitment => {
+                log::info!("Recovered reveal for epoch {} from {} and the derived salt", proof.epoch, path.display());
+                self.state.epoch = proof.epoch;
+                self.state.record_commit(proof.epoch);
+                self.transition_to_waiting_for_reveal(data).await?;
+            }
+            Ok(Some(_)) => log::warn!(
+                "On-chain commitment for epoch {} doesn't match the last solution proof, not recovering a reveal",
+                proof.epoch
+            ),
+            Ok(None) => log::debug!("No commitment on chain for epoch {}, nothing to recover", proof.epoch),
+            Err(e) => log::warn!("Could not query commitment for epoch {} ({}), not recovering a reveal", proof.epoch, e),
+        }
+        Ok(())
+    }
+    
+    /// Read the POWER reward (micro units) and fee (base units) from a claim tx's events
+    /// The tx is broadcast in sync mode, so poll briefly (`attempts` tries) until it has been indexed
+    async fn claimed_reward(&self, tx_hash: &str, attempts: u32) -> (Option<u64>, Option<u64>) {
+        for attempt in 1..=attempts {
+            let client = self.client.read().await;
+            let result = client.get_tx(tx_hash).await;
+            drop(client);
+            match result {
+                Ok(tx) => {
+                    let reward = claimed_reward_from_events(&tx.events, &self.wallet.address);
+                    let gas_price = self.client.read().await.gas_price();
+                    // The fee is charged for the gas limit (gas_wanted), whatever was used
+                    let fee_wei = u64::try_from(fee_for_gas_at(tx.gas_wanted.max(0) as u64, gas_price)).ok();
+                    return (reward, fee_wei);
+                }
+                Err(e) => {
+                    log::debug!("Claim tx {} not available yet: {}", tx_hash, e);
+                    if attempt < attempts {
+                        sleep(Duration::from_secs(2)).await;
                     }
-                } else {
-                    sleep(Duration::from_millis(500)).await;
                 }
             }
-        } else {
-            log::warn!("Transaction manager not initialized, using placeholder");
-            Ok(())
         }
+        (None, None)
+    }
+    
+    /// Stop retrying a reveal that failed `max_reveal_attempts` times while the window
+    /// was still open - something is wrong with it (e.g. a commitment mismatch), and
+    /// more attempts would only burn gas until the window closes
+    async fn abandon_reveal(&mut self, data: &CommitmentData, error: &anyhow::Error) -> Result<()> {
+        let reason = format!(
+            "Reveal for epoch {} abandoned after {} attempts ({:?}): {:#}",
+            data.epoch, self.reveal_attempts, ChainError::classify(error), error
+        );
+        log::error!("{} - the reveal window is still open, but retrying is unlikely to help", reason);
+        self.notify(NotifyEvent::Error { epoch: data.epoch, message: reason.clone() });
+        
+        if let Some(ref reporter) = self.telemetry_reporter {
+            let stats = reporter.get_stats().await;
+            self.send_telemetry(TelemetryUpdate {
+                epoch: data.epoch,
+                phase: "Revealing".to_string(),
+                solutions_found: Some(stats.epochs_won as u32),
+                reveals_submitted: Some(stats.reveals_successful as u32),
+                last_error: Some(reason),
+                ..TelemetryUpdate::default()
+            });
+        }
+        
+        // Nothing was revealed, so there is nothing to claim for this epoch
+        self.transition_to_idle().await
+    }
+    
+    /// Handle a "nonce out of range" rejection: the solution was mined against the
+    /// wrong partition (stale state or an epoch mismatch), so drop it, recompute the
+    /// range for the current epoch and start mining again instead of retrying forever
+    async fn recover_from_nonce_out_of_range(&mut self, data: &CommitmentData) -> Result<()> {
+        let nonce = nonce_from_bytes(data.nonce);
+        let old_range = calculate_nonce_range(&self.wallet.address, data.epoch);
+        
+        let epoch_info = self.epoch_info().await?;
+        let new_range = calculate_nonce_range(&self.wallet.address, epoch_info.epoch_number);
+        
+        log::error!(
+            "Nonce {} rejected as out of range. Solution epoch {} range: {}..{}, current epoch {} range: {}..{}",
+            nonce, data.epoch, old_range.0, old_range.1,
+            epoch_info.epoch_number, new_range.0, new_range.1
+        );
+        // Partitions can wrap around u64::MAX, so compare offsets from the start
+        if nonce.wrapping_sub(old_range.0) >= old_range.1.wrapping_sub(old_range.0) {
+            log::error!("Nonce {} was never inside the epoch {} partition - mined with a stale range", nonce, data.epoch);
+        }
+        
+        self.engine.stop_mining().await?;
+        
+        // Restart right away if this epoch can still take a commitment, otherwise wait in idle
+        match epoch_info.phase {
+            PhaseInfo::Commit { .. } if !self.state.has_committed(epoch_info.epoch_number) => {
+                log::info!("Restarting mining for epoch {} with recomputed nonce range", epoch_info.epoch_number);
+                self.transition_to_finding_solution(epoch_info.epoch_number).await
+            }
+            _ => {
+                log::info!("Discarded out-of-range solution, waiting for next commit phase");
+                self.state.epoch = epoch_info.epoch_number;
+                self.transition_to_idle().await
+            }
+        }
+    }
+    
+    /// Apply the mining schedule; returns false when the loop should stay idle
+    /// Leaving a window stops an in-progress search, but commitments already made are
+    /// still revealed and claimed so their gas isn't wasted
+    async fn check_schedule(&mut self) -> Result<bool> {
+        let schedule = match self.config.schedule {
+            Some(ref schedule) => schedule,
+            None => return Ok(true),
+        };
+        let active = schedule.is_active_now();
+        
+        if self.schedule_active != Some(active) {
+            if active {
+                log::info!("Entering scheduled mining window ({})", schedule);
+            } else {
+                log::info!("Outside scheduled mining windows ({}), pausing mining", schedule);
+            }
+            self.schedule_active = Some(active);
+        }
+        if active {
+            return Ok(true);
+        }
+        
+        match self.state.phase {
+            MiningPhase::Idle => Ok(false),
+            MiningPhase::FindingSolution => {
+                log::info!("Stopping search for epoch {} at end of mining window", self.state.epoch);
+                self.engine.stop_mining().await?;
+                self.transition_to_idle().await?;
+                Ok(false)
+            }
+            // Finish whatever is already committed
+            _ => Ok(true),
+        }
+    }
+    
+    /// Sample the block height for halt detection. Returns true while the chain is
+    /// halted - the workers are stopped and nothing is submitted until it moves again
+    async fn check_chain_halt(&mut self) -> Result<bool> {
+        if self.config.chain_halt_secs == 0 {
+            return Ok(false);
+        }
+        let halted = self.halt_detector.is_halted();
+        if !halted && self.last_halt_check.is_some_and(|at| at.elapsed() < CHAIN_HALT_CHECK_INTERVAL) {
+            return Ok(false);
+        }
+        self.last_halt_check = Some(std::time::Instant::now());
+        
+        let height = self.client.read().await.get_latest_block_height().await;
+        let height = match height {
+            Ok(height) => height,
+            Err(e) => {
+                // Unreachable isn't halted - the epoch queries will surface it
+                log::debug!("Halt check: could not get block height: {}", e);
+                return Ok(halted);
+            }
+        };
+        
+        match self.halt_detector.observe(height, std::time::Instant::now()) {
+            Some(HaltChange::Halted { height, stalled_for }) => {
+                let message = format!("Chain halted: block height stuck at {} for {}s", height, stalled_for.as_secs());
+                log::warn!("{} - pausing mining and submissions until blocks resume", message);
+                if matches!(self.state.phase, MiningPhase::FindingSolution) {
+                    // The search checkpoint lets mining pick up where it left off
+                    self.engine.stop_mining().await?;
+                    self.transition_to_idle().await?;
+                }
+                self.report_chain_halt(message).await;
+            }
+            Some(HaltChange::Resumed { height, halted_for }) => {
+                log::info!("Chain resumed at block {} after {}s, resuming mining", height, halted_for.as_secs());
+                self.epoch_cache.invalidate();
+            }
+            None => {}
+        }
+        Ok(self.halt_detector.is_halted())
+    }
+    
+    /// Run the notify command for `event`, if one is configured (never waits for it)
+    fn notify(&self, event: NotifyEvent) {
+        if let Some(ref hook) = self.notify_hook {
+            hook.notify(&event);
+        }
+    }
+    
+    /// Tell telemetry the chain is halted
+    async fn report_chain_halt(&self, message: String) {
+        let Some(ref reporter) = self.telemetry_reporter else {
+            return;
+        };
+        let stats = reporter.get_stats().await;
+        self.send_telemetry(TelemetryUpdate {
+            epoch: self.state.epoch,
+            phase: "ChainHalted".to_string(),
+            solutions_found: Some(stats.epochs_won as u32),
+            reveals_submitted: Some(stats.reveals_successful as u32),
+            last_error: Some(message),
+            ..TelemetryUpdate::default()
+        });
+    }
+    
+    /// Queue a telemetry update; it's sent in the background and never waited on
+    fn send_telemetry(&self, update: TelemetryUpdate) {
+        if let Some(ref sender) = self.telemetry_sender {
+            sender.send(update);
+        }
+    }
+    
+    /// Sleep a random part of `startup_jitter_seconds`, once per process
+    async fn apply_startup_jitter(&mut self) {
+        if !std::mem::take(&mut self.startup_jitter_pending) || self.config.startup_jitter_seconds == 0 {
+            return;
+        }
+        let delay_ms = rand::thread_rng().gen_range(0..=self.config.startup_jitter_seconds * 1000);
+        log::info!("Startup jitter: delaying first mining start by {:.1}s", delay_ms as f64 / 1000.0);
+        sleep(Duration::from_millis(delay_ms)).await;
     }
     
     /// Check if we're past the reveal window by querying chain state
     /// This replaces local block calculations to prevent timing drift
     async fn is_past_reveal_window(&self) -> Result<bool> {
-        let client = self.client.read().await;
-        match query_epoch_info(&*client, &self.config.contract_address).await {
+        match self.epoch_info().await {
             Ok(epoch_info) => {
                 match epoch_info.phase {
                     PhaseInfo::Settlement { .. } => Ok(true),  // Past reveal
                     PhaseInfo::Commit { .. } => Ok(true),      // Way past reveal (next epoch)
                     PhaseInfo::Reveal { .. } => Ok(false),     // Still in reveal
+                    PhaseInfo::Unknown(name) => {
+                        // Can't tell where an unknown phase sits - don't give up on the reveal
+                        log::warn!("Unrecognized contract phase '{}', assuming reveal window still open", name);
+                        Ok(false)
+                    }
                 }
             }
             Err(e) => {
                 log::warn!("Failed to query epoch phase, falling back to local calculation: {}", e);
                 // Fallback to local calculation if query fails
-                let block_height = self.get_block_height_with_retry().await?;
-                let block_in_epoch = block_height % 50;
-                Ok(block_in_epoch > 45)
+                match self.get_block_height_with_retry().await {
+                    Ok(block_height) => Ok(block_height % 50 > 45),
+                    Err(e) => {
+                        // Without either reading, keep trying the reveal rather than give up on it
+                        log::warn!("Failed to read the block height too, assuming reveal window still open: {}", e);
+                        Ok(false)
+                    }
+                }
             }
         }
     }
@@ -1293,11 +3050,245 @@ impl MiningOrchestrator {
     pub fn get_stats_collector(&self) -> Arc<Mutex<StatsCollector>> {
         self.stats_collector.clone()
     }
+    
+    /// Get the current profitability estimate
+    pub async fn get_profitability(&self) -> ProfitabilityEstimate {
+        self.profitability.estimate(&self.attempt_gas().await)
+    }
+    
+    /// Feed contract events into the main loop so waits end as soon as the epoch changes
+    pub fn set_event_listener(&mut self, event_rx: tokio::sync::mpsc::Receiver<ContractEvent>) {
+        self.event_rx = Some(event_rx);
+    }
+    
+    /// Sleep for up to `timeout`, returning early if a contract event arrives
+    /// Without an event listener this is a plain sleep
+    async fn wait_for_chain_event(&mut self, timeout: Duration) {
+        let rx = match self.event_rx.as_mut() {
+            Some(rx) => rx,
+            None => {
+                sleep(timeout).await;
+                return;
+            }
+        };
+        
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let received = tokio::select! {
+                event = rx.recv() => Some(event),
+                _ = tokio::time::sleep_until(deadline) => None,
+            };
+            
+            match received {
+                // Other miners' commits/reveals don't change our phase - keep waiting
+                Some(Some(ContractEvent::Other { .. })) => continue,
+                Some(Some(event)) => {
+                    log::info!("Contract event {:?}, re-checking epoch state", event);
+                    self.epoch_cache.invalidate();
+                    return;
+                }
+                Some(None) => {
+                    log::warn!("Contract event listener stopped, falling back to polling");
+                    self.event_rx = None;
+                    tokio::time::sleep_until(deadline).await;
+                    return;
+                }
+                None => return,
+            }
+        }
+    }
+    
+    /// Whether this miner sends `advance_epoch` itself: not when turned off, in no-submit
+    /// mode, on standby (the primary advances), or once the contract has refused this
+    /// wallet (permissioned advancement)
+    async fn self_advance_enabled(&self) -> bool {
+        if self.config.no_submit || self.config.no_advance || self.config.standby {
+            return false;
+        }
+        self.stats_collector.lock().await.get_stats().await.advances_unauthorized == 0
+    }
+    
+    /// Get shared health state for the liveness/readiness probe server
+    pub fn health_state(&self) -> Arc<HealthState> {
+        self.health.clone()
+    }
+    
+    /// Update connection status and (periodically) gas balance for readiness probes
+    async fn refresh_health(&mut self) {
+        let client = self.client.read().await;
+        self.health.set_connected(client.is_connected());
+        self.health.set_network_metrics(client.metrics());
+        
+        let due = self.last_gas_check.map_or(true, |t| t.elapsed() >= GAS_BALANCE_CHECK_INTERVAL);
+        if due {
+            let fee_denom = client.fee_denom();
+            // Fees may have fallen back to INJ since the last check
+            let min_balance = if fee_denom == DEFAULT_FEE_DENOM {
+                self.config.min_gas_balance
+            } else {
+                self.config.fee_denom_min_balance
+            };
+            match client.query_bank_balance(&self.wallet.address, fee_denom).await {
+                Ok(balance) => {
+                    self.health.set_gas_balance(balance, min_balance);
+                    if balance < min_balance {
+                        log::warn!("Low gas balance: {} {} (minimum {})", balance, fee_denom, min_balance);
+                    }
+                }
+                Err(e) => log::debug!("Gas balance check failed: {}", e),
+            }
+            drop(client);
+            self.last_gas_check = Some(std::time::Instant::now());
+        }
+        
+        self.refresh_gas_price().await;
+        self.record_hash_totals().await;
+        self.write_metrics_file().await;
+    }
+    
+    /// Copy the engine's lifetime hash counters into the stats
+    async fn record_hash_totals(&mut self) {
+        let total = self.engine.total_hashes().await;
+        let worker_hashes = self.engine.worker_total_hashes().await;
+        self.stats_collector.lock().await.record_hash_totals(total, worker_hashes).await;
+    }
+    
+    /// Rewrite the OpenMetrics file if one is configured and a write is due
+    async fn write_metrics_file(&mut self) {
+        let Some(ref path) = self.config.metrics_file else {
+            return;
+        };
+        if self.last_metrics_write.map_or(false, |t| t.elapsed() < METRICS_FILE_INTERVAL) {
+            return;
+        }
+        self.last_metrics_write = Some(std::time::Instant::now());
+        
+        let stats = self.stats_collector.lock().await.get_stats().await;
+        let status = self.health.status();
+        let network = status.network.unwrap_or_default();
+        let snapshot = MetricsSnapshot {
+            epoch: self.state.epoch,
+            phase: self.state.phase.name(),
+            difficulty: stats.current_difficulty,
+            hashrate: self.engine.get_hashrate().await,
+            total_hashes: stats.total_hashes,
+            solutions_found: stats.solutions_found,
+            solutions_submitted: stats.solutions_submitted,
+            commit_latency_p50_ms: stats.commit_latency.as_ref().map(|l| l.p50_ms),
+            commit_latency_p95_ms: stats.commit_latency.as_ref().map(|l| l.p95_ms),
+            connected: status.readiness.connected,
+            last_chain_query_age_secs: status.readiness.last_query_age_secs,
+            gas_balance: self.health.gas_balance(),
+            grpc_errors: network.grpc_errors,
+            reconnects: network.reconnects,
+        };
+        if let Err(e) = metrics::write_atomic(path, &metrics::render(&snapshot, &self.miner_name)) {
+            log::warn!("Failed to write metrics file {:?}: {}", path, e);
+        }
+    }
+    
+    /// Re-fetch the gas price from the node or oracle when one is configured
+    async fn refresh_gas_price(&mut self) {
+        let due = self.last_gas_price_refresh.map_or(true, |t| t.elapsed() >= GAS_PRICE_REFRESH_INTERVAL);
+        if !due {
+            return;
+        }
+        self.last_gas_price_refresh = Some(std::time::Instant::now());
+        let fetched = self.client.read().await.fetch_gas_price().await;
+        match fetched {
+            Ok(Some(price)) => self.client.write().await.set_gas_price(price),
+            Ok(None) => {}
+            Err(e) => log::warn!("Gas price refresh failed, keeping the current price: {}", e),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::chain::client_real::ClientConfig;
+    use crate::chain::height_source::HeightSource;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    
+    /// Answer LCD latest-block requests on a local port with `heights` in turn, the last
+    /// one repeating. A height of 0 answers with an error instead
+    async fn serve_block_heights(heights: Vec<u64>) -> HeightSource {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut heights = heights.into_iter();
+            let mut last = 0;
+            while let Ok((mut socket, _)) = listener.accept().await {
+                last = heights.next().unwrap_or(last);
+                let (status, body) = match last {
+                    0 => ("500 Internal Server Error", String::new()),
+                    height => ("200 OK", serde_json::json!({"block": {"header": {"height": height.to_string()}}}).to_string()),
+                };
+                let mut request = [0u8; 4096];
+                let _ = socket.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status, body.len(), body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        HeightSource::Lcd(url)
+    }
+    
+    /// An orchestrator on an unconnected client with a throwaway state file: chain calls
+    /// fail straight away, and `epoch_info` answers from whatever is put in the cache
+    async fn offline_orchestrator(config: OrchestratorConfig) -> MiningOrchestrator {
+        orchestrator_with_heights(config, None).await
+    }
+    
+    /// Like `offline_orchestrator`, with block heights read from `height_source`
+    async fn orchestrator_with_heights(config: OrchestratorConfig, height_source: Option<HeightSource>) -> MiningOrchestrator {
+        let dir = std::env::temp_dir().join(format!("gmine-orchestrator-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = OrchestratorConfig {
+            state_file: dir.join("state.json"),
+            contract_address: "inj1contract".to_string(),
+            epoch_poll_interval: 0,
+            max_retries: 0,
+            retry_delay_ms: 1,
+            miner_id: Some(Uuid::nil()),
+            ..config
+        };
+        let client_config = ClientConfig { max_retries: 0, height_source, ..ClientConfig::default() };
+        let client = InjectiveClient::new(client_config, InjectiveWallet::ephemeral().unwrap());
+        let mut orchestrator = MiningOrchestrator::new(config, client, InjectiveWallet::ephemeral().unwrap()).await.unwrap();
+        // Nothing is sent anywhere: no telemetry, and submissions stop at the queue
+        orchestrator.telemetry_sender = None;
+        orchestrator.telemetry_reporter = None;
+        orchestrator.tx_manager = None;
+        orchestrator
+    }
+    
+    fn epoch_info_in(epoch_number: u64, phase: PhaseInfo) -> EpochInfoResponse {
+        EpochInfoResponse {
+            epoch_number,
+            phase,
+            difficulty: 8,
+            reward_pool: "0".to_string(),
+            leading_miner: None,
+            best_score: None,
+            start_block: 0,
+            target_hash: vec![0; 32],
+        }
+    }
+    
+    fn commitment(epoch: u64) -> CommitmentData {
+        CommitmentData {
+            epoch,
+            nonce: [1; 8],
+            digest: [2; 16],
+            salt: [3; 32],
+            commitment: [4; 32],
+            prepared_reveal: None,
+            found_at_ms: None,
+        }
+    }
     
     #[test]
     fn test_state_serialization() {
@@ -1305,6 +3296,7 @@ mod tests {
             epoch: 42,
             phase: MiningPhase::Idle,
             last_saved: 1234567890,
+            ..MiningState::default()
         };
         
         let serialized = serde_json::to_string(&state).unwrap();
@@ -1314,6 +3306,118 @@ mod tests {
         assert_eq!(deserialized.phase, MiningPhase::Idle);
     }
     
+    #[test]
+    fn test_search_checkpoint_applies_only_to_same_epoch_and_range() {
+        let checkpoint = SearchCheckpoint { epoch: 7, ranges: vec![(150, 200), (260, 300)] };
+        assert!(checkpoint.applies_to(7, (100, 300)));
+        // Epoch moved on
+        assert!(!checkpoint.applies_to(8, (100, 300)));
+        // Different partition (e.g. address changed)
+        assert!(!checkpoint.applies_to(7, (1000, 1300)));
+        
+        // Older state files have no checkpoint
+        let state: MiningState = serde_json::from_str(r#"{"epoch":7,"phase":"Idle","last_saved":0}"#).unwrap();
+        assert!(state.search_checkpoint.is_none());
+    }
+    
+    #[test]
+    fn test_defer_claim_is_bounded() {
+        let mut state = MiningState::default();
+        assert!(state.defer_claim(5, 2));
+        // Deferring the same epoch again doesn't take another slot
+        assert!(state.defer_claim(5, 2));
+        assert!(state.defer_claim(6, 2));
+        assert!(!state.defer_claim(7, 2));
+        assert_eq!(state.pending_claims, vec![5, 6]);
+    }
+    
+    #[tokio::test]
+    async fn test_unclaimable_epoch_is_abandoned() {
+        let config = OrchestratorConfig { max_claim_attempts: 2, ..OrchestratorConfig::default() };
+        let mut orchestrator = offline_orchestrator(config).await;
+        orchestrator.config.epoch_poll_interval = 1;
+        orchestrator.state = MiningState { epoch: 7, phase: MiningPhase::Claiming(7), pending_claims: vec![6], ..MiningState::default() };
+        // Settlement never finishes as far as this miner can tell
+        orchestrator.epoch_cache.insert(&orchestrator.config.contract_address, epoch_info_in(7, PhaseInfo::Settlement { ends_at: 100 }));
+        
+        orchestrator.step(7).await.unwrap();
+        assert_eq!(orchestrator.state.phase, MiningPhase::Claiming(7));
+        
+        // Backing off doesn't hold the state machine for the whole delay
+        tokio::time::timeout(Duration::from_secs(3), orchestrator.step(7)).await
+            .expect("claim backoff blocked the state machine")
+            .unwrap();
+        assert_eq!(orchestrator.state.phase, MiningPhase::Claiming(7));
+        
+        // The first attempt was long enough ago: the next one is the last
+        orchestrator.claim_retries = ClaimRetries::default();
+        orchestrator.claim_retries.record_not_ready(7, 2, std::time::Instant::now() - Duration::from_secs(60));
+        orchestrator.epoch_cache.insert(&orchestrator.config.contract_address, epoch_info_in(7, PhaseInfo::Settlement { ends_at: 100 }));
+        orchestrator.step(7).await.unwrap();
+        
+        // Back to idle, where the next epoch is mined
+        assert_eq!(orchestrator.state.phase, MiningPhase::Idle);
+        assert_eq!(orchestrator.state.abandoned_claims, vec![7]);
+        assert_eq!(orchestrator.state.pending_claims, vec![6]);
+        
+        // A deferred claim is dropped without touching the phase
+        orchestrator.state.phase = MiningPhase::FindingSolution;
+        orchestrator.state.abandon_claim(6);
+        assert_eq!(orchestrator.state.phase, MiningPhase::FindingSolution);
+        assert!(orchestrator.state.pending_claims.is_empty());
+        assert_eq!(orchestrator.state.abandoned_claims, vec![7, 6]);
+    }
+    
+    #[test]
+    fn test_reveal_due_on_resume() {
+        let info = |epoch_number, phase| EpochInfoResponse {
+            epoch_number,
+            phase,
+            difficulty: 8,
+            reward_pool: "0".to_string(),
+            leading_miner: None,
+            best_score: None,
+            start_block: 0,
+            target_hash: vec![0; 32],
+        };
+        assert!(reveal_due_on_resume(7, &info(7, PhaseInfo::Reveal { ends_at: 1_000 })));
+        // Window not open yet, already closed, or a later epoch
+        assert!(!reveal_due_on_resume(7, &info(7, PhaseInfo::Commit { ends_at: 1_000 })));
+        assert!(!reveal_due_on_resume(7, &info(7, PhaseInfo::Settlement { ends_at: 1_000 })));
+        assert!(!reveal_due_on_resume(7, &info(8, PhaseInfo::Reveal { ends_at: 1_000 })));
+    }
+    
+    #[test]
+    fn test_reveal_attempts_exhausted() {
+        assert!(!reveal_attempts_exhausted(4, 5));
+        assert!(reveal_attempts_exhausted(5, 5));
+        // 0 keeps retrying until the window closes
+        assert!(!reveal_attempts_exhausted(1_000, 0));
+    }
+    
+    #[test]
+    fn test_target_hash_array_length() {
+        assert_eq!(target_hash_array(3, &[7u8; 32]).unwrap(), [7u8; 32]);
+        let short = target_hash_array(3, &[7u8; 31]).unwrap_err().to_string();
+        assert!(short.contains("expected 32 bytes, got 31"), "{}", short);
+        assert!(target_hash_array(3, &[7u8; 33]).unwrap_err().to_string().contains("got 33"));
+    }
+    
+    #[test]
+    fn test_record_commit_once_per_epoch() {
+        let mut state = MiningState::default();
+        assert!(state.record_commit(5));
+        assert!(!state.record_commit(5));
+        assert!(state.has_committed(5));
+        assert_eq!(state.committed_epochs, vec![5]);
+        
+        for epoch in 6..40 {
+            state.record_commit(epoch);
+        }
+        assert_eq!(state.committed_epochs.len(), COMMITTED_EPOCHS_HISTORY);
+        assert!(!state.has_committed(5));
+    }
+    
     #[test]
     fn test_commitment_data_serialization() {
         let data = CommitmentData {
@@ -1322,6 +3426,8 @@ mod tests {
             digest: [2; 16],
             salt: [3; 32],
             commitment: [4; 32],
+            prepared_reveal: None,
+            found_at_ms: None,
         };
         
         let serialized = serde_json::to_string(&data).unwrap();
@@ -1330,4 +3436,339 @@ mod tests {
         assert_eq!(deserialized.epoch, 10);
         assert_eq!(deserialized.nonce, [1; 8]);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_derived_commitment_is_reproducible() {
+        let wallet = InjectiveWallet::from_mnemonic_no_passphrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        ).unwrap();
+        let (nonce, digest) = (77u64.to_le_bytes(), [5u8; 16]);
+        
+        let committed = CommitmentData::derived(&wallet, CommitmentVersion::Salted, 9, nonce, digest);
+        assert!(committed.is_consistent(CommitmentVersion::Salted, &wallet.address));
+        assert_eq!(committed.commitment, crate::chain::messages::create_commitment(nonce, digest, committed.salt));
+        
+        // Rebuilt after a lost state file from nothing but epoch, nonce and digest
+        assert_eq!(CommitmentData::derived(&wallet, CommitmentVersion::Salted, 9, nonce, digest), committed);
+        assert_ne!(CommitmentData::derived(&wallet, CommitmentVersion::Salted, 10, nonce, digest).salt, committed.salt);
+    }
+    
+    #[test]
+    fn test_commitment_data_consistency() {
+        let (nonce, digest, salt) = ([1; 8], [2; 16], [3; 32]);
+        let miner = "inj1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz";
+        let mut data = CommitmentData {
+            epoch: 10,
+            nonce,
+            digest,
+            salt,
+            commitment: CommitmentVersion::Salted.compute(miner, 10, nonce, digest, salt),
+            prepared_reveal: None,
+            found_at_ms: None,
+        };
+        assert!(data.is_consistent(CommitmentVersion::Salted, miner));
+        // Checked against the wrong scheme it doesn't match
+        assert!(!data.is_consistent(CommitmentVersion::Unsalted, miner));
+        
+        // Salt corrupted on disk
+        data.salt[0] ^= 0xff;
+        assert!(!data.is_consistent(CommitmentVersion::Salted, miner));
+    }
+    
+    #[tokio::test]
+    async fn test_duplicate_commit_goes_on_to_reveal() {
+        let mut orchestrator = offline_orchestrator(OrchestratorConfig::default()).await;
+        let data = commitment(7);
+        orchestrator.state.epoch = 7;
+        orchestrator.state.record_commit(7);
+        orchestrator.state.phase = MiningPhase::Committing(data.clone());
+        
+        orchestrator.step(7).await.unwrap();
+        
+        // The commitment is on-chain, so it still has to be revealed
+        assert_eq!(orchestrator.state.phase, MiningPhase::WaitingForRevealWindow(data));
+    }
+    
+    #[tokio::test]
+    async fn test_standby_never_advances_epochs() {
+        let orchestrator = offline_orchestrator(OrchestratorConfig::default()).await;
+        assert!(orchestrator.self_advance_enabled().await);
+        
+        for config in [
+            OrchestratorConfig { standby: true, ..OrchestratorConfig::default() },
+            OrchestratorConfig { no_advance: true, ..OrchestratorConfig::default() },
+            OrchestratorConfig { no_submit: true, ..OrchestratorConfig::default() },
+        ] {
+            assert!(!offline_orchestrator(config).await.self_advance_enabled().await);
+        }
+    }
+    
+    fn offline_tx_manager() -> transaction_manager::TransactionManager {
+        let client = InjectiveClient::new(ClientConfig::default(), InjectiveWallet::ephemeral().unwrap());
+        transaction_manager::TransactionManager::new(
+            transaction_manager::TransactionManagerConfig::default(),
+            Arc::new(RwLock::new(client)),
+        )
+    }
+    
+    #[tokio::test]
+    async fn test_wait_for_confirmation_timeout_cancels_the_transaction() {
+        use transaction_manager::TransactionStatus;
+        let tx_manager = offline_tx_manager();
+        let poll = Duration::from_millis(5);
+        
+        // Nothing sends it, so it never confirms
+        let tx_id = tx_manager.queue_reveal(7, [1; 8], [2; 16], [3; 32]).await.unwrap();
+        let err = wait_for_confirmation(&tx_manager, tx_id, "Reveal", Duration::from_millis(20), poll).await.unwrap_err();
+        assert!(err.to_string().contains("timeout") && err.to_string().contains("cancelled"), "{}", err);
+        
+        // The retry's reveal is the only one left to send
+        assert!(tx_manager.get_queue().await.is_empty());
+        assert!(matches!(tx_manager.get_status(tx_id).await, Some(TransactionStatus::Skipped { .. })));
+        let retry_id = tx_manager.queue_reveal(7, [1; 8], [2; 16], [3; 32]).await.unwrap();
+        assert_eq!(tx_manager.get_queue().await.iter().map(|tx| tx.id).collect::<Vec<_>>(), vec![retry_id]);
+        
+        // A finished transaction can't be cancelled
+        tx_manager.complete(retry_id, TransactionStatus::Success { tx_hash: "ABC".to_string() }).await;
+        assert!(!tx_manager.cancel(retry_id).await);
+    }
+    
+    #[tokio::test]
+    async fn test_wait_for_confirmation_outcomes() {
+        use transaction_manager::TransactionStatus;
+        let tx_manager = offline_tx_manager();
+        let (timeout, poll) = (Duration::from_secs(5), Duration::from_millis(5));
+        
+        let tx_id = tx_manager.queue_commit(7, [0; 32]).await.unwrap();
+        tx_manager.complete(tx_id, TransactionStatus::Success { tx_hash: "ABC".to_string() }).await;
+        assert_eq!(wait_for_confirmation(&tx_manager, tx_id, "Commitment", timeout, poll).await.unwrap(), Some("ABC".to_string()));
+        
+        let tx_id = tx_manager.queue_reveal(7, [1; 8], [2; 16], [3; 32]).await.unwrap();
+        tx_manager.complete(tx_id, TransactionStatus::Failed { error: "Wrong phase".to_string() }).await;
+        let err = wait_for_confirmation(&tx_manager, tx_id, "Reveal", timeout, poll).await.unwrap_err();
+        assert!(err.to_string().contains("Reveal failed: Wrong phase"), "{}", err);
+        
+        // A lost response whose retry found the reveal on-chain is a success without a hash
+        let tx_id = tx_manager.queue_reveal(7, [1; 8], [2; 16], [3; 32]).await.unwrap();
+        tx_manager.complete(tx_id, TransactionStatus::AlreadyApplied { reason: "already revealed".to_string() }).await;
+        assert_eq!(wait_for_confirmation(&tx_manager, tx_id, "Reveal", timeout, poll).await.unwrap(), None);
+        
+        // Skipped ends the wait instead of running into the timeout
+        let tx_id = tx_manager.queue_advance_epoch().await.unwrap();
+        tx_manager.complete(tx_id, TransactionStatus::Skipped { reason: "advanced by another miner".to_string() }).await;
+        let err = wait_for_confirmation(&tx_manager, tx_id, "Advance", timeout, poll).await.unwrap_err();
+        assert!(err.to_string().contains("not sent"), "{}", err);
+    }
+    
+    #[tokio::test]
+    async fn test_block_height_failures_do_not_stop_claiming_or_revealing() {
+        // Settlement is over at the first reading, then the height endpoint fails
+        let heights = serve_block_heights(vec![120, 0]).await;
+        let mut orchestrator = orchestrator_with_heights(OrchestratorConfig::default(), Some(heights)).await;
+        orchestrator.tx_manager = Some(offline_tx_manager());
+        orchestrator.epoch_cache.insert(&orchestrator.config.contract_address, epoch_info_in(7, PhaseInfo::Settlement { ends_at: 100 }));
+        
+        // Tried again later, with nothing queued in the meantime
+        assert_eq!(orchestrator.try_claim(7).await.unwrap(), ClaimProgress::NotReady);
+        assert!(orchestrator.tx_manager.as_ref().unwrap().get_queue().await.is_empty());
+        
+        // Neither the phase nor the height can be read: keep the reveal window open
+        orchestrator.epoch_cache.invalidate();
+        assert!(!orchestrator.is_past_reveal_window().await.unwrap());
+        
+        // A commit or reveal isn't sent blind: the pass is skipped and tried again
+        orchestrator.state.epoch = 7;
+        for (phase, window) in [
+            (MiningPhase::Committing(commitment(7)), PhaseInfo::Commit { ends_at: 1_000 }),
+            (MiningPhase::Revealing(commitment(7)), PhaseInfo::Reveal { ends_at: 1_000 }),
+        ] {
+            orchestrator.state.phase = phase.clone();
+            orchestrator.epoch_cache.insert(&orchestrator.config.contract_address, epoch_info_in(7, window));
+            orchestrator.step(7).await.unwrap();
+            assert_eq!(orchestrator.state.phase, phase);
+            assert!(orchestrator.tx_manager.as_ref().unwrap().get_queue().await.is_empty());
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_failure_pause_does_not_block_the_loop() {
+        let config = OrchestratorConfig {
+            max_failed_epochs: 1,
+            failure_action: FailureAction::Pause,
+            failure_pause_secs: 3600,
+            ..OrchestratorConfig::default()
+        };
+        let mut orchestrator = offline_orchestrator(config).await;
+        orchestrator.state.failure_streak.record_failure(6);
+        
+        // The breaker trips and returns straight away, leaving a deadline behind
+        tokio::time::timeout(Duration::from_secs(5), orchestrator.check_failure_streak(7)).await
+            .expect("failure pause blocked the loop")
+            .unwrap();
+        assert!(orchestrator.failure_pause_remaining().unwrap().unwrap() > Duration::from_secs(3500));
+        assert_eq!(orchestrator.state.failure_streak.count, 1);
+        
+        // Once the deadline passes, mining resumes with a fresh streak
+        orchestrator.failure_pause_until = Some(std::time::Instant::now());
+        assert_eq!(orchestrator.failure_pause_remaining().unwrap(), None);
+        assert_eq!(orchestrator.state.failure_streak, FailureStreak::default());
+    }
+    
+    #[tokio::test]
+    async fn test_only_contract_rejections_use_up_reveal_attempts() {
+        use transaction_manager::TransactionStatus;
+        let config = OrchestratorConfig {
+            max_reveal_attempts: 2,
+            reveal_confirmation_timeout_secs: 0,
+            confirmation_poll_ms: 1,
+            ..OrchestratorConfig::default()
+        };
+        let heights = serve_block_heights(vec![500]).await;
+        let mut orchestrator = orchestrator_with_heights(config, Some(heights)).await;
+        orchestrator.tx_manager = Some(offline_tx_manager());
+        orchestrator.state.epoch = 7;
+        orchestrator.state.phase = MiningPhase::Revealing(commitment(7));
+        let reveal_window = || epoch_info_in(7, PhaseInfo::Reveal { ends_at: 1_000 });
+        
+        // Nothing sends the reveals, so every wait times out - transient, however often
+        for _ in 0..3 {
+            orchestrator.epoch_cache.insert(&orchestrator.config.contract_address, reveal_window());
+            orchestrator.step(7).await.unwrap();
+            assert_eq!(orchestrator.state.phase, MiningPhase::Revealing(commitment(7)));
+        }
+        assert_eq!(orchestrator.reveal_attempts, 0);
+        
+        // The contract rejects the next two reveals (ids 4 and 5)
+        let tx_manager = orchestrator.tx_manager.as_ref().unwrap();
+        for tx_id in [4, 5] {
+            let error = "Failed after 1 retries: invalid digest: execute wasm contract failed".to_string();
+            tx_manager.complete(tx_id, TransactionStatus::Failed { error }).await;
+        }
+        orchestrator.epoch_cache.insert(&orchestrator.config.contract_address, reveal_window());
+        orchestrator.step(7).await.unwrap();
+        assert_eq!(orchestrator.reveal_attempts, 1);
+        assert_eq!(orchestrator.state.phase, MiningPhase::Revealing(commitment(7)));
+        
+        orchestrator.epoch_cache.insert(&orchestrator.config.contract_address, reveal_window());
+        orchestrator.step(7).await.unwrap();
+        assert_eq!(orchestrator.state.phase, MiningPhase::Idle);
+    }
+    
+    #[tokio::test]
+    async fn test_deferred_claim_runs_alongside_the_next_epoch() {
+        use transaction_manager::TransactionStatus;
+        let heights = serve_block_heights(vec![500]).await;
+        let mut orchestrator = orchestrator_with_heights(OrchestratorConfig::default(), Some(heights)).await;
+        orchestrator.tx_manager = Some(offline_tx_manager());
+        orchestrator.state = MiningState { epoch: 8, phase: MiningPhase::FindingSolution, pending_claims: vec![7], ..MiningState::default() };
+        let contract = orchestrator.config.contract_address.clone();
+        
+        // Nothing sends the finalize (id 1) or the claim (id 2), yet no pass waits on them
+        for _ in 0..2 {
+            orchestrator.epoch_cache.insert(&contract, epoch_info_in(8, PhaseInfo::Commit { ends_at: 1_000 }));
+            tokio::time::timeout(Duration::from_secs(1), orchestrator.process_pending_claim(8)).await
+                .expect("deferred finalize blocked the state machine")
+                .unwrap();
+            assert!(matches!(orchestrator.deferred_claim, Some((7, DeferredClaim::Finalizing { .. }))));
+        }
+        orchestrator.deferred_claim = Some((7, DeferredClaim::Finalizing { claim_at: std::time::Instant::now() }));
+        for _ in 0..2 {
+            tokio::time::timeout(Duration::from_secs(1), orchestrator.process_pending_claim(8)).await
+                .expect("deferred claim blocked the state machine")
+                .unwrap();
+            assert!(matches!(orchestrator.deferred_claim, Some((7, DeferredClaim::Claiming { tx_id: 2, .. }))));
+        }
+        
+        // Epoch 8 is revealed (id 3) while epoch 7's claim is still in flight
+        let tx_manager = orchestrator.tx_manager.as_ref().unwrap();
+        tx_manager.complete(3, TransactionStatus::Success { tx_hash: "REVEAL".to_string() }).await;
+        orchestrator.state.phase = MiningPhase::Revealing(commitment(8));
+        orchestrator.epoch_cache.insert(&contract, epoch_info_in(8, PhaseInfo::Reveal { ends_at: 1_000 }));
+        orchestrator.step(8).await.unwrap();
+        assert_eq!(orchestrator.state.phase, MiningPhase::Claiming(8));
+        assert_eq!(orchestrator.state.pending_claims, vec![7]);
+        
+        // The claim lands and the next pass records it
+        let tx_manager = orchestrator.tx_manager.as_ref().unwrap();
+        tx_manager.complete(2, TransactionStatus::AlreadyApplied { reason: "claimed".to_string() }).await;
+        orchestrator.process_pending_claim(8).await.unwrap();
+        assert_eq!(orchestrator.deferred_claim, None);
+        assert!(orchestrator.state.pending_claims.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_deferred_claim_timeout_is_retried_not_dropped() {
+        let heights = serve_block_heights(vec![500]).await;
+        let config = OrchestratorConfig { claim_confirmation_timeout_secs: 0, ..OrchestratorConfig::default() };
+        let mut orchestrator = orchestrator_with_heights(config, Some(heights)).await;
+        orchestrator.tx_manager = Some(offline_tx_manager());
+        orchestrator.state = MiningState { epoch: 8, phase: MiningPhase::FindingSolution, pending_claims: vec![5, 6, 7], ..MiningState::default() };
+        let contract = orchestrator.config.contract_address.clone();
+        
+        // Three epochs behind is still claimed, not dropped as too old
+        orchestrator.epoch_cache.insert(&contract, epoch_info_in(8, PhaseInfo::Commit { ends_at: 1_000 }));
+        orchestrator.process_pending_claim(8).await.unwrap();
+        assert!(matches!(orchestrator.deferred_claim, Some((5, DeferredClaim::Finalizing { .. }))));
+        
+        // The claim is never confirmed: it stays pending and is retried after a backoff
+        let queued_at = std::time::Instant::now() - Duration::from_secs(1);
+        orchestrator.deferred_claim = Some((5, DeferredClaim::Claiming { tx_id: 2, queued_at }));
+        orchestrator.process_pending_claim(8).await.unwrap();
+        assert_eq!(orchestrator.deferred_claim, None);
+        assert_eq!(orchestrator.state.pending_claims, vec![5, 6, 7]);
+        assert!(orchestrator.claim_retries.wait_remaining(5, std::time::Instant::now()).is_some());
+    }
+    
+    #[tokio::test]
+    async fn test_epoch_advance_restarts_mining_only_when_enabled() {
+        for restart in [true, false] {
+            let config = OrchestratorConfig { restart_on_epoch_advance: restart, worker_count: 1, ..OrchestratorConfig::default() };
+            let mut orchestrator = offline_orchestrator(config).await;
+            orchestrator.state = MiningState { epoch: 7, phase: MiningPhase::FindingSolution, ..MiningState::default() };
+            orchestrator.epoch_cache.insert(
+                &orchestrator.config.contract_address,
+                epoch_info_in(8, PhaseInfo::Commit { ends_at: 1_000 }),
+            );
+            
+            orchestrator.abandon_stale_epoch(7, 8).await.unwrap();
+            if restart {
+                assert_eq!(orchestrator.state.phase, MiningPhase::FindingSolution);
+                assert_eq!(orchestrator.state.epoch, 8);
+            } else {
+                assert_eq!(orchestrator.state.phase, MiningPhase::Idle);
+                assert_eq!(orchestrator.state.epoch, 7);
+            }
+            orchestrator.engine.stop_mining().await.unwrap();
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_hung_telemetry_backend_does_not_hold_up_the_state_machine() {
+        // Accepts connections but never answers, so every POST hangs until the client timeout
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/api/telemetry", listener.local_addr().unwrap());
+        let reporter = Arc::new(
+            EnhancedTelemetryReporter::new("inj1test".to_string(), "test".to_string())
+                .unwrap()
+                .with_endpoint(endpoint),
+        );
+        let mut orchestrator = offline_orchestrator(OrchestratorConfig::default()).await;
+        orchestrator.telemetry_sender = Some(TelemetrySender::spawn(reporter.clone(), TELEMETRY_QUEUE_CAPACITY));
+        orchestrator.telemetry_reporter = Some(reporter);
+        orchestrator.state.epoch = 7;
+        
+        // Each reveal records stats and queues an update; the second lands while the first POST hangs
+        for _ in 0..2 {
+            orchestrator.state.phase = MiningPhase::Revealing(commitment(7));
+            orchestrator.epoch_cache.insert(
+                &orchestrator.config.contract_address,
+                epoch_info_in(7, PhaseInfo::Reveal { ends_at: 1_000 }),
+            );
+            tokio::time::timeout(Duration::from_secs(3), orchestrator.step(7)).await
+                .expect("telemetry held up the state machine")
+                .unwrap();
+            assert_eq!(orchestrator.state.phase, MiningPhase::Claiming(7));
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+}
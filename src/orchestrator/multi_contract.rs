@@ -0,0 +1,160 @@
+/// Mining several contracts at once (e.g. the old and new contract during a migration) -
+/// one orchestrator per contract, sharing the chain client, wallet and broadcast limiter.
+/// Contracts after the first get their own state, metrics and heartbeat files
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::{MiningStatistics, OrchestratorConfig};
+
+/// How often the combined stats are logged when mining more than one contract
+pub const AGGREGATE_STATS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `path` with the contract address inserted before the extension:
+/// `gmine_miner.state` -> `gmine_miner.inj1....state`
+pub fn contract_scoped_path(path: &Path, contract: &str) -> PathBuf {
+    let name = match (path.file_stem(), path.extension()) {
+        (Some(stem), Some(ext)) => format!("{}.{}.{}", stem.to_string_lossy(), contract, ext.to_string_lossy()),
+        (Some(stem), None) => format!("{}.{}", stem.to_string_lossy(), contract),
+        _ => contract.to_string(),
+    };
+    path.with_file_name(name)
+}
+
+/// Split `total` workers across `contracts` as evenly as possible, at least one each
+pub fn split_workers(total: usize, contracts: usize) -> Vec<usize> {
+    let contracts = contracts.max(1);
+    (0..contracts)
+        .map(|i| (total / contracts + usize::from(i < total % contracts)).max(1))
+        .collect()
+}
+
+impl OrchestratorConfig {
+    /// One config per contract: `contract_address` followed by `additional_contracts`.
+    /// The first keeps this config's files (so an existing state file carries over);
+    /// the others get contract-scoped files and a miner id of their own. Workers and
+    /// the adaptive thread bounds are divided between them
+    pub fn per_contract(&self) -> Vec<OrchestratorConfig> {
+        if self.additional_contracts.is_empty() {
+            return vec![self.clone()];
+        }
+        let contracts: Vec<&String> = std::iter::once(&self.contract_address)
+            .chain(&self.additional_contracts)
+            .collect();
+        let workers = split_workers(self.worker_count, contracts.len());
+        let max_threads = split_workers(self.max_threads, contracts.len());
+
+        contracts.into_iter().enumerate().map(|(i, contract)| {
+            let mut config = self.clone();
+            config.contract_address = contract.clone();
+            config.additional_contracts = Vec::new();
+            config.worker_count = workers[i];
+            config.max_threads = max_threads[i];
+            config.min_threads = self.min_threads.min(max_threads[i]);
+            if i > 0 {
+                config.state_file = contract_scoped_path(&self.state_file, contract);
+                config.metrics_file = self.metrics_file.as_deref().map(|path| contract_scoped_path(path, contract));
+                config.heartbeat_file = self.heartbeat_file.as_deref().map(|path| contract_scoped_path(path, contract));
+                config.miner_id = None;
+            }
+            config
+        }).collect()
+    }
+}
+
+/// Totals across the orchestrators of one process
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AggregateStats {
+    pub contracts: usize,
+    pub hashrate: f64,
+    pub total_hashes: u64,
+    pub solutions_found: u64,
+    pub solutions_submitted: u64,
+    pub solutions_accepted: u64,
+    pub errors: u64,
+}
+
+impl AggregateStats {
+    pub fn from_stats<'a>(stats: impl IntoIterator<Item = &'a MiningStatistics>) -> Self {
+        stats.into_iter().fold(Self::default(), |mut total, stats| {
+            total.contracts += 1;
+            total.hashrate += stats.current_hashrate;
+            total.total_hashes += stats.total_hashes;
+            total.solutions_found += stats.solutions_found;
+            total.solutions_submitted += stats.solutions_submitted;
+            total.solutions_accepted += stats.solutions_accepted;
+            total.errors += stats.connection_errors + stats.mining_errors;
+            total
+        })
+    }
+}
+
+impl std::fmt::Display for AggregateStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} contracts: {:.1} H/s, {} hashes, {} found / {} submitted / {} accepted, {} errors",
+            self.contracts, self.hashrate, self.total_hashes,
+            self.solutions_found, self.solutions_submitted, self.solutions_accepted, self.errors
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_scoped_path() {
+        assert_eq!(contract_scoped_path(Path::new("/var/gmine/gmine_miner.state"), "inj1abc"), PathBuf::from("/var/gmine/gmine_miner.inj1abc.state"));
+        assert_eq!(contract_scoped_path(Path::new("heartbeat"), "inj1abc"), PathBuf::from("heartbeat.inj1abc"));
+    }
+
+    #[test]
+    fn test_split_workers() {
+        assert_eq!(split_workers(8, 2), vec![4, 4]);
+        assert_eq!(split_workers(7, 3), vec![3, 2, 2]);
+        // Every contract mines, even with fewer workers than contracts
+        assert_eq!(split_workers(1, 2), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_per_contract() {
+        let config = OrchestratorConfig {
+            state_file: PathBuf::from("miner.state"),
+            contract_address: "inj1old".to_string(),
+            additional_contracts: vec!["inj1new".to_string()],
+            worker_count: 6,
+            max_threads: 6,
+            miner_id: Some(uuid::Uuid::nil()),
+            ..OrchestratorConfig::default()
+        };
+        let configs = config.per_contract();
+        assert_eq!(configs.len(), 2);
+
+        assert_eq!(configs[0].contract_address, "inj1old");
+        assert_eq!(configs[0].state_file, PathBuf::from("miner.state"));
+        assert_eq!(configs[0].miner_id, Some(uuid::Uuid::nil()));
+
+        assert_eq!(configs[1].contract_address, "inj1new");
+        assert_eq!(configs[1].state_file, PathBuf::from("miner.inj1new.state"));
+        assert_eq!(configs[1].miner_id, None);
+        assert!(configs.iter().all(|c| c.worker_count == 3 && c.additional_contracts.is_empty()));
+
+        let single = OrchestratorConfig::default().per_contract();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].worker_count, OrchestratorConfig::default().worker_count);
+    }
+
+    #[test]
+    fn test_aggregate_stats() {
+        let a = MiningStatistics { current_hashrate: 100.0, total_hashes: 1_000, solutions_found: 2, solutions_accepted: 1, mining_errors: 1, ..MiningStatistics::default() };
+        let b = MiningStatistics { current_hashrate: 50.5, total_hashes: 500, solutions_found: 1, connection_errors: 2, ..MiningStatistics::default() };
+        let total = AggregateStats::from_stats([&a, &b]);
+        assert_eq!(total.contracts, 2);
+        assert_eq!(total.hashrate, 150.5);
+        assert_eq!(total.total_hashes, 1_500);
+        assert_eq!(total.solutions_found, 3);
+        assert_eq!(total.solutions_accepted, 1);
+        assert_eq!(total.errors, 3);
+    }
+}
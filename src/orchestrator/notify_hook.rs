@@ -0,0 +1,110 @@
+/// User notification hook - runs a configured command on key events (solution found,
+/// reward claimed, errors) with the details as arguments, e.g. `notify-send` for a
+/// desktop notification or a script that plays a sound. Fire and forget: the command
+/// is spawned and never waited on by the miner
+use anyhow::{Result, anyhow};
+use std::process::Stdio;
+
+/// Something worth telling the operator about
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotifyEvent {
+    SolutionFound { epoch: u64, difficulty: u8 },
+    /// `reward` in micro POWER, when it could be read from the claim tx
+    RewardClaimed { epoch: u64, reward: Option<u64> },
+    Error { epoch: u64, message: String },
+}
+
+impl NotifyEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NotifyEvent::SolutionFound { .. } => "solution_found",
+            NotifyEvent::RewardClaimed { .. } => "reward_claimed",
+            NotifyEvent::Error { .. } => "error",
+        }
+    }
+
+    /// Arguments appended to the command: event name, epoch, then a human-readable detail
+    pub fn args(&self) -> Vec<String> {
+        let (epoch, detail) = match self {
+            NotifyEvent::SolutionFound { epoch, difficulty } => (epoch, format!("Solution found at difficulty {}", difficulty)),
+            NotifyEvent::RewardClaimed { epoch, reward: Some(micro) } => (epoch, format!("Claimed {} POWER", *micro as f64 / 1_000_000.0)),
+            NotifyEvent::RewardClaimed { epoch, reward: None } => (epoch, "Reward claimed".to_string()),
+            NotifyEvent::Error { epoch, message } => (epoch, message.clone()),
+        };
+        vec![self.name().to_string(), epoch.to_string(), detail]
+    }
+}
+
+/// The configured command, split into program and leading arguments
+#[derive(Debug, Clone)]
+pub struct NotifyHook {
+    program: String,
+    args: Vec<String>,
+}
+
+impl NotifyHook {
+    /// `command` is split on whitespace - wrap anything more involved in a script
+    pub fn new(command: &str) -> Result<Self> {
+        let mut parts = command.split_whitespace().map(str::to_string);
+        let program = parts.next().ok_or_else(|| anyhow!("Notify command is empty"))?;
+        Ok(Self { program, args: parts.collect() })
+    }
+
+    pub fn command(&self) -> String {
+        std::iter::once(self.program.as_str()).chain(self.args.iter().map(String::as_str)).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Start the command for `event` without waiting for it; failures are only logged
+    pub fn notify(&self, event: &NotifyEvent) {
+        let spawned = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .args(event.args())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                log::warn!("Notify command {} could not be started: {}", self.program, e);
+                return;
+            }
+        };
+        // Reap it in the background so it doesn't linger as a zombie
+        let name = event.name();
+        tokio::spawn(async move {
+            match child.wait().await {
+                Ok(status) if !status.success() => log::warn!("Notify command for {} exited with {}", name, status),
+                Ok(_) => {}
+                Err(e) => log::warn!("Notify command for {} failed: {}", name, e),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_args() {
+        assert_eq!(
+            NotifyEvent::SolutionFound { epoch: 42, difficulty: 18 }.args(),
+            vec!["solution_found", "42", "Solution found at difficulty 18"]
+        );
+        assert_eq!(
+            NotifyEvent::RewardClaimed { epoch: 42, reward: Some(2_500_000) }.args(),
+            vec!["reward_claimed", "42", "Claimed 2.5 POWER"]
+        );
+        assert_eq!(NotifyEvent::Error { epoch: 7, message: "Reveal failed".to_string() }.args()[0], "error");
+    }
+
+    #[test]
+    fn test_new_splits_command() {
+        let hook = NotifyHook::new("  notify-send -a gmine ").unwrap();
+        assert_eq!(hook.program, "notify-send");
+        assert_eq!(hook.args, vec!["-a", "gmine"]);
+        assert_eq!(hook.command(), "notify-send -a gmine");
+        assert!(NotifyHook::new("   ").is_err());
+    }
+}
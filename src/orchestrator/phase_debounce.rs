@@ -0,0 +1,83 @@
+/// Phase debouncing - around phase boundaries `query_epoch_info` can briefly report
+/// a stale or transitional phase, so commit/reveal decisions only act on a phase
+/// once it has been seen on N consecutive polls
+use std::mem::{discriminant, Discriminant};
+
+use crate::chain::queries::PhaseInfo;
+
+#[derive(Debug)]
+pub struct PhaseDebouncer {
+    /// Consecutive identical readings needed before a phase is trusted
+    required: u32,
+    /// Epoch and phase of the current streak
+    last: Option<(u64, Discriminant<PhaseInfo>)>,
+    /// Length of the current streak
+    count: u32,
+}
+
+impl PhaseDebouncer {
+    pub fn new(required: u32) -> Self {
+        Self {
+            required: required.max(1),
+            last: None,
+            count: 0,
+        }
+    }
+
+    /// Record a phase reading; returns true once it has been seen `required` times in a row
+    /// Only the phase kind is compared - `ends_at` may legitimately shift between polls
+    pub fn observe(&mut self, epoch: u64, phase: &PhaseInfo) -> bool {
+        let key = (epoch, discriminant(phase));
+        if self.last == Some(key) {
+            self.count = self.count.saturating_add(1);
+        } else {
+            if let Some((last_epoch, _)) = self.last {
+                if self.count < self.required {
+                    log::info!(
+                        "Debounced a transient phase reading for epoch {} (seen {}/{} times), now {:?} for epoch {}",
+                        last_epoch, self.count, self.required, phase, epoch
+                    );
+                }
+            }
+            self.last = Some(key);
+            self.count = 1;
+        }
+
+        let stable = self.count >= self.required;
+        if !stable {
+            log::debug!(
+                "Phase {:?} for epoch {} seen {}/{} times, waiting for confirmation",
+                phase, epoch, self.count, self.required
+            );
+        }
+        stable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_reading_is_trusted_by_default() {
+        let mut debouncer = PhaseDebouncer::new(1);
+        assert!(debouncer.observe(5, &PhaseInfo::Commit { ends_at: 100 }));
+        assert!(debouncer.observe(5, &PhaseInfo::Reveal { ends_at: 120 }));
+    }
+
+    #[test]
+    fn test_transient_reading_is_debounced() {
+        let mut debouncer = PhaseDebouncer::new(2);
+        assert!(!debouncer.observe(5, &PhaseInfo::Commit { ends_at: 100 }));
+        // ends_at moving doesn't break the streak
+        assert!(debouncer.observe(5, &PhaseInfo::Commit { ends_at: 101 }));
+
+        // A one-off Reveal reading is ignored and the Commit streak restarts
+        assert!(!debouncer.observe(5, &PhaseInfo::Reveal { ends_at: 120 }));
+        assert!(!debouncer.observe(5, &PhaseInfo::Commit { ends_at: 101 }));
+        assert!(debouncer.observe(5, &PhaseInfo::Commit { ends_at: 101 }));
+
+        // Same phase in a new epoch is a new reading
+        assert!(!debouncer.observe(6, &PhaseInfo::Commit { ends_at: 200 }));
+    }
+}
@@ -0,0 +1,234 @@
+/// Profitability estimation - combines difficulty, hashrate and claim history
+/// into a rough expected-value figure so miners can tell if they're net-positive
+use serde::{Serialize, Deserialize};
+use std::time::Instant;
+
+/// Smoothing factor for the exponential moving averages
+const EMA_ALPHA: f64 = 0.2;
+/// Default epoch length before we've observed a real one (50 blocks)
+const DEFAULT_EPOCH_SECS: f64 = 50.0;
+/// Default fraction of an epoch spent hashing (commit phase is 30 of 50 blocks)
+const COMMIT_WINDOW_FRACTION: f64 = 0.6;
+
+/// Fees of one commit + reveal attempt, as the client currently pays them
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttemptGas {
+    /// Commit plus reveal gas limit
+    pub gas_limit: u64,
+    /// Base units of `fee_denom` per gas
+    pub gas_price: u128,
+    pub fee_denom: String,
+}
+
+impl AttemptGas {
+    /// Fee in display units: whole INJ for `inj` (18 decimals), base units for any
+    /// other denom, whose decimals aren't known here
+    fn cost(&self) -> (f64, String) {
+        let base = self.gas_limit as u128 * self.gas_price;
+        if self.fee_denom == "inj" {
+            (base as f64 / 1e18, "INJ".to_string())
+        } else {
+            (base as f64, self.fee_denom.clone())
+        }
+    }
+}
+
+/// Snapshot of the current profitability estimate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfitabilityEstimate {
+    /// Difficulty the estimate was computed against
+    pub difficulty: u8,
+    /// Smoothed hashrate in H/s
+    pub hashrate: f64,
+    /// Probability of finding a qualifying solution in one epoch (0.0 - 1.0)
+    pub win_probability: f64,
+    /// Expected POWER per epoch attempt (None until we have claim history)
+    pub expected_reward_power: Option<f64>,
+    /// Expected POWER per hour (None until we have claim history)
+    pub reward_per_hour_power: Option<f64>,
+    /// Gas cost of one commit + reveal attempt, in `fee_unit`
+    #[serde(alias = "gas_cost_inj")]
+    pub gas_cost: f64,
+    /// What fees are paid in: `INJ`, or the raw denom for other fee tokens
+    #[serde(default = "default_fee_unit")]
+    pub fee_unit: String,
+    /// Price of 1 POWER, in `fee_unit`, at which an attempt breaks even
+    #[serde(alias = "break_even_inj_per_power")]
+    pub break_even_per_power: Option<f64>,
+}
+
+fn default_fee_unit() -> String {
+    "INJ".to_string()
+}
+
+impl ProfitabilityEstimate {
+    /// One-line summary for periodic logs
+    pub fn summary(&self) -> String {
+        let reward = match self.expected_reward_power {
+            Some(r) => format!("{:.4} POWER", r),
+            None => "n/a (no claims yet)".to_string(),
+        };
+        format!(
+            "at difficulty {} and {:.2} MH/s, estimated win probability is {:.2}%, expected reward {}, gas cost {:.6} {} per attempt",
+            self.difficulty,
+            self.hashrate / 1_000_000.0,
+            self.win_probability * 100.0,
+            reward,
+            self.gas_cost,
+            self.fee_unit,
+        )
+    }
+}
+
+/// Tracks smoothed hashrate, reward and epoch length
+pub struct ProfitabilityTracker {
+    hashrate_ema: Option<f64>,
+    reward_ema: Option<f64>,
+    epoch_secs_ema: Option<f64>,
+    difficulty: u8,
+    last_epoch: Option<(u64, Instant)>,
+}
+
+impl ProfitabilityTracker {
+    pub fn new() -> Self {
+        Self {
+            hashrate_ema: None,
+            reward_ema: None,
+            epoch_secs_ema: None,
+            difficulty: 0,
+            last_epoch: None,
+        }
+    }
+
+    /// Record the difficulty of a newly started epoch and measure epoch length
+    pub fn record_epoch(&mut self, epoch: u64, difficulty: u8) {
+        self.difficulty = difficulty;
+        let now = Instant::now();
+        if let Some((last_epoch, last_time)) = self.last_epoch {
+            // Only consecutive epochs give a meaningful duration
+            if epoch == last_epoch + 1 {
+                let secs = now.duration_since(last_time).as_secs_f64();
+                self.epoch_secs_ema = Some(ema(self.epoch_secs_ema, secs));
+            }
+        }
+        if self.last_epoch.map_or(true, |(e, _)| e != epoch) {
+            self.last_epoch = Some((epoch, now));
+        }
+    }
+
+    /// Feed a hashrate sample (H/s)
+    pub fn record_hashrate(&mut self, hashrate: f64) {
+        if hashrate > 0.0 {
+            self.hashrate_ema = Some(ema(self.hashrate_ema, hashrate));
+        }
+    }
+
+    /// Record the outcome of a claim for a revealed epoch (0 for no reward)
+    pub fn record_reward(&mut self, power_micro: u64) {
+        let power = power_micro as f64 / 1_000_000.0;
+        self.reward_ema = Some(ema(self.reward_ema, power));
+    }
+
+    /// Compute the current estimate for attempts costing `gas`
+    pub fn estimate(&self, gas: &AttemptGas) -> ProfitabilityEstimate {
+        let hashrate = self.hashrate_ema.unwrap_or(0.0);
+        let epoch_secs = self.epoch_secs_ema.unwrap_or(DEFAULT_EPOCH_SECS);
+        let win_probability = win_probability(hashrate, self.difficulty, epoch_secs * COMMIT_WINDOW_FRACTION);
+        let (gas_cost, fee_unit) = gas.cost();
+
+        let expected_reward_power = self.reward_ema.map(|r| r * win_probability);
+        let reward_per_hour_power = expected_reward_power.map(|r| r * 3600.0 / epoch_secs);
+        let break_even_per_power = expected_reward_power
+            .filter(|r| *r > 0.0)
+            .map(|r| gas_cost / r);
+
+        ProfitabilityEstimate {
+            difficulty: self.difficulty,
+            hashrate,
+            win_probability,
+            expected_reward_power,
+            reward_per_hour_power,
+            gas_cost,
+            fee_unit,
+            break_even_per_power,
+        }
+    }
+}
+
+impl Default for ProfitabilityTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn ema(prev: Option<f64>, sample: f64) -> f64 {
+    match prev {
+        Some(p) => p + EMA_ALPHA * (sample - p),
+        None => sample,
+    }
+}
+
+/// Probability of at least one hash meeting `difficulty` leading zero bits
+/// within `window_secs` at `hashrate` H/s (Poisson approximation)
+pub fn win_probability(hashrate: f64, difficulty: u8, window_secs: f64) -> f64 {
+    let per_hash = 2f64.powi(-(difficulty as i32));
+    let expected = hashrate * window_secs * per_hash;
+    1.0 - (-expected).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_win_probability() {
+        assert_eq!(win_probability(0.0, 8, 30.0), 0.0);
+        // Expected one solution in the window -> ~63%
+        let p = win_probability(256.0, 8, 1.0);
+        assert!((p - 0.632).abs() < 0.001);
+        // Higher difficulty lowers the odds
+        assert!(win_probability(1000.0, 20, 30.0) < win_probability(1000.0, 10, 30.0));
+    }
+
+    fn inj_gas(gas_price: u128) -> AttemptGas {
+        AttemptGas { gas_limit: 550_000, gas_price, fee_denom: "inj".to_string() }
+    }
+
+    #[test]
+    fn test_estimate_without_rewards() {
+        let mut tracker = ProfitabilityTracker::new();
+        tracker.record_epoch(1, 10);
+        tracker.record_hashrate(1000.0);
+        let est = tracker.estimate(&inj_gas(500_000_000));
+        assert_eq!(est.difficulty, 10);
+        assert!(est.expected_reward_power.is_none());
+        assert!(est.gas_cost > 0.0);
+    }
+
+    #[test]
+    fn test_gas_cost_follows_price_and_denom() {
+        let tracker = ProfitabilityTracker::new();
+        let est = tracker.estimate(&inj_gas(160_000_000));
+        assert!((est.gas_cost - 0.000088).abs() < 1e-12);
+        assert_eq!(est.fee_unit, "INJ");
+        // A fetched price spike shows up in the estimate
+        assert!(tracker.estimate(&inj_gas(2_000_000_000)).gas_cost > est.gas_cost);
+
+        let peggy = AttemptGas { gas_limit: 550_000, gas_price: 2, fee_denom: "peggy0xusdt".to_string() };
+        let est = tracker.estimate(&peggy);
+        assert_eq!((est.gas_cost, est.fee_unit.as_str()), (1_100_000.0, "peggy0xusdt"));
+    }
+
+    #[test]
+    fn test_estimate_with_rewards() {
+        let mut tracker = ProfitabilityTracker::new();
+        tracker.record_epoch(1, 1);
+        tracker.record_hashrate(1_000_000.0);
+        tracker.record_reward(1_000_000);
+        let est = tracker.estimate(&inj_gas(500_000_000));
+        // Difficulty 1 at 1 MH/s is a near-certain win
+        assert!(est.win_probability > 0.99);
+        assert!((est.expected_reward_power.unwrap() - 1.0).abs() < 0.01);
+        assert!(est.break_even_per_power.is_some());
+    }
+}
@@ -0,0 +1,134 @@
+/// Mining schedule - restricts mining to daily time windows (local time), e.g. to
+/// mine only during off-peak electricity hours
+use anyhow::{Result, anyhow};
+use chrono::{Local, NaiveTime, Timelike};
+use std::fmt;
+use std::str::FromStr;
+
+/// A daily window; `end` before `start` means the window runs past midnight
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduleWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl ScheduleWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Set of daily windows during which mining is allowed
+/// Parsed from a comma-separated list like `22:00-06:00,12-14`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiningSchedule {
+    windows: Vec<ScheduleWindow>,
+}
+
+impl MiningSchedule {
+    pub fn windows(&self) -> &[ScheduleWindow] {
+        &self.windows
+    }
+
+    /// Whether mining is allowed at the given local time of day
+    pub fn is_active_at(&self, time: NaiveTime) -> bool {
+        self.windows.iter().any(|w| w.contains(time))
+    }
+
+    /// Whether mining is allowed right now (local time)
+    pub fn is_active_now(&self) -> bool {
+        let now = Local::now().time();
+        // Drop sub-second precision so window edges compare cleanly
+        self.is_active_at(now.with_nanosecond(0).unwrap_or(now))
+    }
+}
+
+/// Parse `HH:MM` or a bare hour `HH`
+fn parse_time(s: &str) -> Result<NaiveTime> {
+    let s = s.trim();
+    let parsed = if s.contains(':') {
+        NaiveTime::parse_from_str(s, "%H:%M").ok()
+    } else {
+        s.parse::<u32>().ok().filter(|h| *h <= 24).and_then(|h| NaiveTime::from_hms_opt(h % 24, 0, 0))
+    };
+    parsed.ok_or_else(|| anyhow!("Invalid schedule time '{}' (expected HH:MM or an hour 0-24)", s))
+}
+
+impl FromStr for MiningSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let windows = s
+            .split(',')
+            .filter(|part| !part.trim().is_empty())
+            .map(|part| {
+                let (start, end) = part
+                    .split_once('-')
+                    .ok_or_else(|| anyhow!("Invalid schedule window '{}' (expected START-END)", part.trim()))?;
+                let window = ScheduleWindow { start: parse_time(start)?, end: parse_time(end)? };
+                if window.start == window.end {
+                    return Err(anyhow!("Schedule window '{}' is empty", part.trim()));
+                }
+                Ok(window)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if windows.is_empty() {
+            return Err(anyhow!("Schedule must contain at least one window"));
+        }
+        Ok(Self { windows })
+    }
+}
+
+impl fmt::Display for MiningSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let windows: Vec<String> = self
+            .windows
+            .iter()
+            .map(|w| format!("{}-{}", w.start.format("%H:%M"), w.end.format("%H:%M")))
+            .collect();
+        write!(f, "{}", windows.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_windows() {
+        let schedule: MiningSchedule = "22:00-06:30, 12-14".parse().unwrap();
+        assert_eq!(schedule.to_string(), "22:00-06:30,12:00-14:00");
+
+        // Overnight window wraps past midnight
+        assert!(schedule.is_active_at(at(23, 0)));
+        assert!(schedule.is_active_at(at(3, 0)));
+        assert!(!schedule.is_active_at(at(6, 30)));
+        assert!(!schedule.is_active_at(at(9, 0)));
+        // Daytime window, end exclusive
+        assert!(schedule.is_active_at(at(12, 0)));
+        assert!(!schedule.is_active_at(at(14, 0)));
+
+        // "24" means midnight
+        let evenings: MiningSchedule = "18-24".parse().unwrap();
+        assert!(evenings.is_active_at(at(23, 59)));
+        assert!(!evenings.is_active_at(at(0, 30)));
+    }
+
+    #[test]
+    fn test_invalid_schedules() {
+        assert!("".parse::<MiningSchedule>().is_err());
+        assert!("22".parse::<MiningSchedule>().is_err());
+        assert!("25-3".parse::<MiningSchedule>().is_err());
+        assert!("8:00-8:00".parse::<MiningSchedule>().is_err());
+        assert!("9:75-10".parse::<MiningSchedule>().is_err());
+    }
+}
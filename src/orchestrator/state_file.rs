@@ -0,0 +1,143 @@
+/// State file persistence - atomic writes plus a few rotated backups, so a corrupt
+/// or truncated state file (crash mid-write, full disk) falls back to a recent good copy
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Backups are rotated at most this often - the state is saved every few seconds
+/// while mining, and copies seconds apart don't add any safety
+pub const STATE_BACKUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Path of the `n`th most recent backup (`state.1` is the newest)
+pub fn backup_path(path: &Path, n: usize) -> PathBuf {
+    PathBuf::from(format!("{}.{}", path.display(), n))
+}
+
+/// Where an unreadable state file is moved once a backup has replaced it
+fn corrupt_path(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.corrupt", path.display()))
+}
+
+#[derive(Debug)]
+pub struct StateFile {
+    path: PathBuf,
+    /// Backups kept (0 = none)
+    backups: usize,
+    last_backup: Option<Instant>,
+}
+
+impl StateFile {
+    pub fn new(path: PathBuf, backups: usize) -> Self {
+        Self { path, backups, last_backup: None }
+    }
+
+    /// Write `state` to a temp file, sync it and rename it over the current file,
+    /// first rotating the current file into the backups if one is due
+    pub fn save<T: Serialize>(&mut self, state: &T) -> Result<()> {
+        let serialized = serde_json::to_string_pretty(state)?;
+
+        let temp_path = format!("{}.tmp", self.path.display());
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(serialized.as_bytes())?;
+        // Without the sync a crash can leave a renamed but empty file behind
+        file.sync_all()?;
+        drop(file);
+
+        let backup_due = self.backups > 0
+            && self.last_backup.map_or(true, |t| t.elapsed() >= STATE_BACKUP_INTERVAL);
+        if backup_due && self.path.exists() {
+            self.rotate()?;
+            self.last_backup = Some(Instant::now());
+        }
+
+        // Rename is atomic on most filesystems
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Shift `state.1..state.N-1` up by one and move the current file to `state.1`
+    fn rotate(&self) -> Result<()> {
+        for n in (1..self.backups).rev() {
+            let from = backup_path(&self.path, n);
+            if from.exists() {
+                fs::rename(&from, backup_path(&self.path, n + 1))?;
+            }
+        }
+        fs::rename(&self.path, backup_path(&self.path, 1))?;
+        Ok(())
+    }
+
+    /// Load the current file, falling back to the newest backup that parses
+    /// None if there is no usable state at all
+    pub fn load<T: DeserializeOwned>(&self) -> Option<T> {
+        match read(&self.path) {
+            Ok(state) => {
+                log::info!("Loaded saved state from {:?}", self.path);
+                return Some(state);
+            }
+            // A rotation interrupted by a crash leaves no current file but a good backup
+            Err(e) if self.path.exists() => log::warn!("State file {:?} is unreadable: {}", self.path, e),
+            Err(_) => {}
+        }
+
+        for n in 1..=self.backups {
+            let backup = backup_path(&self.path, n);
+            if !backup.exists() {
+                continue;
+            }
+            match read(&backup) {
+                Ok(state) => {
+                    log::warn!("Recovered state from backup {:?}", backup);
+                    // Keep the broken file for inspection, out of the rotation
+                    if self.path.exists() {
+                        if let Err(e) = fs::rename(&self.path, corrupt_path(&self.path)) {
+                            log::warn!("Failed to move aside unreadable state file: {}", e);
+                        }
+                    }
+                    return Some(state);
+                }
+                Err(e) => log::warn!("State backup {:?} is unreadable: {}", backup, e),
+            }
+        }
+        None
+    }
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let serialized = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&serialized)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_falls_back_to_newest_good_backup() {
+        let path = std::env::temp_dir().join(format!("gmine_state_{}.state", uuid::Uuid::new_v4()));
+        let mut state_file = StateFile::new(path.clone(), 2);
+
+        state_file.save(&1u64).unwrap();
+        for epoch in 2..=4u64 {
+            // Make every save due for a backup
+            state_file.last_backup = None;
+            state_file.save(&epoch).unwrap();
+        }
+        assert_eq!(read::<u64>(&backup_path(&path, 1)).unwrap(), 3);
+        assert_eq!(read::<u64>(&backup_path(&path, 2)).unwrap(), 2);
+        assert!(!backup_path(&path, 3).exists());
+
+        // Truncated by a crash
+        fs::write(&path, "{\"epo").unwrap();
+        fs::write(backup_path(&path, 1), "").unwrap();
+        assert_eq!(state_file.load::<u64>(), Some(2));
+        assert!(corrupt_path(&path).exists());
+
+        for file in [path.clone(), corrupt_path(&path), backup_path(&path, 1), backup_path(&path, 2)] {
+            let _ = fs::remove_file(file);
+        }
+    }
+}
@@ -1,16 +1,65 @@
 /// Mining statistics collection and reporting
 use serde::{Serialize, Deserialize};
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Number of recent find-to-commit latencies kept for percentiles
+const LATENCY_WINDOW: usize = 100;
+
+/// Distribution of recent find-to-commit latencies (milliseconds)
+/// High values mean submission, not hashing, is what risks missing the commit window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub avg_ms: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencySummary {
+    pub fn from_samples(samples: &[u64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        // Nearest-rank percentile
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1];
+        Some(Self {
+            count: sorted.len(),
+            avg_ms: sorted.iter().sum::<u64>() / sorted.len() as u64,
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            max_ms: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// How an `advance_epoch` transaction turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdvanceOutcome {
+    /// Ours landed
+    Advanced,
+    /// Someone else advanced the epoch first - harmless
+    AlreadyAdvanced,
+    /// The contract doesn't let this wallet advance epochs
+    Unauthorized,
+}
+
 /// Mining statistics snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MiningStatistics {
     // Performance metrics
     pub current_hashrate: f64,
     pub average_hashrate: f64,
+    /// Hashes computed since start - never reset between epochs
     pub total_hashes: u64,
+    /// Lifetime hashes of each worker slot
+    #[serde(default)]
+    pub worker_hashes: Vec<u64>,
     pub mining_duration: Duration,
     
     // Current state
@@ -25,6 +74,19 @@ pub struct MiningStatistics {
     pub solutions_submitted: u64,
     pub solutions_accepted: u64,
     pub best_solution_difficulty: Option<u8>,
+    #[serde(default)]
+    pub commit_latency: Option<LatencySummary>,
+    
+    // Epoch advancement
+    /// `advance_epoch` transactions of ours that landed
+    #[serde(default)]
+    pub epochs_advanced: u64,
+    /// `advance_epoch` attempts another miner beat us to
+    #[serde(default)]
+    pub advances_already_done: u64,
+    /// `advance_epoch` attempts the contract refused (permissioned advancement)
+    #[serde(default)]
+    pub advances_unauthorized: u64,
     
     // Errors
     pub connection_errors: u64,
@@ -38,6 +100,7 @@ impl Default for MiningStatistics {
             current_hashrate: 0.0,
             average_hashrate: 0.0,
             total_hashes: 0,
+            worker_hashes: Vec::new(),
             mining_duration: Duration::from_secs(0),
             current_epoch: 0,
             current_difficulty: 0,
@@ -48,6 +111,10 @@ impl Default for MiningStatistics {
             solutions_submitted: 0,
             solutions_accepted: 0,
             best_solution_difficulty: None,
+            commit_latency: None,
+            epochs_advanced: 0,
+            advances_already_done: 0,
+            advances_unauthorized: 0,
             connection_errors: 0,
             mining_errors: 0,
             last_error: None,
@@ -55,12 +122,20 @@ impl Default for MiningStatistics {
     }
 }
 
+impl MiningStatistics {
+    /// Average hashes it took to find a solution (None before the first one)
+    pub fn hashes_per_solution(&self) -> Option<f64> {
+        (self.solutions_found > 0).then(|| self.total_hashes as f64 / self.solutions_found as f64)
+    }
+}
+
 /// Statistics collector for mining operations
 pub struct StatsCollector {
     stats: Arc<RwLock<MiningStatistics>>,
     start_time: Option<Instant>,
     last_update: Instant,
     hash_count_window: Vec<(Instant, u64)>, // For calculating current hashrate
+    commit_latencies_ms: VecDeque<u64>,
 }
 
 impl StatsCollector {
@@ -70,6 +145,7 @@ impl StatsCollector {
             start_time: None,
             last_update: Instant::now(),
             hash_count_window: Vec::new(),
+            commit_latencies_ms: VecDeque::new(),
         }
     }
     
@@ -120,6 +196,15 @@ impl StatsCollector {
         }
     }
     
+    /// Bring the hash totals up to the engine's lifetime counters
+    pub async fn record_hash_totals(&mut self, total: u64, worker_hashes: Vec<u64>) {
+        let recorded = self.stats.read().await.total_hashes;
+        if total > recorded {
+            self.update_hashes(total - recorded).await;
+        }
+        self.stats.write().await.worker_hashes = worker_hashes;
+    }
+    
     /// Update mining phase
     pub async fn update_phase(&mut self, phase: &str) {
         let mut stats = self.stats.write().await;
@@ -147,12 +232,32 @@ impl StatsCollector {
         stats.solutions_submitted += 1;
     }
     
+    /// Record how long a solution took from being found to its commit landing
+    pub async fn record_commit_latency(&mut self, latency: Duration) {
+        self.commit_latencies_ms.push_back(latency.as_millis() as u64);
+        if self.commit_latencies_ms.len() > LATENCY_WINDOW {
+            self.commit_latencies_ms.pop_front();
+        }
+        let summary = LatencySummary::from_samples(self.commit_latencies_ms.make_contiguous());
+        self.stats.write().await.commit_latency = summary;
+    }
+    
     /// Record solution accepted
     pub async fn solution_accepted(&mut self) {
         let mut stats = self.stats.write().await;
         stats.solutions_accepted += 1;
     }
     
+    /// Record the outcome of an `advance_epoch` transaction
+    pub async fn record_advance(&mut self, outcome: AdvanceOutcome) {
+        let mut stats = self.stats.write().await;
+        match outcome {
+            AdvanceOutcome::Advanced => stats.epochs_advanced += 1,
+            AdvanceOutcome::AlreadyAdvanced => stats.advances_already_done += 1,
+            AdvanceOutcome::Unauthorized => stats.advances_unauthorized += 1,
+        }
+    }
+    
     /// Record error
     pub async fn record_error(&mut self, error_type: &str, message: String) {
         let mut stats = self.stats.write().await;
@@ -174,5 +279,45 @@ impl StatsCollector {
         *self.stats.write().await = MiningStatistics::default();
         self.start_time = None;
         self.hash_count_window.clear();
+        self.commit_latencies_ms.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_percentiles() {
+        assert_eq!(LatencySummary::from_samples(&[]), None);
+
+        let samples: Vec<u64> = (1..=20).map(|i| i * 100).collect();
+        let summary = LatencySummary::from_samples(&samples).unwrap();
+        assert_eq!(summary.count, 20);
+        assert_eq!(summary.avg_ms, 1050);
+        assert_eq!(summary.p50_ms, 1000);
+        assert_eq!(summary.p95_ms, 1900);
+        assert_eq!(summary.max_ms, 2000);
+
+        let single = LatencySummary::from_samples(&[750]).unwrap();
+        assert_eq!((single.p50_ms, single.p95_ms), (750, 750));
+    }
+
+    #[tokio::test]
+    async fn test_hash_totals_accumulate_across_epochs() {
+        let mut collector = StatsCollector::new();
+        collector.start_mining(1, 10, 0, 1000).await;
+        collector.record_hash_totals(600, vec![300, 300]).await;
+        collector.solution_found(12).await;
+
+        collector.start_mining(2, 10, 0, 1000).await;
+        collector.record_hash_totals(1_000, vec![500, 500]).await;
+        collector.solution_found(11).await;
+
+        let stats = collector.get_stats().await;
+        assert_eq!(stats.total_hashes, 1_000);
+        assert_eq!(stats.worker_hashes, vec![500, 500]);
+        assert_eq!(stats.hashes_per_solution(), Some(500.0));
+        assert_eq!(MiningStatistics::default().hashes_per_solution(), None);
     }
 }
\ No newline at end of file
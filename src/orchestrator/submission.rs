@@ -0,0 +1,85 @@
+/// Solution submission targets - commit/reveal on-chain, hand the solution to a
+/// mining pool over HTTP, or both
+use anyhow::{Result, anyhow};
+use gmine_mobile::proof::SolutionProof;
+use reqwest::{Client, ClientBuilder};
+use serde::Serialize;
+use std::time::Duration;
+
+const POOL_SUBMIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where found solutions go
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmissionTarget {
+    /// Commit and reveal directly on-chain (default)
+    OnChain,
+    /// POST the solution proof to a pool server, which does the commit/reveal
+    Pool {
+        url: String,
+        /// Sent as a bearer token
+        auth: Option<String>,
+    },
+}
+
+/// Body POSTed to the pool
+#[derive(Debug, Serialize)]
+struct PoolSubmission<'a> {
+    miner_label: &'a str,
+    proof: &'a SolutionProof,
+}
+
+/// HTTP client for a pool's solution endpoint
+pub struct PoolClient {
+    client: Client,
+    url: String,
+    auth: Option<String>,
+}
+
+impl PoolClient {
+    pub fn new(url: &str, auth: Option<String>) -> Result<Self> {
+        let client = ClientBuilder::new()
+            .timeout(POOL_SUBMIT_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            url: url.to_string(),
+            auth,
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Submit a solution; fails unless the pool answers with a 2xx status
+    pub async fn submit(&self, proof: &SolutionProof, miner_label: &str) -> Result<()> {
+        let mut request = self.client.post(&self.url).json(&PoolSubmission { miner_label, proof });
+        if let Some(ref auth) = self.auth {
+            request = request.bearer_auth(auth);
+        }
+
+        let response = request.send().await
+            .map_err(|e| anyhow!("Pool {} unreachable: {}", self.url, e))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Pool {} rejected solution ({}): {}", self.url, status, body.trim()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_submission_body() {
+        let proof = SolutionProof::new(12, "inj1testaddress123456789", &[1u8; 32], 99, &[2u8; 16], 9);
+        let body = serde_json::to_value(PoolSubmission { miner_label: "rig-01", proof: &proof }).unwrap();
+        assert_eq!(body["miner_label"], "rig-01");
+        assert_eq!(body["proof"]["epoch"], 12);
+        assert_eq!(body["proof"]["nonce"], "99");
+        assert_eq!(body["proof"]["miner"], "inj1testaddress123456789");
+    }
+}
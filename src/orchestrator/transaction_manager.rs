@@ -3,13 +3,23 @@
 
 use anyhow::{Result, anyhow};
 use serde::{Serialize, Deserialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, Semaphore};
 use tokio::time::{sleep, Duration};
 
-use crate::chain::InjectiveClient;
-// Messages are created inline as JSON
+use crate::chain::{InjectiveClient, ChainError, MessageData, PreparedTx};
+use crate::chain::queries::query_commitment;
+use crate::chain::messages::{
+    AdvanceEpochMsg, ClaimRewardMsg, CommitSolutionMsg, FinalizeEpochMsg, MessageBuilder, RevealSolutionMsg,
+    StakeTokensMsg,
+};
+use cosmwasm_std::Uint128;
+use super::stats::{AdvanceOutcome, StatsCollector};
+
+/// Gas limits commits and reveals are sent with (no simulation - they're time-critical)
+pub const COMMIT_GAS_LIMIT: u64 = 250_000;
+pub const REVEAL_GAS_LIMIT: u64 = 300_000;
 
 /// Transaction status
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -22,6 +32,12 @@ pub enum TransactionStatus {
     Success { tx_hash: String },
     /// Transaction failed after all retries
     Failed { error: String },
+    /// Transaction turned out to be unnecessary or not ours to send
+    /// (e.g. another miner already advanced the epoch) - not retried
+    Skipped { reason: String },
+    /// A retry found an earlier attempt's effect already on chain (its response was
+    /// lost), so it wasn't sent again - counts as success
+    AlreadyApplied { reason: String },
 }
 
 /// Transaction types supported by the manager
@@ -38,6 +54,9 @@ pub enum TransactionType {
         nonce: [u8; 8],
         digest: [u8; 16],
         salt: [u8; 32],
+        /// Reveal signed ahead of the window, broadcast as-is if still valid
+        #[serde(default)]
+        prepared: Option<PreparedTx>,
     },
     /// Claim reward transaction
     Claim {
@@ -49,6 +68,38 @@ pub enum TransactionType {
     },
     /// Advance epoch (permissionless) - moves current to next epoch
     AdvanceEpoch,
+    /// Claim reward, then stake POWER (micro units) in the same transaction - the stake
+    /// is a CW20 `send` from the POWER token to the mining contract
+    ClaimAndStake {
+        epoch: u64,
+        amount: u128,
+        duration_days: u64,
+    },
+}
+
+impl TransactionType {
+    /// Scheduling priority, lower goes first: a missed reveal loses the epoch, a late
+    /// commit loses the chance to reveal, and claims can wait
+    pub fn priority(&self) -> u8 {
+        match self {
+            TransactionType::Reveal { .. } => 0,
+            TransactionType::Commit { .. } => 1,
+            TransactionType::AdvanceEpoch | TransactionType::FinalizeEpoch { .. } => 2,
+            TransactionType::Claim { .. } | TransactionType::ClaimAndStake { .. } => 3,
+        }
+    }
+}
+
+/// Position of the transaction to send next: the most urgent one, oldest first among
+/// equals. Without `prioritize`, simply the oldest
+fn next_transaction(queue: &VecDeque<QueuedTransaction>, prioritize: bool) -> Option<usize> {
+    if !prioritize {
+        return (!queue.is_empty()).then_some(0);
+    }
+    queue.iter()
+        .enumerate()
+        .min_by_key(|(position, tx)| (tx.tx_type.priority(), *position))
+        .map(|(position, _)| position)
 }
 
 /// A transaction in the queue
@@ -66,6 +117,9 @@ pub struct QueuedTransaction {
     pub queued_at: u64,
     /// Estimated gas required
     pub gas_estimate: Option<u64>,
+    /// Hash of the previous attempt if its outcome was unknown (it may still land)
+    #[serde(default)]
+    pub last_tx_hash: Option<String>,
 }
 
 /// Configuration for transaction manager
@@ -83,6 +137,16 @@ pub struct TransactionManagerConfig {
     pub max_queue_size: usize,
     /// Contract address
     pub contract_address: String,
+    /// CW20 POWER token contract stakes are sent from (empty = staking unavailable)
+    pub power_token_address: String,
+    /// Maximum broadcasts in flight per signer
+    /// Sequence numbers are strictly ordered, so more than 1 invites sequence races
+    pub max_in_flight_per_signer: usize,
+    /// How long to wait for a previous attempt to show up on chain before resubmitting (milliseconds)
+    pub pending_tx_wait_ms: u64,
+    /// Send queued reveals before commits before claims (see `TransactionType::priority`)
+    /// instead of in the order they were queued
+    pub prioritize: bool,
 }
 
 impl Default for TransactionManagerConfig {
@@ -94,10 +158,44 @@ impl Default for TransactionManagerConfig {
             gas_price_multiplier: 1.1,
             max_queue_size: 100,
             contract_address: String::new(),
+            power_token_address: String::new(),
+            max_in_flight_per_signer: 1,
+            pending_tx_wait_ms: 6000,
+            prioritize: true,
         }
     }
 }
 
+/// Limits concurrent broadcasts per signer address
+/// Broadcasts for one signer are serialized (or capped), while different signers
+/// proceed in parallel. Share one limiter between managers that may use the same wallet.
+pub struct BroadcastLimiter {
+    permits_per_signer: usize,
+    semaphores: std::sync::Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl BroadcastLimiter {
+    pub fn new(permits_per_signer: usize) -> Self {
+        Self {
+            permits_per_signer: permits_per_signer.max(1),
+            semaphores: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+    
+    /// Wait for a broadcast slot for `signer`; the slot is released when the permit drops
+    pub async fn acquire(&self, signer: &str) -> OwnedSemaphorePermit {
+        let semaphore = {
+            let mut semaphores = self.semaphores.lock().unwrap_or_else(|e| e.into_inner());
+            semaphores
+                .entry(signer.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(self.permits_per_signer)))
+                .clone()
+        };
+        // The semaphore is never closed, so acquiring can't fail
+        semaphore.acquire_owned().await.expect("broadcast semaphore closed")
+    }
+}
+
 /// Manages transaction queue and submission with retry logic
 pub struct TransactionManager {
     /// Configuration
@@ -112,11 +210,18 @@ pub struct TransactionManager {
     is_running: Arc<RwLock<bool>>,
     /// Completed transactions (for status tracking)
     completed: Arc<RwLock<std::collections::HashMap<u64, TransactionStatus>>>,
+    /// Transactions whose caller gave up on them - never (re)sent
+    cancelled: Arc<RwLock<HashSet<u64>>>,
+    /// Per-signer broadcast concurrency limit
+    limiter: Arc<BroadcastLimiter>,
+    /// Where `advance_epoch` outcomes are counted
+    stats: Option<Arc<Mutex<StatsCollector>>>,
 }
 
 impl TransactionManager {
     /// Create a new transaction manager
     pub fn new(config: TransactionManagerConfig, client: Arc<RwLock<InjectiveClient>>) -> Self {
+        let limiter = Arc::new(BroadcastLimiter::new(config.max_in_flight_per_signer));
         Self {
             config,
             client,
@@ -124,9 +229,24 @@ impl TransactionManager {
             next_id: Arc::new(RwLock::new(1)),
             is_running: Arc::new(RwLock::new(false)),
             completed: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            cancelled: Arc::new(RwLock::new(HashSet::new())),
+            limiter,
+            stats: None,
         }
     }
     
+    /// Count `advance_epoch` outcomes in the miner's statistics
+    pub fn with_stats(mut self, stats: Arc<Mutex<StatsCollector>>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+    
+    /// Share a broadcast limiter with other managers (e.g. one per wallet)
+    pub fn with_limiter(mut self, limiter: Arc<BroadcastLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+    
     /// Start processing transactions in the background
     pub async fn start(&self) -> Result<()> {
         let mut running = self.is_running.write().await;
@@ -140,8 +260,12 @@ impl TransactionManager {
         let client = self.client.clone();
         let queue = self.queue.clone();
         let completed = self.completed.clone();
+        let cancelled = self.cancelled.clone();
         let is_running = self.is_running.clone();
         let config = self.config.clone();
+        let limiter = self.limiter.clone();
+        let stats = self.stats.clone();
+        let signer = self.client.read().await.address().to_string();
         
         // Spawn background processing task
         tokio::spawn(async move {
@@ -150,29 +274,80 @@ impl TransactionManager {
             while *is_running.read().await {
                 // Process next transaction in queue
                 let mut queue_guard = queue.write().await;
-                if let Some(mut tx) = queue_guard.pop_front() {
+                let next = next_transaction(&queue_guard, config.prioritize)
+                    .and_then(|position| queue_guard.remove(position));
+                if let Some(mut tx) = next {
                     drop(queue_guard); // Release lock while processing
                     
                     // Process transaction
                     tx.status = TransactionStatus::Processing;
                     log::info!("Processing transaction {}: {:?}", tx.id, tx.tx_type);
                     
-                    match Self::process_transaction(&client, &config, &mut tx).await {
+                    // An earlier attempt may have landed even though we never heard back
+                    if tx.retry_count > 0 {
+                        if let Some(reason) = Self::effect_on_chain(&client, &config, &tx.tx_type).await {
+                            log::info!("Transaction {}: {} - not resubmitting", tx.id, reason);
+                            let mut completed_guard = completed.write().await;
+                            completed_guard.insert(tx.id, TransactionStatus::AlreadyApplied { reason });
+                            continue;
+                        }
+                    }
+                    
+                    // Hold the signer's broadcast slot only while broadcasting, not during retry backoff
+                    let permit = limiter.acquire(&signer).await;
+                    let result = Self::process_transaction(&client, &config, &mut tx).await;
+                    drop(permit);
+                    
+                    match result {
                         Ok(tx_hash) => {
                             let status = TransactionStatus::Success { tx_hash: tx_hash.clone() };
                             tx.status = status.clone();
                             log::info!("Transaction {} succeeded: {}", tx.id, tx_hash);
+                            if let (TransactionType::AdvanceEpoch, Some(stats)) = (&tx.tx_type, &stats) {
+                                stats.lock().await.record_advance(AdvanceOutcome::Advanced).await;
+                            }
                             
                             // Store in completed map for status tracking
                             let mut completed_guard = completed.write().await;
                             completed_guard.insert(tx.id, status);
                         }
                         Err(e) => {
+                            // Losing the advance race (or not being allowed to advance) is not an error
+                            let advance_outcome = match tx.tx_type {
+                                TransactionType::AdvanceEpoch => Self::advance_outcome(ChainError::classify(&e)),
+                                _ => None,
+                            };
+                            if let Some(outcome) = advance_outcome {
+                                match outcome {
+                                    AdvanceOutcome::Unauthorized => log::warn!(
+                                        "Transaction {}: this wallet may not advance epochs, leaving it to others ({})", tx.id, e
+                                    ),
+                                    _ => log::info!("Transaction {}: epoch already advanced by another miner", tx.id),
+                                }
+                                if let Some(ref stats) = stats {
+                                    stats.lock().await.record_advance(outcome).await;
+                                }
+                                let mut completed_guard = completed.write().await;
+                                completed_guard.insert(tx.id, TransactionStatus::Skipped { reason: e.to_string() });
+                                continue;
+                            }
+                            
+                            // A retry rejected as a duplicate means an earlier attempt landed
+                            if tx.retry_count > 0 && Self::is_duplicate_of_earlier_attempt(&tx.tx_type, ChainError::classify(&e)) {
+                                log::info!("Transaction {}: earlier attempt already applied ({})", tx.id, e);
+                                let mut completed_guard = completed.write().await;
+                                completed_guard.insert(tx.id, TransactionStatus::AlreadyApplied { reason: e.to_string() });
+                                continue;
+                            }
+                            
                             // Enhanced error logging to understand failures
                             log::error!("Transaction {} ({:?}) failed: {}", tx.id, tx.tx_type, e);
                             
                             // Log specific error details
-                            if e.to_string().contains("Wrong phase") {
+                            let fee_capped = ChainError::classify(&e) == ChainError::FeeCapExceeded;
+                            if fee_capped {
+                                log::error!("FEE CAP: Transaction refused locally, not retrying (raise --max-fee-inj if intended)");
+                            } else if e.to_string().contains("Wrong phase") {
                                 log::error!("TIMING ERROR: Transaction arrived too late - phase already changed!");
                             } else if e.to_string().contains("gas") {
                                 log::error!("GAS ERROR: Insufficient gas or gas estimation failed");
@@ -199,8 +374,8 @@ impl TransactionManager {
                                 }
                             };
                             
-                            // Check if we should retry
-                            if tx.retry_count < max_retries {
+                            // Check if we should retry (a capped fee won't get cheaper by retrying)
+                            if !fee_capped && tx.retry_count < max_retries {
                                 tx.retry_count += 1;
                                 tx.status = TransactionStatus::Pending;
                                 
@@ -214,6 +389,12 @@ impl TransactionManager {
                                 
                                 sleep(Duration::from_millis(retry_delay)).await;
                                 
+                                // Cancelled while backing off - the caller has moved on
+                                if cancelled.read().await.contains(&tx.id) {
+                                    log::info!("Transaction {} cancelled, not retrying", tx.id);
+                                    continue;
+                                }
+                                
                                 // Re-queue for retry
                                 let mut queue_guard = queue.write().await;
                                 queue_guard.push_back(tx);
@@ -268,7 +449,19 @@ impl TransactionManager {
         digest: [u8; 16],
         salt: [u8; 32],
     ) -> Result<u64> {
-        let tx_type = TransactionType::Reveal { epoch, nonce, digest, salt };
+        self.queue_prepared_reveal(epoch, nonce, digest, salt, None).await
+    }
+    
+    /// Queue a reveal, optionally with a pre-signed transaction for it
+    pub async fn queue_prepared_reveal(
+        &self,
+        epoch: u64,
+        nonce: [u8; 8],
+        digest: [u8; 16],
+        salt: [u8; 32],
+        prepared: Option<PreparedTx>,
+    ) -> Result<u64> {
+        let tx_type = TransactionType::Reveal { epoch, nonce, digest, salt, prepared };
         self.queue_transaction(tx_type).await
     }
     
@@ -290,6 +483,19 @@ impl TransactionManager {
         self.queue_transaction(tx_type).await
     }
     
+    /// Queue a claim that stakes `amount` of the reward in the same transaction, so the
+    /// stake lands exactly when the claim does. Needs the EIP-712 bridge: the Rust signer
+    /// only signs single mining-contract messages
+    pub async fn queue_claim_and_stake(&self, epoch: u64, amount: u128, duration_days: u64) -> Result<u64> {
+        if self.config.power_token_address.is_empty() {
+            return Err(anyhow!("No POWER token contract configured for staking"));
+        }
+        // Validate now rather than when the transaction is built
+        StakeTokensMsg::new(Uint128::new(amount), duration_days)?;
+        let tx_type = TransactionType::ClaimAndStake { epoch, amount, duration_days };
+        self.queue_transaction(tx_type).await
+    }
+    
     /// Get transaction status by ID
     pub async fn get_status(&self, id: u64) -> Option<TransactionStatus> {
         // First check completed transactions
@@ -307,6 +513,35 @@ impl TransactionManager {
             .map(|tx| tx.status.clone())
     }
     
+    /// Give up on a transaction: it is dropped from the queue, or not retried if it is
+    /// backing off after a failed attempt, so a caller that timed out and submits again
+    /// doesn't leave a duplicate behind. Returns false if it was already done or is
+    /// being broadcast right now, in which case it may still land
+    pub async fn cancel(&self, id: u64) -> bool {
+        if self.completed.read().await.contains_key(&id) {
+            return false;
+        }
+        self.cancelled.write().await.insert(id);
+        
+        let mut queue = self.queue.write().await;
+        let queued = match queue.iter().position(|tx| tx.id == id) {
+            Some(position) => queue.remove(position).is_some(),
+            None => false,
+        };
+        drop(queue);
+        
+        let reason = if queued { "cancelled before it was sent" } else { "cancelled after it was sent" };
+        self.completed.write().await.insert(id, TransactionStatus::Skipped { reason: reason.to_string() });
+        queued
+    }
+    
+    /// Record a final status without sending anything
+    #[cfg(test)]
+    pub(crate) async fn complete(&self, id: u64, status: TransactionStatus) {
+        self.queue.write().await.retain(|tx| tx.id != id);
+        self.completed.write().await.insert(id, status);
+    }
+    
     /// Get all queued transactions
     pub async fn get_queue(&self) -> Vec<QueuedTransaction> {
         self.queue.read().await.iter().cloned().collect()
@@ -347,6 +582,7 @@ impl TransactionManager {
                 .duration_since(std::time::UNIX_EPOCH)?
                 .as_secs(),
             gas_estimate: None,
+            last_tx_hash: None,
         };
         
         queue.push_back(tx);
@@ -368,25 +604,109 @@ impl TransactionManager {
             client.connect().await?;
         }
         
-        // Build and submit transaction based on type
-        let tx_hash = match &tx.tx_type {
+        // A previous attempt may still be pending - resubmitting it would only
+        // produce a duplicate or a sequence conflict
+        if let Some(prior_hash) = tx.last_tx_hash.take() {
+            if let Some(tx_hash) = Self::await_prior_attempt(&client, config, &prior_hash).await {
+                return Ok(tx_hash);
+            }
+        }
+        
+        // Drop any hash left over from an unrelated broadcast
+        client.take_last_broadcast_hash();
+        let result = Self::submit_transaction(&mut client, config, &tx.tx_type).await;
+        if result.is_err() {
+            tx.last_tx_hash = client.take_last_broadcast_hash();
+        }
+        result
+    }
+    
+    /// Wait up to `pending_tx_wait_ms` for a previous attempt to be included
+    /// Returns its hash if it succeeded on chain, None if it should be resubmitted
+    async fn await_prior_attempt(
+        client: &InjectiveClient,
+        config: &TransactionManagerConfig,
+        tx_hash: &str,
+    ) -> Option<String> {
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(config.pending_tx_wait_ms);
+        loop {
+            match client.find_tx(tx_hash).await {
+                Ok(Some(response)) if response.code == 0 => {
+                    log::info!("Previous attempt {} was included, not resubmitting", tx_hash);
+                    return Some(tx_hash.to_string());
+                }
+                Ok(Some(response)) => {
+                    log::warn!("Previous attempt {} failed on chain ({}), resubmitting", tx_hash, response.raw_log);
+                    return None;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!("Could not look up previous attempt {}: {}", tx_hash, e);
+                }
+            }
+            if tokio::time::Instant::now() >= deadline {
+                log::info!(
+                    "Previous attempt {} not on chain after {}ms, resubmitting",
+                    tx_hash, config.pending_tx_wait_ms
+                );
+                return None;
+            }
+            log::debug!("Previous attempt {} may still be pending, waiting", tx_hash);
+            sleep(Duration::from_millis(500)).await;
+        }
+    }
+    
+    /// Whether the effect of `tx_type` is already visible in contract state, described
+    /// for the log. Only commits can be looked up (the contract stores one commitment per
+    /// miner and epoch); it has no per-epoch reveal or claim query, so duplicates of those
+    /// are recognized from the contract's rejection instead (`is_duplicate_of_earlier_attempt`)
+    async fn effect_on_chain(
+        client: &Arc<RwLock<InjectiveClient>>,
+        config: &TransactionManagerConfig,
+        tx_type: &TransactionType,
+    ) -> Option<String> {
+        let TransactionType::Commit { epoch, commitment } = tx_type else {
+            return None;
+        };
+        let client = client.read().await;
+        match query_commitment(&*client, &config.contract_address, client.address(), *epoch).await {
+            Ok(Some(stored)) if stored == *commitment => Some(format!("commitment for epoch {} is already on chain", epoch)),
+            Ok(_) => None,
+            Err(e) => {
+                log::debug!("Could not check for an existing commitment for epoch {}: {}", epoch, e);
+                None
+            }
+        }
+    }
+    
+    /// Whether `error` on a retry of `tx_type` means an earlier attempt already did the job
+    fn is_duplicate_of_earlier_attempt(tx_type: &TransactionType, error: ChainError) -> bool {
+        matches!(
+            (tx_type, error),
+            (TransactionType::Reveal { .. }, ChainError::AlreadyRevealed)
+                | (TransactionType::Claim { .. } | TransactionType::ClaimAndStake { .. }, ChainError::AlreadyClaimed)
+        )
+    }
+    
+    /// Build and broadcast a transaction
+    async fn submit_transaction(
+        client: &mut InjectiveClient,
+        config: &TransactionManagerConfig,
+        tx_type: &TransactionType,
+    ) -> Result<String> {
+        let tx_hash = match tx_type {
             TransactionType::Commit { commitment, .. } => {
-                // Create the message wrapped in the correct enum variant
-                let msg = serde_json::json!({
-                    "commit_solution": {
-                        "commitment": commitment.to_vec()
-                    }
-                });
+                let msg = CommitSolutionMsg::from_commitment(*commitment);
                 
                 // Commits are time-critical (15 block window) - skip gas simulation!
                 log::warn!("Commit transaction - CRITICAL TIME WINDOW - SKIPPING GAS SIMULATION");
                 
                 let start = std::time::Instant::now();
-                let result = client.execute_contract_fast(
+                let result = client.execute_message_fast(
                     &config.contract_address,
-                    msg,
+                    &msg,
                     vec![],
-                    250_000,  // Fixed gas limit for commits
+                    COMMIT_GAS_LIMIT,
                 ).await?;
                 
                 let elapsed = start.elapsed();
@@ -394,87 +714,101 @@ impl TransactionManager {
                 result
             }
             
-            TransactionType::Reveal { nonce, digest, salt, .. } => {
-                // Create the message wrapped in the correct enum variant
-                // The contract expects nonce as [u8; 8], not u64
-                let msg = serde_json::json!({
-                    "reveal_solution": {
-                        "nonce": nonce.to_vec(),  // Send as byte array
-                        "digest": digest.to_vec(),
-                        "salt": salt.to_vec()
-                    }
-                });
-                
+            TransactionType::Reveal { nonce, digest, salt, prepared, .. } => {
                 // For reveals, we need to be FAST - skip gas simulation!
                 // Use execute_contract_fast with fixed 300k gas
                 log::warn!("Reveal transaction - CRITICAL TIME WINDOW - SKIPPING GAS SIMULATION");
                 
                 let start = std::time::Instant::now();
-                let result = client.execute_contract_fast(
-                    &config.contract_address,
-                    msg,
-                    vec![],
-                    300_000,  // Fixed gas limit for reveals
-                ).await?;
+                let result = match prepared {
+                    Some(prepared) => client.execute_prepared_fast(
+                        &config.contract_address,
+                        prepared,
+                        REVEAL_GAS_LIMIT,
+                    ).await?,
+                    None => {
+                        // The contract expects nonce as [u8; 8], not u64
+                        let msg = RevealSolutionMsg::new(*nonce, *digest, *salt);
+                        client.execute_message_fast(
+                            &config.contract_address,
+                            &msg,
+                            vec![],
+                            REVEAL_GAS_LIMIT,
+                        ).await?
+                    }
+                };
                 
                 let elapsed = start.elapsed();
-                log::info!("Reveal transaction submitted in {:?} (no gas simulation)", elapsed);
+                log::info!("Reveal transaction submitted in {:?} (no gas simulation, pre-signed: {})", elapsed, prepared.is_some());
                 result
             }
             
             TransactionType::Claim { epoch } => {
-                // Create the message wrapped in the correct enum variant
-                let msg = serde_json::json!({
-                    "claim_reward": {
-                        "epoch_number": *epoch
-                    }
-                });
-                
                 // Claims need more gas due to token minting
-                client.execute_contract_fast(
+                client.execute_message_fast(
                     &config.contract_address,
-                    msg,
+                    &ClaimRewardMsg::new(*epoch),
                     vec![],
                     400_000,  // Increased gas limit for claims
                 ).await?
             }
             
             TransactionType::FinalizeEpoch { epoch } => {
-                // Create the message wrapped in the correct enum variant
-                let msg = serde_json::json!({
-                    "finalize_epoch": {
-                        "epoch_number": *epoch
-                    }
-                });
-                
                 // Use fast path for all transactions - skip gas simulation!
-                client.execute_contract_fast(
+                client.execute_message_fast(
                     &config.contract_address,
-                    msg,
+                    &FinalizeEpochMsg::new(*epoch),
                     vec![],
                     250_000,  // Fixed gas limit
                 ).await?
             }
             
             TransactionType::AdvanceEpoch => {
-                // Create the message wrapped in the correct enum variant
-                let msg = serde_json::json!({
-                    "advance_epoch": {}
-                });
-                
                 // Use fast path for all transactions - skip gas simulation!
-                client.execute_contract_fast(
+                client.execute_message_fast(
                     &config.contract_address,
-                    msg,
+                    &AdvanceEpochMsg::new(),
                     vec![],
                     250_000,  // Fixed gas limit
                 ).await?
             }
+            
+            TransactionType::ClaimAndStake { epoch, amount, duration_days } => {
+                // Staking goes through the token: CW20 send with a stake hook for the mining contract
+                let send = StakeTokensMsg::new(Uint128::new(*amount), *duration_days)?
+                    .build_cw20_send(&config.contract_address)?;
+                client.execute_contracts_fast(
+                    vec![
+                        MessageData {
+                            contract: config.contract_address.clone(),
+                            msg: ClaimRewardMsg::new(*epoch).to_json()?,
+                            funds: vec![],
+                        },
+                        MessageData {
+                            contract: config.power_token_address.clone(),
+                            msg: serde_json::to_value(&send)?,
+                            funds: vec![],
+                        },
+                    ],
+                    800_000,  // The claim, then a token transfer plus the contract's receive hook
+                ).await?
+            }
         };
         
         Ok(tx_hash)
     }
     
+    /// Failures of `advance_epoch` that are expected outcomes rather than errors
+    /// It is only sent once settlement has ended, so a wrong-phase rejection means the
+    /// next epoch has already started
+    fn advance_outcome(error: ChainError) -> Option<AdvanceOutcome> {
+        match error {
+            ChainError::AlreadyAdvanced | ChainError::WrongPhase => Some(AdvanceOutcome::AlreadyAdvanced),
+            ChainError::Unauthorized => Some(AdvanceOutcome::Unauthorized),
+            _ => None,
+        }
+    }
+    
     /// Calculate retry delay with exponential backoff
     fn calculate_retry_delay(retry_count: u32, initial_ms: u64, max_ms: u64) -> u64 {
         let delay = initial_ms * 2u64.pow(retry_count - 1);
@@ -497,6 +831,44 @@ mod tests {
         assert_eq!(TransactionManager::calculate_retry_delay(6, 1000, 30000), 30000); // Capped at max
     }
     
+    #[test]
+    fn test_advance_outcome() {
+        assert_eq!(TransactionManager::advance_outcome(ChainError::AlreadyAdvanced), Some(AdvanceOutcome::AlreadyAdvanced));
+        assert_eq!(TransactionManager::advance_outcome(ChainError::WrongPhase), Some(AdvanceOutcome::AlreadyAdvanced));
+        assert_eq!(TransactionManager::advance_outcome(ChainError::Unauthorized), Some(AdvanceOutcome::Unauthorized));
+        // Real failures still count and are retried
+        assert_eq!(TransactionManager::advance_outcome(ChainError::AccountSequence), None);
+        assert_eq!(TransactionManager::advance_outcome(ChainError::Other), None);
+    }
+    
+    #[test]
+    fn test_duplicate_of_earlier_attempt() {
+        let reveal = TransactionType::Reveal { epoch: 3, nonce: [0; 8], digest: [0; 16], salt: [0; 32], prepared: None };
+        let claim = TransactionType::Claim { epoch: 3 };
+        assert!(TransactionManager::is_duplicate_of_earlier_attempt(&reveal, ChainError::AlreadyRevealed));
+        assert!(TransactionManager::is_duplicate_of_earlier_attempt(&claim, ChainError::AlreadyClaimed));
+        // Only the transaction's own effect counts
+        assert!(!TransactionManager::is_duplicate_of_earlier_attempt(&claim, ChainError::AlreadyRevealed));
+        assert!(!TransactionManager::is_duplicate_of_earlier_attempt(&reveal, ChainError::WrongPhase));
+    }
+    
+    #[tokio::test]
+    async fn test_broadcast_limiter_is_per_signer() {
+        let limiter = BroadcastLimiter::new(1);
+        let permit = limiter.acquire("inj1alice").await;
+        
+        // Another signer is not blocked
+        let other = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("inj1bob")).await;
+        assert!(other.is_ok());
+        
+        // The same signer waits until the first broadcast finishes
+        let same = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("inj1alice")).await;
+        assert!(same.is_err());
+        drop(permit);
+        let same = tokio::time::timeout(Duration::from_millis(50), limiter.acquire("inj1alice")).await;
+        assert!(same.is_ok());
+    }
+    
     #[tokio::test]
     async fn test_transaction_queue() {
         let client = Arc::new(RwLock::new(
@@ -528,4 +900,68 @@ mod tests {
         let queue = manager.get_queue().await;
         assert_eq!(queue.len(), 0);
     }
+    
+    #[test]
+    fn test_reveals_go_before_claims() {
+        let queued = |id: u64, tx_type: TransactionType| QueuedTransaction {
+            id,
+            tx_type,
+            status: TransactionStatus::Pending,
+            retry_count: 0,
+            queued_at: 0,
+            gas_estimate: None,
+            last_tx_hash: None,
+        };
+        let reveal = || TransactionType::Reveal { epoch: 8, nonce: [1; 8], digest: [2; 16], salt: [3; 32], prepared: None };
+        let mut queue: VecDeque<QueuedTransaction> = vec![
+            queued(1, TransactionType::Claim { epoch: 7 }),
+            queued(2, TransactionType::ClaimAndStake { epoch: 6, amount: 5_000_000, duration_days: 30 }),
+            queued(3, TransactionType::Commit { epoch: 9, commitment: [0; 32] }),
+            queued(4, reveal()),
+        ].into();
+        
+        let mut order = Vec::new();
+        while let Some(position) = next_transaction(&queue, true) {
+            order.push(queue.remove(position).unwrap().id);
+        }
+        assert_eq!(order, vec![4, 3, 1, 2]);
+        
+        // Whatever the enqueue order, and FIFO when prioritizing is off
+        let mut queue: VecDeque<QueuedTransaction> = vec![queued(1, reveal()), queued(2, TransactionType::Claim { epoch: 7 })].into();
+        assert_eq!(next_transaction(&queue, true), Some(0));
+        queue.swap(0, 1);
+        assert_eq!(next_transaction(&queue, true), Some(1));
+        assert_eq!(next_transaction(&queue, false), Some(0));
+        assert_eq!(next_transaction(&VecDeque::new(), true), None);
+    }
+    
+    #[tokio::test]
+    async fn test_claim_and_stake_share_one_transaction() {
+        let client = Arc::new(RwLock::new(
+            InjectiveClient::new_testnet(
+                crate::chain::wallet::InjectiveWallet::from_mnemonic_no_passphrase(
+                    "test test test test test test test test test test test junk"
+                ).unwrap()
+            )
+        ));
+        
+        // Without a token contract there is nothing to send the stake from
+        let manager = TransactionManager::new(TransactionManagerConfig::default(), client.clone());
+        assert!(manager.queue_claim_and_stake(7, 5_000_000, 30).await.is_err());
+        
+        let config = TransactionManagerConfig {
+            power_token_address: "inj1esn6fgltm0fvqe2n57cdkvtwwpyyf9due8ps49".to_string(),
+            ..TransactionManagerConfig::default()
+        };
+        let manager = TransactionManager::new(config, client);
+        // Invalid stakes are refused before the claim is queued
+        assert!(manager.queue_claim_and_stake(7, 5_000_000, 45).await.is_err());
+        assert!(manager.get_queue().await.is_empty());
+        
+        let id = manager.queue_claim_and_stake(7, 5_000_000, 30).await.unwrap();
+        let queue = manager.get_queue().await;
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].id, id);
+        assert!(matches!(queue[0].tx_type, TransactionType::ClaimAndStake { epoch: 7, amount: 5_000_000, duration_days: 30 }));
+    }
 }
\ No newline at end of file
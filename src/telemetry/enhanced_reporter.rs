@@ -4,7 +4,8 @@ use reqwest::{Client, ClientBuilder};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, warn};
 
 /// Enhanced telemetry data with comprehensive metrics
@@ -13,6 +14,8 @@ pub struct EnhancedTelemetryData {
     // Identity
     pub wallet_address: String,
     pub miner_instance_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub miner_label: Option<String>,
     
     // Mining Performance
     pub current_epoch: u64,
@@ -155,6 +158,7 @@ pub struct EnhancedTelemetryReporter {
     endpoint: String,
     wallet_address: String,
     miner_instance_id: String,
+    miner_label: Option<String>,
     stats: Arc<RwLock<MiningStats>>,
     last_power_balance: Arc<RwLock<Option<u64>>>,
 }
@@ -173,37 +177,50 @@ impl EnhancedTelemetryReporter {
             endpoint,
             wallet_address,
             miner_instance_id,
+            miner_label: None,
             stats: Arc::new(RwLock::new(MiningStats::default())),
             last_power_balance: Arc::new(RwLock::new(None)),
         })
     }
     
-    /// Record a commit attempt
-    pub async fn record_commit_attempt(&self, success: bool, gas_used: Option<u64>) {
+    /// Send to another backend than the production one
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+    
+    /// Attach a human-readable miner label to every telemetry update
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.miner_label = label;
+        self
+    }
+    
+    /// Record a commit attempt and the fee it paid (gas limit × gas price, in wei)
+    pub async fn record_commit_attempt(&self, success: bool, fee_wei: Option<u64>) {
         let mut stats = self.stats.write().await;
         stats.commits_attempted += 1;
         if success {
             stats.commits_successful += 1;
         }
-        if let Some(gas) = gas_used {
-            stats.total_gas_spent_wei += gas;
+        if let Some(fee) = fee_wei {
+            stats.total_gas_spent_wei += fee;
         }
     }
     
-    /// Record a reveal attempt
-    pub async fn record_reveal_attempt(&self, success: bool, gas_used: Option<u64>) {
+    /// Record a reveal attempt and the fee it paid (gas limit × gas price, in wei)
+    pub async fn record_reveal_attempt(&self, success: bool, fee_wei: Option<u64>) {
         let mut stats = self.stats.write().await;
         stats.reveals_attempted += 1;
         if success {
             stats.reveals_successful += 1;
         }
-        if let Some(gas) = gas_used {
-            stats.total_gas_spent_wei += gas;
+        if let Some(fee) = fee_wei {
+            stats.total_gas_spent_wei += fee;
         }
     }
     
-    /// Record a claim attempt
-    pub async fn record_claim_attempt(&self, success: bool, power_earned: Option<u64>, gas_used: Option<u64>) {
+    /// Record a claim attempt and the fee it paid (gas limit × gas price, in wei)
+    pub async fn record_claim_attempt(&self, success: bool, power_earned: Option<u64>, fee_wei: Option<u64>) {
         let mut stats = self.stats.write().await;
         stats.claims_attempted += 1;
         if success {
@@ -213,8 +230,8 @@ impl EnhancedTelemetryReporter {
                 stats.epochs_won += 1;
             }
         }
-        if let Some(gas) = gas_used {
-            stats.total_gas_spent_wei += gas;
+        if let Some(fee) = fee_wei {
+            stats.total_gas_spent_wei += fee;
         }
     }
     
@@ -270,6 +287,7 @@ impl EnhancedTelemetryReporter {
         let data = EnhancedTelemetryData {
             wallet_address: self.wallet_address.clone(),
             miner_instance_id: self.miner_instance_id.clone(),
+            miner_label: self.miner_label.clone(),
             current_epoch: epoch,
             current_phase: phase.to_string(),
             timestamp: Utc::now().to_rfc3339(),
@@ -308,6 +326,8 @@ impl EnhancedTelemetryReporter {
             gas_balance,
             last_error_message: last_error,
         };
+        // Let record_* calls through while the POST is in flight
+        drop(stats);
         
         debug!("Sending enhanced telemetry: epoch={}, phase={}, hashrate={:?}", epoch, phase, hashrate_mhs);
         
@@ -338,6 +358,22 @@ impl EnhancedTelemetryReporter {
         }
     }
     
+    /// Send a queued update (see `TelemetrySender`)
+    pub async fn send_update(&self, update: TelemetryUpdate) -> Result<()> {
+        self.send_telemetry(
+            update.epoch,
+            &update.phase,
+            update.hashrate_mhs,
+            update.solutions_found,
+            update.reveals_submitted,
+            update.network_info,
+            update.power_balance,
+            update.gas_balance,
+            update.last_error,
+            update.nonce_range,
+        ).await
+    }
+    
     /// Get current statistics
     pub async fn get_stats(&self) -> MiningStats {
         self.stats.read().await.clone()
@@ -348,6 +384,7 @@ impl EnhancedTelemetryReporter {
         let test_data = EnhancedTelemetryData {
             wallet_address: "inj1testwalletaddressfortestingconnection".to_string(),
             miner_instance_id: "00000000-0000-0000-0000-000000000000".to_string(),
+            miner_label: None,
             current_epoch: 0,
             current_phase: "test".to_string(),
             timestamp: Utc::now().to_rfc3339(),
@@ -382,4 +419,93 @@ impl EnhancedTelemetryReporter {
             Err(_) => Ok(false)
         }
     }
-}
\ No newline at end of file
+}
+
+/// Updates waiting to be sent before new ones are dropped
+pub const TELEMETRY_QUEUE_CAPACITY: usize = 32;
+
+/// One telemetry update - the arguments of `send_telemetry`
+#[derive(Debug, Clone, Default)]
+pub struct TelemetryUpdate {
+    pub epoch: u64,
+    pub phase: String,
+    pub hashrate_mhs: Option<f64>,
+    pub solutions_found: Option<u32>,
+    pub reveals_submitted: Option<u32>,
+    /// (total_miners, difficulty)
+    pub network_info: Option<(u32, u32)>,
+    pub power_balance: Option<u64>,
+    pub gas_balance: Option<String>,
+    pub last_error: Option<String>,
+    pub nonce_range: Option<(u64, u64)>,
+}
+
+/// Sends telemetry from a background task, so a slow or hung backend never holds up
+/// mining, commits or reveals. Updates are buffered up to a bound; when the backend
+/// can't keep up, new ones are dropped rather than queued without limit
+#[derive(Clone)]
+pub struct TelemetrySender {
+    queue: mpsc::Sender<TelemetryUpdate>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl TelemetrySender {
+    /// Start the background task sending through `reporter`
+    pub fn spawn(reporter: Arc<EnhancedTelemetryReporter>, capacity: usize) -> Self {
+        let (queue, mut updates) = mpsc::channel::<TelemetryUpdate>(capacity.max(1));
+        tokio::spawn(async move {
+            while let Some(update) = updates.recv().await {
+                let (epoch, phase) = (update.epoch, update.phase.clone());
+                match reporter.send_update(update).await {
+                    Ok(()) => debug!("Telemetry sent: {} for epoch {}", phase, epoch),
+                    Err(e) => warn!("Failed to send {} telemetry for epoch {}: {}", phase, epoch, e),
+                }
+            }
+        });
+        Self { queue, dropped: Arc::new(AtomicU64::new(0)) }
+    }
+    
+    /// Queue an update without waiting for it to be sent
+    pub fn send(&self, update: TelemetryUpdate) {
+        match self.queue.try_send(update) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(update)) => {
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                debug!("Telemetry backlog full, dropped {} update ({} dropped so far)", update.phase, dropped);
+            }
+            Err(mpsc::error::TrySendError::Closed(update)) => {
+                debug!("Telemetry sender stopped, dropped {} update", update.phase);
+            }
+        }
+    }
+    
+    /// Updates dropped because the backlog was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hung_endpoint_does_not_block_sender() {
+        // Accepts connections (into the backlog) but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/api/telemetry", listener.local_addr().unwrap());
+        let reporter = EnhancedTelemetryReporter::new("inj1test".to_string(), "test".to_string())
+            .unwrap()
+            .with_endpoint(endpoint);
+        let sender = TelemetrySender::spawn(Arc::new(reporter), 4);
+        
+        let start = std::time::Instant::now();
+        for epoch in 0..20 {
+            sender.send(TelemetryUpdate { epoch, phase: "Revealing".to_string(), ..TelemetryUpdate::default() });
+        }
+        assert!(start.elapsed() < Duration::from_millis(500));
+        // The background task hasn't run yet on this single-threaded runtime:
+        // 4 updates fit in the backlog, the rest are dropped
+        assert_eq!(sender.dropped(), 16);
+    }
+}
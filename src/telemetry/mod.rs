@@ -1,5 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::time::{Duration, interval};
 use uuid::Uuid;
@@ -16,7 +17,24 @@ use types::*;
 
 // Re-export telemetry reporters for easier access
 pub use simple_reporter::SimpleTelemetryReporter;
-pub use enhanced_reporter::{EnhancedTelemetryReporter, MiningStats};
+pub use enhanced_reporter::{EnhancedTelemetryReporter, MiningStats, TelemetrySender, TelemetryUpdate, TELEMETRY_QUEUE_CAPACITY};
+
+/// Load the miner id persisted in `id_file`, generating and saving one on first run
+/// Keeps the dashboard from seeing every restart as a brand-new miner
+pub fn load_or_create_miner_id(id_file: &Path) -> Uuid {
+    if let Ok(contents) = std::fs::read_to_string(id_file) {
+        match Uuid::parse_str(contents.trim()) {
+            Ok(id) => return id,
+            Err(e) => tracing::warn!("Ignoring invalid miner id in {}: {}", id_file.display(), e),
+        }
+    }
+    
+    let id = Uuid::new_v4();
+    if let Err(e) = std::fs::write(id_file, id.to_string()) {
+        tracing::warn!("Could not persist miner id to {}: {}", id_file.display(), e);
+    }
+    id
+}
 
 /// Main telemetry manager that coordinates collection and reporting
 pub struct TelemetryManager {
@@ -36,6 +54,13 @@ pub struct TelemetryConfig {
     pub flush_interval_secs: u64,
     pub retry_attempts: u32,
     pub timeout_secs: u64,
+    /// Fixed miner id (overrides `miner_id_file`)
+    #[serde(default)]
+    pub miner_id: Option<Uuid>,
+    /// File the miner id is persisted to so it survives restarts
+    /// None = a fresh id every start
+    #[serde(default)]
+    pub miner_id_file: Option<PathBuf>,
 }
 
 impl Default for TelemetryConfig {
@@ -47,6 +72,8 @@ impl Default for TelemetryConfig {
             flush_interval_secs: 30,
             retry_attempts: 3,
             timeout_secs: 10,
+            miner_id: None,
+            miner_id_file: None,
         }
     }
 }
@@ -56,7 +83,9 @@ impl TelemetryManager {
         wallet_address: String,
         config: TelemetryConfig,
     ) -> Result<Self> {
-        let miner_id = Uuid::new_v4();
+        let miner_id = config.miner_id
+            .or_else(|| config.miner_id_file.as_deref().map(load_or_create_miner_id))
+            .unwrap_or_else(Uuid::new_v4);
         let collector = Arc::new(TelemetryCollector::new());
         
         let reporter = if config.enabled {
@@ -235,4 +264,23 @@ impl TelemetryManager {
     pub async fn get_stats(&self) -> Result<MinerStats> {
         self.collector.get_current_stats().await
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_miner_id_persists() {
+        let id_file = std::env::temp_dir().join(format!("gmine_test_{}.id", Uuid::new_v4()));
+        let first = load_or_create_miner_id(&id_file);
+        assert_eq!(load_or_create_miner_id(&id_file), first);
+
+        // A corrupted id file is replaced rather than failing startup
+        std::fs::write(&id_file, "not-a-uuid").unwrap();
+        let replaced = load_or_create_miner_id(&id_file);
+        assert_ne!(replaced, first);
+        assert_eq!(load_or_create_miner_id(&id_file), replaced);
+        let _ = std::fs::remove_file(id_file);
+    }
+}